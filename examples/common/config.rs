@@ -2,6 +2,7 @@ use dotenv::dotenv;
 use envconfig::Envconfig;
 use ethers::prelude::*;
 use eyre::Result;
+use mev_share_rs::wallet;
 use tokio::sync::OnceCell;
 use tracing::*;
 
@@ -20,8 +21,8 @@ impl Config {
         dotenv()?;
         let config = ConfigRaw::init_from_env()?;
 
-        let auth_wallet = LocalWallet::from_bytes(&config.auth_private_key)?;
-        let sender_wallet = LocalWallet::from_bytes(&config.sender_private_key)?;
+        let auth_wallet = config.wallet(&config.auth_keystore, &config.auth_private_key)?;
+        let sender_wallet = config.wallet(&config.sender_keystore, &config.sender_private_key)?;
 
         info!(
             sender = ?sender_wallet.address(),
@@ -42,10 +43,36 @@ static CONFIG: OnceCell<Config> = OnceCell::const_new();
 
 #[derive(Envconfig)]
 pub struct ConfigRaw {
+    #[envconfig(from = "AUTH_KEYSTORE")]
+    pub auth_keystore: Option<String>,
     #[envconfig(from = "AUTH_PRIVATE_KEY")]
-    pub auth_private_key: Bytes,
+    pub auth_private_key: Option<Bytes>,
+    #[envconfig(from = "SENDER_KEYSTORE")]
+    pub sender_keystore: Option<String>,
     #[envconfig(from = "SENDER_PRIVATE_KEY")]
-    pub sender_private_key: Bytes,
+    pub sender_private_key: Option<Bytes>,
+    #[envconfig(from = "KEYSTORE_PASSWORD")]
+    pub keystore_password: Option<String>,
     #[envconfig(from = "PROVIDER_URL")]
     pub provider_url: String,
 }
+
+impl ConfigRaw {
+    /// Decrypts `keystore` if set, or falls back to `private_key`.
+    fn wallet(
+        &self,
+        keystore: &Option<String>,
+        private_key: &Option<Bytes>,
+    ) -> Result<LocalWallet> {
+        match (keystore, private_key) {
+            (Some(keystore), _) => Ok(wallet::from_keystore(
+                keystore,
+                self.keystore_password.as_deref().unwrap_or_default(),
+            )?),
+            (None, Some(private_key)) => Ok(LocalWallet::from_bytes(private_key)?),
+            (None, None) => Err(eyre::eyre!(
+                "either a keystore or a private key must be set"
+            )),
+        }
+    }
+}
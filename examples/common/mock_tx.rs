@@ -8,7 +8,7 @@ pub struct MockTx {
     data: Option<Bytes>,
     tip: Option<(U256, U256)>,
     nonce_add: Option<U256>,
-    to: Option<Address>,
+    to: Option<NameOrAddress>,
 }
 
 impl MockTx {
@@ -22,7 +22,8 @@ impl MockTx {
         self
     }
 
-    pub fn to<T: Into<Address>>(mut self, to: T) -> Self {
+    /// `to` may be an ENS name, resolved when the transaction is built.
+    pub fn to<T: Into<NameOrAddress>>(mut self, to: T) -> Self {
         self.to = Some(to.into());
         self
     }
@@ -43,6 +44,12 @@ impl MockTx {
                 .get_transaction_count(c.sender_wallet.address(), None),
         )?;
 
+        let to = match self.to {
+            Some(NameOrAddress::Name(name)) => c.provider.resolve_name(&name).await?,
+            Some(NameOrAddress::Address(address)) => address,
+            None => c.sender_wallet.address(),
+        };
+
         let tip = self.tip.unwrap_or_default();
         let gas = 500_000;
         let nonce = transaction_count + self.nonce_add.unwrap_or(U256::zero());
@@ -50,7 +57,7 @@ impl MockTx {
         let tx: TypedTransaction = Eip1559TransactionRequest::new()
             .chain_id(chain_id.as_u64())
             .from(c.sender_wallet.address())
-            .to(self.to.unwrap_or(c.sender_wallet.address()))
+            .to(to)
             .data(self.data.unwrap_or_default())
             .nonce(nonce)
             .gas(gas)
@@ -1,10 +1,8 @@
-mod builders;
 mod config;
 mod events;
 mod mock_tx;
 mod tracing;
 
-pub use builders::*;
 pub use config::*;
 pub use events::*;
 pub use mock_tx::*;
@@ -2,9 +2,10 @@
 #![feature(let_chains)]
 #![allow(dead_code)]
 
-use ethers::types::U256;
+use ethers::types::Chain;
+use futures::StreamExt;
 use mev_share_rs::prelude::*;
-use mev_share_rs::GetEventHistoryParams;
+use std::pin::pin;
 use tracing::*;
 
 mod common;
@@ -18,42 +19,33 @@ async fn main() -> eyre::Result<()> {
     let client = MevShareClient::new_with_chain_id(
         config.auth_wallet.clone(),
         config.provider.clone(),
-        U256::one(), // EventHistory seems to be only supported on mainnet
+        Chain::Mainnet, // EventHistory seems to be only supported on mainnet
     )?;
     let event_history_info = client.get_event_history_info().await?;
     debug!("{event_history_info:#?}");
 
-    let mut page = 0;
-    let mut done = false;
-
-    while !done {
-        let events = client
-            .get_event_history(
-                GetEventHistoryParams::builder()
-                    .limit(event_history_info.max_limit)
-                    .offset(page * event_history_info.max_limit)
-                    .block_start(event_history_info.min_block)
-                    .build(),
-            )
-            .await?;
-
-        for event in &events {
-            if let Some(txs) = &event.hint.txs && !txs.is_empty() {
-                debug!("event: {event:#?}");
-                debug!("txs: {txs:#?}");
-                break;
-            }
-        }
+    // pages through the whole history, `max_limit` events at a time
+    let mut events = pin!(client.event_history_stream(
+        GetEventHistoryParams::builder()
+            .block_start(event_history_info.min_block)
+            .build(),
+    ));
+
+    let mut txs_found = false;
 
-        for event in &events {
-            if let Some(logs) = &event.hint.logs && !logs.is_empty() {
-                debug!("logs: {logs:#?}");
-                done = true;
-                break;
-            }
+    while let Some(event) = events.next().await {
+        let event = event?;
+
+        if !txs_found && let Some(txs) = &event.hint.txs && !txs.is_empty() {
+            debug!("event: {event:#?}");
+            debug!("txs: {txs:#?}");
+            txs_found = true;
         }
 
-        page += 1;
+        if let Some(logs) = &event.hint.logs && !logs.is_empty() {
+            debug!("logs: {logs:#?}");
+            break;
+        }
     }
 
     Ok(())
@@ -8,7 +8,6 @@ use ethers::utils::keccak256;
 use ethers::utils::parse_ether;
 use eyre::Result;
 use mev_share_rs::prelude::*;
-use mev_share_rs::SendTransactionParams;
 use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -16,7 +15,7 @@ use tokio::try_join;
 use tracing::*;
 
 mod common;
-use common::{init_tracing, Config, MockTx, BUILDERS};
+use common::{init_tracing, Config, MockTx};
 
 const INCLUSION_BLOCKS: u64 = 10;
 
@@ -34,18 +33,18 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-struct Executor<'a> {
+struct Executor {
     provider: Provider<Ws>,
-    client: MevShareClient<'a>,
+    client: MevShareClient,
 
     // used for tracking txs we sent. we only want to backrun txs we sent.
     target_txs: Arc<Mutex<HashSet<TxHash>>>,
 }
 
-impl<'a> Executor<'a> {
+impl Executor {
     pub async fn new(provider: Provider<Ws>, auth_wallet: LocalWallet) -> Result<Self> {
         Ok(Self {
-            client: MevShareClient::<'a>::new(auth_wallet, provider.clone()).await?,
+            client: MevShareClient::new(auth_wallet, provider.clone()).await?,
             provider,
             target_txs: Default::default(),
         })
@@ -91,7 +90,7 @@ impl<'a> Executor<'a> {
                     SendTransactionParams::builder()
                         .tx(MockTx::default().data(b"plz backrun me").build().await?)
                         .max_block_number(block_number + 1 + INCLUSION_BLOCKS)
-                        .preferences(None, Some(BUILDERS.clone()))
+                        .preferences([], Builders::all_known())
                         .build(),
                 )
                 .await?;
@@ -169,7 +168,7 @@ impl<'a> Executor<'a> {
                 },
             ])
             // .validity(vec![], vec![])
-            .privacy(None, Some(BUILDERS.clone()))
+            .privacy([], Builders::all_known())
             .build();
 
         info!("simulating backrun bundle");
@@ -8,11 +8,10 @@ use ethers::prelude::*;
 use ethers::utils::parse_ether;
 use eyre::{eyre, Result};
 use mev_share_rs::prelude::*;
-use mev_share_rs::MevShareClient;
 use tracing::*;
 
 mod common;
-use common::{init_tracing, Config, MockTx, BUILDERS};
+use common::{init_tracing, Config, MockTx};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -45,15 +44,15 @@ async fn main() -> Result<()> {
         .inclusion(current_block + 1, Some(current_block + 1 + 20))
         .privacy(
             // what to disclose
-            Some(set![
+            [
                 Hint::TxHash,
-                Calldata,
-                Logs,
-                FunctionSelector,
-                ContractAddress
-            ]),
+                Hint::Calldata,
+                Hint::Logs,
+                Hint::FunctionSelector,
+                Hint::ContractAddress,
+            ],
             // to whom
-            Some(BUILDERS.clone()),
+            Builders::all_known(),
         )
         .build();
 
@@ -4,8 +4,6 @@
 
 use ethers::{prelude::*, types::transaction::eip2718::TypedTransaction};
 use mev_share_rs::prelude::*;
-use mev_share_rs::MevShareClient;
-use mev_share_rs::SendTransactionParams;
 use tokio::try_join;
 use tracing::*;
 
@@ -51,7 +49,7 @@ async fn main() -> eyre::Result<()> {
 
     let tx_request = SendTransactionParams::builder()
         .tx(tx.rlp_signed(&c.sender_wallet.sign_transaction_sync(&tx)?))
-        .preferences(Some(set![Hint::Calldata, Hint::TransactionHash]), None)
+        .preferences([Hint::Calldata, Hint::TransactionHash], [])
         .build();
 
     loop {
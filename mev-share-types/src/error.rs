@@ -0,0 +1,33 @@
+use crate::networks::Capability;
+use crate::RelayLimit;
+use ethers_core::types::U256;
+use thiserror::Error;
+
+/// The crate `Error` type: what can go wrong building, validating and (de)serializing the types, before any transport
+/// is involved.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Refund percentages add up to {0}%, more than 100%")]
+    InvalidRefund(u32),
+
+    #[error("Invalid params: {0}")]
+    InvalidParams(String),
+
+    #[error("Bundle exceeds a relay limit: {0}")]
+    RelayLimit(RelayLimit),
+
+    #[error("UnsupportedNetwork: {0}")]
+    UnsupportedNetwork(U256),
+
+    #[error("{0} is not available on chain {1}")]
+    UnsupportedOnNetwork(Capability, u64),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// The crate `Result` type.
+pub type Result<T> = core::result::Result<T, Error>;
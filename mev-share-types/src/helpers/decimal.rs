@@ -0,0 +1,41 @@
+use ethers_core::types::U256;
+use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeAs, SerializeAs};
+
+/// Helper for (de)serializing a [`U256`] from/to a decimal string, e.g. `"1280749594841588639"`, the way the relay
+/// encodes amounts that may not fit in a JSON number.
+pub struct DecimalU256;
+
+/// Decimal amounts as sent on the wire: usually strings, sometimes plain numbers.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Decimal {
+    String(String),
+    Number(u64),
+}
+
+impl<'de> DeserializeAs<'de, U256> for DecimalU256 {
+    fn deserialize_as<D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match Decimal::deserialize(deserializer)? {
+            Decimal::String(decimal) => U256::from_dec_str(&decimal).map_err(|_| {
+                serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Str(&decimal),
+                    &"a decimal string",
+                )
+            }),
+            Decimal::Number(number) => Ok(number.into()),
+        }
+    }
+}
+
+impl SerializeAs<U256> for DecimalU256 {
+    fn serialize_as<S>(value: &U256, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        value.to_string().serialize(serializer)
+    }
+}
@@ -0,0 +1,9 @@
+//! `serde_with` adapters for the wire formats of the API, and a clock that works in the browser.
+
+mod decimal;
+mod selector;
+mod time;
+
+pub use decimal::DecimalU256;
+pub use selector::SelectorDeserializer;
+pub use time::system_now;
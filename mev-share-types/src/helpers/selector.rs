@@ -0,0 +1,27 @@
+use ethers_core::types::{Bytes, Selector};
+use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeAs, SerializeAs};
+
+/// Helper for (de)serializing a function selector from/to the hex string representation of a [`Selector`].
+pub struct SelectorDeserializer(Selector);
+
+impl<'de> DeserializeAs<'de, Selector> for SelectorDeserializer {
+    fn deserialize_as<D>(deserializer: D) -> Result<Selector, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = Bytes::deserialize(deserializer)?;
+        bytes.to_vec().try_into().map_err(|_| {
+            serde::de::Error::invalid_value(serde::de::Unexpected::Bytes(&bytes), &"0x01234567")
+        })
+    }
+}
+
+impl SerializeAs<Selector> for SelectorDeserializer {
+    fn serialize_as<S>(selector: &Selector, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Bytes::from(selector.to_vec()).serialize(serializer)
+    }
+}
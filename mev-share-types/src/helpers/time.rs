@@ -0,0 +1,10 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Like [`SystemTime::now`], which panics on `wasm32-unknown-unknown`, but reads the browser clock there.
+#[must_use]
+pub fn system_now() -> SystemTime {
+    UNIX_EPOCH
+        + instant::SystemTime::now()
+            .duration_since(instant::SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+}
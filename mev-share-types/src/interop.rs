@@ -0,0 +1,251 @@
+//! Conversions from and to the types of Paradigm's [`mev-share-sse`] crate, so that projects can migrate one component
+//! at a time, or feed the events of its `EventClient` to code written against this crate.
+//!
+//! Both crates use the `ethers` primitives, so conversions are field by field. They are lossy where the types differ:
+//!
+//! * `mev-share-sse` has no `mevGasPrice` nor `gasUsed` on [`mev_share_sse::Event`]: they are dropped, and `None` on
+//!   the way back.
+//! * `mev-share-sse` only keeps the address, topics and data of the logs.
+//! * Disclosed-nothing is `None` here, and an empty list there: both serialize to `null`.
+//!
+//! [`mev-share-sse`]: https://docs.rs/mev-share-sse
+
+use super::{
+    EventHint, EventHistory, EventHistoryInfo, GetEventHistoryParams, MevShareEvent, Transaction,
+};
+use ethers_core::types::Log;
+use mev_share_sse as sse;
+use std::num::TryFromIntError;
+
+/// Empty lists are "not disclosed" in `mev-share-sse`.
+fn disclosed<T>(items: Vec<T>) -> Option<Vec<T>> {
+    (!items.is_empty()).then_some(items)
+}
+
+fn logs_from_sse(logs: Vec<sse::EventTransactionLog>) -> Option<Vec<Log>> {
+    disclosed(
+        logs.into_iter()
+            .map(|log| Log {
+                address: log.address,
+                topics: log.topics,
+                data: log.data,
+                ..Default::default()
+            })
+            .collect(),
+    )
+}
+
+fn logs_to_sse(logs: Option<Vec<Log>>) -> Vec<sse::EventTransactionLog> {
+    logs.unwrap_or_default()
+        .into_iter()
+        .map(|log| sse::EventTransactionLog {
+            address: log.address,
+            topics: log.topics,
+            data: log.data,
+        })
+        .collect()
+}
+
+fn txs_from_sse(txs: Vec<sse::EventTransaction>) -> Option<Vec<Transaction>> {
+    disclosed(txs.into_iter().map(Into::into).collect())
+}
+
+fn txs_to_sse(txs: Option<Vec<Transaction>>) -> Vec<sse::EventTransaction> {
+    txs.unwrap_or_default()
+        .into_iter()
+        .map(Into::into)
+        .collect()
+}
+
+impl From<sse::Event> for MevShareEvent {
+    fn from(event: sse::Event) -> Self {
+        Self {
+            hash: event.hash,
+            logs: logs_from_sse(event.logs),
+            txs: txs_from_sse(event.transactions),
+            mev_gas_price: None,
+            gas_used: None,
+        }
+    }
+}
+
+impl From<MevShareEvent> for sse::Event {
+    fn from(event: MevShareEvent) -> Self {
+        Self {
+            hash: event.hash,
+            transactions: txs_to_sse(event.txs),
+            logs: logs_to_sse(event.logs),
+        }
+    }
+}
+
+impl From<sse::EventTransaction> for Transaction {
+    fn from(tx: sse::EventTransaction) -> Self {
+        Self {
+            to: tx.to,
+            function_selector: tx.function_selector.map(|selector| selector.0),
+            call_data: tx.calldata,
+        }
+    }
+}
+
+impl From<Transaction> for sse::EventTransaction {
+    fn from(tx: Transaction) -> Self {
+        Self {
+            to: tx.to,
+            function_selector: tx.function_selector.map(sse::FunctionSelector),
+            calldata: tx.call_data,
+        }
+    }
+}
+
+impl From<sse::EventHistory> for EventHistory {
+    fn from(history: sse::EventHistory) -> Self {
+        Self {
+            block: history.block,
+            timestamp: history.timestamp,
+            hint: history.hint.into(),
+        }
+    }
+}
+
+impl From<EventHistory> for sse::EventHistory {
+    fn from(history: EventHistory) -> Self {
+        Self {
+            block: history.block,
+            timestamp: history.timestamp,
+            hint: history.hint.into(),
+        }
+    }
+}
+
+impl From<sse::Hint> for EventHint {
+    fn from(hint: sse::Hint) -> Self {
+        Self {
+            txs: txs_from_sse(hint.txs),
+            hash: hint.hash,
+            logs: logs_from_sse(hint.logs),
+            gas_used: hint.gas_used,
+            mev_gas_price: hint.mev_gas_price,
+        }
+    }
+}
+
+impl From<EventHint> for sse::Hint {
+    fn from(hint: EventHint) -> Self {
+        Self {
+            txs: txs_to_sse(hint.txs),
+            hash: hint.hash,
+            logs: logs_to_sse(hint.logs),
+            gas_used: hint.gas_used,
+            mev_gas_price: hint.mev_gas_price,
+        }
+    }
+}
+
+/// Fails if the counts don't fit in `u32`.
+impl TryFrom<sse::EventHistoryInfo> for EventHistoryInfo {
+    type Error = TryFromIntError;
+
+    fn try_from(info: sse::EventHistoryInfo) -> Result<Self, Self::Error> {
+        Ok(Self {
+            min_block: info.min_block,
+            max_block: info.max_block,
+            min_timestamp: info.min_timestamp,
+            max_timestamp: info.max_timestamp,
+            count: info.count.try_into()?,
+            max_limit: info.max_limit.try_into()?,
+        })
+    }
+}
+
+impl From<EventHistoryInfo> for sse::EventHistoryInfo {
+    fn from(info: EventHistoryInfo) -> Self {
+        Self {
+            count: info.count.into(),
+            min_block: info.min_block,
+            max_block: info.max_block,
+            min_timestamp: info.min_timestamp,
+            max_timestamp: info.max_timestamp,
+            max_limit: info.max_limit.into(),
+        }
+    }
+}
+
+/// Fails if `limit` or `offset` don't fit in `u32`.
+impl TryFrom<sse::EventHistoryParams> for GetEventHistoryParams {
+    type Error = TryFromIntError;
+
+    fn try_from(params: sse::EventHistoryParams) -> Result<Self, Self::Error> {
+        Ok(Self {
+            block_start: params.block_start,
+            block_end: params.block_end,
+            timestamp_start: params.timestamp_start,
+            timestamp_end: params.timestamp_end,
+            limit: params.limit.map(TryInto::try_into).transpose()?,
+            offset: params.offset.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<GetEventHistoryParams> for sse::EventHistoryParams {
+    fn from(params: GetEventHistoryParams) -> Self {
+        Self {
+            block_start: params.block_start,
+            block_end: params.block_end,
+            timestamp_start: params.timestamp_start,
+            timestamp_end: params.timestamp_end,
+            limit: params.limit.map(Into::into),
+            offset: params.offset.map(Into::into),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_round_trip() {
+        let event: MevShareEvent =
+            serde_json::from_str(include_str!("fixtures/sse_event.json")).unwrap();
+
+        let sse_event = sse::Event::from(event.clone());
+        assert_eq!(
+            serde_json::to_value(&sse_event).unwrap()["txs"],
+            serde_json::to_value(&event).unwrap()["txs"]
+        );
+
+        let back = MevShareEvent::from(sse_event);
+        assert_eq!(back.hash, event.hash);
+        assert_eq!(
+            back.txs.unwrap()[0].function_selector,
+            event.txs.unwrap()[0].function_selector
+        );
+        assert_eq!(back.logs.unwrap()[0].topics, event.logs.unwrap()[0].topics);
+        assert_eq!(back.mev_gas_price, None);
+
+        let undisclosed = MevShareEvent::from(sse::Event {
+            hash: event.hash,
+            transactions: Vec::new(),
+            logs: Vec::new(),
+        });
+        assert!(undisclosed.txs.is_none() && undisclosed.logs.is_none());
+    }
+
+    #[test]
+    fn test_history_params() {
+        let params = sse::EventHistoryParams::default().with_limit(u64::from(u32::MAX) + 1);
+        assert!(GetEventHistoryParams::try_from(params).is_err());
+
+        let params = GetEventHistoryParams::builder()
+            .block_start(100)
+            .limit(50)
+            .build();
+        let sse_params = sse::EventHistoryParams::from(params);
+        assert_eq!(
+            (sse_params.block_start, sse_params.limit),
+            (Some(100), Some(50))
+        );
+    }
+}
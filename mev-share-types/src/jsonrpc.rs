@@ -0,0 +1,46 @@
+//! The JSON-RPC 2.0 envelopes of the relay requests and responses, around the params and results of the crate types.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JsonRpcRequest<'a> {
+    pub jsonrpc: &'a str,
+    pub id: i32,
+    pub method: &'a str,
+    pub params: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum JsonRpcResponse<T> {
+    Success(JsonRpcResponseSuccess<T>),
+    Error(JsonRpcResponseError),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JsonRpcResponseSuccess<T> {
+    pub jsonrpc: String,
+    pub id: i32,
+    pub result: T,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JsonRpcResponseError {
+    pub jsonrpc: Option<String>,
+    pub id: Option<i32>,
+    pub error: Error,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum Error {
+    Simple(String),
+    Detailed(JsonRpcResponseDetailedError),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JsonRpcResponseDetailedError {
+    code: i32,
+    message: String,
+}
@@ -0,0 +1,61 @@
+//! The request, response and event types of the [MEV-Share] API, and their serialization, without a transport.
+//!
+//! This is the core of [`mev-share-rs`], which sends these types over HTTP with `reqwest` and streams the events over
+//! SSE. Depend on this crate alone to speak MEV-Share over your own transport, or to serve the API relay-side: it
+//! only needs `ethers-core` and `serde`.
+//!
+//! # Example
+//!
+//! ```
+//! use mev_share_types::jsonrpc::JsonRpcRequest;
+//! use mev_share_types::{Hint, MevShareEvent, SendBundleParams};
+//!
+//! let event: MevShareEvent = serde_json::from_str(&sse_data)?;
+//!
+//! let bundle = SendBundleParams::builder()
+//!     .body(vec![Body::Tx { hash: event.hash }, backrun])
+//!     .inclusion(block, None)
+//!     .privacy([Hint::Hash], [])
+//!     .build();
+//!
+//! let request = JsonRpcRequest {
+//!     jsonrpc: "2.0",
+//!     id: 1,
+//!     method: "mev_sendBundle",
+//!     params: serde_json::to_value([bundle])?,
+//! };
+//! my_transport.post(serde_json::to_vec(&request)?).await?;
+//! ```
+//!
+//! ## Features
+//!
+//! * `schema`: JSON Schema of the types, see [`schema`].
+//! * `mev-share-sse`: conversions from and to the event types of Paradigm's [`mev-share-sse`] crate, which depends on
+//!   `reqwest` itself.
+//! * `wasm`: reads the browser clock on `wasm32-unknown-unknown`, see [`helpers::system_now`].
+//!
+//! [MEV-Share]: https://docs.flashbots.net/flashbots-mev-share/overview
+//! [`mev-share-rs`]: https://docs.rs/mev-share-rs
+//! [`mev-share-sse`]: https://docs.rs/mev-share-sse
+
+#![warn(clippy::pedantic)]
+#![allow(
+    clippy::wildcard_imports,
+    clippy::module_name_repetitions,
+    clippy::single_match_else
+)]
+
+mod error;
+pub mod helpers;
+#[cfg(feature = "mev-share-sse")]
+mod interop;
+pub mod jsonrpc;
+pub mod networks;
+mod rpc;
+#[cfg(feature = "schema")]
+pub mod schema;
+mod streaming;
+
+pub use error::{Error, Result};
+pub use rpc::*;
+pub use streaming::*;
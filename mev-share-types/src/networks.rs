@@ -0,0 +1,572 @@
+use crate::Error;
+use crate::{Builder, Builders};
+use ethers_core::types::{Chain, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+
+/// A feature of MEV-Share that not every network offers, see [`NetworkCapabilities`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// The SSE event stream, see `MevShareClient::subscribe_bundles`.
+    EventStream,
+    /// The event history API, see `MevShareClient::get_event_history`.
+    EventHistory,
+    /// The gas fee refund methods, see `MevShareClient::fee_refunds_by_bundle`.
+    FeeRefunds,
+}
+
+impl Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::EventStream => "event stream",
+            Self::EventHistory => "event history",
+            Self::FeeRefunds => "fee refunds",
+        })
+    }
+}
+
+/// What a [`MevShareNetwork`] offers beyond sending and simulating bundles and transactions, so that the client fails
+/// early with [`Error::UnsupportedOnNetwork`] rather than with whatever the endpoint answers for a missing API.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NetworkCapabilities {
+    /// Whether the network serves the SSE event stream.
+    pub event_stream: bool,
+    /// Whether the network serves the event history API.
+    pub event_history: bool,
+    /// Whether the relay of the network pays gas fee refunds.
+    pub fee_refunds: bool,
+}
+
+impl NetworkCapabilities {
+    /// Every capability, e.g. of mainnet.
+    pub const ALL: Self = Self {
+        event_stream: true,
+        event_history: true,
+        fee_refunds: true,
+    };
+
+    /// Whether the network offers `capability`.
+    #[must_use]
+    pub fn supports(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::EventStream => self.event_stream,
+            Capability::EventHistory => self.event_history,
+            Capability::FeeRefunds => self.fee_refunds,
+        }
+    }
+}
+
+impl Default for NetworkCapabilities {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// The MEV-Share endpoints of a chain, see `MevShareClient::network`.
+///
+/// Chains are identified by their id rather than by [`Chain`], so that matchmakers on chains `ethers` doesn't know
+/// of can be reached too.
+#[derive(Debug, Clone)]
+pub struct MevShareNetwork {
+    chain_id: u64,
+    stream_url: String,
+    api_url: String,
+    capabilities: NetworkCapabilities,
+    builders: Option<Builders>,
+}
+
+impl MevShareNetwork {
+    /// A network served by other endpoints than the Flashbots ones, e.g. a self-hosted matchmaker, the relay of a
+    /// fork, or a MEV-Share compatible matchmaker on Gnosis or an L2. See `MevShareClient::new_with_network`.
+    ///
+    /// The network is assumed to offer every capability: restrict them with [`Self::with_capabilities`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let network = MevShareNetwork::custom(
+    ///     Chain::Mainnet,
+    ///     "https://relay.example.org",
+    ///     config.stream_url,
+    /// );
+    /// let client = MevShareClient::new_with_network(auth_wallet, provider, network);
+    ///
+    /// // any chain id
+    /// let gnosis = MevShareNetwork::custom(
+    ///     100,
+    ///     "https://relay.gnosis.example.org",
+    ///     "https://mev-share.gnosis.example.org",
+    /// )
+    ///     .with_builders(Builders::from_iter([Builder::Other("gnosis-builder".to_owned())]));
+    /// ```
+    #[must_use]
+    pub fn custom(
+        chain_id: impl Into<u64>,
+        api_url: impl Into<String>,
+        stream_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            chain_id: chain_id.into(),
+            stream_url: stream_url.into(),
+            api_url: api_url.into(),
+            capabilities: NetworkCapabilities::ALL,
+            builders: None,
+        }
+    }
+
+    /// This network, with its relay JSON-RPC API served at `api_url` instead, e.g. by a private relay proxy.
+    #[must_use]
+    pub fn with_api_url(self, api_url: impl Into<String>) -> Self {
+        Self {
+            api_url: api_url.into(),
+            ..self
+        }
+    }
+
+    /// This network, with its SSE event stream and event history API served at `stream_url` instead.
+    #[must_use]
+    pub fn with_stream_url(self, stream_url: impl Into<String>) -> Self {
+        Self {
+            stream_url: stream_url.into(),
+            ..self
+        }
+    }
+
+    /// This network, offering `capabilities` instead.
+    #[must_use]
+    pub fn with_capabilities(self, capabilities: NetworkCapabilities) -> Self {
+        Self {
+            capabilities,
+            ..self
+        }
+    }
+
+    /// This network, with `builders` as the ones to share bundles with by default, see
+    /// [`crate::Privacy::default_for`], e.g. the builders of a chain other than mainnet.
+    #[must_use]
+    pub fn with_builders(self, builders: Builders) -> Self {
+        Self {
+            builders: Some(builders),
+            ..self
+        }
+    }
+
+    /// The builders to share bundles with by default on this network, if set with [`Self::with_builders`].
+    #[must_use]
+    pub fn builders(&self) -> Option<&Builders> {
+        self.builders.as_ref()
+    }
+
+    /// What this network offers.
+    #[must_use]
+    pub fn capabilities(&self) -> NetworkCapabilities {
+        self.capabilities
+    }
+
+    /// Fails with [`Error::UnsupportedOnNetwork`] if this network doesn't offer `capability`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnsupportedOnNetwork`] if this network doesn't offer `capability`.
+    pub fn require(&self, capability: Capability) -> crate::Result<()> {
+        if self.capabilities.supports(capability) {
+            Ok(())
+        } else {
+            Err(Error::UnsupportedOnNetwork(capability, self.chain_id))
+        }
+    }
+
+    /// Id of the chain this network is on.
+    #[must_use]
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// The chain this network is on, if `ethers` knows of it.
+    #[must_use]
+    pub fn chain(&self) -> Option<Chain> {
+        Chain::try_from(self.chain_id).ok()
+    }
+
+    /// URL of the SSE event stream, also serving the event history API.
+    #[must_use]
+    pub fn stream_url(&self) -> &str {
+        &self.stream_url
+    }
+
+    /// URL of the relay JSON-RPC API, where transactions and bundles are sent.
+    #[must_use]
+    pub fn api_url(&self) -> &str {
+        &self.api_url
+    }
+}
+
+fn mainnet() -> MevShareNetwork {
+    MevShareNetwork::custom(
+        1_u64,
+        "https://relay.flashbots.net",
+        "https://mev-share.flashbots.net",
+    )
+}
+
+fn goerli() -> MevShareNetwork {
+    MevShareNetwork::custom(
+        5_u64,
+        "https://relay-goerli.flashbots.net",
+        "https://mev-share-goerli.flashbots.net",
+    )
+    .with_capabilities(NetworkCapabilities {
+        event_stream: true,
+        event_history: false,
+        fee_refunds: false,
+    })
+}
+
+// fn sepolia() -> MevShareNetwork {
+//     MevShareNetwork::custom(11_155_111_u64, "https://relay-sepolia.flashbots.net", "NOT AVAILABLE YET")
+// }
+
+impl TryFrom<U256> for MevShareNetwork {
+    type Error = crate::Error;
+
+    fn try_from(chain: U256) -> Result<Self, Self::Error> {
+        match chain.as_u64() {
+            1 => Ok(mainnet()),
+            5 => Ok(goerli()),
+            // 11_155_111 => Ok(sepolia()),
+            _ => Err(crate::Error::UnsupportedNetwork(chain)),
+        }
+    }
+}
+
+impl TryFrom<u64> for MevShareNetwork {
+    type Error = crate::Error;
+
+    fn try_from(chain: u64) -> Result<Self, Self::Error> {
+        Self::try_from(U256::from(chain))
+    }
+}
+
+impl TryFrom<Chain> for MevShareNetwork {
+    type Error = crate::Error;
+
+    fn try_from(chain: Chain) -> Result<Self, Self::Error> {
+        Self::try_from(u64::from(chain))
+    }
+}
+
+/// The endpoints of a chain in a [`NetworkTable`], e.g. read from a config file.
+///
+/// For a chain already in the table, the URLs given override its endpoints. For a new chain, both are required.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NetworkConfig {
+    /// Chain id of the network.
+    pub chain_id: u64,
+    /// URL of the relay JSON-RPC API, see [`MevShareNetwork::api_url`].
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// URL of the SSE event stream and event history API, see [`MevShareNetwork::stream_url`].
+    #[serde(default)]
+    pub stream_url: Option<String>,
+    /// What the network offers, see [`MevShareNetwork::capabilities`].
+    #[serde(default)]
+    pub capabilities: Option<NetworkCapabilities>,
+    /// Names of the builders to share bundles with by default, see [`MevShareNetwork::with_builders`] and
+    /// [`Builder::from_name`].
+    #[serde(default)]
+    pub builders: Option<Vec<String>>,
+}
+
+/// The MEV-Share network of each chain id, the Flashbots ones by default, extended or overridden at runtime, e.g.
+/// with a matchmaker on another chain, without forking the crate. See `MevShareClient::new_with_networks`.
+///
+/// # Example
+///
+/// ```
+/// // MEV_SHARE_NETWORK_10_API_URL=https://relay.example.org
+/// // MEV_SHARE_NETWORK_10_STREAM_URL=https://mev-share.example.org
+/// let networks = NetworkTable::from_env()?;
+/// let client = MevShareClient::new_with_networks(auth_wallet, provider, &networks).await?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct NetworkTable(HashMap<u64, MevShareNetwork>);
+
+impl Default for NetworkTable {
+    /// The networks Flashbots serves.
+    fn default() -> Self {
+        Self::empty().with_network(mainnet()).with_network(goerli())
+    }
+}
+
+impl NetworkTable {
+    /// Prefix of the environment variables read by [`Self::from_env`].
+    pub const ENV_PREFIX: &'static str = "MEV_SHARE_NETWORK_";
+
+    /// A table without networks, not even the Flashbots ones.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// The Flashbots networks, overridden and extended by the `MEV_SHARE_NETWORK_<CHAIN_ID>_API_URL` and
+    /// `MEV_SHARE_NETWORK_<CHAIN_ID>_STREAM_URL` environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::with_configs`].
+    pub fn from_env() -> crate::Result<Self> {
+        Self::default().with_configs(Self::configs_from_vars(std::env::vars()))
+    }
+
+    /// This table, with the network of `network.chain_id()` replaced by `network`.
+    #[must_use]
+    pub fn with_network(mut self, network: MevShareNetwork) -> Self {
+        self.0.insert(network.chain_id, network);
+        self
+    }
+
+    /// This table, with the networks of `configs` added or overridden, see [`NetworkConfig`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidParams`] if a network not in the table yet misses an URL.
+    pub fn with_configs(
+        mut self,
+        configs: impl IntoIterator<Item = NetworkConfig>,
+    ) -> crate::Result<Self> {
+        for config in configs {
+            let builders = config.builders.map(|names| {
+                names
+                    .into_iter()
+                    .map(|name| Builder::from_name(&name))
+                    .collect()
+            });
+
+            let mut network = match (
+                self.0.remove(&config.chain_id),
+                config.api_url,
+                config.stream_url,
+            ) {
+                (Some(network), api_url, stream_url) => MevShareNetwork {
+                    api_url: api_url.unwrap_or(network.api_url),
+                    stream_url: stream_url.unwrap_or(network.stream_url),
+                    capabilities: config.capabilities.unwrap_or(network.capabilities),
+                    ..network
+                },
+                (None, Some(api_url), Some(stream_url)) => {
+                    MevShareNetwork::custom(config.chain_id, api_url, stream_url)
+                        .with_capabilities(config.capabilities.unwrap_or_default())
+                }
+                (None, ..) => {
+                    return Err(Error::InvalidParams(format!(
+                        "network {} needs both an API and a stream URL",
+                        config.chain_id
+                    )))
+                }
+            };
+            if builders.is_some() {
+                network.builders = builders;
+            }
+            self = self.with_network(network);
+        }
+
+        Ok(self)
+    }
+
+    /// The network of `chain_id`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnsupportedNetwork`] if the table has no network for `chain_id`.
+    pub fn get(&self, chain_id: impl Into<U256>) -> crate::Result<MevShareNetwork> {
+        let chain_id = chain_id.into();
+
+        u64::try_from(chain_id)
+            .ok()
+            .and_then(|chain_id| self.0.get(&chain_id))
+            .cloned()
+            .ok_or(Error::UnsupportedNetwork(chain_id))
+    }
+
+    /// The networks of the table, in no particular order.
+    pub fn networks(&self) -> impl Iterator<Item = &MevShareNetwork> {
+        self.0.values()
+    }
+
+    /// The [`NetworkConfig`]s of the `MEV_SHARE_NETWORK_*` variables among `vars`.
+    fn configs_from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Vec<NetworkConfig> {
+        let mut configs = BTreeMap::<u64, NetworkConfig>::new();
+
+        for (name, value) in vars {
+            let Some(name) = name.strip_prefix(Self::ENV_PREFIX) else {
+                continue;
+            };
+            let (chain_id, is_api_url) = if let Some(chain_id) = name.strip_suffix("_API_URL") {
+                (chain_id, true)
+            } else if let Some(chain_id) = name.strip_suffix("_STREAM_URL") {
+                (chain_id, false)
+            } else {
+                continue;
+            };
+            let Ok(chain_id) = chain_id.parse() else {
+                continue;
+            };
+
+            let config = configs.entry(chain_id).or_insert_with(|| NetworkConfig {
+                chain_id,
+                ..NetworkConfig::default()
+            });
+            if is_api_url {
+                config.api_url = Some(value);
+            } else {
+                config.stream_url = Some(value);
+            }
+        }
+
+        configs.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_networks() {
+        let network = MevShareNetwork::try_from(Chain::Goerli).unwrap();
+        assert_eq!(network.chain(), Some(Chain::Goerli));
+        assert_eq!(network.api_url(), "https://relay-goerli.flashbots.net");
+
+        assert_eq!(
+            MevShareNetwork::try_from(1).unwrap().chain(),
+            Some(Chain::Mainnet)
+        );
+        assert!(matches!(
+            MevShareNetwork::try_from(Chain::Polygon),
+            Err(crate::Error::UnsupportedNetwork(_))
+        ));
+
+        let network = MevShareNetwork::custom(
+            Chain::AnvilHardhat,
+            "http://localhost:8080",
+            "http://localhost:8081/",
+        );
+        assert_eq!(network.chain(), Some(Chain::AnvilHardhat));
+        assert_eq!(network.api_url(), "http://localhost:8080");
+        assert_eq!(network.stream_url(), "http://localhost:8081/");
+
+        let network = MevShareNetwork::try_from(Chain::Mainnet)
+            .unwrap()
+            .with_api_url("https://relay-proxy.example.org");
+        assert_eq!(network.api_url(), "https://relay-proxy.example.org");
+        assert_eq!(network.stream_url(), "https://mev-share.flashbots.net");
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let goerli = MevShareNetwork::try_from(Chain::Goerli).unwrap();
+        assert!(goerli.require(Capability::EventStream).is_ok());
+        assert!(matches!(
+            goerli.require(Capability::EventHistory),
+            Err(Error::UnsupportedOnNetwork(Capability::EventHistory, 5))
+        ));
+
+        let custom = MevShareNetwork::custom(
+            Chain::Optimism,
+            "http://localhost:8080",
+            "http://localhost:8081",
+        )
+        .with_capabilities(NetworkCapabilities {
+            fee_refunds: false,
+            ..NetworkCapabilities::ALL
+        });
+        assert!(custom.require(Capability::EventHistory).is_ok());
+        assert_eq!(
+            custom
+                .require(Capability::FeeRefunds)
+                .unwrap_err()
+                .to_string(),
+            "fee refunds is not available on chain 10"
+        );
+    }
+
+    #[test]
+    fn test_network_table() {
+        let vars = [
+            (
+                "MEV_SHARE_NETWORK_1_API_URL",
+                "https://relay-proxy.example.org",
+            ),
+            ("MEV_SHARE_NETWORK_10_API_URL", "https://relay.example.org"),
+            (
+                "MEV_SHARE_NETWORK_10_STREAM_URL",
+                "https://mev-share.example.org",
+            ),
+            ("MEV_SHARE_NETWORK_X_API_URL", "ignored"),
+            ("PATH", "/usr/bin"),
+        ]
+        .map(|(name, value)| (name.to_owned(), value.to_owned()));
+
+        let networks = NetworkTable::default()
+            .with_configs(NetworkTable::configs_from_vars(vars))
+            .unwrap();
+        assert_eq!(networks.networks().count(), 3);
+
+        let mainnet = networks.get(1).unwrap();
+        assert_eq!(mainnet.api_url(), "https://relay-proxy.example.org");
+        assert_eq!(mainnet.stream_url(), "https://mev-share.flashbots.net");
+        assert_eq!(networks.get(10).unwrap().chain(), Some(Chain::Optimism));
+        assert!(matches!(
+            networks.get(137),
+            Err(Error::UnsupportedNetwork(_))
+        ));
+
+        let incomplete = NetworkConfig {
+            chain_id: 137,
+            api_url: Some("https://relay.example.org".to_owned()),
+            ..NetworkConfig::default()
+        };
+        assert!(matches!(
+            NetworkTable::default().with_configs([incomplete]),
+            Err(Error::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_other_chains() {
+        let gnosis: NetworkConfig = serde_json::from_value(serde_json::json!({
+            "chain_id": 100,
+            "api_url": "https://relay.gnosis.example.org",
+            "stream_url": "https://mev-share.gnosis.example.org",
+            "capabilities": { "event_stream": true, "event_history": false, "fee_refunds": false },
+            "builders": ["gnosis-builder", "flashbots"]
+        }))
+        .unwrap();
+        let unknown_chain = NetworkConfig {
+            chain_id: 123_456_789,
+            api_url: Some("http://localhost:8080".to_owned()),
+            stream_url: Some("http://localhost:8081".to_owned()),
+            ..NetworkConfig::default()
+        };
+
+        let networks = NetworkTable::empty()
+            .with_configs([gnosis, unknown_chain])
+            .unwrap();
+
+        let gnosis = networks.get(100).unwrap();
+        assert_eq!(gnosis.chain(), Some(Chain::Gnosis));
+        assert!(!gnosis.capabilities().event_history);
+        assert_eq!(
+            gnosis.builders(),
+            Some(&Builders::from_iter([
+                Builder::Other("gnosis-builder".to_owned()),
+                Builder::Flashbots
+            ]))
+        );
+
+        let unknown_chain = networks.get(123_456_789).unwrap();
+        assert_eq!(unknown_chain.chain_id(), 123_456_789);
+        assert_eq!(unknown_chain.chain(), None);
+    }
+}
@@ -0,0 +1,126 @@
+use super::Builder;
+use std::collections::HashSet;
+
+/// A set of builders to share bundles and transactions with, e.g. one of the maintained presets.
+///
+/// The presets follow [`Builder::KNOWN`], updated with each release, so that users don't maintain their own list.
+///
+/// # Example
+///
+/// ```
+/// let bundle = SendBundleParams::builder()
+///     .body(body)
+///     .inclusion(block, None)
+///     .privacy([Hint::Hash], Builders::all_known())
+///     .build();
+///
+/// let without_titan = Builders::high_inclusion().without(&Builder::Titan);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Builders(HashSet<Builder>);
+
+impl Builders {
+    /// Every builder known to this client, see [`Builder::KNOWN`].
+    #[must_use]
+    pub fn all_known() -> Self {
+        Self(Builder::KNOWN.into_iter().collect())
+    }
+
+    /// The Flashbots builder only, the most private choice.
+    #[must_use]
+    pub fn flashbots_only() -> Self {
+        Self([Builder::Flashbots].into())
+    }
+
+    /// The builders landing the most blocks, as of this release: fast inclusion, sharing with few parties.
+    #[must_use]
+    pub fn high_inclusion() -> Self {
+        Self(
+            [
+                Builder::Flashbots,
+                Builder::BeaverBuild,
+                Builder::Rsync,
+                Builder::Titan,
+                Builder::Builder0x69,
+            ]
+            .into(),
+        )
+    }
+
+    /// Adds `builder` to the set.
+    #[must_use]
+    pub fn with(mut self, builder: Builder) -> Self {
+        self.0.insert(builder);
+        self
+    }
+
+    /// Removes `builder` from the set.
+    #[must_use]
+    pub fn without(mut self, builder: &Builder) -> Self {
+        self.0.remove(builder);
+        self
+    }
+
+    /// Whether `builder` is in the set.
+    #[must_use]
+    pub fn contains(&self, builder: &Builder) -> bool {
+        self.0.contains(builder)
+    }
+
+    /// Number of builders in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the set is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The builders of the set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &Builder> {
+        self.0.iter()
+    }
+}
+
+impl IntoIterator for Builders {
+    type Item = Builder;
+    type IntoIter = std::collections::hash_set::IntoIter<Builder>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl FromIterator<Builder> for Builders {
+    fn from_iter<T: IntoIterator<Item = Builder>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Privacy;
+
+    #[test]
+    fn test_builder_presets() {
+        assert_eq!(Builders::all_known().len(), Builder::KNOWN.len());
+        assert!(Builders::high_inclusion()
+            .iter()
+            .all(|builder| Builders::all_known().contains(builder)));
+
+        let privacy = Privacy::new([], Builders::flashbots_only());
+        assert_eq!(privacy.builders, Some([Builder::Flashbots].into()));
+
+        let builders = Builders::flashbots_only()
+            .with(Builder::Titan)
+            .without(&Builder::Flashbots);
+        assert_eq!(
+            builders.into_iter().collect::<Vec<_>>(),
+            vec![Builder::Titan]
+        );
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(feature = "schema")]
+use crate::schema::HexHash;
+use ethers_core::types::*;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for RPC `eth_cancelPrivateTransaction` requests.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CancelPrivateTransactionParams {
+    /// Hash of the private transaction to cancel.
+    #[cfg_attr(feature = "schema", schemars(with = "HexHash"))]
+    pub tx_hash: TxHash,
+}
+
+/// Response for RPC `eth_cancelPrivateTransaction` requests, see
+/// `MevShareClient::cancel_private_transaction`.
+///
+/// The relay answers with a boolean: this is it, named.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "bool", into = "bool")]
+pub enum CancelPrivateTransactionResponse {
+    /// The relay withdrew the transaction.
+    Cancelled,
+    /// The relay didn't withdraw the transaction, e.g. because it already landed or expired.
+    NotCancelled,
+}
+
+impl CancelPrivateTransactionResponse {
+    /// Whether the transaction was withdrawn.
+    #[must_use]
+    pub fn is_cancelled(self) -> bool {
+        self == Self::Cancelled
+    }
+}
+
+impl From<bool> for CancelPrivateTransactionResponse {
+    fn from(cancelled: bool) -> Self {
+        if cancelled {
+            Self::Cancelled
+        } else {
+            Self::NotCancelled
+        }
+    }
+}
+
+impl From<CancelPrivateTransactionResponse> for bool {
+    fn from(response: CancelPrivateTransactionResponse) -> Self {
+        response.is_cancelled()
+    }
+}
+
+/// Parameters for RPC `eth_cancelBundle` requests.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CancelBundleParams {
+    /// UUID the bundle was submitted with.
+    pub replacement_uuid: String,
+}
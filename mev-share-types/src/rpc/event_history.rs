@@ -0,0 +1,247 @@
+use crate::helpers::system_now;
+#[cfg(feature = "schema")]
+use crate::schema::{HexHash, HexQuantity};
+use crate::{MevShareEvent, Transaction};
+use ethers_core::types::*;
+use serde::{Deserialize, Serialize};
+use std::ops::{Bound, RangeBounds};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+use typed_builder::TypedBuilder;
+
+/// MEV-Share API response from '/history/info'. See `MevShareClient::get_event_history_info`.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[allow(clippy::module_name_repetitions)]
+pub struct EventHistoryInfo {
+    pub min_block: u64,
+    pub max_block: u64,
+    pub min_timestamp: u64,
+    pub max_timestamp: u64,
+    pub count: u32,
+    pub max_limit: u32,
+}
+
+/// MEV-Share API parameteres for requests to '/history'. See `MevShareClient::get_event_history`.
+#[derive(Clone, Serialize, Default, TypedBuilder, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(field_defaults(default, setter(strip_option),))]
+#[serde(rename_all = "camelCase")]
+pub struct GetEventHistoryParams {
+    pub block_start: Option<u64>,
+    pub block_end: Option<u64>,
+    pub timestamp_start: Option<u64>,
+    pub timestamp_end: Option<u64>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+impl GetEventHistoryParams {
+    /// Params for the events broadcast in the last `duration`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let events = client.get_event_history(GetEventHistoryParams::last(Duration::from_secs(90))).await?;
+    /// ```
+    #[must_use]
+    pub fn last(duration: Duration) -> Self {
+        let now = system_now();
+        Self::default().time_range(now - duration..=now)
+    }
+
+    /// Params for the events broadcast in the last `hours` hours.
+    #[must_use]
+    pub fn last_hours(hours: u64) -> Self {
+        Self::last(Duration::from_secs(hours * 60 * 60))
+    }
+
+    /// Params for the events broadcast in the last `days` days.
+    #[must_use]
+    pub fn last_days(days: u64) -> Self {
+        Self::last_hours(days * 24)
+    }
+
+    /// Restricts the params to the events broadcast within `range`, setting `timestamp_start` and `timestamp_end`.
+    ///
+    /// Accepts anything convertible to a [`SystemTime`], e.g. `chrono::DateTime`. Timestamps are truncated to the second.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let params = GetEventHistoryParams::builder()
+    ///     .limit(100)
+    ///     .build()
+    ///     .time_range(Utc.with_ymd_and_hms(2023, 6, 1, 0, 0, 0).unwrap()..);
+    /// ```
+    #[must_use]
+    pub fn time_range<T>(self, range: impl RangeBounds<T>) -> Self
+    where
+        T: Into<SystemTime> + Clone,
+    {
+        let timestamp_start = match range.start_bound() {
+            Bound::Included(start) => Some(unix_timestamp(start.clone())),
+            Bound::Excluded(start) => Some(unix_timestamp(start.clone()) + 1),
+            Bound::Unbounded => None,
+        };
+
+        let timestamp_end = match range.end_bound() {
+            Bound::Included(end) => Some(unix_timestamp(end.clone())),
+            Bound::Excluded(end) => Some(unix_timestamp(end.clone()).saturating_sub(1)),
+            Bound::Unbounded => None,
+        };
+
+        Self {
+            timestamp_start,
+            timestamp_end,
+            ..self
+        }
+    }
+
+    /// Clamps `limit`, `block_start` and `timestamp_start` to the bounds advertised by `info`, warning about each adjustment,
+    /// since the relay rejects out-of-bounds requests altogether.
+    #[must_use]
+    pub fn clamp(self, info: &EventHistoryInfo) -> Self {
+        let limit = match self.limit {
+            Some(limit) if limit > info.max_limit => {
+                warn!(limit, info.max_limit, "clamping event history limit");
+                Some(info.max_limit)
+            }
+            limit => limit,
+        };
+
+        let block_start = match self.block_start {
+            Some(block) if block < info.min_block => {
+                warn!(block, info.min_block, "clamping event history block_start");
+                Some(info.min_block)
+            }
+            block => block,
+        };
+
+        let timestamp_start = match self.timestamp_start {
+            Some(timestamp) if timestamp < info.min_timestamp => {
+                warn!(
+                    timestamp,
+                    info.min_timestamp, "clamping event history timestamp_start"
+                );
+                Some(info.min_timestamp)
+            }
+            timestamp => timestamp,
+        };
+
+        Self {
+            block_start,
+            timestamp_start,
+            limit,
+            ..self
+        }
+    }
+}
+
+/// Seconds since the unix epoch, or 0 for times before it.
+fn unix_timestamp(time: impl Into<SystemTime>) -> u64 {
+    time.into()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// MEV-Share API return from '/history'. See `MevShareClient::get_event_history`.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EventHistory {
+    pub block: u64,
+    pub timestamp: u64,
+    pub hint: EventHint,
+}
+
+/// See [`EventHistory::hint`].
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EventHint {
+    pub txs: Option<Vec<Transaction>>,
+    #[cfg_attr(feature = "schema", schemars(with = "HexHash"))]
+    pub hash: H256,
+    #[cfg_attr(feature = "schema", schemars(with = "Option<Vec<serde_json::Value>>"))]
+    pub logs: Option<Vec<Log>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
+    pub gas_used: Option<U256>,
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
+    pub mev_gas_price: Option<U256>,
+}
+
+impl std::fmt::Display for EventHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "hint {:?} at block {} ({}): {} txs, {} logs",
+            self.hint.hash,
+            self.block,
+            self.timestamp,
+            self.hint.txs.as_ref().map_or(0, Vec::len),
+            self.hint.logs.as_ref().map_or(0, Vec::len)
+        )
+    }
+}
+
+impl From<EventHistory> for MevShareEvent {
+    fn from(event: EventHistory) -> Self {
+        Self {
+            hash: event.hint.hash,
+            logs: event.hint.logs,
+            txs: event.hint.txs,
+            mev_gas_price: event.hint.mev_gas_price,
+            gas_used: event.hint.gas_used,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp() {
+        let info = EventHistoryInfo {
+            min_block: 100,
+            max_block: 200,
+            min_timestamp: 1000,
+            max_timestamp: 2000,
+            count: 10,
+            max_limit: 500,
+        };
+
+        let params = GetEventHistoryParams::builder()
+            .block_start(50)
+            .limit(1000)
+            .offset(7)
+            .build()
+            .clamp(&info);
+
+        assert_eq!(params.block_start, Some(100));
+        assert_eq!(params.limit, Some(500));
+        assert_eq!(params.offset, Some(7));
+        assert_eq!(params.timestamp_start, None);
+    }
+
+    #[test]
+    fn test_time_range() {
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+
+        let params = GetEventHistoryParams::default().time_range(at(100)..at(200));
+        assert_eq!(params.timestamp_start, Some(100));
+        assert_eq!(params.timestamp_end, Some(199));
+
+        let params = GetEventHistoryParams::default().time_range(at(100)..);
+        assert_eq!(params.timestamp_start, Some(100));
+        assert_eq!(params.timestamp_end, None);
+
+        let params = GetEventHistoryParams::last_hours(2);
+        assert_eq!(
+            params.timestamp_end.unwrap() - params.timestamp_start.unwrap(),
+            2 * 60 * 60
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Bundle and private transaction files: the JSON params of `mev_sendBundle` and `eth_sendPrivateTransaction`, with a
+//! spec `version`, so that submissions can be reviewed, versioned along the strategy code, and replayed, e.g. with
+//! the `mev-share` command line tool.
+
+use super::{Body, RefundConfig, SendBundleParams, SendTransactionParams, Validity};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Versions of the bundle and transaction file formats this crate can read and write.
+pub const SUPPORTED_VERSIONS: [&str; 1] = ["v0.1"];
+
+fn check_version(version: &str) -> Result<()> {
+    if SUPPORTED_VERSIONS.contains(&version) {
+        Ok(())
+    } else {
+        Err(Error::InvalidParams(format!(
+            "unsupported version {version:?}, expected one of {SUPPORTED_VERSIONS:?}"
+        )))
+    }
+}
+
+fn check_refund_configs(refund_configs: &[RefundConfig]) -> Result<()> {
+    let total: u32 = refund_configs.iter().map(|config| config.percent).sum();
+    if total > 100 {
+        return Err(Error::InvalidRefund(total));
+    }
+    Ok(())
+}
+
+/// Pretty-prints `value` to `path`, one field per line, so that files diff well in code review.
+fn write_json(path: &Path, value: &impl Serialize) -> Result<()> {
+    let mut json = serde_json::to_string_pretty(value)?;
+    json.push('\n');
+    Ok(fs::write(path, json)?)
+}
+
+impl SendBundleParams {
+    /// Parses and [validates](Self::validate) a bundle file.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Json`] if `json` is not a bundle.
+    /// * Same as [`Self::validate`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        let params: Self = serde_json::from_str(json)?;
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Reads, parses and [validates](Self::validate) the bundle file at `path`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let bundle = SendBundleParams::from_json_file("bundles/backrun.json")?;
+    /// let pending_bundle = client.send_bundle(bundle).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Io`] if the file can't be read.
+    /// * Same as [`Self::from_json`].
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// [Validates](Self::validate) the bundle and writes it to `path`, pretty-printed.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Io`] if the file can't be written.
+    /// * Same as [`Self::validate`].
+    pub fn to_json_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.validate()?;
+        write_json(path.as_ref(), self)
+    }
+
+    /// Checks that the relay would accept the bundle, as far as can be told without sending it.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::InvalidParams`] if the `version` is not one of [`SUPPORTED_VERSIONS`], the `body` is empty,
+    ///   `inclusion.max_block` is before `inclusion.block`, or a refund points past the body.
+    /// * [`crate::Error::InvalidRefund`] if a refund exceeds 100%, or the refund configs add up to more than 100%.
+    pub fn validate(&self) -> Result<()> {
+        check_version(&self.version)?;
+
+        if self.body.is_empty() {
+            return Err(Error::InvalidParams("empty bundle body".to_owned()));
+        }
+
+        if let Some(max_block) = self.inclusion.max_block {
+            if max_block < self.inclusion.block {
+                return Err(Error::InvalidParams(format!(
+                    "inclusion max block {max_block} is before block {}",
+                    self.inclusion.block
+                )));
+            }
+        }
+
+        if let Some(Validity {
+            refund,
+            refund_config,
+        }) = &self.validity
+        {
+            for refund in refund {
+                if refund.body_idx as usize >= self.body.len() {
+                    return Err(Error::InvalidParams(format!(
+                        "refund of body {} out of a body of {} items",
+                        refund.body_idx,
+                        self.body.len()
+                    )));
+                }
+                if refund.percent > 100 {
+                    return Err(Error::InvalidRefund(refund.percent));
+                }
+            }
+            check_refund_configs(refund_config)?;
+        }
+
+        for body in &self.body {
+            if let Body::Bundle(bundle) = body {
+                bundle.validate()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`SendTransactionParams`] file: the params of `eth_sendPrivateTransaction`, with a `version` like bundles.
+#[derive(Serialize, Deserialize)]
+struct TransactionFile {
+    version: String,
+    #[serde(flatten)]
+    params: SendTransactionParams,
+}
+
+impl SendTransactionParams {
+    /// Parses and [validates](Self::validate) a private transaction file.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Json`] if `json` is not a private transaction file.
+    /// * [`crate::Error::InvalidParams`] if its `version` is not one of [`SUPPORTED_VERSIONS`].
+    /// * Same as [`Self::validate`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        let file: TransactionFile = serde_json::from_str(json)?;
+        check_version(&file.version)?;
+        file.params.validate()?;
+        Ok(file.params)
+    }
+
+    /// Reads, parses and [validates](Self::validate) the private transaction file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Io`] if the file can't be read.
+    /// * Same as [`Self::from_json`].
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// [Validates](Self::validate) the transaction and writes it to `path`, pretty-printed, with the latest
+    /// [`SUPPORTED_VERSIONS`].
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Io`] if the file can't be written.
+    /// * Same as [`Self::validate`].
+    pub fn to_json_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.validate()?;
+        write_json(
+            path.as_ref(),
+            &TransactionFile {
+                version: SUPPORTED_VERSIONS[SUPPORTED_VERSIONS.len() - 1].to_owned(),
+                params: self.clone(),
+            },
+        )
+    }
+
+    /// Checks that the relay would accept the transaction, as far as can be told without sending it.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::InvalidParams`] if the transaction is empty.
+    /// * [`crate::Error::InvalidRefund`] if the refunds add up to more than 100%.
+    pub fn validate(&self) -> Result<()> {
+        if self.tx.is_empty() {
+            return Err(Error::InvalidParams("empty transaction".to_owned()));
+        }
+
+        if let Some(validity) = self
+            .preferences
+            .as_ref()
+            .and_then(|preferences| preferences.validity.as_ref())
+        {
+            check_refund_configs(&validity.refund)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Builder, Hint, Refund};
+    use ethers_core::types::{Bytes, TxHash};
+
+    #[test]
+    fn test_bundle_file() {
+        let path = std::env::temp_dir().join(format!("bundle-{}.json", std::process::id()));
+        let bundle = SendBundleParams::builder()
+            .inclusion(100, Some(102.into()))
+            .body(vec![
+                Body::tx(TxHash::zero()),
+                Body::signed(Bytes::from(vec![0x02, 0xf8]), false),
+            ])
+            .validity(
+                vec![Refund {
+                    body_idx: 0,
+                    percent: 90,
+                }],
+                vec![],
+            )
+            .privacy([Hint::Hash], [Builder::Other("my-builder".to_owned())])
+            .build();
+
+        bundle.to_json_file(&path).unwrap();
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        let loaded = SendBundleParams::from_json_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&bundle).unwrap()
+        );
+        assert!(json.ends_with("}\n"));
+
+        let future_version = json.replace("v0.1", "v9.9");
+        assert!(matches!(
+            SendBundleParams::from_json(&future_version),
+            Err(Error::InvalidParams(_))
+        ));
+
+        let bad_refund = json.replace(r#""bodyIdx": 0"#, r#""bodyIdx": 2"#);
+        assert!(matches!(
+            SendBundleParams::from_json(&bad_refund),
+            Err(Error::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_transaction_file() {
+        let path = std::env::temp_dir().join(format!("tx-{}.json", std::process::id()));
+        let params = SendTransactionParams::builder()
+            .tx(Bytes::from(vec![0x02, 0xf8]))
+            .max_block_number(100)
+            .preferences([Hint::Hash], [Builder::Other("my-builder".to_owned())])
+            .build();
+
+        params.to_json_file(&path).unwrap();
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        let loaded = SendTransactionParams::from_json_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&params).unwrap()
+        );
+        assert!(json.contains(r#""version": "v0.1""#));
+
+        assert!(matches!(
+            SendTransactionParams::from_json(r#"{"tx": "0x02f8"}"#),
+            Err(Error::Json(_))
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+use super::Body;
+use ethers_core::types::TxHash;
+use ethers_core::utils::keccak256;
+use std::slice::Iter;
+
+/// Iterator over the hashes of a bundle body.
+/// See [`HahsIter`] for usage.
+pub struct BodyHashIterator<'lt> {
+    stack: Vec<Iter<'lt, Body>>,
+}
+
+impl<'lt> BodyHashIterator<'lt> {
+    pub fn new(bodies: &'lt [Body]) -> Self {
+        BodyHashIterator {
+            stack: vec![bodies.iter()],
+        }
+    }
+}
+
+impl Iterator for BodyHashIterator<'_> {
+    type Item = TxHash;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(bodies) = self.stack.last_mut() {
+            match bodies.next() {
+                Some(body) => match body {
+                    Body::Tx { hash } => return Some(*hash),
+                    Body::Signed { tx, .. } => return Some(keccak256(tx).into()),
+                    Body::Bundle(bundle) => {
+                        self.stack.push(bundle.body.iter());
+                        continue;
+                    }
+                },
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Trait for iterating over the hashes of a bundle.
+pub trait HashesIter {
+    fn hashes(&self) -> BodyHashIterator<'_>;
+}
+
+impl HashesIter for Vec<Body> {
+    fn hashes(&self) -> BodyHashIterator<'_> {
+        BodyHashIterator::new(self.as_slice())
+    }
+}
+
+impl HashesIter for &[Body] {
+    fn hashes(&self) -> BodyHashIterator<'_> {
+        BodyHashIterator::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SendBundleParams;
+
+    #[test]
+    fn test_hashes() {
+        let mut txs = [[0_u8; 64]; 7];
+        let mut hashes = [TxHash::default(); 7];
+
+        for (i, (tx, hash)) in txs.iter_mut().zip(&mut hashes).enumerate() {
+            tx[63] += u8::try_from(i).unwrap();
+            *hash = TxHash(keccak256(*tx));
+        }
+
+        let tx = |i: usize| Body::Signed {
+            tx: txs[i].into(),
+            can_revert: false,
+        };
+
+        let unsigned_tx = |i: usize| Body::Tx { hash: hashes[i] };
+
+        let bundle = SendBundleParams {
+            body: vec![
+                tx(0),
+                tx(1),
+                unsigned_tx(2),
+                Body::Bundle(Box::new(SendBundleParams {
+                    body: vec![tx(3), tx(4), unsigned_tx(5)],
+                    ..Default::default()
+                })),
+                tx(6),
+            ],
+            ..Default::default()
+        };
+
+        assert!(bundle.body.hashes().eq(hashes));
+    }
+}
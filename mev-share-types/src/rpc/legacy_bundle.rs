@@ -0,0 +1,234 @@
+use crate::helpers::DecimalU256;
+#[cfg(feature = "schema")]
+use crate::schema::{HexAddress, HexData, HexHash, HexQuantity};
+use ethers_core::types::*;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use typed_builder::TypedBuilder;
+
+/// Parameters for RPC `eth_sendBundle` requests, the bundle format that predates `mev_sendBundle`, still the only one
+/// some builders accept. See `MevShareClient::send_legacy_bundle`.
+///
+/// # Example
+///
+/// ```
+/// let bundle = EthSendBundleParams::builder()
+///     .txs(vec![signed_tx_1, signed_tx_2])
+///     .block_number(block + 1)
+///     .replacement_uuid(Uuid::new_v4().to_string())
+///     .build();
+/// ```
+#[derive(Clone, Serialize, Deserialize, Debug, Default, TypedBuilder)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EthSendBundleParams {
+    /// Signed transactions, executed in order.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<HexData>"))]
+    pub txs: Vec<Bytes>,
+
+    /// Block the bundle is valid for.
+    #[builder(setter(into))]
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub block_number: U64,
+
+    /// Minimum timestamp, in seconds, of the block the bundle is valid for.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_timestamp: Option<u64>,
+
+    /// Maximum timestamp, in seconds, of the block the bundle is valid for.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_timestamp: Option<u64>,
+
+    /// Hashes of the transactions of `txs` that are allowed to revert.
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<HexHash>"))]
+    pub reverting_tx_hashes: Vec<TxHash>,
+
+    /// UUID to replace the bundle with a later submission, or withdraw it with `eth_cancelBundle`.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement_uuid: Option<String>,
+}
+
+/// Parameters for RPC `eth_callBundle` requests: simulates a legacy bundle on top of the state of a block. See
+/// `MevShareClient::call_bundle`.
+///
+/// # Example
+///
+/// ```
+/// // simulates `bundle` on top of the latest block, as it would execute in the block it targets
+/// let simulation = client.call_bundle(EthCallBundleParams::from(&bundle)).await?;
+/// ```
+#[derive(Clone, Serialize, Deserialize, Debug, TypedBuilder)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallBundleParams {
+    /// Signed transactions, executed in order.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<HexData>"))]
+    pub txs: Vec<Bytes>,
+
+    /// Block the bundle is simulated in.
+    #[builder(setter(into))]
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub block_number: U64,
+
+    /// Block whose state the bundle is simulated on top of, e.g. [`BlockNumber::Latest`].
+    #[builder(default = BlockNumber::Latest, setter(into))]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub state_block_number: BlockNumber,
+
+    /// Timestamp, in seconds, of the simulated block. Defaults to the one of the state block.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+}
+
+impl From<&EthSendBundleParams> for EthCallBundleParams {
+    /// Simulates `bundle` in the block it targets, on top of the latest block.
+    fn from(bundle: &EthSendBundleParams) -> Self {
+        Self {
+            txs: bundle.txs.clone(),
+            block_number: bundle.block_number,
+            state_block_number: BlockNumber::Latest,
+            timestamp: bundle.min_timestamp,
+        }
+    }
+}
+
+/// Response for RPC `eth_callBundle` requests. See `MevShareClient::call_bundle`.
+///
+/// Amounts are returned by the relay as decimal strings, in wei.
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallBundleResponse {
+    /// Hash of the bundle.
+    #[cfg_attr(feature = "schema", schemars(with = "HexHash"))]
+    pub bundle_hash: TxHash,
+    /// Effective gas price of the bundle: what the bundle pays the block builder per unit of gas.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub bundle_gas_price: U256,
+    /// Balance change of the coinbase: gas fees and direct payments.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub coinbase_diff: U256,
+    /// Direct payments to the coinbase.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub eth_sent_to_coinbase: U256,
+    /// Gas fees paid to the coinbase.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub gas_fees: U256,
+    /// Result of each transaction, in order.
+    pub results: Vec<EthCallBundleTxResult>,
+    /// Block whose state the bundle was simulated on top of.
+    pub state_block_number: u64,
+    /// Gas used by the whole bundle.
+    pub total_gas_used: u64,
+}
+
+impl EthCallBundleResponse {
+    /// Whether every transaction succeeded.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(EthCallBundleTxResult::is_success)
+    }
+
+    /// The first transaction that failed, if any.
+    #[must_use]
+    pub fn first_failure(&self) -> Option<&EthCallBundleTxResult> {
+        self.results.iter().find(|result| !result.is_success())
+    }
+}
+
+/// See [`EthCallBundleResponse::results`].
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallBundleTxResult {
+    /// Hash of the transaction.
+    #[cfg_attr(feature = "schema", schemars(with = "HexHash"))]
+    pub tx_hash: TxHash,
+    /// Sender of the transaction.
+    #[cfg_attr(feature = "schema", schemars(with = "HexAddress"))]
+    pub from_address: Address,
+    /// Recipient of the transaction, `None` for contract creations.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexAddress>"))]
+    pub to_address: Option<Address>,
+    /// Gas used by the transaction.
+    pub gas_used: u64,
+    /// Gas price of the transaction.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub gas_price: U256,
+    /// Gas fees paid to the coinbase.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub gas_fees: U256,
+    /// Balance change of the coinbase: gas fees and direct payments.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub coinbase_diff: U256,
+    /// Direct payments to the coinbase.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub eth_sent_to_coinbase: U256,
+    /// Return data of a successful transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexData>"))]
+    pub value: Option<Bytes>,
+    /// Why the transaction failed, e.g. `"execution reverted"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Revert reason, if the transaction reverted with one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert: Option<String>,
+}
+
+impl EthCallBundleTxResult {
+    /// Whether the transaction succeeded.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.error.is_none() && self.revert.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_eth_send_bundle_params() {
+        let bundle = EthSendBundleParams::builder()
+            .txs(vec![Bytes::from(vec![0x02, 0xf8])])
+            .block_number(100)
+            .max_timestamp(1_700_000_000)
+            .replacement_uuid("2e5bd2f4-3a4c-4f35-9bc7-5a0b5a4a6f1c")
+            .build();
+
+        assert_eq!(
+            serde_json::to_value(&bundle).unwrap(),
+            json!({
+                "txs": ["0x02f8"],
+                "blockNumber": "0x64",
+                "maxTimestamp": 1_700_000_000,
+                "replacementUuid": "2e5bd2f4-3a4c-4f35-9bc7-5a0b5a4a6f1c",
+            })
+        );
+
+        let call = EthCallBundleParams::from(&bundle);
+        assert_eq!(
+            serde_json::to_value(&call).unwrap(),
+            json!({ "txs": ["0x02f8"], "blockNumber": "0x64", "stateBlockNumber": "latest" })
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use super::{Body, SendBundleParams};
+use crate::{Error, Result};
+use ethers_core::types::U64;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+/// Structural limits a relay enforces on `mev_sendBundle` requests, to reject bundles locally, with a
+/// [`RelayLimit`] saying what's wrong, rather than with an opaque relay error. See
+/// [`SendBundleParams::check_limits`].
+///
+/// `None` disables a limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RelayLimits {
+    /// Maximum number of items in a bundle body, nested bundles included.
+    pub max_body_size: Option<usize>,
+    /// Maximum depth of nested bundles: `0` for none, `1` for bundles of bundles.
+    pub max_nesting_depth: Option<usize>,
+    /// Maximum distance from the current block to `inclusion.block`.
+    pub max_block_distance: Option<u64>,
+    /// Maximum distance from `inclusion.block` to `inclusion.max_block`.
+    pub max_block_range: Option<u64>,
+    /// Maximum number of hints disclosed by a bundle, or any of its nested bundles.
+    pub max_hints: Option<usize>,
+}
+
+impl RelayLimits {
+    /// The limits of the Flashbots MEV-Share node.
+    pub const FLASHBOTS: Self = Self {
+        max_body_size: Some(50),
+        max_nesting_depth: Some(1),
+        max_block_distance: Some(5),
+        max_block_range: Some(30),
+        max_hints: None,
+    };
+
+    /// No limits at all.
+    pub const NONE: Self = Self {
+        max_body_size: None,
+        max_nesting_depth: None,
+        max_block_distance: None,
+        max_block_range: None,
+        max_hints: None,
+    };
+}
+
+impl Default for RelayLimits {
+    fn default() -> Self {
+        Self::FLASHBOTS
+    }
+}
+
+/// The limit of [`RelayLimits`] a bundle exceeds. See [`Error::RelayLimit`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RelayLimit {
+    /// [`RelayLimits::max_body_size`] is exceeded.
+    BodySize { size: usize, max: usize },
+    /// [`RelayLimits::max_nesting_depth`] is exceeded.
+    NestingDepth { depth: usize, max: usize },
+    /// [`RelayLimits::max_block_distance`] is exceeded.
+    BlockDistance {
+        block: U64,
+        current_block: U64,
+        max: u64,
+    },
+    /// [`RelayLimits::max_block_range`] is exceeded.
+    BlockRange {
+        block: U64,
+        max_block: U64,
+        max: u64,
+    },
+    /// [`RelayLimits::max_hints`] is exceeded.
+    Hints { count: usize, max: usize },
+}
+
+impl Display for RelayLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BodySize { size, max } => write!(f, "body of {size} items > max {max}"),
+            Self::NestingDepth { depth, max } => {
+                write!(f, "bundles nested {depth} deep > max {max}")
+            }
+            Self::BlockDistance {
+                block,
+                current_block,
+                max,
+            } => write!(
+                f,
+                "block {block} is more than {max} blocks after the current block {current_block}"
+            ),
+            Self::BlockRange {
+                block,
+                max_block,
+                max,
+            } => write!(
+                f,
+                "blocks {block}..={max_block} span more than {max} blocks"
+            ),
+            Self::Hints { count, max } => write!(f, "{count} hints > max {max}"),
+        }
+    }
+}
+
+impl SendBundleParams {
+    /// Checks the bundle against the structural `limits` of the relay, and, if `current_block` is known, that it
+    /// doesn't target a block too far ahead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let current_block = provider.get_block_number().await?;
+    /// bundle.check_limits(&RelayLimits::FLASHBOTS, Some(current_block))?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::RelayLimit`] with the first limit the bundle exceeds.
+    pub fn check_limits(&self, limits: &RelayLimits, current_block: Option<U64>) -> Result<()> {
+        self.check_nested_limits(limits, 0)?;
+
+        if let Some(max) = limits.max_body_size {
+            let size = self.body_size();
+            if size > max {
+                return Err(Error::RelayLimit(RelayLimit::BodySize { size, max }));
+            }
+        }
+
+        let block = self.inclusion.block;
+        if let (Some(max), Some(current_block)) = (limits.max_block_distance, current_block) {
+            if block > current_block + max {
+                return Err(Error::RelayLimit(RelayLimit::BlockDistance {
+                    block,
+                    current_block,
+                    max,
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the limits that apply to every level of nesting.
+    fn check_nested_limits(&self, limits: &RelayLimits, depth: usize) -> Result<()> {
+        if let Some(max) = limits.max_nesting_depth {
+            if depth > max {
+                return Err(Error::RelayLimit(RelayLimit::NestingDepth { depth, max }));
+            }
+        }
+
+        if let (Some(max), Some(max_block)) = (limits.max_block_range, self.inclusion.max_block) {
+            if max_block > self.inclusion.block + max {
+                return Err(Error::RelayLimit(RelayLimit::BlockRange {
+                    block: self.inclusion.block,
+                    max_block,
+                    max,
+                }));
+            }
+        }
+
+        if let Some(max) = limits.max_hints {
+            let count = self
+                .privacy
+                .as_ref()
+                .and_then(|privacy| privacy.hints.as_ref())
+                .map_or(0, HashSet::len);
+            if count > max {
+                return Err(Error::RelayLimit(RelayLimit::Hints { count, max }));
+            }
+        }
+
+        for body in &self.body {
+            if let Body::Bundle(bundle) = body {
+                bundle.check_nested_limits(limits, depth + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Number of items in the body, those of nested bundles included.
+    fn body_size(&self) -> usize {
+        self.body
+            .iter()
+            .map(|body| match body {
+                Body::Bundle(bundle) => bundle.body_size(),
+                _ => 1,
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Hint;
+    use ethers_core::types::TxHash;
+
+    fn bundle(body: Vec<Body>) -> SendBundleParams {
+        SendBundleParams::builder()
+            .inclusion(100, Some(110.into()))
+            .body(body)
+            .build()
+    }
+
+    #[test]
+    fn test_check_limits() {
+        let limits = RelayLimits::FLASHBOTS;
+        let backrun = bundle(vec![
+            Body::tx(TxHash::zero()),
+            Body::signed(vec![0x02], false),
+        ]);
+        assert!(backrun.check_limits(&limits, Some(98.into())).is_ok());
+
+        assert!(matches!(
+            backrun.check_limits(&limits, Some(90.into())),
+            Err(Error::RelayLimit(RelayLimit::BlockDistance { max: 5, .. }))
+        ));
+
+        let too_deep = bundle(vec![Body::Bundle(Box::new(bundle(vec![Body::Bundle(
+            Box::new(backrun.clone()),
+        )])))]);
+        assert!(matches!(
+            too_deep.check_limits(&limits, None),
+            Err(Error::RelayLimit(RelayLimit::NestingDepth {
+                depth: 2,
+                max: 1
+            }))
+        ));
+
+        let too_big = bundle(vec![Body::tx(TxHash::zero()); 51]);
+        assert!(matches!(
+            too_big.check_limits(&limits, None),
+            Err(Error::RelayLimit(RelayLimit::BodySize {
+                size: 51,
+                max: 50
+            }))
+        ));
+
+        let mut too_long = backrun.clone();
+        too_long.inclusion.max_block = Some(200.into());
+        assert!(matches!(
+            too_long.check_limits(&limits, None),
+            Err(Error::RelayLimit(RelayLimit::BlockRange { max: 30, .. }))
+        ));
+
+        let mut chatty = backrun;
+        chatty.privacy = Some(crate::Privacy::new([Hint::Calldata, Hint::Logs], []));
+        let hints = RelayLimits {
+            max_hints: Some(1),
+            ..RelayLimits::NONE
+        };
+        assert!(matches!(
+            chatty.check_limits(&hints, None),
+            Err(Error::RelayLimit(RelayLimit::Hints { count: 2, max: 1 }))
+        ));
+    }
+}
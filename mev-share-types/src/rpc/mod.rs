@@ -0,0 +1,27 @@
+mod builders;
+mod cancel;
+mod event_history;
+mod files;
+mod hashes;
+mod legacy_bundle;
+mod limits;
+mod refund;
+mod registry;
+mod send_bundle;
+mod send_transaction;
+mod simulate_bundle;
+mod stats;
+
+pub use builders::*;
+pub use cancel::*;
+pub use event_history::*;
+pub use files::SUPPORTED_VERSIONS;
+pub use hashes::*;
+pub use legacy_bundle::*;
+pub use limits::*;
+pub use refund::*;
+pub use registry::*;
+pub use send_bundle::*;
+pub use send_transaction::*;
+pub use simulate_bundle::*;
+pub use stats::*;
@@ -0,0 +1,253 @@
+#[cfg(feature = "schema")]
+use crate::schema::{HexAddress, HexHash, HexQuantity};
+use ethers_core::types::{Address, TransactionReceipt, TxHash, U256, U64};
+use ethers_core::utils::format_ether;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use typed_builder::TypedBuilder;
+
+/// Flashbots API response for RPC `flashbots_getFeeRefundsByBundle` and `flashbots_getFeeRefundsByBlock` requests.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FeeRefunds {
+    /// The refunds, one per bundle or transaction and recipient.
+    pub refunds: Vec<FeeRefund>,
+    /// Where to resume listing from, if there are more refunds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// A gas fee refund owed by Flashbots for a landed bundle or transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FeeRefund {
+    /// Bundle, or transaction, hash.
+    #[cfg_attr(feature = "schema", schemars(with = "HexHash"))]
+    pub hash: TxHash,
+    /// Refunded amount, in wei.
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub amount: U256,
+    /// Block the bundle landed in.
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub block_number: U64,
+    /// Whether the refund was paid yet.
+    pub status: RefundStatus,
+    /// Address the refund is, or was, paid to.
+    #[cfg_attr(feature = "schema", schemars(with = "HexAddress"))]
+    pub recipient: Address,
+}
+
+/// Parameters for RPC `flashbots_getFeeRefundsByRecipient` requests, see
+/// `MevShareClient::fee_refunds_by_recipient`.
+///
+/// # Example
+///
+/// ```
+/// let params = GetFeeRefundsByRecipientParams::builder()
+///     .recipient(searcher)
+///     .block_range_from(17_000_000)
+///     .build();
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, TypedBuilder)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeRefundsByRecipientParams {
+    /// Address the refunds are paid to.
+    #[cfg_attr(feature = "schema", schemars(with = "HexAddress"))]
+    pub recipient: Address,
+    /// First block of the refunds to list.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
+    pub block_range_from: Option<U64>,
+    /// Last block of the refunds to list.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
+    pub block_range_to: Option<U64>,
+    /// Where to resume listing from, see [`FeeRefunds::cursor`].
+    #[builder(default, setter(strip_option, into))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Flashbots API response for RPC `flashbots_getFeeRefundTotalsByRecipient` requests: what a recipient has accrued.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FeeRefundTotals {
+    /// Refunds owed, to be paid in a later batch, in wei.
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub pending: U256,
+    /// Refunds paid so far, in wei.
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub received: U256,
+    /// Last block accounted for in the totals.
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub max_block_number: U64,
+}
+
+impl FeeRefundTotals {
+    /// Refunds accrued, pending or paid, in wei.
+    #[must_use]
+    pub fn total(&self) -> U256 {
+        self.pending.saturating_add(self.received)
+    }
+}
+
+impl Display for FeeRefundTotals {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ETH pending, {} ETH received up to block {}",
+            format_ether(self.pending),
+            format_ether(self.received),
+            self.max_block_number
+        )
+    }
+}
+
+/// See [`FeeRefund::status`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum RefundStatus {
+    /// Owed, to be paid in a later batch.
+    Pending,
+    /// Paid to the recipient.
+    Received,
+    /// Any status this client doesn't know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+impl FeeRefunds {
+    /// Sum of the refunds with `status`, in wei.
+    #[must_use]
+    pub fn total(&self, status: RefundStatus) -> U256 {
+        self.refunds
+            .iter()
+            .filter(|refund| refund.status == status)
+            .fold(U256::zero(), |total, refund| total + refund.amount)
+    }
+}
+
+impl Display for FeeRefund {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ETH {:?} to {:?} for {:?} in block {}",
+            format_ether(self.amount),
+            self.status,
+            self.recipient,
+            self.hash,
+            self.block_number
+        )
+    }
+}
+
+/// How gas fee refunds are computed for a block, to estimate what a bundle will be refunded before the relay reports it
+/// with `MevShareClient::fee_refunds_by_bundle`.
+///
+/// Per block, the refund pool is the difference between the winning bid and the runner-up, i.e. what the bundles
+/// overpaid to win the block. It is split among the bundles of the block in proportion of their contribution, the fees
+/// and coinbase payments they paid, each bundle getting at most `share_percent` of its own contribution.
+///
+/// Estimates only: the relay's figures are authoritative, and the rule changes now and then.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RefundRule {
+    /// Share of the refund pool paid back, in percent.
+    pub share_percent: u32,
+}
+
+impl Default for RefundRule {
+    fn default() -> Self {
+        Self { share_percent: 90 }
+    }
+}
+
+impl RefundRule {
+    /// Applies the rule to a block won with `bid` over `runner_up`, returning the refund of each of the
+    /// `contributions`, by bundle hash, in wei.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let contribution = bundle_contribution(&receipts, block.base_fee_per_gas.unwrap_or_default(), coinbase_payment);
+    /// let refunds = RefundRule::default().apply(bid, runner_up, &[(bundle_hash, contribution)]);
+    /// ```
+    #[must_use]
+    pub fn apply(
+        &self,
+        bid: U256,
+        runner_up: U256,
+        contributions: &[(TxHash, U256)],
+    ) -> Vec<(TxHash, U256)> {
+        let total = contributions
+            .iter()
+            .fold(U256::zero(), |total, (_, contribution)| {
+                total.saturating_add(*contribution)
+            });
+        let pool = bid.saturating_sub(runner_up).min(total);
+
+        contributions
+            .iter()
+            .map(|(hash, contribution)| {
+                let refund = if total.is_zero() {
+                    U256::zero()
+                } else {
+                    pool * contribution / total * self.share_percent / 100
+                };
+                (*hash, refund)
+            })
+            .collect()
+    }
+}
+
+/// What a landed bundle contributed to the value of its block, in wei: the priority fees of its transactions, from
+/// their `receipts` and the `base_fee` of the block, plus its direct `coinbase_payment`s.
+#[must_use]
+pub fn bundle_contribution(
+    receipts: &[TransactionReceipt],
+    base_fee: U256,
+    coinbase_payment: U256,
+) -> U256 {
+    receipts.iter().fold(coinbase_payment, |total, receipt| {
+        let priority_fee = receipt
+            .effective_gas_price
+            .unwrap_or_default()
+            .saturating_sub(base_fee);
+        total + receipt.gas_used.unwrap_or_default() * priority_fee
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refund_rule() {
+        let gwei = U256::exp10(9);
+        let receipt = TransactionReceipt {
+            gas_used: Some(100_000.into()),
+            effective_gas_price: Some(gwei * 32),
+            ..Default::default()
+        };
+        let contribution = bundle_contribution(&[receipt], gwei * 30, gwei * 1_000_000);
+        assert_eq!(contribution, gwei * 1_200_000);
+
+        let (a, b) = (TxHash::random(), TxHash::random());
+        let refunds = RefundRule::default().apply(
+            U256::exp10(18),
+            U256::exp10(18) - gwei * 1_000_000,
+            &[(a, gwei * 3_000_000), (b, gwei * 1_000_000)],
+        );
+        assert_eq!(refunds, vec![(a, gwei * 675_000), (b, gwei * 225_000)]);
+
+        // nothing overpaid, nothing refunded
+        let refunds = RefundRule::default().apply(gwei, gwei * 2, &[(a, gwei)]);
+        assert_eq!(refunds, vec![(a, U256::zero())]);
+    }
+}
@@ -0,0 +1,113 @@
+use super::Builder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Where Flashbots publishes the builders registered to receive MEV-Share order flow.
+pub const BUILDER_REGISTRY_URL: &str =
+    "https://raw.githubusercontent.com/flashbots/dowg/main/builder-registrations.json";
+
+/// A builder registered to receive MEV-Share order flow, see [`BuilderRegistry`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BuilderRegistration {
+    /// Name to share bundles with the builder by, see [`Builder::from_name`].
+    pub name: String,
+    /// URL of the builder RPC the relay forwards the order flow to.
+    #[serde(default)]
+    pub rpc: Option<String>,
+    /// Versions of the MEV-Share API the builder supports.
+    #[serde(default, rename = "supported-apis")]
+    pub supported_apis: Vec<String>,
+}
+
+/// The builders currently registered with Flashbots, as published at [`BUILDER_REGISTRY_URL`]. `mev-share-rs` fetches
+/// it with `fetch_builder_registry`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct BuilderRegistry(pub Vec<BuilderRegistration>);
+
+impl BuilderRegistry {
+    /// The registered builders, newly registered ones as [`Builder::Other`].
+    #[must_use]
+    pub fn builders(&self) -> HashSet<Builder> {
+        self.0
+            .iter()
+            .map(|registration| Builder::from_name(&registration.name))
+            .collect()
+    }
+
+    /// The registered builders this release of the client doesn't know of yet.
+    pub fn unknown(&self) -> impl Iterator<Item = &BuilderRegistration> {
+        self.0.iter().filter(|registration| {
+            matches!(Builder::from_name(&registration.name), Builder::Other(_))
+        })
+    }
+}
+
+impl Builder {
+    /// The builder MEV-Share knows by `name`, as serialized, or [`Builder::Other`]. Case-insensitive.
+    #[must_use]
+    pub fn from_name(name: &str) -> Self {
+        Builder::KNOWN
+            .into_iter()
+            .chain([Builder::Default])
+            .find(|builder| {
+                serde_json::to_value(builder).is_ok_and(|known| {
+                    known
+                        .as_str()
+                        .is_some_and(|known| known.eq_ignore_ascii_case(name))
+                })
+            })
+            .unwrap_or_else(|| Builder::Other(name.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_names() {
+        for builder in Builder::KNOWN.into_iter().chain([Builder::Default]) {
+            let name = serde_json::to_value(&builder).unwrap();
+            assert_eq!(Builder::from_name(name.as_str().unwrap()), builder);
+        }
+
+        assert_eq!(Builder::from_name("titan"), Builder::Titan);
+        assert_eq!(Builder::from_name("beaverbuild.org"), Builder::BeaverBuild);
+        assert_eq!(
+            Builder::from_name("new-builder"),
+            Builder::Other("new-builder".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_builder_registry() {
+        let registry: BuilderRegistry = serde_json::from_str(
+            r#"[
+                {"name": "flashbots", "rpc": "https://relay.flashbots.net", "supported-apis": ["v0.1"]},
+                {"name": "beaverbuild.org", "rpc": "https://rpc.beaverbuild.org", "supported-apis": ["v0.1"]},
+                {"name": "titan", "rpc": "https://rpc.titanbuilder.xyz"},
+                {"name": "new-builder"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            registry.builders(),
+            [
+                Builder::Flashbots,
+                Builder::BeaverBuild,
+                Builder::Titan,
+                Builder::Other("new-builder".to_owned()),
+            ]
+            .into()
+        );
+        assert_eq!(
+            registry
+                .unknown()
+                .map(|registration| registration.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["new-builder"]
+        );
+    }
+}
@@ -0,0 +1,454 @@
+use super::Builders;
+use crate::networks::MevShareNetwork;
+#[cfg(feature = "schema")]
+use crate::schema::{HexAddress, HexData, HexHash, HexQuantity};
+use ethers_core::types::*;
+use ethers_core::utils::keccak256;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use typed_builder::TypedBuilder;
+
+/// Parameters for RPC `mev_sendBundle` requests. See `MevShareClient::send_bundle`.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, TypedBuilder)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SendBundleParams {
+    /// Smart bundle spec version
+    #[builder(default = "v0.1".to_owned(), setter(into))]
+    pub version: String,
+
+    /// Conditions for the bundle to be considered for inclusion in a block, evaluated _before_ the bundle is placed in a block
+    #[builder(setter(transform = |block: impl Into<U64>, max_block: impl Into<Option<U64>>| Inclusion { block: block.into(), max_block: max_block.into() }))]
+    pub inclusion: Inclusion,
+
+    /// Transactions that make up the bundle. `hash` refers to a transaction hash from the MevShare event stream
+    pub body: Vec<Body>,
+
+    /// Conditions for bundle to be considered for inclusion in a block, evaluated _after_ the bundle is placed in the block
+    #[builder(default, setter(transform = |refund: Vec<Refund>, refund_config: Vec<RefundConfig>| Some(Validity { refund, refund_config })))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validity: Option<Validity>,
+
+    /// Privacy settings. See [`Hint`] and [`Builder`] for more info, and [`Privacy::new`] for the defaults.
+    #[builder(default, setter(transform = |hints: impl IntoIterator<Item = Hint>, builders: impl IntoIterator<Item = Builder>| Some(Privacy::new(hints, builders))))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privacy: Option<Privacy>,
+
+    #[builder(default, setter(transform = |origin_id: impl Into<String>| Some(Metadata { origin_id: Some(origin_id.into()) })))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// UUID to replace the bundle with a later submission using the same UUID, or to withdraw it with
+    /// `MevShareClient::cancel_bundle`.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement_uuid: Option<String>,
+}
+
+/// Response for RPC `mev_sendBundle` requests. See `MevShareClient::send_bundle`.
+#[derive(Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SendBundleResponse {
+    #[cfg_attr(feature = "schema", schemars(with = "HexHash"))]
+    pub bundle_hash: TxHash,
+}
+
+impl SendBundleParams {
+    /// The hash the relay identifies the bundle by, computed locally as the MEV-Share spec defines it: the keccak256
+    /// of the concatenated hashes of the body, the transaction hashes of transactions and the bundle hashes of nested
+    /// bundles.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let bundle_hash = bundle.bundle_hash();
+    /// let stats = client.get_bundle_stats(bundle_hash, block).await?;
+    /// ```
+    #[must_use]
+    pub fn bundle_hash(&self) -> TxHash {
+        let mut hashes = Vec::with_capacity(32 * self.body.len());
+        for body in &self.body {
+            let hash = match body {
+                Body::Tx { hash } => *hash,
+                Body::Signed { tx, .. } => keccak256(tx).into(),
+                Body::Bundle(bundle) => bundle.bundle_hash(),
+            };
+            hashes.extend_from_slice(hash.as_bytes());
+        }
+
+        keccak256(hashes).into()
+    }
+}
+
+/// See [`SendBundleParams::validity`].
+#[derive(Clone, Serialize, Deserialize, Default, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Validity {
+    /// Conditions for receiving refunds (MEV kickbacks)
+    pub refund: Vec<Refund>,
+    /// Specifies how refund should be paid if bundle is used by another searcher
+    pub refund_config: Vec<RefundConfig>,
+}
+
+/// See [`Validity::refund`].
+#[derive(Clone, Serialize, Deserialize, Default, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Refund {
+    /// Index of entry in `body` to which the refund percentage applies.
+    pub body_idx: u32,
+    /// Minimum refund percentage required for this bundle to be eligible for use by another searcher.
+    pub percent: u32,
+}
+
+/// See [`SendBundleParams::privacy`].
+#[derive(Clone, Serialize, Deserialize, Default, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Privacy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hints: Option<HashSet<Hint>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builders: Option<HashSet<Builder>>,
+}
+
+impl Privacy {
+    /// Shares `hints` with `builders`.
+    ///
+    /// Leaving either empty omits it from the request, so that the relay applies its defaults.
+    pub fn new(
+        hints: impl IntoIterator<Item = Hint>,
+        builders: impl IntoIterator<Item = Builder>,
+    ) -> Self {
+        let hints: HashSet<_> = hints.into_iter().collect();
+        let builders: HashSet<_> = builders.into_iter().collect();
+
+        Self {
+            hints: (!hints.is_empty()).then_some(hints),
+            builders: (!builders.is_empty()).then_some(builders),
+        }
+    }
+
+    /// Sensible privacy for bundles sent on `network`, e.g. as the client-wide default, see
+    /// `MevShareClient::with_default_privacy`:
+    ///
+    /// * on networks configured with [`MevShareNetwork::with_builders`], the transaction hashes and the logs the relay
+    ///   shares by default, with those builders,
+    /// * on mainnet, the same hints with the [`Builders::high_inclusion`] builders,
+    /// * on Goerli, the same hints with the Flashbots builder, the only one building there,
+    /// * elsewhere, the relay defaults.
+    #[must_use]
+    pub fn default_for(network: &MevShareNetwork) -> Self {
+        let hints = [Hint::Hash, Hint::DefaultLogs];
+
+        if let Some(builders) = network.builders() {
+            return Self::new(hints, builders.clone());
+        }
+        match network.chain() {
+            Some(Chain::Mainnet) => Self::new(hints, Builders::high_inclusion()),
+            Some(Chain::Goerli) => Self::new(hints, Builders::flashbots_only()),
+            _ => Self::default(),
+        }
+    }
+}
+
+/// List of builders to share transactions/bundles with that are currently [supported by Flashbots].
+///
+/// ## Usage:
+///
+/// ```
+/// let bundle = BundleParams::builder()
+///     ...
+///     .privacy(/* hints */, [
+///         Builder::Flashbots,
+///         Builder::Rsync,
+///         Builder::Other("a non-flashbots builder".to_owned())
+///     ])
+///     .build();
+/// ```
+///
+/// [supported by Flashbots]: https://docs.flashbots.net/flashbots-auction/searchers/advanced/rpc-endpoint#mev_sendbundle
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Builder {
+    Default,
+    Flashbots,
+    Rsync,
+    #[serde(rename = "beaverbuild.org")]
+    BeaverBuild,
+    Builder0x69,
+    #[serde(rename = "Titan")]
+    Titan,
+    #[serde(rename = "EigenPhi")]
+    EigenPhi,
+    #[serde(rename = "boba-builder")]
+    BobaBuilder,
+    Other(String),
+}
+
+impl Builder {
+    /// Every builder known to this client, i.e. all but [`Builder::Default`] and [`Builder::Other`].
+    pub const KNOWN: [Builder; 7] = [
+        Builder::Flashbots,
+        Builder::Rsync,
+        Builder::BeaverBuild,
+        Builder::Builder0x69,
+        Builder::Titan,
+        Builder::EigenPhi,
+        Builder::BobaBuilder,
+    ];
+}
+
+/// See [`SendBundleParams::metadata`].
+#[derive(Clone, Serialize, Deserialize, Default, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin_id: Option<String>,
+}
+
+/// See [`SendBundleParams::inclusion`].
+#[derive(Clone, Serialize, Deserialize, Default, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Inclusion {
+    /// Target block number in which to include the bundle.
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub block: U64,
+    /// Maximum block height in which the bundle can be included.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
+    pub max_block: Option<U64>,
+}
+
+/// See [`Validity::refund_config`].
+#[derive(Clone, Serialize, Deserialize, Default, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RefundConfig {
+    /// The address that receives this portion of the refund.
+    ///
+    /// To pay an ENS name, resolve it with `EnsResolver::refund_config` or
+    /// `MevShareClient::resolve_name`.
+    #[cfg_attr(feature = "schema", schemars(with = "HexAddress"))]
+    pub address: Address,
+    /// Percentage of refund to be paid to `address`.
+    /// Set this to `100` unless splitting refunds between multiple recipients.
+    pub percent: u32,
+}
+
+/// Transactions that make up the bundle.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", untagged)]
+pub enum Body {
+    // A transaction hash from the MEV-Share event stream.
+    Tx {
+        #[cfg_attr(feature = "schema", schemars(with = "HexHash"))]
+        hash: TxHash,
+    },
+    // A signed transaction.
+    #[serde(rename_all = "camelCase")]
+    Signed {
+        #[cfg_attr(feature = "schema", schemars(with = "HexData"))]
+        tx: Bytes,
+        // accepted for the bundle files written before the field was camel-cased, like the spec requires
+        #[serde(alias = "can_revert")]
+        can_revert: bool,
+    },
+    // A nested bundle
+    Bundle(Box<SendBundleParams>),
+}
+
+impl Body {
+    /// A transaction hash from the MEV-Share event stream, e.g. the transaction to backrun.
+    #[must_use]
+    pub fn tx(hash: impl Into<TxHash>) -> Self {
+        Self::Tx { hash: hash.into() }
+    }
+
+    /// A signed transaction, e.g. `tx.rlp_signed(&signature)`.
+    #[must_use]
+    pub fn signed(tx: impl Into<Bytes>, can_revert: bool) -> Self {
+        Self::Signed {
+            tx: tx.into(),
+            can_revert,
+        }
+    }
+}
+
+/// Privacy settings for the submitted bundle.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    /// Share the calldata of the transaction.
+    Calldata,
+    /// Share the contract address of the transaction.
+    ContractAddress,
+    /// Share the 4byte function selector of the transaction.
+    FunctionSelector,
+    /// Share the logs emitted by the transaction.
+    Logs,
+    /// Share tx hashes of the transactions in the bundle.
+    TxHash,
+    TransactionHash,
+    /// Share the hash of the bundle/transaction being sent.
+    Hash,
+    /// Share the logs of the transaction the relay shares by default, e.g. the swap events of well-known DEX pools,
+    /// rather than all of them as [`Hint::Logs`] does.
+    DefaultLogs,
+    /// Share only the logs the relay singles out, e.g. the swap events of well-known DEX pools.
+    SpecialLogs,
+    /// Any hint this client doesn't know about yet, sent as is, e.g. `Hint::Custom("full".into())`.
+    #[serde(untagged)]
+    Custom(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_conversions() {
+        let bundle = SendBundleParams::builder()
+            .body(vec![
+                Body::tx(TxHash::zero()),
+                Body::signed(vec![0x02, 0xf8], false),
+            ])
+            .inclusion(100, None)
+            .build();
+
+        assert_eq!(bundle.inclusion.block, U64::from(100));
+        assert_eq!(bundle.inclusion.max_block, None);
+        assert!(matches!(&bundle.body[1], Body::Signed { tx, .. } if tx.len() == 2));
+
+        let bundle = SendBundleParams::builder()
+            .body(vec![])
+            .inclusion(U64::from(100), U64::from(103))
+            .build();
+
+        assert_eq!(bundle.inclusion.max_block, Some(U64::from(103)));
+    }
+
+    #[test]
+    fn test_bundle_hash() {
+        // expected hashes computed outside of this crate, with `openssl dgst -keccak-256` over the concatenated body
+        // hashes: the hash of the transaction `0x02f8` is 0x0ddf8a6d…3a1e70
+        let hash = |hex: &str| hex.parse::<TxHash>().unwrap();
+
+        let empty = SendBundleParams::builder()
+            .body(vec![])
+            .inclusion(100, None)
+            .build();
+        assert_eq!(
+            empty.bundle_hash(),
+            hash("0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+        );
+
+        let backrun = Bytes::from(vec![0x02, 0xf8]);
+        let inner = SendBundleParams::builder()
+            .body(vec![
+                Body::tx(TxHash::repeat_byte(0x11)),
+                Body::signed(backrun.clone(), false),
+            ])
+            .inclusion(100, None)
+            .build();
+        assert_eq!(
+            inner.bundle_hash(),
+            hash("0xade41540666c964ad55d5b7292766ec1c10417fb937c66cfeb28381fab70a0a5")
+        );
+
+        // nested bundles count by their own bundle hash, not by their transactions
+        let outer = SendBundleParams::builder()
+            .body(vec![
+                Body::Bundle(Box::new(inner)),
+                Body::signed(backrun, false),
+            ])
+            .inclusion(100, None)
+            .build();
+        assert_eq!(
+            outer.bundle_hash(),
+            hash("0x14c47e06a123d826bac80864e3f849606258b5fa6a9a905d77e50968b4dd2bab")
+        );
+    }
+
+    #[test]
+    fn test_replacement_uuid() {
+        let bundle = SendBundleParams::builder()
+            .body(vec![])
+            .inclusion(100, None)
+            .replacement_uuid("5c9a1e73-0b1d-4f4e-9d6a-2f3b8c7e1a90")
+            .build();
+
+        let json = serde_json::to_value(&bundle).unwrap();
+        assert_eq!(
+            json["replacementUuid"],
+            "5c9a1e73-0b1d-4f4e-9d6a-2f3b8c7e1a90"
+        );
+
+        let bundle = SendBundleParams::builder()
+            .body(vec![])
+            .inclusion(100, None)
+            .build();
+        assert!(serde_json::to_value(&bundle)
+            .unwrap()
+            .get("replacementUuid")
+            .is_none());
+    }
+
+    #[test]
+    fn test_privacy() {
+        let privacy = Privacy::new([Hint::Calldata, Hint::Logs, Hint::Calldata], []);
+        assert_eq!(privacy.hints.map(|hints| hints.len()), Some(2));
+        assert_eq!(privacy.builders, None);
+
+        let json = serde_json::to_value(Privacy::new([], [Builder::Flashbots])).unwrap();
+        assert_eq!(json, serde_json::json!({ "builders": ["flashbots"] }));
+
+        let goerli = Privacy::default_for(&MevShareNetwork::try_from(Chain::Goerli).unwrap());
+        assert_eq!(goerli.builders, Some([Builder::Flashbots].into()));
+        assert!(goerli
+            .hints
+            .is_some_and(|hints| hints.contains(&Hint::Hash)));
+
+        let mainnet = Privacy::default_for(&MevShareNetwork::try_from(Chain::Mainnet).unwrap());
+        assert_eq!(
+            mainnet.builders.map(|builders| builders.len()),
+            Some(Builders::high_inclusion().len())
+        );
+
+        let custom = MevShareNetwork::custom(
+            Chain::Gnosis,
+            "http://localhost:8080",
+            "http://localhost:8081",
+        );
+        assert!(Privacy::default_for(&custom).builders.is_none());
+
+        let custom = custom.with_builders(Builders::flashbots_only());
+        assert_eq!(
+            Privacy::default_for(&custom).builders,
+            Some([Builder::Flashbots].into())
+        );
+    }
+
+    #[test]
+    fn test_hints() {
+        let hints = [
+            Hint::DefaultLogs,
+            Hint::SpecialLogs,
+            Hint::Custom("full".to_owned()),
+        ];
+        let json = serde_json::to_value(&hints).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!(["default_logs", "special_logs", "full"])
+        );
+
+        let hints: Vec<Hint> = serde_json::from_value(serde_json::json!(["logs", "full"])).unwrap();
+        assert_eq!(hints, vec![Hint::Logs, Hint::Custom("full".to_owned())]);
+    }
+}
@@ -0,0 +1,171 @@
+use super::{Builder, Builders, Hint, Privacy, RefundConfig};
+#[cfg(feature = "schema")]
+use crate::schema::{HexData, HexQuantity};
+use ethers_core::types::*;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+/// Parameters for RPC `eth_sendPrivateTransaction` requests. See `MevShareClient::send_private_transaction`.
+#[derive(Clone, Default, Debug, Serialize, Deserialize, TypedBuilder)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SendTransactionParams {
+    /// The signed transaction bytes.
+    #[builder(setter(into))]
+    #[cfg_attr(feature = "schema", schemars(with = "HexData"))]
+    pub tx: Bytes,
+
+    /// Maximum block number for the transaction to be included in.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
+    pub max_block_number: Option<U64>,
+
+    #[builder(default, setter(transform = |hints: impl IntoIterator<Item = Hint>, builders: impl IntoIterator<Item = Builder>| Some(Preferences { fast: true, privacy: Privacy::new(hints, builders), validity: None })))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferences: Option<Preferences>,
+}
+
+/// See [`SendTransactionParams`].
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Preferences {
+    pub fast: bool,
+    pub privacy: Privacy,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validity: Option<TransactionValidity>,
+}
+
+/// See [`Preferences::validity`].
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TransactionValidity {
+    /// Who receives the MEV refund of the transaction, in which proportion. Percentages can't add up to more than 100.
+    pub refund: Vec<RefundConfig>,
+}
+
+/// Hints shared by `MevShareClient::send_protected_transaction`, the Flashbots Protect defaults:
+/// enough for searchers to backrun the transaction, but not its calldata.
+pub const PROTECT_HINTS: [Hint; 4] = [
+    Hint::Hash,
+    Hint::ContractAddress,
+    Hint::FunctionSelector,
+    Hint::Logs,
+];
+
+/// Ready-made privacy settings for wallets, so that end users can pick a trade-off without learning the hint matrix.
+///
+/// # Example
+///
+/// ```
+/// let params = SendTransactionParams::builder()
+///     .tx(signed_tx)
+///     .preferences(Preset::MaxPrivacy.hints(), Preset::MaxPrivacy.builders())
+///     .build();
+///
+/// // or, on existing params
+/// params.preferences = Some(Preset::MaxSpeed.into());
+/// ```
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// Shares everything with every known builder: the most backrunning opportunities, the biggest refunds,
+    /// and the fastest inclusion.
+    MaxSpeed,
+    /// The Flashbots Protect defaults: [`PROTECT_HINTS`] with every builder.
+    #[default]
+    Protect,
+    /// Shares only the transaction hash, with Flashbots only: searchers can't backrun the transaction,
+    /// and inclusion may take longer.
+    MaxPrivacy,
+}
+
+impl Preset {
+    /// Returns the hints shared by this preset.
+    #[must_use]
+    pub fn hints(self) -> Vec<Hint> {
+        match self {
+            Self::MaxSpeed => vec![
+                Hint::Calldata,
+                Hint::ContractAddress,
+                Hint::FunctionSelector,
+                Hint::Logs,
+                Hint::Hash,
+            ],
+            Self::Protect => PROTECT_HINTS.to_vec(),
+            Self::MaxPrivacy => vec![Hint::Hash],
+        }
+    }
+
+    /// Returns the builders this preset shares with.
+    #[must_use]
+    pub fn builders(self) -> Vec<Builder> {
+        match self {
+            Self::MaxSpeed | Self::Protect => Builders::all_known().into_iter().collect(),
+            Self::MaxPrivacy => Builders::flashbots_only().into_iter().collect(),
+        }
+    }
+}
+
+impl From<Preset> for Privacy {
+    fn from(preset: Preset) -> Self {
+        Privacy::new(preset.hints(), preset.builders())
+    }
+}
+
+impl From<Preset> for Preferences {
+    fn from(preset: Preset) -> Self {
+        Preferences {
+            fast: true,
+            privacy: preset.into(),
+            validity: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_refund_preferences() {
+        let preferences = Preferences {
+            fast: true,
+            privacy: Privacy::new([Hint::Hash], []),
+            validity: Some(TransactionValidity {
+                refund: vec![RefundConfig {
+                    address: Address::zero(),
+                    percent: 90,
+                }],
+            }),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&preferences).unwrap(),
+            json!({
+                "fast": true,
+                "privacy": { "hints": ["hash"] },
+                "validity": { "refund": [{ "address": Address::zero(), "percent": 90 }] },
+            })
+        );
+    }
+
+    #[test]
+    fn test_presets() {
+        let privacy = Privacy::from(Preset::MaxPrivacy);
+        assert_eq!(privacy.hints, Some([Hint::Hash].into()));
+        assert_eq!(privacy.builders, Some([Builder::Flashbots].into()));
+
+        let preferences = Preferences::from(Preset::MaxSpeed);
+        assert!(preferences.fast);
+        assert!(preferences.validity.is_none());
+        assert!(preferences.privacy.hints.unwrap().contains(&Hint::Calldata));
+        assert_eq!(
+            preferences.privacy.builders.unwrap().len(),
+            Builder::KNOWN.len()
+        );
+
+        let privacy = Privacy::from(Preset::default());
+        assert_eq!(privacy.hints, Some(PROTECT_HINTS.into()));
+    }
+}
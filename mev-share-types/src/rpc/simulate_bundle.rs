@@ -0,0 +1,106 @@
+#[cfg(feature = "schema")]
+use crate::schema::{HexAddress, HexQuantity};
+use ethers_core::types::*;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+/// MEV-Share API parameters for RPC `mev_simBundle` requests:
+/// optional fields to override simulation state
+///
+/// See `MevShareClient::simulate_bundle`.
+#[derive(Serialize, Deserialize, Clone, Default, TypedBuilder, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(field_defaults(default, setter(strip_option, into)))]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBundleParams {
+    /// Block used for simulation state. Defaults to latest block.
+    /// Block header data will be derived from parent block by default.
+    /// Specify other params in this interface to override the default values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
+    pub parent_block: Option<U64>,
+    // override the default values for the parentBlock header
+    /// default = parentBlock.number + 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
+    pub block_number: Option<U64>,
+    /// default = parentBlock.coinbase.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexAddress>"))]
+    pub coinbase: Option<Address>,
+    /// default = parentBlock.timestamp + 12.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
+    pub timestamp: Option<U64>,
+    /// default = parentBlock.gasLimit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
+    pub gas_limit: Option<U64>,
+    /// default = parentBlock.baseFeePerGas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
+    pub base_fee: Option<U256>,
+    /// default = 5 (defined in seconds).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+/// MEV-Share API response for RPC `mev_simBundle` requests:
+/// simulation details.
+/// .
+/// See `MevShareClient::simulate_bundle`.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBundleResponse {
+    pub success: bool,
+    pub error: Option<String>,
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub state_block: U64,
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub mev_gas_price: U256,
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub profit: U256,
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub refundable_value: U256,
+    #[cfg_attr(feature = "schema", schemars(with = "HexQuantity"))]
+    pub gas_used: U256,
+    pub logs: Vec<BundleLogs>,
+}
+
+/// See [`SimulateBundleResponse::logs`].
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BundleLogs {
+    #[cfg_attr(feature = "schema", schemars(with = "Option<Vec<serde_json::Value>>"))]
+    pub tx_logs: Option<Vec<Log>>,
+    pub bundle_logs: Option<Vec<BundleLogs>>,
+}
+
+impl SimulateBundleResponse {
+    /// [`Self::profit`] in USD, at `eth_usd` dollars per ETH, e.g. from a `mev_share_rs::price::PriceOracle`.
+    #[must_use]
+    pub fn profit_usd(&self, eth_usd: f64) -> f64 {
+        crate::wei_to_eth(self.profit) * eth_usd
+    }
+}
+
+impl std::fmt::Display for SimulateBundleResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.success {
+            write!(
+                f,
+                "simulation succeeded on block {}: profit {} wei, gas used {}, mev gas price {}",
+                self.state_block, self.profit, self.gas_used, self.mev_gas_price
+            )
+        } else {
+            write!(
+                f,
+                "simulation failed on block {}: {}",
+                self.state_block,
+                self.error.as_deref().unwrap_or("unknown error")
+            )
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::helpers::DecimalU256;
+#[cfg(feature = "schema")]
+use crate::schema::HexData;
+use chrono::{DateTime, Utc};
+use ethers_core::types::{Bytes, U256};
+use ethers_core::utils::format_ether;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Flashbots API response for RPC `flashbots_getUserStatsV2` requests: reputation of the searcher signing the requests.
+///
+/// Amounts are returned by the relay as decimal strings, in wei, and parsed into [`U256`] without loss of precision;
+/// the `_eth` methods convert payments to ETH for display.
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UserStats {
+    /// Whether the searcher has high priority access to the relay.
+    pub is_high_priority: bool,
+    /// Total payments to validators since the searcher's first submission, in wei.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub all_time_validator_payments: U256,
+    /// Total gas simulated since the searcher's first submission.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub all_time_gas_simulated: U256,
+    /// Payments to validators over the last 7 days, in wei.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub last_7d_validator_payments: U256,
+    /// Gas simulated over the last 7 days.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub last_7d_gas_simulated: U256,
+    /// Payments to validators over the last day, in wei.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub last_1d_validator_payments: U256,
+    /// Gas simulated over the last day.
+    #[serde_as(as = "DecimalU256")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub last_1d_gas_simulated: U256,
+}
+
+impl UserStats {
+    /// [`Self::all_time_validator_payments`], in ETH.
+    #[must_use]
+    pub fn all_time_validator_payments_eth(&self) -> f64 {
+        wei_to_eth(self.all_time_validator_payments)
+    }
+
+    /// [`Self::last_7d_validator_payments`], in ETH.
+    #[must_use]
+    pub fn last_7d_validator_payments_eth(&self) -> f64 {
+        wei_to_eth(self.last_7d_validator_payments)
+    }
+
+    /// [`Self::last_1d_validator_payments`], in ETH.
+    #[must_use]
+    pub fn last_1d_validator_payments_eth(&self) -> f64 {
+        wei_to_eth(self.last_1d_validator_payments)
+    }
+}
+
+/// Converts `wei` to ETH, for display: amounts over 2^53 wei lose precision.
+#[must_use]
+pub fn wei_to_eth(wei: U256) -> f64 {
+    // `format_ether` always returns a decimal number
+    format_ether(wei).parse().unwrap_or_default()
+}
+
+/// Flashbots API response for RPC `flashbots_getBundleStatsV2` requests: how far a bundle made it through the relay
+/// and the builders.
+///
+/// A bundle goes through the relay (`received_at`), its simulation (`simulated_at`), and is then considered by builders
+/// for their blocks, some of which seal it into their block. Fields of the stages a bundle didn't reach are missing.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BundleStats {
+    /// Whether the bundle was sent by a searcher with high priority access to the relay.
+    #[serde(default)]
+    pub is_high_priority: bool,
+    /// Whether the relay simulated the bundle.
+    #[serde(default)]
+    pub is_simulated: bool,
+    /// When the relay simulated the bundle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simulated_at: Option<DateTime<Utc>>,
+    /// When the relay received the bundle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub received_at: Option<DateTime<Utc>>,
+    /// Builders the bundle was sent to, and when.
+    #[serde(default)]
+    pub considered_by_builders_at: Vec<BuilderTimestamp>,
+    /// Builders that included the bundle in a block they built, and when.
+    #[serde(default)]
+    pub sealed_by_builders_at: Vec<BuilderTimestamp>,
+}
+
+/// When a builder reached a stage of a bundle, see [`BundleStats`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BuilderTimestamp {
+    /// BLS public key of the builder.
+    #[cfg_attr(feature = "schema", schemars(with = "HexData"))]
+    pub pubkey: Bytes,
+    /// When the builder considered, or sealed, the bundle.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl BundleStats {
+    /// Whether at least one builder included the bundle in a block. The block may still lose the auction.
+    #[must_use]
+    pub fn is_sealed(&self) -> bool {
+        !self.sealed_by_builders_at.is_empty()
+    }
+
+    /// When the first builder sealed the bundle, if any did.
+    #[must_use]
+    pub fn first_sealed_at(&self) -> Option<DateTime<Utc>> {
+        self.sealed_by_builders_at
+            .iter()
+            .map(|sealed| sealed.timestamp)
+            .min()
+    }
+
+    /// Time the relay took to simulate the bundle, once it did.
+    #[must_use]
+    pub fn simulation_latency(&self) -> Option<chrono::Duration> {
+        Some(self.simulated_at? - self.received_at?)
+    }
+}
+
+impl std::fmt::Display for UserStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} priority, validator payments {:.4} / {:.4} / {:.4} ETH, gas simulated {} / {} / {} (1d / 7d / all time)",
+            if self.is_high_priority { "high" } else { "normal" },
+            self.last_1d_validator_payments_eth(),
+            self.last_7d_validator_payments_eth(),
+            self.all_time_validator_payments_eth(),
+            self.last_1d_gas_simulated,
+            self.last_7d_gas_simulated,
+            self.all_time_gas_simulated
+        )
+    }
+}
+
+impl std::fmt::Display for BundleStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, considered by {} builders, sealed by {}",
+            if self.is_simulated {
+                "simulated"
+            } else {
+                "not simulated"
+            },
+            self.considered_by_builders_at.len(),
+            self.sealed_by_builders_at.len()
+        )
+    }
+}
@@ -0,0 +1,59 @@
+//! JSON Schema of the hex-encoded `ethers` types, which don't implement [`JsonSchema`] themselves.
+//!
+//! Enabled by the `schema` feature, which derives [`JsonSchema`] for every request and response type. Use these to
+//! describe the `ethers` fields of your own types the same way, with `#[schemars(with = "HexHash")]`.
+
+use schemars::schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation};
+use schemars::{JsonSchema, SchemaGenerator};
+
+/// Defines the JSON Schema of hex-encoded values, for the `ethers` types that don't implement [`JsonSchema`].
+macro_rules! hex_schema {
+    ($name: ident, $pattern: literal, $description: literal) => {
+        #[doc = $description]
+        pub struct $name;
+
+        impl JsonSchema for $name {
+            fn schema_name() -> String {
+                stringify!($name).to_owned()
+            }
+
+            fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                SchemaObject {
+                    instance_type: Some(InstanceType::String.into()),
+                    string: Some(Box::new(StringValidation {
+                        pattern: Some($pattern.to_owned()),
+                        ..Default::default()
+                    })),
+                    metadata: Some(Box::new(Metadata {
+                        description: Some($description.to_owned()),
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }
+                .into()
+            }
+        }
+    };
+}
+
+hex_schema!(
+    HexQuantity,
+    "^0x(0|[1-9a-fA-F][0-9a-fA-F]*)$",
+    "Unsigned integer, hex encoded without leading zeros."
+);
+hex_schema!(HexData, "^0x([0-9a-fA-F]{2})*$", "Bytes, hex encoded.");
+hex_schema!(
+    HexAddress,
+    "^0x[0-9a-fA-F]{40}$",
+    "20 bytes address, hex encoded."
+);
+hex_schema!(
+    HexHash,
+    "^0x[0-9a-fA-F]{64}$",
+    "32 bytes hash, hex encoded."
+);
+hex_schema!(
+    HexSelector,
+    "^0x[0-9a-fA-F]{8}$",
+    "4 bytes function selector, hex encoded."
+);
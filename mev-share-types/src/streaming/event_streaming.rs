@@ -1,15 +1,20 @@
 use crate::helpers::SelectorDeserializer;
-use ethers::types::{Address, Bytes, Log, Selector, TxHash, U256};
-use serde::Deserialize;
+#[cfg(feature = "schema")]
+use crate::schema::{HexAddress, HexData, HexHash, HexQuantity, HexSelector};
+use ethers_core::types::{Address, Bytes, Log, Selector, TxHash, U256};
+use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-/// MEV-Share API response for subscription to the SSE bundles stream (via [`crate::MevShareClient::subscribe_bundles`])
-#[derive(Clone, Deserialize, Debug)]
+/// MEV-Share API response for subscription to the SSE bundles stream (via `MevShareClient::subscribe_bundles`)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct MevShareEvent {
     /// Transaction or Bundle hash
+    #[cfg_attr(feature = "schema", schemars(with = "HexHash"))]
     pub hash: TxHash,
     /// Logs emitted by the transaction or bundle
+    #[cfg_attr(feature = "schema", schemars(with = "Option<Vec<serde_json::Value>>"))]
     pub logs: Option<Vec<Log>>,
     /// Transactions included in the bundle.
     pub txs: Option<Vec<Transaction>>,
@@ -17,22 +22,28 @@ pub struct MevShareEvent {
     ///
     /// Can be used to determine the minimum payment to the builder to make your backrun look more profitable to builders.
     /// _Note: this only applies to builders like Flashbots who order bundles by MEV gas price._
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
     pub mev_gas_price: Option<U256>,
     /// Gas used by the tx/bundle, rounded up to 2 most significant digi
     /// _Note: EXPERIMENTAL; only implemented on Goerli_
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexQuantity>"))]
     pub gas_used: Option<U256>,
 }
 
 /// See [`MevShareEvent::txs`].
 #[serde_as]
-#[derive(Deserialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
     /// Transaction recipient address
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexAddress>"))]
     pub to: Option<Address>,
     /// 4byte function selector
     #[serde_as(as = "Option<SelectorDeserializer>")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexSelector>"))]
     pub function_selector: Option<Selector>,
     /// Calldata of the tx
+    #[cfg_attr(feature = "schema", schemars(with = "Option<HexData>"))]
     pub call_data: Option<Bytes>,
 }
@@ -0,0 +1,106 @@
+use super::MevShareEvent;
+use crate::helpers::SelectorDeserializer;
+#[cfg(feature = "schema")]
+use crate::schema::{HexAddress, HexSelector};
+use ethers_core::types::{Address, Selector};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Selects the [`MevShareEvent`]s worth looking at. Empty criteria match every event.
+///
+/// # Example
+///
+/// ```
+/// let filter = EventFilter {
+///     to: vec![WETH],
+///     ..Default::default()
+/// };
+///
+/// let mut stream = client.subscribe_bundles().filter(|event| future::ready(event.as_ref().map_or(true, |event| filter.matches(event))));
+/// ```
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", default)]
+pub struct EventFilter {
+    /// At least a transaction is sent to one of these addresses.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<HexAddress>"))]
+    pub to: Vec<Address>,
+    /// At least a transaction calls one of these function selectors.
+    #[serde_as(as = "Vec<SelectorDeserializer>")]
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<HexSelector>"))]
+    pub selectors: Vec<Selector>,
+    /// The event discloses logs.
+    pub with_logs: bool,
+}
+
+impl EventFilter {
+    /// Whether `event` meets every criteria.
+    #[must_use]
+    pub fn matches(&self, event: &MevShareEvent) -> bool {
+        let txs = event.txs.as_deref().unwrap_or_default();
+
+        let to = self.to.is_empty()
+            || txs
+                .iter()
+                .any(|tx| tx.to.is_some_and(|to| self.to.contains(&to)));
+
+        let selector = self.selectors.is_empty()
+            || txs.iter().any(|tx| {
+                tx.function_selector
+                    .is_some_and(|selector| self.selectors.contains(&selector))
+            });
+
+        let logs = !self.with_logs || event.logs.as_ref().is_some_and(|logs| !logs.is_empty());
+
+        to && selector && logs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Transaction;
+
+    #[test]
+    fn test_event_filter() {
+        let event: MevShareEvent = serde_json::from_value(serde_json::json!({
+            "hash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "logs": null,
+            "txs": [{ "to": "0x0000000000000000000000000000000000000002", "functionSelector": "0xa9059cbb", "callData": null }],
+            "mevGasPrice": null,
+            "gasUsed": null
+        }))
+        .unwrap();
+
+        assert!(EventFilter::default().matches(&event));
+
+        let filter: EventFilter = serde_json::from_value(serde_json::json!({
+            "to": ["0x0000000000000000000000000000000000000002"],
+            "selectors": ["0xa9059cbb"]
+        }))
+        .unwrap();
+        assert!(filter.matches(&event));
+
+        assert!(!EventFilter {
+            with_logs: true,
+            ..filter.clone()
+        }
+        .matches(&event));
+        assert!(!EventFilter {
+            to: vec![Address::zero()],
+            ..filter
+        }
+        .matches(&event));
+
+        let hidden_selector = MevShareEvent {
+            txs: Some(vec![Transaction::default()]),
+            ..event
+        };
+        assert!(!EventFilter {
+            selectors: vec![[0xa9, 0x05, 0x9c, 0xbb]],
+            ..Default::default()
+        }
+        .matches(&hidden_selector));
+    }
+}
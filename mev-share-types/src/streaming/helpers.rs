@@ -0,0 +1,153 @@
+use super::*;
+use ethers_core::types::{Log, TxHash};
+use std::fmt::Display;
+
+impl MevShareEvent {
+    /// Helps check whether this [`MevShareEvent`] instance is a transaction or not:
+    /// a transaction appears as a bundle composed of a single transaction.
+    #[must_use]
+    pub fn as_transaction(&self) -> Option<(TxHash, Option<&Transaction>, Option<&Log>)> {
+        let logs = self.logs.as_ref().and_then(|logs| logs.first());
+
+        match &self.txs {
+            None => Some((self.hash, None, logs)),
+            Some(txs) if txs.len() == 1 => Some((self.hash, txs.first(), logs)),
+            _ => None,
+        }
+    }
+
+    /// Whether the event is a bundle of several transactions, rather than a single transaction.
+    #[must_use]
+    pub fn is_bundle(&self) -> bool {
+        self.transactions().len() > 1
+    }
+
+    /// The transactions disclosed, empty if none is.
+    #[must_use]
+    pub fn transactions(&self) -> &[Transaction] {
+        self.txs.as_deref().unwrap_or_default()
+    }
+
+    /// The logs disclosed, of all the transactions, empty if none is.
+    #[must_use]
+    pub fn logs(&self) -> &[Log] {
+        self.logs.as_deref().unwrap_or_default()
+    }
+
+    /// The transactions disclosed, each with the logs it emitted.
+    ///
+    /// Logs are matched to the transaction at their `transaction_index` in the bundle, or, for a single transaction, are
+    /// all its own; logs of a bundle without an index are only available from [`Self::logs`].
+    #[must_use]
+    pub fn hinted_transactions(&self) -> Vec<HintedTransaction<'_>> {
+        let single = !self.is_bundle();
+
+        self.transactions()
+            .iter()
+            .enumerate()
+            .map(|(index, tx)| HintedTransaction {
+                index,
+                tx,
+                logs: self
+                    .logs()
+                    .iter()
+                    .filter(|log| {
+                        single || log.transaction_index.is_some_and(|i| i.as_usize() == index)
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// A transaction of a [`MevShareEvent`] with its logs, see [`MevShareEvent::hinted_transactions`].
+#[derive(Clone, Debug)]
+pub struct HintedTransaction<'a> {
+    /// Position of the transaction in the bundle.
+    pub index: usize,
+    /// What the transaction discloses.
+    pub tx: &'a Transaction,
+    /// Logs the transaction emitted.
+    pub logs: Vec<&'a Log>,
+}
+
+impl Display for MevShareEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "hint {:?}: {} txs, {} logs",
+            self.hash,
+            self.txs.as_ref().map_or(0, Vec::len),
+            self.logs.as_ref().map_or(0, Vec::len)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_event_json_round_trip() {
+        let event: MevShareEvent = serde_json::from_value(json!({
+            "hash": "0x3a4ce7a8e8f2e0ef4ac6d2e5d6a5cc0e4f4f2b1c1a7a7f1b1e6b0b4f2c8d9e0a",
+            "logs": null,
+            "txs": [{
+                "to": "0x0000000000000000000000000000000000000001",
+                "functionSelector": "0x38ed1739",
+                "callData": null
+            }],
+            "mevGasPrice": "0x1",
+            "gasUsed": null
+        }))
+        .unwrap();
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["txs"][0]["functionSelector"], "0x38ed1739");
+
+        let event: MevShareEvent = serde_json::from_value(json).unwrap();
+        let tx = &event.txs.as_ref().unwrap()[0];
+        assert_eq!(tx.function_selector, Some([0x38, 0xed, 0x17, 0x39]));
+        assert_eq!(
+            event.to_string(),
+            format!("hint {:?}: 1 txs, 0 logs", event.hash)
+        );
+    }
+
+    #[test]
+    fn test_hinted_transactions() {
+        let log = |index: Option<u64>| {
+            json!({
+                "address": "0x0000000000000000000000000000000000000003",
+                "topics": [],
+                "data": "0x",
+                "transactionIndex": index.map(|index| format!("{index:#x}"))
+            })
+        };
+        let tx = json!({ "to": "0x0000000000000000000000000000000000000002", "functionSelector": "0xa9059cbb", "callData": null });
+        let event = |txs: Vec<serde_json::Value>| -> MevShareEvent {
+            serde_json::from_value(json!({
+                "hash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "logs": [log(Some(1)), log(None), log(Some(0))],
+                "txs": txs,
+                "mevGasPrice": null,
+                "gasUsed": null
+            }))
+            .unwrap()
+        };
+
+        let single = event(vec![tx.clone()]);
+        assert!(!single.is_bundle());
+        let txs = single.hinted_transactions();
+        assert_eq!((txs.len(), txs[0].logs.len()), (1, 3));
+
+        let bundle = event(vec![tx.clone(), tx]);
+        assert!(bundle.is_bundle());
+        assert!(bundle.as_transaction().is_none());
+        let txs = bundle.hinted_transactions();
+        assert_eq!(txs.len(), 2);
+        assert_eq!(txs[0].logs, vec![&bundle.logs()[2]]);
+        assert_eq!(txs[1].logs, vec![&bundle.logs()[0]]);
+    }
+}
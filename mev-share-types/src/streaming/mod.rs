@@ -1,5 +1,7 @@
 mod event_streaming;
+mod filter;
 mod helpers;
 
 pub use event_streaming::*;
+pub use filter::*;
 pub use helpers::*;
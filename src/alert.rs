@@ -0,0 +1,360 @@
+//! Notifying operators when something goes wrong, e.g. by posting to a Slack or Discord webhook, or paging through a
+//! callback, without running a monitoring stack.
+//!
+//! A client [with an alerter](crate::MevShareClient::with_alerter) fires an [`Alert`] when:
+//!
+//! * a bundle or transaction it sent reverts, as reported by [`crate::bundle::PendingBundle::inclusion`] and friends,
+//! * a submission is refused by its [`crate::budget::Budget`],
+//! * the event stream of [`crate::MevShareClient::subscribe_bundles`] stays disconnected for longer than
+//!   [`AlertRules::stream_disconnected`],
+//! * the searcher loses its high priority, as observed by [`crate::MevShareClient::get_user_stats`] or fed to
+//!   [`Alerter::observe_user_stats`].
+//!
+//! [`AlertRules`] selects which of these are worth a notification. Failing to notify a target is logged, and never
+//! fails the operation that fired the alert.
+//!
+//! # Example
+//!
+//! ```
+//! let alerter = Alerter::new(AlertRules::builder().stream_disconnected(Duration::from_secs(30)).build())
+//!     .with_webhook(Webhook::new("https://hooks.slack.com/services/..."))
+//!     .with_callback(|alert| error!(%alert, "paging"));
+//!
+//! let client = MevShareClient::new(auth_wallet, provider)
+//!     .await?
+//!     .with_alerter(Arc::new(alerter));
+//! ```
+
+use crate::api::types::UserStats;
+use crate::budget::BudgetLimit;
+use crate::{Result, RpcError};
+use async_trait::async_trait;
+use ethers::types::TxHash;
+#[cfg(feature = "stream")]
+use instant::Instant;
+use serde_json::json;
+use std::fmt::Display;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use tracing::warn;
+use typed_builder::TypedBuilder;
+
+/// Something worth notifying an operator about, see [`crate::alert`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Alert {
+    /// A bundle landed, but reverted.
+    BundleReverted { hash: TxHash },
+    /// A private transaction landed, but reverted.
+    TransactionReverted { hash: TxHash },
+    /// A submission was refused by the client budget, or by the budget of `tenant`.
+    BudgetExceeded {
+        tenant: Option<String>,
+        limit: BudgetLimit,
+    },
+    /// The event stream has been disconnected for `duration`.
+    StreamDisconnected { duration: Duration },
+    /// The searcher lost its high priority access to the relay.
+    ReputationDropped,
+}
+
+impl Alert {
+    /// Identifier of the kind of alert, e.g. to route notifications.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::BundleReverted { .. } => "bundle_reverted",
+            Self::TransactionReverted { .. } => "transaction_reverted",
+            Self::BudgetExceeded { .. } => "budget_exceeded",
+            Self::StreamDisconnected { .. } => "stream_disconnected",
+            Self::ReputationDropped => "reputation_dropped",
+        }
+    }
+}
+
+impl Display for Alert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BundleReverted { hash } => write!(f, "bundle {hash:?} reverted"),
+            Self::TransactionReverted { hash } => write!(f, "transaction {hash:?} reverted"),
+            Self::BudgetExceeded {
+                tenant: None,
+                limit,
+            } => write!(f, "budget exceeded: {limit}"),
+            Self::BudgetExceeded {
+                tenant: Some(tenant),
+                limit,
+            } => write!(f, "budget of tenant {tenant:?} exceeded: {limit}"),
+            Self::StreamDisconnected { duration } => {
+                write!(f, "event stream disconnected for {duration:.0?}")
+            }
+            Self::ReputationDropped => f.write_str("searcher lost high priority"),
+        }
+    }
+}
+
+/// Which [`Alert`]s an [`Alerter`] notifies. By default, all of them, stream disconnections after a minute.
+#[derive(Clone, Debug, TypedBuilder)]
+pub struct AlertRules {
+    /// Notify [`Alert::BundleReverted`] and [`Alert::TransactionReverted`].
+    #[builder(default = true)]
+    pub reverted: bool,
+    /// Notify [`Alert::BudgetExceeded`].
+    #[builder(default = true)]
+    pub budget_exceeded: bool,
+    /// Notify [`Alert::StreamDisconnected`] once the stream has been disconnected for this long, `None` to never.
+    #[builder(default = Some(Duration::from_secs(60)), setter(strip_option))]
+    pub stream_disconnected: Option<Duration>,
+    /// Notify [`Alert::ReputationDropped`].
+    #[builder(default = true)]
+    pub reputation_dropped: bool,
+}
+
+impl Default for AlertRules {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl AlertRules {
+    /// Whether `alert` is to be notified.
+    #[must_use]
+    pub fn allows(&self, alert: &Alert) -> bool {
+        match alert {
+            Alert::BundleReverted { .. } | Alert::TransactionReverted { .. } => self.reverted,
+            Alert::BudgetExceeded { .. } => self.budget_exceeded,
+            Alert::StreamDisconnected { duration } => self
+                .stream_disconnected
+                .is_some_and(|threshold| *duration >= threshold),
+            Alert::ReputationDropped => self.reputation_dropped,
+        }
+    }
+}
+
+/// Where an [`Alerter`] sends its notifications.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait AlertTarget: Send + Sync {
+    /// Notifies `alert`.
+    async fn notify(&self, alert: &Alert) -> Result<()>;
+}
+
+/// Posts alerts as JSON to a webhook: `{"text": "bundle 0x.. reverted", "kind": "bundle_reverted"}`.
+///
+/// The `text` field is what Slack and Mattermost incoming webhooks display.
+#[derive(Clone, Debug)]
+pub struct Webhook {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl Webhook {
+    /// Posts to `url`.
+    #[must_use]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into(),
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AlertTarget for Webhook {
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        self.client
+            .post(&self.url)
+            .json(&json!({ "text": alert.to_string(), "kind": alert.kind() }))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(RpcError::from)?;
+
+        Ok(())
+    }
+}
+
+/// Calls a function with each alert, see [`Alerter::with_callback`].
+struct Callback<F>(F);
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<F> AlertTarget for Callback<F>
+where
+    F: Fn(&Alert) + Send + Sync,
+{
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        (self.0)(alert);
+        Ok(())
+    }
+}
+
+/// Notifies the [`Alert`]s allowed by its [`AlertRules`] to its targets, see [`crate::alert`].
+#[derive(Default)]
+pub struct Alerter {
+    rules: AlertRules,
+    targets: Vec<Box<dyn AlertTarget>>,
+    high_priority: Mutex<Option<bool>>,
+}
+
+impl Alerter {
+    /// An alerter without targets, notifying nobody until [`Self::with_target`] is called.
+    #[must_use]
+    pub fn new(rules: AlertRules) -> Self {
+        Self {
+            rules,
+            ..Self::default()
+        }
+    }
+
+    /// Notifies `target` too.
+    #[must_use]
+    pub fn with_target(mut self, target: impl AlertTarget + 'static) -> Self {
+        self.targets.push(Box::new(target));
+        self
+    }
+
+    /// Notifies `webhook` too.
+    #[must_use]
+    pub fn with_webhook(self, webhook: Webhook) -> Self {
+        self.with_target(webhook)
+    }
+
+    /// Calls `callback` with each alert too.
+    #[must_use]
+    pub fn with_callback(self, callback: impl Fn(&Alert) + Send + Sync + 'static) -> Self {
+        self.with_target(Callback(callback))
+    }
+
+    /// The conditions notified.
+    #[must_use]
+    pub fn rules(&self) -> &AlertRules {
+        &self.rules
+    }
+
+    /// Notifies `alert` to every target, if the rules allow it.
+    pub async fn fire(&self, alert: Alert) {
+        if !self.rules.allows(&alert) {
+            return;
+        }
+
+        for target in &self.targets {
+            if let Err(err) = target.notify(&alert).await {
+                warn!(%err, %alert, "failed to notify alert");
+            }
+        }
+    }
+
+    /// Fires [`Alert::ReputationDropped`] if the searcher had high priority in the previously observed `stats`, but no
+    /// longer has. [`crate::MevShareClient::get_user_stats`] feeds it the stats it gets, e.g. polled every few minutes.
+    pub async fn observe_user_stats(&self, stats: &UserStats) {
+        let previous = self
+            .high_priority
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .replace(stats.is_high_priority);
+
+        if previous == Some(true) && !stats.is_high_priority {
+            self.fire(Alert::ReputationDropped).await;
+        }
+    }
+}
+
+/// Tracks since when the event stream has been disconnected, for [`Alert::StreamDisconnected`].
+#[cfg(feature = "stream")]
+#[derive(Debug, Default)]
+pub(crate) struct StreamWatch {
+    disconnected_since: Option<Instant>,
+    alerted: bool,
+}
+
+#[cfg(feature = "stream")]
+impl StreamWatch {
+    /// Records whether the stream yielded an event or an error, and returns the alert to fire, once per disconnection,
+    /// when it has been disconnected for `threshold`.
+    ///
+    /// The stream retries to connect with a backoff, yielding an error each time it fails, so the check runs on each
+    /// attempt, and on reconnection.
+    pub(crate) fn observe(&mut self, connected: bool, threshold: Duration) -> Option<Alert> {
+        if connected && self.disconnected_since.is_none() {
+            return None;
+        }
+
+        let since = *self.disconnected_since.get_or_insert_with(Instant::now);
+        let duration = since.elapsed();
+        let alert = (!self.alerted && duration >= threshold)
+            .then_some(Alert::StreamDisconnected { duration });
+
+        if connected {
+            self.disconnected_since = None;
+            self.alerted = false;
+        } else {
+            self.alerted |= alert.is_some();
+        }
+
+        alert
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_alerter() {
+        let fired = Arc::new(Mutex::new(Vec::new()));
+        let alerter = Alerter::new(AlertRules::builder().budget_exceeded(false).build())
+            .with_callback({
+                let fired = fired.clone();
+                move |alert| fired.lock().unwrap().push(alert.kind())
+            });
+
+        alerter
+            .fire(Alert::BundleReverted {
+                hash: TxHash::zero(),
+            })
+            .await;
+        alerter
+            .fire(Alert::BudgetExceeded {
+                tenant: None,
+                limit: BudgetLimit::InFlight { max: 1 },
+            })
+            .await;
+        alerter
+            .fire(Alert::StreamDisconnected {
+                duration: Duration::from_secs(10),
+            })
+            .await;
+
+        let mut stats: UserStats =
+            serde_json::from_str(include_str!("api/types/fixtures/user_stats.json")).unwrap();
+        stats.is_high_priority = true;
+        alerter.observe_user_stats(&stats).await;
+        stats.is_high_priority = false;
+        alerter.observe_user_stats(&stats).await;
+        alerter.observe_user_stats(&stats).await;
+
+        assert_eq!(
+            *fired.lock().unwrap(),
+            vec!["bundle_reverted", "reputation_dropped"]
+        );
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn test_stream_watch() {
+        let mut watch = StreamWatch::default();
+        assert_eq!(watch.observe(true, Duration::ZERO), None);
+
+        assert!(watch.observe(false, Duration::ZERO).is_some());
+        // once per disconnection
+        assert_eq!(watch.observe(false, Duration::ZERO), None);
+        assert_eq!(watch.observe(true, Duration::ZERO), None);
+        assert!(watch.observe(false, Duration::ZERO).is_some());
+
+        let mut watch = StreamWatch::default();
+        assert_eq!(watch.observe(false, Duration::from_secs(60)), None);
+        assert_eq!(watch.observe(true, Duration::from_secs(60)), None);
+    }
+}
@@ -0,0 +1,421 @@
+use crate::api::rpc_client::ResponseMeta;
+use crate::api::types::*;
+use crate::budget::InFlightPermit;
+use crate::client::MevShareRequest;
+use crate::error::{JsonError, RpcError};
+use crate::helpers::provider::Waiter;
+use crate::{MevShareClient, Result};
+use ethers::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Requests sent to the MEV-Share API together, as a single signed JSON-RPC batch, see
+/// [`crate::MevShareClient::batch`].
+///
+/// Each request returns a [`BatchId`] to look its result up in the [`BatchResponse`].
+///
+/// Bundles and transactions sent in a batch go through the same checks as the ones sent one by one: the relay limits,
+/// the [`crate::Budget`]s and the dedup store admit them when the batch is sent, and the submission tracker and the
+/// [`crate::MevShareClient::cancel_guard`] track the ones the relay accepts. If any is refused, none of the batch is
+/// sent.
+///
+/// # Example
+///
+/// ```
+/// let mut batch = client.batch();
+/// let simulation = batch.simulate_bundle(bundles[0].clone(), SimulateBundleParams::default())?;
+/// // the same bundle, targeting the next blocks
+/// let sent = bundles
+///     .into_iter()
+///     .map(|bundle| batch.send_bundle(bundle))
+///     .collect::<Result<Vec<_>>>()?;
+///
+/// let response = batch.send().await?;
+/// debug!(profit = ?response.get(simulation)?.profit);
+/// for id in sent {
+///     debug!(bundle_hash = ?response.get(id)?.bundle_hash);
+/// }
+/// ```
+pub struct Batch<'c, M = Provider<Ws>> {
+    client: &'c MevShareClient<M>,
+    requests: Vec<JsonRpcRequest<'static>>,
+    /// Bundles and transactions among `requests`, by id, admitted by the client when the batch is sent.
+    sends: Vec<(i32, BatchedSend)>,
+}
+
+/// A bundle or transaction of a [`Batch`], kept to admit it when the batch is sent.
+pub(crate) enum BatchedSend {
+    Bundle(SendBundleParams),
+    LegacyBundle(EthSendBundleParams),
+    Transaction(Bytes),
+}
+
+impl<'c, M: Waiter> Batch<'c, M> {
+    pub(crate) fn new(client: &'c MevShareClient<M>) -> Self {
+        Self {
+            client,
+            requests: Vec::new(),
+            sends: Vec::new(),
+        }
+    }
+
+    /// Adds a `mev_sendBundle` request.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Json`] if `params` can't be serialized.
+    pub fn send_bundle(
+        &mut self,
+        mut params: SendBundleParams,
+    ) -> Result<BatchId<SendBundleResponse>> {
+        // the request is serialized now, with the privacy the client would apply when admitting it
+        self.client.apply_default_privacy(&mut params);
+        let id = self.push(MevShareRequest::SendBundle, [&params])?;
+        self.sends.push((id.id, BatchedSend::Bundle(params)));
+
+        Ok(id)
+    }
+
+    /// Adds an `eth_sendBundle` request.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Json`] if `params` can't be serialized.
+    pub fn send_legacy_bundle(
+        &mut self,
+        params: EthSendBundleParams,
+    ) -> Result<BatchId<SendBundleResponse>> {
+        let id = self.push(MevShareRequest::SendLegacyBundle, [&params])?;
+        self.sends.push((id.id, BatchedSend::LegacyBundle(params)));
+
+        Ok(id)
+    }
+
+    /// Adds a `mev_simBundle` request. Unlike [`crate::MevShareClient::simulate_bundle`], doesn't wait for the
+    /// transaction hashes leading the bundle to land.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Json`] if `params` can't be serialized.
+    pub fn simulate_bundle(
+        &mut self,
+        params: SendBundleParams,
+        sim_options: SimulateBundleParams,
+    ) -> Result<BatchId<SimulateBundleResponse>> {
+        self.push(MevShareRequest::SimBundle, (params, sim_options))
+    }
+
+    /// Adds an `eth_callBundle` request.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Json`] if `params` can't be serialized.
+    pub fn call_bundle(
+        &mut self,
+        params: EthCallBundleParams,
+    ) -> Result<BatchId<EthCallBundleResponse>> {
+        self.push(MevShareRequest::CallBundle, [params])
+    }
+
+    /// Adds an `eth_sendPrivateTransaction` request, whose result is the hash of the transaction.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Json`] if `params` can't be serialized.
+    pub fn send_private_transaction(
+        &mut self,
+        params: SendTransactionParams,
+    ) -> Result<BatchId<TxHash>> {
+        let id = self.push(MevShareRequest::SendPrivateTransaction, [&params])?;
+        self.sends
+            .push((id.id, BatchedSend::Transaction(params.tx)));
+
+        Ok(id)
+    }
+
+    /// Number of requests in the batch.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Whether no request was added to the batch.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Sends the batch, once the client admitted every bundle and transaction in it. An empty batch is not sent.
+    ///
+    /// The [`crate::Budget::max_in_flight`] slots the bundles take are held until the [`BatchResponse`] is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Nothing is sent if the client refuses a bundle or a transaction:
+    ///
+    /// * [`crate::Error::InvalidParams`] if the batch has bundles or transactions and the client has a
+    ///   [`crate::MevShareClient::with_backend`] backend, which can't take batches.
+    /// * [`crate::Error::RelayLimit`], [`crate::Error::BudgetExceeded`], [`crate::Error::QuotaExceeded`] or
+    ///   [`crate::Error::DuplicateSubmission`] as for [`crate::MevShareClient::send_bundle`].
+    ///
+    /// Otherwise:
+    ///
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails as a whole. Errors of single
+    ///   requests are returned by [`BatchResponse::get`].
+    pub async fn send(self) -> Result<BatchResponse> {
+        if self.requests.is_empty() {
+            return Ok(BatchResponse::new(Vec::new(), ResponseMeta::default()));
+        }
+
+        self.client.send_batch(&self.requests, self.sends).await
+    }
+
+    fn push<T>(&mut self, method: MevShareRequest, params: impl Serialize) -> Result<BatchId<T>> {
+        let id = self.client.rpc().next_request_id();
+        self.requests.push(JsonRpcRequest {
+            jsonrpc: "2.0",
+            id,
+            method: method.as_method_name(),
+            params: serde_json::to_value(params).map_err(JsonError::from)?,
+        });
+
+        Ok(BatchId::new(id))
+    }
+}
+
+/// Id of a request in a [`Batch`], to look its result of type `T` up in the [`BatchResponse`].
+pub struct BatchId<T> {
+    id: i32,
+    result: PhantomData<fn() -> T>,
+}
+
+impl<T> BatchId<T> {
+    pub(crate) fn new(id: i32) -> Self {
+        Self {
+            id,
+            result: PhantomData,
+        }
+    }
+
+    /// JSON-RPC id of the request.
+    #[must_use]
+    pub fn id(self) -> i32 {
+        self.id
+    }
+}
+
+impl<T> Clone for BatchId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for BatchId<T> {}
+
+impl<T> fmt::Debug for BatchId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BatchId").field(&self.id).finish()
+    }
+}
+
+/// Responses to a [`Batch`], correlated with its requests by id: the relay may answer in any order.
+#[derive(Clone, Debug)]
+pub struct BatchResponse {
+    responses: HashMap<i32, std::result::Result<Value, JsonRpcResponseError>>,
+    uncorrelated: Vec<JsonRpcResponseError>,
+    meta: ResponseMeta,
+    /// In-flight slots of the bundles the relay accepted, released when the last clone is dropped.
+    permits: Arc<[InFlightPermit]>,
+}
+
+impl BatchResponse {
+    pub(crate) fn new(responses: Vec<JsonRpcResponse<Value>>, meta: ResponseMeta) -> Self {
+        let mut correlated = HashMap::with_capacity(responses.len());
+        let mut uncorrelated = Vec::new();
+
+        for response in responses {
+            match response {
+                JsonRpcResponse::Success(success) => {
+                    correlated.insert(success.id, Ok(success.result));
+                }
+                JsonRpcResponse::Error(err) => match err.id {
+                    Some(id) => {
+                        correlated.insert(id, Err(err));
+                    }
+                    None => uncorrelated.push(err),
+                },
+            }
+        }
+
+        Self {
+            responses: correlated,
+            uncorrelated,
+            meta,
+            permits: Arc::new([]),
+        }
+    }
+
+    /// Holds `permits` until the response is dropped.
+    pub(crate) fn with_permits(mut self, permits: Vec<InFlightPermit>) -> Self {
+        self.permits = permits.into();
+        self
+    }
+
+    /// Result of the request `id`.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Rpc`] if the relay answered the request with an error, or didn't answer it.
+    /// * [`crate::Error::Json`] if the result can't be deserialized into `T`.
+    pub fn get<T: DeserializeOwned>(&self, id: BatchId<T>) -> Result<T> {
+        match self.responses.get(&id.id) {
+            Some(Ok(result)) => Ok(serde_json::from_value(result.clone()).map_err(|source| {
+                JsonError::Deserialization {
+                    source,
+                    text: result.to_string(),
+                }
+            })?),
+            Some(Err(err)) => Err(RpcError::Response(err.clone(), self.meta.clone()).into()),
+            None => Err(RpcError::MissingResponse(id.id).into()),
+        }
+    }
+
+    /// Errors the relay answered without a request id, e.g. for a malformed request of the batch.
+    #[must_use]
+    pub fn uncorrelated(&self) -> &[JsonRpcResponseError] {
+        &self.uncorrelated
+    }
+
+    /// Metadata of the HTTP response to the whole batch.
+    #[must_use]
+    pub fn meta(&self) -> &ResponseMeta {
+        &self.meta
+    }
+
+    /// Number of responses, errors included.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.responses.len() + self.uncorrelated.len()
+    }
+
+    /// Whether the relay didn't answer any request.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::networks::MevShareNetwork;
+    use crate::budget::{Budget, BudgetLimit};
+    use crate::helpers::{http_response, mock_relay};
+    use crate::Error;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    #[test]
+    fn test_batch_response_correlation() {
+        let responses = serde_json::from_value(json!([
+            { "jsonrpc": "2.0", "id": 3, "error": { "code": -32000, "message": "bundle too old" } },
+            { "jsonrpc": "2.0", "id": 1, "result": { "bundleHash": format!("{:?}", TxHash::repeat_byte(1)) } },
+            { "jsonrpc": "2.0", "id": 2, "result": { "bundleHash": format!("{:?}", TxHash::repeat_byte(2)) } },
+        ]))
+        .unwrap();
+        let response = BatchResponse::new(responses, ResponseMeta::default());
+
+        assert_eq!(response.len(), 3);
+        let first = BatchId::<SendBundleResponse>::new(1);
+        let second = BatchId::<SendBundleResponse>::new(2);
+        assert_eq!(
+            response.get(first).unwrap().bundle_hash,
+            TxHash::repeat_byte(1)
+        );
+        assert_eq!(
+            response.get(second).unwrap().bundle_hash,
+            TxHash::repeat_byte(2)
+        );
+
+        assert!(matches!(
+            response.get(BatchId::<SendBundleResponse>::new(3)),
+            Err(Error::Rpc(RpcError::Response(..)))
+        ));
+        assert!(matches!(
+            response.get(BatchId::<SendBundleResponse>::new(4)),
+            Err(Error::Rpc(RpcError::MissingResponse(4)))
+        ));
+        assert!(matches!(
+            response.get(BatchId::<TxHash>::new(1)),
+            Err(Error::Json(JsonError::Deserialization { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_batch_admission() {
+        // answers the request `id` of the batch
+        let id = Arc::new(AtomicI32::new(0));
+        let (url, mut requests) = mock_relay({
+            let id = Arc::clone(&id);
+            move |_| {
+                let body = json!([{
+                    "jsonrpc": "2.0",
+                    "id": id.load(Ordering::Relaxed),
+                    "result": { "bundleHash": TxHash::repeat_byte(1) },
+                }]);
+                Some(http_response("200 OK", &body.to_string()))
+            }
+        })
+        .await;
+        let (provider, _) = Provider::mocked();
+        let client = MevShareClient::new_with_network(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+            MevShareNetwork::try_from(5_u64).unwrap(),
+        )
+        .with_api_url(url)
+        .with_budget(Budget::builder().max_submissions(1).build());
+
+        let bundle = |block: u64| {
+            SendBundleParams::builder()
+                .body(vec![Body::Tx {
+                    hash: TxHash::repeat_byte(2),
+                }])
+                .inclusion(block, None)
+                .build()
+        };
+
+        // the second bundle exceeds the budget: neither is sent, and the first one is refunded
+        let mut batch = client.batch();
+        batch.send_bundle(bundle(1)).unwrap();
+        batch.send_bundle(bundle(2)).unwrap();
+        assert!(matches!(
+            batch.send().await,
+            Err(Error::BudgetExceeded(BudgetLimit::Submissions { max: 1 }))
+        ));
+        assert!(requests.try_recv().is_err());
+
+        let mut batch = client.batch();
+        let sent = batch.send_bundle(bundle(1)).unwrap();
+        id.store(sent.id(), Ordering::Relaxed);
+        let response = batch.send().await.unwrap();
+        assert!(requests.recv().await.unwrap().contains("mev_sendbundle"));
+        assert_eq!(
+            response.get(sent).unwrap().bundle_hash,
+            TxHash::repeat_byte(1)
+        );
+
+        // the accepted bundle counts against the budget
+        let mut batch = client.batch();
+        batch.send_bundle(bundle(3)).unwrap();
+        assert!(matches!(
+            batch.send().await,
+            Err(Error::BudgetExceeded(BudgetLimit::Submissions { max: 1 }))
+        ));
+    }
+}
@@ -1,5 +1,8 @@
-pub mod networks;
+pub mod batch;
 pub mod types;
 
+#[cfg(feature = "history")]
 pub mod rest_client;
 pub mod rpc_client;
+
+pub use mev_share_types::networks;
@@ -1,27 +1,71 @@
+use crate::api::rpc_client::default_headers;
 use crate::error::{JsonError, RestError};
+use crate::helpers::within;
+use reqwest::header::HeaderMap;
 use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
 use tracing::*;
 
 type Result<T> = std::result::Result<T, RestError>;
 
+#[derive(Clone)]
 pub struct RestClient {
     base_url: String,
     http: reqwest::Client,
+    headers: HeaderMap,
+    timeout: Option<Duration>,
 }
 
 impl RestClient {
     pub fn new(base_url: String) -> Self {
+        Self::with_client(base_url, reqwest::Client::new())
+    }
+
+    /// Like [`Self::new`], but sends the requests with `http`, e.g. to go through a proxy or trust custom TLS roots.
+    pub fn with_client(base_url: String, http: reqwest::Client) -> Self {
         Self {
             base_url,
-            http: reqwest::Client::new(),
+            http,
+            headers: default_headers(),
+            timeout: None,
         }
     }
 
+    /// Sends the requests with `http` from now on, see [`Self::with_client`].
+    pub fn set_http_client(&mut self, http: reqwest::Client) {
+        self.http = http;
+    }
+
+    /// Sends the requests to `base_url` from now on, e.g. to switch networks.
+    pub fn set_base_url(&mut self, base_url: String) {
+        self.base_url = base_url;
+    }
+
+    /// Fails the requests taking longer than `timeout` with [`RestError::Timeout`] from now on; `None`, the default,
+    /// waits forever.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Headers sent with every request.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
     pub async fn get<T>(&self, path: &str) -> Result<T>
     where
         T: DeserializeOwned,
     {
-        get(&self.http, &self.base_url, path, Option::<String>::None).await
+        let request = get(
+            &self.http,
+            &self.headers,
+            &self.base_url,
+            path,
+            Option::<String>::None,
+        );
+        within(self.timeout, request)
+            .await
+            .map_err(RestError::Timeout)?
     }
 
     pub async fn get_with_params<T, P>(&self, path: &str, params: P) -> Result<T>
@@ -29,7 +73,16 @@ impl RestClient {
         P: Serialize + std::fmt::Debug,
         T: DeserializeOwned,
     {
-        get(&self.http, &self.base_url, path, Some(params)).await
+        let request = get(
+            &self.http,
+            &self.headers,
+            &self.base_url,
+            path,
+            Some(params),
+        );
+        within(self.timeout, request)
+            .await
+            .map_err(RestError::Timeout)?
     }
 }
 
@@ -43,9 +96,10 @@ impl RestClient {
 /// # Errors
 ///
 /// * [`RestError`] if the request fails.
-#[instrument]
+#[instrument(skip(headers))]
 async fn get<T, P>(
     client: &reqwest::Client,
+    headers: &HeaderMap,
     base_url: &str,
     path: &str,
     params: Option<P>,
@@ -64,6 +118,7 @@ where
 
     let response: String = client
         .get(url)
+        .headers(headers.clone())
         .send()
         .await?
         .error_for_status()?
@@ -1,31 +1,241 @@
+use crate::api::batch::BatchResponse;
 use crate::api::types::{JsonRpcRequest, JsonRpcResponse};
 use crate::client::MevShareRequest;
 use crate::error::{JsonError, RpcError};
-use ethers::signers::{LocalWallet, Signer};
-use ethers::utils::keccak256;
-use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use crate::helpers::{system_now, within};
+use crate::layer::{Next, RpcLayer, RpcRequest, RpcResponse};
+use crate::retry::RetryPolicy;
+use crate::signer::{AuthScheme, AuthSigner};
+use futures_timer::Delay;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, RETRY_AFTER, USER_AGENT};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::Value;
+use std::future::Future;
 use std::sync::atomic::{AtomicI32, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, UNIX_EPOCH};
 use tracing::*;
 
 type Result<T> = std::result::Result<T, RpcError>;
 
-pub struct MevShareRpcClient<'a> {
-    base_url: &'a str,
-    request_id: AtomicI32,
+pub struct MevShareRpcClient {
+    base_url: String,
+    /// Shared by the copies, so that their requests don't reuse ids.
+    request_id: Arc<AtomicI32>,
     http: reqwest::Client,
-    auth_wallet: LocalWallet,
+    /// Whether `http` was injected with [`Self::with_client`] or [`Self::set_http_client`], rather than a default one.
+    injected_http: bool,
+    auth: Arc<dyn AuthScheme>,
+    headers: HeaderMap,
+    /// How long a request may take, from signing to the last byte of the response.
+    timeout: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    /// Hooks around every request, outermost first.
+    layers: Vec<Arc<dyn RpcLayer>>,
+    last_response: Mutex<Option<ResponseMeta>>,
 }
 
-impl<'a> MevShareRpcClient<'a> {
-    pub fn new(base_url: &'a str, auth_wallet: LocalWallet) -> Self {
+/// Copies share the connection pool and the request ids, e.g. for a client to reconfigure its copy without affecting the pending bundles
+/// and transactions sent with the original.
+impl Clone for MevShareRpcClient {
+    fn clone(&self) -> Self {
         Self {
-            base_url,
+            base_url: self.base_url.clone(),
+            request_id: Arc::clone(&self.request_id),
+            http: self.http.clone(),
+            injected_http: self.injected_http,
+            auth: Arc::clone(&self.auth),
+            headers: self.headers.clone(),
+            timeout: self.timeout,
+            retry: self.retry.clone(),
+            layers: self.layers.clone(),
+            last_response: Mutex::new(self.last_response()),
+        }
+    }
+}
+
+/// What the relay said about a request besides its result: the HTTP status, the request id to quote in support
+/// issues, and the rate limit of the searcher.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// HTTP status code.
+    pub status: u16,
+    /// `X-Request-Id` header.
+    pub request_id: Option<String>,
+    /// `X-RateLimit-*` and `Retry-After` headers, if any was sent.
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// Rate limit of the searcher, as reported by the relay. Every field is optional: relays and proxies send different
+/// subsets of the headers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    /// `X-RateLimit-Limit`: requests allowed in the current window.
+    pub limit: Option<u64>,
+    /// `X-RateLimit-Remaining`: requests left in the current window.
+    pub remaining: Option<u64>,
+    /// `X-RateLimit-Reset`: time until the window resets.
+    pub reset: Option<Duration>,
+    /// `Retry-After`: time to wait before retrying a rate limited request.
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimit {
+    /// Whether the next request is expected to be rate limited.
+    #[must_use]
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0) || self.retry_after.is_some()
+    }
+}
+
+impl ResponseMeta {
+    /// Reads the metadata of a response with `status` and `headers`.
+    #[must_use]
+    pub fn from_headers(status: u16, headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let number = |name: &str| header(name).and_then(|value| value.trim().parse::<u64>().ok());
+
+        let rate_limit = RateLimit {
+            limit: number("x-ratelimit-limit"),
+            remaining: number("x-ratelimit-remaining"),
+            reset: number("x-ratelimit-reset").map(Duration::from_secs),
+            // the HTTP-date form of `Retry-After` is not used by the relay
+            retry_after: number(RETRY_AFTER.as_str()).map(Duration::from_secs),
+        };
+
+        Self {
+            status,
+            request_id: header("x-request-id").map(ToOwned::to_owned),
+            rate_limit: (rate_limit != RateLimit::default()).then_some(rate_limit),
+        }
+    }
+}
+
+/// `User-Agent` sent to the relay unless overridden.
+pub const DEFAULT_USER_AGENT: &str = concat!("mev-share-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Headers sent with every request unless overridden.
+pub fn default_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
+    headers
+}
+
+impl MevShareRpcClient {
+    pub fn new(base_url: impl Into<String>, auth: impl Into<AuthSigner>) -> Self {
+        Self {
+            injected_http: false,
+            ..Self::with_client(base_url, auth, reqwest::Client::new())
+        }
+    }
+
+    /// Like [`Self::new`], but sends the requests with `http`, e.g. to go through a proxy, trust custom TLS roots or
+    /// tune the connection pool.
+    pub fn with_client(
+        base_url: impl Into<String>,
+        auth: impl Into<AuthSigner>,
+        http: reqwest::Client,
+    ) -> Self {
+        Self {
+            base_url: base_url.into(),
             request_id: Self::new_request_id(),
-            http: reqwest::Client::new(),
-            auth_wallet,
+            http,
+            injected_http: true,
+            auth: Arc::new(auth.into()),
+            headers: default_headers(),
+            timeout: None,
+            retry: None,
+            layers: Vec::new(),
+            last_response: Mutex::default(),
+        }
+    }
+
+    /// Sends the requests with `http` from now on, see [`Self::with_client`].
+    pub fn set_http_client(&mut self, http: reqwest::Client) {
+        self.http = http;
+        self.injected_http = true;
+    }
+
+    /// The HTTP client the requests are sent with.
+    pub fn http_client(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    /// Sends the requests to `base_url` from now on, e.g. to switch networks.
+    pub fn set_base_url(&mut self, base_url: impl Into<String>) {
+        self.base_url = base_url.into();
+    }
+
+    /// Fails the requests taking longer than `timeout` with [`RpcError::Timeout`] from now on; `None`, the default,
+    /// waits for the relay forever.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// How long a request may take, see [`Self::set_timeout`].
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Retries the requests failing transiently as `policy` says from now on; `None`, the default, never retries.
+    /// See [`crate::retry`].
+    pub fn set_retry_policy(&mut self, policy: Option<RetryPolicy>) {
+        self.retry = policy;
+    }
+
+    /// Runs `layer` around every request from now on, within the layers added before it. See [`crate::layer`].
+    pub fn push_layer(&mut self, layer: Arc<dyn RpcLayer>) {
+        self.layers.push(layer);
+    }
+
+    /// Authenticates the requests with `auth` instead of the `X-Flashbots-Signature` of the signer given to [`Self::new`].
+    pub fn set_auth_scheme(&mut self, auth: Arc<dyn AuthScheme>) {
+        self.auth = auth;
+    }
+
+    /// Metadata of the last response of the relay, successful or not: when requests are concurrent, the last one
+    /// to complete.
+    pub fn last_response(&self) -> Option<ResponseMeta> {
+        self.last_response
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Headers sent with every request, on top of the authentication ones.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Headers sent with every request, on top of the authentication ones.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// Returns a copy of this client with its own connection pool.
+    ///
+    /// Useful when requests have to be sent from a different runtime than the one that drove this client so far,
+    /// e.g. from within a [`Drop`] implementation.
+    ///
+    /// A client injected with [`Self::with_client`] is kept, pool included, as its settings, e.g. a proxy, may be
+    /// required to reach the relay at all.
+    pub fn detached(&self) -> Self {
+        Self {
+            base_url: self.base_url.clone(),
+            request_id: Self::new_request_id(),
+            http: if self.injected_http {
+                self.http.clone()
+            } else {
+                reqwest::Client::new()
+            },
+            injected_http: self.injected_http,
+            auth: self.auth.clone(),
+            headers: self.headers.clone(),
+            timeout: self.timeout,
+            retry: self.retry.clone(),
+            layers: self.layers.clone(),
+            last_response: Mutex::default(),
         }
     }
 
@@ -42,75 +252,364 @@ impl<'a> MevShareRpcClient<'a> {
     ///
     /// # Errors
     ///
-    /// * [`RpcError`] if the request fails.
+    /// * [`RpcError::Response`] if the relay returns a JSON-RPC error.
+    /// * [`RpcError::Http`] if the relay, or a proxy in front of it, fails the request with a body that is not JSON-RPC,
+    ///   e.g. on `429 Too Many Requests`.
+    /// * [`RpcError::Timeout`] if the relay does not respond within [`Self::timeout`].
+    /// * [`RpcError`] if the request fails otherwise.
+    ///
+    /// Errors that are [`RpcError::is_transient`] are only returned once the [`Self::set_retry_policy`], if any, gives up;
+    /// each attempt has its own timeout.
     pub async fn post<T, P>(&self, method: MevShareRequest, params: P) -> Result<T>
+    where
+        P: Serialize,
+        T: DeserializeOwned,
+    {
+        self.post_with_timeout(method, params, self.timeout).await
+    }
+
+    /// Like [`Self::post`], but waits for the relay for `timeout` rather than [`Self::timeout`], e.g. longer for a
+    /// simulation.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::post`].
+    pub async fn post_with_timeout<T, P>(
+        &self,
+        method: MevShareRequest,
+        params: P,
+        timeout: Option<Duration>,
+    ) -> Result<T>
     where
         P: Serialize,
         T: DeserializeOwned,
     {
         let body = JsonRpcRequest {
             jsonrpc: "2.0",
-            id: self.request_id.fetch_add(1, Ordering::Relaxed),
+            id: self.next_request_id(),
             method: method.as_method_name(),
             params: serde_json::to_value(params)?,
         };
 
-        trace!(request = %serde_json::to_string(&body).unwrap());
-
-        let signature = format!(
-            "{:?}:0x{}",
-            self.auth_wallet.address(),
-            self.auth_wallet
-                .sign_message(format!(
-                    "0x{}",
-                    ethers::utils::hex::encode(keccak256(serde_json::to_string(&body)?.as_bytes()))
-                ))
-                .await?
-        );
+        let body = &body;
+        self.with_retries(method.is_idempotent(), || async move {
+            let (response, meta) = self.send(method.as_method_name(), body, timeout).await?;
+            parse_response(response, meta)
+        })
+        .await
+    }
 
-        trace!(?signature);
+    /// Sends `requests` to the MEV-Share API as a single JSON-RPC batch, signed as a whole.
+    ///
+    /// # Errors
+    ///
+    /// * [`RpcError::Response`] if the relay rejects the whole batch with a JSON-RPC error.
+    /// * Same as [`Self::post`] otherwise; errors of single requests are returned by [`BatchResponse::get`].
+    ///
+    /// Batches may hold sends, so they are only retried if [`RetryPolicy::retry_sends`] says so.
+    pub async fn post_batch(&self, requests: &[JsonRpcRequest<'_>]) -> Result<BatchResponse> {
+        self.with_retries(false, || async move {
+            let (response, meta) = self.send("batch", &requests, self.timeout).await?;
+            parse_batch_response(response, meta)
+        })
+        .await
+    }
+
+    /// Runs `request` again while it fails transiently, as long as the [`RetryPolicy`] allows: for any request if
+    /// `idempotent`, only if [`RetryPolicy::retry_sends`] otherwise.
+    async fn with_retries<T, F>(
+        &self,
+        idempotent: bool,
+        mut request: impl FnMut() -> F,
+    ) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        let Some(policy) = self
+            .retry
+            .as_ref()
+            .filter(|policy| idempotent || policy.retry_sends)
+        else {
+            return request().await;
+        };
+
+        let mut attempt = 1;
+        loop {
+            match request().await {
+                Err(err) if err.is_transient() && attempt < policy.max_attempts => {
+                    let retry_after = err
+                        .response_meta()
+                        .and_then(|meta| meta.rate_limit.as_ref())
+                        .and_then(|rate_limit| rate_limit.retry_after);
+                    let backoff = policy.backoff(attempt, retry_after);
+                    debug!(%err, ?backoff, attempt, "transient relay error, retrying");
+                    Delay::new(backoff).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Id of the next request, unique for this client.
+    pub fn next_request_id(&self) -> i32 {
+        self.request_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Runs `body` through the layers, signs and posts it, and returns the text of the response, whatever its
+    /// status, unless it takes longer than `timeout`.
+    async fn send(
+        &self,
+        method: &'static str,
+        body: &impl Serialize,
+        timeout: Option<Duration>,
+    ) -> Result<(String, ResponseMeta)> {
+        let request = RpcRequest {
+            method,
+            // authentication schemes may sign the exact bytes, so the body is serialized once
+            body: serde_json::to_string(body)?,
+            headers: self.headers.clone(),
+        };
+
+        let response = within(timeout, Next::new(self, &self.layers).run(request))
+            .await
+            .map_err(RpcError::Timeout)??;
+
+        Ok((response.body, response.meta))
+    }
+
+    /// Signs and posts `request`, the innermost [`Next`] layer.
+    pub(crate) async fn sign_and_post(&self, request: RpcRequest) -> Result<RpcResponse> {
+        let RpcRequest { body, headers, .. } = request;
+        trace!(request = %body);
+
+        let auth_headers = self.auth.authenticate(&body).await?;
+
+        trace!(?auth_headers);
 
         let headers = {
-            let mut headers = HeaderMap::new();
+            let mut headers = headers;
             headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-            headers.insert("X-Flashbots-Signature", HeaderValue::from_str(&signature)?);
+            headers.extend(auth_headers);
             headers
         };
 
-        let response: String = self
+        let response = self
             .http
-            .post(self.base_url)
+            .post(&self.base_url)
             .headers(headers)
-            .json(&body)
+            .body(body)
             .send()
-            .await?
-            .text()
             .await?;
 
-        trace!(%response);
+        let meta = ResponseMeta::from_headers(response.status().as_u16(), response.headers());
+        *self
+            .last_response
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(meta.clone());
 
-        let response = serde_json::from_str::<JsonRpcResponse<T>>(&response).map_err(|source| {
-            JsonError::Deserialization {
-                source,
-                text: response,
-            }
-        })?;
+        let response: String = response.text().await?;
 
-        match response {
-            JsonRpcResponse::Error(err) => Err(RpcError::Response(err)),
-            JsonRpcResponse::Success(data) => Ok(data.result),
-        }
+        trace!(%response, ?meta);
+
+        Ok(RpcResponse {
+            body: response,
+            meta,
+        })
     }
 
     // Pseudo-random number to avoid collisions between requests coming from different instances of this client.
     // It doesn't need to be cryptographically secure, so it's not worth adding a dependency for it.
-    fn new_request_id() -> AtomicI32 {
-        AtomicI32::new(
-            (SystemTime::now()
+    fn new_request_id() -> Arc<AtomicI32> {
+        Arc::new(AtomicI32::new(
+            (system_now()
                 .duration_since(UNIX_EPOCH)
                 .expect("time went backwards")
                 .as_nanos()
                 % 1_000_000) as i32,
-        )
+        ))
+    }
+}
+
+/// Parses the response of the relay to a single request.
+fn parse_response<T: DeserializeOwned>(response: String, meta: ResponseMeta) -> Result<T> {
+    let response = match serde_json::from_str::<JsonRpcResponse<T>>(&response) {
+        Ok(response) => response,
+        Err(_) if !(200..300).contains(&meta.status) => {
+            return Err(RpcError::Http(meta, response));
+        }
+        Err(source) => {
+            return Err(JsonError::Deserialization {
+                source,
+                text: response,
+            }
+            .into())
+        }
+    };
+
+    match response {
+        JsonRpcResponse::Error(err) => Err(RpcError::Response(err, meta)),
+        JsonRpcResponse::Success(data) => Ok(data.result),
+    }
+}
+
+/// Parses the response of the relay to a batch.
+fn parse_batch_response(response: String, meta: ResponseMeta) -> Result<BatchResponse> {
+    match serde_json::from_str::<Vec<JsonRpcResponse<Value>>>(&response) {
+        Ok(responses) => Ok(BatchResponse::new(responses, meta)),
+        Err(source) => match serde_json::from_str::<JsonRpcResponse<Value>>(&response) {
+            Ok(JsonRpcResponse::Error(err)) => Err(RpcError::Response(err, meta)),
+            _ if !(200..300).contains(&meta.status) => Err(RpcError::Http(meta, response)),
+            _ => Err(JsonError::Deserialization {
+                source,
+                text: response,
+            }
+            .into()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{http_response, json_rpc_response, mock_relay};
+    use crate::Error;
+    use ethers::signers::LocalWallet;
+    use ethers::types::TxHash;
+
+    #[test]
+    fn test_clone_request_ids() {
+        let rpc = MevShareRpcClient::new(
+            "http://localhost",
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+        );
+        let copy = rpc.clone();
+
+        let first = rpc.next_request_id();
+        assert_eq!(copy.next_request_id(), first + 1);
+        assert_eq!(rpc.next_request_id(), first + 2);
+    }
+
+    #[tokio::test]
+    async fn test_response_meta() {
+        let (url, _) = mock_relay(|_| {
+            Some(
+                "HTTP/1.1 429 Too Many Requests\r\nX-Request-Id: req-42\r\nRetry-After: 12\r\n\
+                 X-RateLimit-Limit: 100\r\nX-RateLimit-Remaining: 0\r\nContent-Length: 17\r\n\r\ntoo many requests"
+                    .to_owned(),
+            )
+        })
+        .await;
+        let rpc = MevShareRpcClient::new(
+            &url,
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+        );
+
+        let err: Error = rpc
+            .post::<TxHash, _>(MevShareRequest::SendBundle, [()])
+            .await
+            .unwrap_err()
+            .into();
+
+        let meta = err.response_meta().unwrap();
+        assert_eq!(meta.status, 429);
+        assert_eq!(meta.request_id.as_deref(), Some("req-42"));
+
+        let rate_limit = meta.rate_limit.as_ref().unwrap();
+        assert_eq!(rate_limit.limit, Some(100));
+        assert_eq!(rate_limit.retry_after, Some(Duration::from_secs(12)));
+        assert!(rate_limit.is_exhausted());
+
+        assert_eq!(rpc.last_response().as_ref(), Some(meta));
+        assert!(err.to_string().contains("too many requests"));
+    }
+
+    #[tokio::test]
+    async fn test_injected_client() {
+        let (url, mut requests) = mock_relay(|_| Some(json_rpc_response("null"))).await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-injected", HeaderValue::from_static("yes"));
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+        let rpc = MevShareRpcClient::with_client(
+            &url,
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            http,
+        );
+
+        // sent through the injected client, with its settings, by copies of the client too
+        rpc.detached()
+            .post::<Option<TxHash>, _>(MevShareRequest::SendBundle, [()])
+            .await
+            .unwrap();
+        assert!(requests.recv().await.unwrap().contains("x-injected: yes"));
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        // a relay that accepts connections, and never responds
+        let (url, _) = mock_relay(|_| None::<String>).await;
+
+        let mut rpc = MevShareRpcClient::new(
+            &url,
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+        );
+        rpc.set_timeout(Some(Duration::from_millis(100)));
+
+        let err: Error = rpc
+            .post::<TxHash, _>(MevShareRequest::SendBundle, [()])
+            .await
+            .unwrap_err()
+            .into();
+        assert!(matches!(err, Error::Timeout(timeout) if timeout == Duration::from_millis(100)));
+
+        // overridden per call
+        let err = rpc
+            .post_with_timeout::<TxHash, _>(
+                MevShareRequest::SimBundle,
+                [()],
+                Some(Duration::from_millis(10)),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RpcError::Timeout(timeout) if timeout == Duration::from_millis(10)));
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        // a relay failing every other request
+        let (url, _) = mock_relay(|attempt| {
+            Some(if attempt % 2 == 0 {
+                http_response("503 Service Unavailable", "")
+            } else {
+                json_rpc_response("null")
+            })
+        })
+        .await;
+
+        let mut rpc = MevShareRpcClient::new(
+            &url,
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+        );
+        rpc.set_retry_policy(Some(
+            RetryPolicy::builder()
+                .initial_backoff(Duration::from_millis(1))
+                .build(),
+        ));
+
+        // reads are retried
+        rpc.post::<Option<TxHash>, _>(MevShareRequest::GetBundleStats, [()])
+            .await
+            .unwrap();
+
+        // sends are not
+        let err = rpc
+            .post::<Option<TxHash>, _>(MevShareRequest::SendBundle, [()])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RpcError::Http(meta, _) if meta.status == 503));
     }
 }
@@ -1,5 +1,7 @@
 mod rpc;
-mod streaming;
+#[cfg(test)]
+mod wire_compat;
 
+pub use mev_share_types::jsonrpc::{JsonRpcRequest, JsonRpcResponse, JsonRpcResponseError};
+pub use mev_share_types::*;
 pub use rpc::*;
-pub use streaming::*;
@@ -0,0 +1,213 @@
+use super::{HashesIter, SendBundleParams};
+use crate::helpers::provider::Waiter;
+use crate::{Error, Result};
+use ethers::prelude::*;
+use futures::future::try_join_all;
+use std::fmt::Display;
+
+/// How the transactions of a landed bundle are laid out in their block, see [`verify_adjacency`].
+///
+/// Bundles are atomic: their transactions land in the same block, one right after the other, in body order. For a
+/// backrun, the backrun right after the target transaction. Anything else is a builder, or relay, bug worth reporting.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AdjacencyReport {
+    /// Block the first transaction of the bundle landed in, if any did.
+    pub block: Option<U64>,
+    /// Position of each transaction of the bundle in its block, in body order; `None` if it didn't land.
+    pub positions: Vec<(TxHash, Option<U64>)>,
+    /// Every way the layout breaks the bundle atomicity; empty if it doesn't.
+    pub violations: Vec<AdjacencyViolation>,
+}
+
+/// See [`AdjacencyReport::violations`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdjacencyViolation {
+    /// The transaction didn't land, while others of the bundle did.
+    Missing(TxHash),
+    /// The transaction landed in another block than the first transaction of the bundle.
+    OtherBlock { tx: TxHash, block: Option<U64> },
+    /// The transaction is not right after the previous transaction of the bundle.
+    NotAdjacent {
+        previous: TxHash,
+        previous_index: U64,
+        tx: TxHash,
+        index: U64,
+    },
+}
+
+impl AdjacencyReport {
+    /// Checks the layout of the transactions `txs`, in body order, from their `receipts`, in the same order, `None`
+    /// for the transactions that didn't land.
+    #[must_use]
+    pub fn from_receipts(txs: &[TxHash], receipts: &[Option<TransactionReceipt>]) -> Self {
+        let first = receipts.iter().flatten().next();
+        let block_hash = first.and_then(|receipt| receipt.block_hash);
+
+        let mut report = Self {
+            block: first.and_then(|receipt| receipt.block_number),
+            positions: Vec::with_capacity(txs.len()),
+            violations: Vec::new(),
+        };
+
+        if first.is_none() {
+            // nothing landed: nothing to be adjacent to
+            report.positions = txs.iter().map(|tx| (*tx, None)).collect();
+            return report;
+        }
+
+        let mut previous: Option<(TxHash, U64)> = None;
+
+        for (tx, receipt) in txs.iter().zip(receipts) {
+            let Some(receipt) = receipt else {
+                report.positions.push((*tx, None));
+                report.violations.push(AdjacencyViolation::Missing(*tx));
+                previous = None;
+                continue;
+            };
+
+            report
+                .positions
+                .push((*tx, Some(receipt.transaction_index)));
+
+            if receipt.block_hash != block_hash {
+                report.violations.push(AdjacencyViolation::OtherBlock {
+                    tx: *tx,
+                    block: receipt.block_number,
+                });
+                previous = None;
+                continue;
+            }
+
+            if let Some((previous_tx, previous_index)) = previous
+                && receipt.transaction_index != previous_index + 1
+            {
+                report.violations.push(AdjacencyViolation::NotAdjacent {
+                    previous: previous_tx,
+                    previous_index,
+                    tx: *tx,
+                    index: receipt.transaction_index,
+                });
+            }
+
+            previous = Some((*tx, receipt.transaction_index));
+        }
+
+        report
+    }
+
+    /// Whether the bundle landed as a whole, its transactions one right after the other.
+    #[must_use]
+    pub fn is_adjacent(&self) -> bool {
+        self.block.is_some() && self.violations.is_empty()
+    }
+}
+
+impl Display for AdjacencyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing(tx) => write!(f, "{tx:?} did not land"),
+            Self::OtherBlock { tx, block } => {
+                write!(f, "{tx:?} landed in another block, {block:?}")
+            }
+            Self::NotAdjacent {
+                previous,
+                previous_index,
+                tx,
+                index,
+            } => write!(
+                f,
+                "{tx:?} at index {index} does not follow {previous:?} at index {previous_index}"
+            ),
+        }
+    }
+}
+
+/// Fetches the receipts of the transactions of `bundle` and checks that they landed one right after the other, e.g.
+/// that a backrun directly follows its target transaction.
+///
+/// # Example
+///
+/// ```
+/// let pending_bundle = client.send_bundle(bundle.clone()).await?;
+/// pending_bundle.inclusion().await?;
+///
+/// let report = verify_adjacency(&provider, &bundle).await?;
+/// for violation in &report.violations {
+///     warn!(%violation, block = ?report.block, "bundle atomicity violated");
+/// }
+/// ```
+///
+/// # Errors
+///
+/// * [`crate::Error::Provider`] if any receipt can't be fetched.
+pub async fn verify_adjacency<M: Waiter>(
+    provider: &M,
+    bundle: &SendBundleParams,
+) -> Result<AdjacencyReport> {
+    let txs: Vec<TxHash> = bundle.body.hashes().collect();
+    let receipts = try_join_all(txs.iter().map(|tx| provider.get_transaction_receipt(*tx)))
+        .await
+        .map_err(Error::middleware)?;
+
+    Ok(AdjacencyReport::from_receipts(&txs, &receipts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Receipt of a transaction that landed at `index` in `block`.
+    fn landed(block: u64, index: u64) -> TransactionReceipt {
+        TransactionReceipt {
+            block_number: Some(block.into()),
+            block_hash: Some(H256::from_low_u64_be(block)),
+            transaction_index: index.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_adjacency_report() {
+        let (target, backrun) = (TxHash::random(), TxHash::random());
+
+        let report = AdjacencyReport::from_receipts(
+            &[target, backrun],
+            &[Some(landed(100, 4)), Some(landed(100, 5))],
+        );
+        assert!(report.is_adjacent());
+        assert_eq!(report.positions[1], (backrun, Some(5.into())));
+
+        let report = AdjacencyReport::from_receipts(
+            &[target, backrun],
+            &[Some(landed(100, 4)), Some(landed(100, 7))],
+        );
+        assert_eq!(
+            report.violations,
+            vec![AdjacencyViolation::NotAdjacent {
+                previous: target,
+                previous_index: 4.into(),
+                tx: backrun,
+                index: 7.into(),
+            }]
+        );
+
+        let report = AdjacencyReport::from_receipts(
+            &[target, backrun],
+            &[Some(landed(100, 4)), Some(landed(101, 0))],
+        );
+        assert!(matches!(
+            report.violations[..],
+            [AdjacencyViolation::OtherBlock { tx, .. }] if tx == backrun
+        ));
+
+        let report =
+            AdjacencyReport::from_receipts(&[target, backrun], &[Some(landed(100, 4)), None]);
+        assert_eq!(
+            report.violations,
+            vec![AdjacencyViolation::Missing(backrun)]
+        );
+
+        let report = AdjacencyReport::from_receipts(&[target, backrun], &[None, None]);
+        assert!(!report.is_adjacent() && report.violations.is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+use super::Builder;
+use crate::helpers::provider::Waiter;
+use crate::{Error, Result};
+use ethers::prelude::*;
+use std::fmt::Display;
+
+/// A block builder, as recognized on-chain from the blocks it produces, see [`BuilderAttribution`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KnownBuilder {
+    /// Display name of the builder.
+    pub name: &'static str,
+    /// The builder to share bundles with to reach it, if MEV-Share knows it.
+    pub builder: Option<Builder>,
+    /// Case-insensitive substrings of the extra-data the builder signs its blocks with.
+    pub tags: &'static [&'static str],
+    /// Fee recipients the builder sets as coinbase of its blocks.
+    pub coinbases: &'static [Address],
+}
+
+/// Parses a coinbase at compile time.
+const fn address(hex: &str) -> Address {
+    const fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            b'A'..=b'F' => c - b'A' + 10,
+            _ => panic!("invalid hex digit"),
+        }
+    }
+
+    let hex = hex.as_bytes();
+    assert!(hex.len() == 42 && hex[0] == b'0' && hex[1] == b'x');
+
+    let mut bytes = [0u8; 20];
+    let mut i = 0;
+    while i < 20 {
+        bytes[i] = nibble(hex[2 + 2 * i]) << 4 | nibble(hex[3 + 2 * i]);
+        i += 1;
+    }
+    H160(bytes)
+}
+
+/// Builders recognized by [`BuilderAttribution::from_block`].
+///
+/// Builders rotate their coinbases and extra-data now and then: open a PR when one goes unrecognized.
+pub static KNOWN_BUILDERS: [KnownBuilder; 7] = [
+    KnownBuilder {
+        name: "Flashbots",
+        builder: Some(Builder::Flashbots),
+        tags: &["illuminate dmocratize dstribute", "flashbots"],
+        coinbases: &[address("0xdafea492d9c6733ae3d56b7ed1adb60692c98bc5")],
+    },
+    KnownBuilder {
+        name: "rsync-builder",
+        builder: Some(Builder::Rsync),
+        tags: &["rsync-builder"],
+        coinbases: &[address("0x1f9090aae28b8a3dceadf281b0f12828e676c326")],
+    },
+    KnownBuilder {
+        name: "beaverbuild",
+        builder: Some(Builder::BeaverBuild),
+        tags: &["beaverbuild"],
+        coinbases: &[address("0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5")],
+    },
+    KnownBuilder {
+        name: "builder0x69",
+        builder: Some(Builder::Builder0x69),
+        tags: &["builder0x69"],
+        coinbases: &[address("0x690b9a9e9aa1c9db991c7721a92d351db4fac990")],
+    },
+    KnownBuilder {
+        name: "Titan",
+        builder: Some(Builder::Titan),
+        tags: &["titanbuilder", "titan"],
+        coinbases: &[address("0x4838b106fce9647bdf1e7877bf73ce8b0bad5f97")],
+    },
+    KnownBuilder {
+        name: "EigenPhi",
+        builder: Some(Builder::EigenPhi),
+        tags: &["eigenphi"],
+        coinbases: &[],
+    },
+    KnownBuilder {
+        name: "boba-builder",
+        builder: Some(Builder::BobaBuilder),
+        tags: &["boba-builder", "boba builder"],
+        coinbases: &[],
+    },
+];
+
+impl KnownBuilder {
+    /// Looks `coinbase` up among the [`KNOWN_BUILDERS`] first, it being the more reliable, then `extra_data`.
+    #[must_use]
+    pub fn recognize(coinbase: Address, extra_data: &[u8]) -> Option<&'static Self> {
+        let tag = String::from_utf8_lossy(extra_data).to_lowercase();
+
+        KNOWN_BUILDERS
+            .iter()
+            .find(|known| known.coinbases.contains(&coinbase))
+            .or_else(|| {
+                KNOWN_BUILDERS
+                    .iter()
+                    .find(|known| known.tags.iter().any(|t| tag.contains(t)))
+            })
+    }
+}
+
+/// Who produced a block, e.g. the one a bundle landed in, see [`attribute_block`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuilderAttribution {
+    /// Number of the block.
+    pub block: Option<U64>,
+    /// Fee recipient of the block.
+    pub coinbase: Address,
+    /// Extra-data of the block, usually signed by its builder.
+    pub extra_data: Bytes,
+    /// The builder, if recognized.
+    pub builder: Option<&'static KnownBuilder>,
+}
+
+impl BuilderAttribution {
+    /// Attributes `block` from its coinbase and extra-data.
+    #[must_use]
+    pub fn from_block<Tx>(block: &Block<Tx>) -> Self {
+        let coinbase = block.author.unwrap_or_default();
+
+        Self {
+            block: block.number,
+            coinbase,
+            extra_data: block.extra_data.clone(),
+            builder: KnownBuilder::recognize(coinbase, &block.extra_data),
+        }
+    }
+
+    /// The extra-data as text, without the non-printable characters some builders pad it with.
+    #[must_use]
+    pub fn tag(&self) -> String {
+        String::from_utf8_lossy(&self.extra_data)
+            .chars()
+            .filter(|c| !c.is_control() && *c != char::REPLACEMENT_CHARACTER)
+            .collect::<String>()
+            .trim()
+            .to_owned()
+    }
+}
+
+impl Display for BuilderAttribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.builder {
+            Some(builder) => f.write_str(builder.name),
+            None => write!(f, "unknown builder {:?} ({:?})", self.coinbase, self.tag()),
+        }
+    }
+}
+
+/// Fetches block `number`, e.g. the one a bundle landed in, and attributes it to its builder.
+///
+/// Returns `None` if the provider doesn't know the block.
+///
+/// # Example
+///
+/// ```
+/// let (_, block) = client.send_bundle(bundle).await?.inclusion().await?;
+///
+/// if let Some(attribution) = attribute_block(&provider, block).await? {
+///     info!(%block, builder = %attribution, "bundle landed");
+/// }
+/// ```
+///
+/// # Errors
+///
+/// * [`crate::Error::Provider`] if the block can't be fetched.
+pub async fn attribute_block<M: Waiter>(
+    provider: &M,
+    number: U64,
+) -> Result<Option<BuilderAttribution>> {
+    let block = provider
+        .get_block(number)
+        .await
+        .map_err(Error::middleware)?;
+    Ok(block.as_ref().map(BuilderAttribution::from_block))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_attribution() {
+        let block = |coinbase: Address, extra_data: &[u8]| Block::<TxHash> {
+            number: Some(17_000_000.into()),
+            author: Some(coinbase),
+            extra_data: Bytes::from(extra_data.to_vec()),
+            ..Default::default()
+        };
+
+        let attribution = BuilderAttribution::from_block(&block(
+            "0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5"
+                .parse()
+                .unwrap(),
+            b"beaverbuild.org",
+        ));
+        assert_eq!(
+            attribution.builder.and_then(|builder| builder.builder.clone()),
+            Some(Builder::BeaverBuild)
+        );
+
+        // rotated coinbase, still signed
+        let attribution =
+            BuilderAttribution::from_block(&block(Address::random(), b"Titan (titanbuilder.xyz)"));
+        assert_eq!(attribution.to_string(), "Titan");
+
+        let attribution =
+            BuilderAttribution::from_block(&block(Address::zero(), b"\x00\x01my-builder\xff"));
+        assert!(attribution.builder.is_none());
+        assert_eq!(attribution.tag(), "my-builder");
+    }
+}
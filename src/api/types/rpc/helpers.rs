@@ -1,43 +1,222 @@
 use super::*;
+#[cfg(feature = "wait")]
+use crate::alert::Alert;
+use crate::alert::Alerter;
+use crate::api::rpc_client::MevShareRpcClient;
+use crate::budget::InFlightPermit;
+use crate::client::MevShareRequest;
+use crate::guard::{self, Orders, Outstanding};
 use crate::helpers::provider::Waiter;
+#[cfg(feature = "wait")]
+use crate::tracker::Outcome;
+use crate::tracker::SubmissionTracker;
 use crate::{Error, Result};
 use derive_new::new;
 use ethers::prelude::*;
-use ethers::utils::keccak256;
-use std::fmt::Display;
-use std::slice::Iter;
+use futures::channel::oneshot;
+#[cfg(feature = "wait")]
+use futures::future::{self, Either};
+use futures::future::{FutureExt, Shared};
+use instant::Instant;
+use std::fmt::{Debug, Display};
+use std::sync::{Arc, Mutex, PoisonError};
+#[cfg(feature = "wait")]
+use tracing::debug;
 
 /// A bundle that is pending inclusion.
 ///
 /// See [`PendingBundle::inclusion`] for usage.
 #[derive(new)]
-pub struct PendingBundle<'lt> {
+pub struct PendingBundle<M = Provider<Ws>> {
     /// Bundle hash.
     pub hash: TxHash,
 
     /// Bundle info.
-    pub request: SendBundleParams<'lt>,
+    pub request: SendBundleParams,
 
-    /// Client to simulate the bundle with, in case it's necessary.
-    pub provider: &'lt Provider<Ws>,
+    /// Provider to wait for the bundle with.
+    pub provider: Arc<M>,
+
+    /// Slots taken among the bundles in flight allowed by the client and tenant [`crate::budget::Budget`]s, released on drop.
+    #[new(default)]
+    pub(crate) permits: Vec<InFlightPermit>,
+
+    /// Where the outcome of the bundle is recorded, see [`crate::MevShareClient::with_submission_tracker`].
+    #[new(default)]
+    pub(crate) tracker: Option<Arc<SubmissionTracker>>,
+
+    /// Notified if the bundle reverts, see [`crate::MevShareClient::with_alerter`].
+    #[new(default)]
+    pub(crate) alerter: Option<Arc<Alerter>>,
+
+    /// Relay to cancel the bundle with and poll its stats from, and the orders of the client to stop tracking once
+    /// cancelled.
+    #[new(default)]
+    pub(crate) relay: Option<(Arc<MevShareRpcClient>, Orders)>,
+
+    /// Fired by [`Self::cancel`], to stop waiting for the bundle.
+    #[new(default)]
+    pub(crate) cancelled: CancelSignal,
+
+    /// When the bundle was accepted by the relay.
+    #[new(value = "Instant::now()")]
+    pub sent_at: Instant,
+}
+
+/// Resolves the waits for a bundle or transaction once it is cancelled.
+pub(crate) struct CancelSignal {
+    sender: Mutex<Option<oneshot::Sender<()>>>,
+    #[cfg_attr(not(feature = "wait"), allow(dead_code))]
+    receiver: Shared<oneshot::Receiver<()>>,
+}
+
+impl Default for CancelSignal {
+    fn default() -> Self {
+        let (sender, receiver) = oneshot::channel();
+        Self {
+            sender: Mutex::new(Some(sender)),
+            receiver: receiver.shared(),
+        }
+    }
+}
+
+impl CancelSignal {
+    fn fire(&self) {
+        if let Some(sender) = self
+            .sender
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            let _ = sender.send(());
+        }
+    }
+
+    /// Runs `wait` to completion, unless the signal fires first, in which case [`Error::Cancelled`] is returned for
+    /// `hash`.
+    #[cfg(feature = "wait")]
+    async fn unless_fired<T>(
+        &self,
+        hash: TxHash,
+        wait: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let wait = std::pin::pin!(wait);
+        match future::select(wait, self.receiver.clone()).await {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => Err(Error::Cancelled(hash)),
+        }
+    }
 }
 
-impl Display for PendingBundle<'_> {
+impl<M> Display for PendingBundle<M> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.hash)
+        let Inclusion { block, max_block } = &self.request.inclusion;
+
+        write!(
+            f,
+            "bundle {:?} ({} legs) for blocks {block}..={}, pending for {:.1?}",
+            self.hash,
+            self.request.body.len(),
+            max_block.unwrap_or(*block),
+            self.sent_at.elapsed()
+        )
+    }
+}
+
+impl<M> Debug for PendingBundle<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingBundle")
+            .field("hash", &self.hash)
+            .field("block", &self.request.inclusion.block)
+            .field("max_block", &self.request.inclusion.max_block)
+            .field("legs", &self.request.body.len())
+            .field("elapsed", &self.sent_at.elapsed())
+            .finish_non_exhaustive()
     }
 }
 
-impl PendingBundle<'_> {
+impl<M: Waiter> PendingBundle<M> {
     /// Returns a [`futures::Future`] that becomes [`std::task::Poll::Ready`] when the bundle lands on-chain.
     ///
+    /// Requires the `wait` feature (enabled by default).
+    ///
     /// # Errors
     ///
     /// * [`Error::BundleTimeout`] if the bundle is not included in a block before `max_block`.
     /// * [`Error::BundleRevert`] if the bundle reverted.
     /// * [`Error::Provider`] if the provider fails to subscribe to fetch the [`TransactionReceipt`]s
     /// or to `subscribe_blocks` in order to to wait for them.
-    pub async fn inclusion(self) -> Result<(Vec<TransactionReceipt>, U64)> {
+    /// * [`Error::Cancelled`] if the bundle is cancelled with [`Self::cancel`], before or while waiting.
+    #[cfg(feature = "wait")]
+    pub async fn inclusion(&self) -> Result<(Vec<TransactionReceipt>, U64)> {
+        let result = self.wait_for_inclusion().await;
+        track_inclusion(self.tracker.as_deref(), &result);
+        untrack_settled(self.relay.as_ref(), self.order(), &result);
+        alert_revert(
+            self.alerter.as_deref(),
+            &result,
+            Alert::BundleReverted { hash: self.hash },
+        )
+        .await;
+        result
+    }
+
+    /// Like [`Self::inclusion`], then waits for `confirmations` blocks on top of the including block, to not act on a
+    /// bundle that a short reorg drops.
+    ///
+    /// Requires the `wait` feature (enabled by default).
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Self::inclusion`].
+    /// * [`Error::Reorged`] if the including block is reorged out and `on_reorg` is [`OnReorg::Fail`].
+    #[cfg(feature = "wait")]
+    pub async fn confirmation(
+        &self,
+        confirmations: u64,
+        on_reorg: OnReorg,
+    ) -> Result<(Vec<TransactionReceipt>, U64)> {
+        let result = loop {
+            let (receipts, block) = match self.wait_for_inclusion().await {
+                Ok(inclusion) => inclusion,
+                Err(err) => break Err(err),
+            };
+
+            match self
+                .provider
+                .wait_for_confirmations(&receipts, confirmations)
+                .await
+            {
+                Err(Error::Reorged(..)) if on_reorg == OnReorg::Resume => {
+                    debug!(?self.hash, %block, "bundle reorged out, waiting for it to land again");
+                }
+                Err(err) => break Err(err),
+                Ok(()) => break Ok((receipts, block)),
+            }
+        };
+
+        track_inclusion(self.tracker.as_deref(), &result);
+        untrack_settled(self.relay.as_ref(), self.order(), &result);
+        alert_revert(
+            self.alerter.as_deref(),
+            &result,
+            Alert::BundleReverted { hash: self.hash },
+        )
+        .await;
+        result
+    }
+
+    /// The order to cancel the bundle by, if it was sent with a replacement UUID.
+    #[cfg(feature = "wait")]
+    fn order(&self) -> Option<Outstanding> {
+        self.request
+            .replacement_uuid
+            .clone()
+            .map(Outstanding::Bundle)
+    }
+
+    #[cfg(feature = "wait")]
+    async fn wait_for_inclusion(&self) -> Result<(Vec<TransactionReceipt>, U64)> {
         let txs = self.request.body.hashes().collect();
         let max_block = self
             .request
@@ -45,10 +224,86 @@ impl PendingBundle<'_> {
             .max_block
             .unwrap_or(self.request.inclusion.block);
 
-        self.provider
-            .wait_for_bundle(self.hash, txs, max_block)
+        self.cancelled
+            .unless_fired(
+                self.hash,
+                self.provider.wait_for_bundle(self.hash, txs, max_block),
+            )
             .await
     }
+
+    /// Withdraws the bundle from the relay with `eth_cancelBundle`, by the replacement UUID it was sent with, and
+    /// stops waiting for it: [`Self::inclusion`] and [`Self::confirmation`] return [`Error::Cancelled`].
+    ///
+    /// Builders that already received the bundle may still include it. If it landed by the time the relay withdrew
+    /// it, [`BundleCancellation::TooLate`] is returned instead, and [`Self::inclusion`] still reports it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pending_bundle = client.send_bundle(bundle.replacement_uuid(uuid).build()).await?;
+    ///
+    /// tokio::select! {
+    ///     result = pending_bundle.inclusion() => handle(result?),
+    ///     _ = opportunity_gone() => {
+    ///         if let BundleCancellation::TooLate(receipts) = pending_bundle.cancel().await? {
+    ///             warn!(?pending_bundle.hash, block = ?receipts[0].block_number, "too late to cancel");
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidParams`] if the bundle was sent without a replacement UUID, or not by a
+    ///   [`crate::MevShareClient`]: it can't be cancelled.
+    /// * [`Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    /// * [`Error::Provider`] if the provider fails to fetch the receipts of the bundle.
+    pub async fn cancel(&self) -> Result<BundleCancellation> {
+        let Some(replacement_uuid) = &self.request.replacement_uuid else {
+            return Err(Error::InvalidParams(format!(
+                "bundle {:?} was sent without a replacement UUID",
+                self.hash
+            )));
+        };
+        let Some((rpc, orders)) = &self.relay else {
+            return Err(Error::InvalidParams(format!(
+                "bundle {:?} was not sent by a client",
+                self.hash
+            )));
+        };
+
+        let order = Outstanding::Bundle(replacement_uuid.clone());
+        guard::cancel(rpc, &order).await?;
+        guard::untrack(orders, &order);
+
+        // checked once withdrawn, not to miss a bundle landing in between
+        if let Some(receipts) = self.receipts().await? {
+            return Ok(BundleCancellation::TooLate(receipts));
+        }
+        self.cancelled.fire();
+
+        Ok(BundleCancellation::Cancelled)
+    }
+
+    /// The receipts of the transactions of the bundle, if they all landed.
+    async fn receipts(&self) -> Result<Option<Vec<TransactionReceipt>>> {
+        let mut receipts = Vec::with_capacity(self.request.body.len());
+
+        for hash in self.request.body.hashes() {
+            let receipt = self
+                .provider
+                .get_transaction_receipt(hash)
+                .await
+                .map_err(Error::middleware)?;
+            let Some(receipt) = receipt else {
+                return Ok(None);
+            };
+            receipts.push(receipt);
+        }
+
+        Ok(Some(receipts))
+    }
 }
 
 /// Number of blocks to wait before the transaction is considered dropped.
@@ -61,26 +316,146 @@ pub const TX_WAIT_MAX_BLOCKS: u64 = 25;
 ///
 /// See [`PendingTransaction::inclusion`] for usage.
 #[derive(new)]
-pub struct PendingTransaction<'lt> {
+pub struct PendingTransaction<M = Provider<Ws>> {
     /// Transaction hash.
     pub hash: TxHash,
 
     /// Maximum block number to wait for.
     pub max_block: Option<U64>,
 
-    /// Client to simulate the bundle with, in case it's necessary.
-    pub provider: &'lt Provider<Ws>,
+    /// Provider to wait for the transaction with.
+    pub provider: Arc<M>,
+
+    /// When the transaction was accepted by the relay.
+    #[new(value = "Instant::now()")]
+    pub sent_at: Instant,
+
+    /// Where the outcome of the transaction is recorded, see [`crate::MevShareClient::with_submission_tracker`].
+    #[new(default)]
+    pub(crate) tracker: Option<Arc<SubmissionTracker>>,
+
+    /// Notified if the transaction reverts, see [`crate::MevShareClient::with_alerter`].
+    #[new(default)]
+    pub(crate) alerter: Option<Arc<Alerter>>,
+
+    /// Relay to cancel the transaction with, and the orders of the client to stop tracking once cancelled.
+    #[new(default)]
+    pub(crate) relay: Option<(Arc<MevShareRpcClient>, Orders)>,
+
+    /// Fired by [`Self::cancel`], to stop waiting for the transaction.
+    #[new(default)]
+    pub(crate) cancelled: CancelSignal,
+}
+
+/// What [`PendingBundle::confirmation`] and [`PendingTransaction::confirmation`] do when the block they were included
+/// in is reorged out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnReorg {
+    /// Fail with [`Error::Reorged`].
+    #[default]
+    Fail,
+    /// Wait for the bundle or transaction to be included again, until its maximum block.
+    Resume,
+}
+
+/// Where a [`PendingTransaction`] stands, see [`PendingTransaction::status`].
+#[derive(Clone, Debug)]
+pub enum TransactionStatus {
+    /// Not included yet.
+    Pending,
+    /// Included in the block, and didn't revert, or has no status to tell, e.g. before Byzantium.
+    Included(Box<TransactionReceipt>, U64),
+    /// Included, but reverted.
+    Reverted(Box<TransactionReceipt>),
+    /// Not included before its maximum block: it won't be anymore.
+    Expired,
+}
+
+/// Whether [`PendingBundle::cancel`] withdrew the bundle in time.
+#[derive(Clone, Debug)]
+pub enum BundleCancellation {
+    /// The relay withdrew the bundle, and it hasn't landed.
+    Cancelled,
+    /// The bundle already landed, with these receipts.
+    TooLate(Vec<TransactionReceipt>),
+}
+
+/// Which transaction took the nonce, see [`crate::MevShareClient::cancel_by_replacement`].
+#[derive(Clone, Debug)]
+pub enum ReplacementOutcome {
+    /// The cancellation landed: the original transaction can't anymore.
+    Cancelled(Box<TransactionReceipt>),
+    /// The original transaction landed first.
+    TooLate(Box<TransactionReceipt>),
+}
+
+/// State changes reported by [`crate::MevShareClient::send_private_transaction_until`], e.g. to update a UI.
+#[derive(Debug)]
+pub enum TransactionProgress<'a> {
+    /// (Re)submitted to the relay, valid until `max_block`.
+    Submitted { hash: TxHash, max_block: U64 },
+    /// Still not included at this block.
+    Pending(U64),
+    /// Included in the block.
+    Included(&'a TransactionReceipt, U64),
+    /// Gave up: reverted, deadline reached, or the relay or the provider failed.
+    Failed(&'a Error),
+}
+
+impl<M> Display for PendingTransaction<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "transaction {:?} ", self.hash)?;
+
+        match self.max_block {
+            Some(max_block) => write!(f, "until block {max_block}")?,
+            None => write!(f, "for {TX_WAIT_MAX_BLOCKS} blocks")?,
+        }
+
+        write!(f, ", pending for {:.1?}", self.sent_at.elapsed())
+    }
 }
 
-impl Display for PendingTransaction<'_> {
+impl<M> Debug for PendingTransaction<M> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.hash)
+        f.debug_struct("PendingTransaction")
+            .field("hash", &self.hash)
+            .field("max_block", &self.max_block)
+            .field("elapsed", &self.sent_at.elapsed())
+            .finish_non_exhaustive()
     }
 }
 
-impl PendingTransaction<'_> {
+impl<M: Waiter> PendingTransaction<M> {
+    /// Checks once where the transaction stands, without waiting: e.g. to poll it from a UI.
+    ///
+    /// # Errors
+    ///
+    /// * The error of the provider, e.g. a [`ProviderError`], if it fails to fetch the receipt or the current block.
+    pub async fn status(&self) -> std::result::Result<TransactionStatus, M::Error> {
+        if let Some(receipt) = self.provider.get_transaction_receipt(self.hash).await? {
+            // pre-Byzantium receipts, and some nodes, have no status: only a zero one means a revert
+            return Ok(match (receipt.status, receipt.block_number) {
+                (Some(status), _) if status.is_zero() => {
+                    TransactionStatus::Reverted(Box::new(receipt))
+                }
+                (_, Some(block)) => TransactionStatus::Included(Box::new(receipt), block),
+                (_, None) => TransactionStatus::Pending,
+            });
+        }
+
+        if let Some(max_block) = self.max_block
+            && self.provider.get_block_number().await? > max_block
+        {
+            return Ok(TransactionStatus::Expired);
+        }
+
+        Ok(TransactionStatus::Pending)
+    }
+
     /// Waits for transaction inclusion.
     ///
+    /// Requires the `wait` feature (enabled by default).
+    ///
     /// # Returns
     ///
     /// A tuple containing the [`TransactionReceipt`] and the block number it was included in.
@@ -91,10 +466,43 @@ impl PendingTransaction<'_> {
     /// * [`Error::TransactionRevert`] if the transaction reverted.
     /// * [`Error::Provider`] if the provider fails to subscribe to fetch the [`TransactionReceipt`]
     /// or to `subscribe_blocks` in order to to wait for them.
+    /// * [`Error::Cancelled`] if the transaction is cancelled with [`Self::cancel`], before or while waiting.
+    #[cfg(feature = "wait")]
     pub async fn inclusion(&self) -> Result<(TransactionReceipt, U64)> {
+        let result = self.wait_for_inclusion().await;
+        track_inclusion(self.tracker.as_deref(), &result);
+        untrack_settled(
+            self.relay.as_ref(),
+            Some(Outstanding::Transaction(self.hash)),
+            &result,
+        );
+        alert_revert(
+            self.alerter.as_deref(),
+            &result,
+            Alert::TransactionReverted { hash: self.hash },
+        )
+        .await;
+        result
+    }
+
+    #[cfg(feature = "wait")]
+    async fn wait_for_inclusion(&self) -> Result<(TransactionReceipt, U64)> {
+        self.cancelled
+            .unless_fired(self.hash, self.wait_for_receipt())
+            .await
+    }
+
+    #[cfg(feature = "wait")]
+    async fn wait_for_receipt(&self) -> Result<(TransactionReceipt, U64)> {
         let max_block = match self.max_block {
             Some(block) => block,
-            None => self.provider.get_block_number().await? + TX_WAIT_MAX_BLOCKS,
+            None => {
+                self.provider
+                    .get_block_number()
+                    .await
+                    .map_err(Error::middleware)?
+                    + TX_WAIT_MAX_BLOCKS
+            }
         };
 
         let (receipt, block) = self
@@ -102,104 +510,268 @@ impl PendingTransaction<'_> {
             .wait_for_tx_receipt(self.hash, max_block)
             .await?;
 
-        if receipt.status.unwrap() != U64::one() {
+        if receipt.status.is_some_and(|status| status.is_zero()) {
             return Err(Error::TransactionRevert(receipt));
         }
 
         Ok((receipt, block))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Withdraws the transaction from the relay with `eth_cancelPrivateTransaction` and, if the relay did, stops
+    /// waiting for it: [`Self::inclusion`] and [`Self::confirmation`] return [`Error::Cancelled`].
+    ///
+    /// Builders that already received the transaction may still include it; see
+    /// [`crate::MevShareClient::cancel_by_replacement`] to stop them too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pending_tx = client.send_private_transaction(params).await?;
+    ///
+    /// tokio::select! {
+    ///     result = pending_tx.inclusion() => handle(result?),
+    ///     _ = user_clicked_cancel() => {
+    ///         if !pending_tx.cancel().await?.is_cancelled() {
+    ///             warn!(?pending_tx.hash, "too late to cancel");
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidParams`] if the transaction was not sent by a [`crate::MevShareClient`]: it can't be
+    ///   cancelled.
+    /// * [`Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    pub async fn cancel(&self) -> Result<CancelPrivateTransactionResponse> {
+        let Some((rpc, orders)) = &self.relay else {
+            return Err(Error::InvalidParams(format!(
+                "transaction {:?} was not sent by a client",
+                self.hash
+            )));
+        };
 
-    #[test]
-    fn test_hashes() {
-        let mut txs = [[0_u8; 64]; 7];
-        let mut hashes = [TxHash::default(); 7];
+        let response: CancelPrivateTransactionResponse = rpc
+            .post(
+                MevShareRequest::CancelPrivateTransaction,
+                [CancelPrivateTransactionParams { tx_hash: self.hash }],
+            )
+            .await?;
 
-        for i in 0..8 {
-            txs[i][63] += u8::try_from(i).unwrap();
-            hashes[i] = TxHash(keccak256(txs[i]));
+        if response.is_cancelled() {
+            guard::untrack(orders, &Outstanding::Transaction(self.hash));
+            self.cancelled.fire();
         }
 
-        let tx = |i: usize| Body::Signed {
-            tx: txs[i].into(),
-            can_revert: false,
-        };
+        Ok(response)
+    }
+
+    /// Like [`Self::inclusion`], then waits for `confirmations` blocks on top of the including block, to not act on a
+    /// transaction that a short reorg drops.
+    ///
+    /// Requires the `wait` feature (enabled by default).
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Self::inclusion`].
+    /// * [`Error::Reorged`] if the including block is reorged out and `on_reorg` is [`OnReorg::Fail`].
+    #[cfg(feature = "wait")]
+    pub async fn confirmation(
+        &self,
+        confirmations: u64,
+        on_reorg: OnReorg,
+    ) -> Result<(TransactionReceipt, U64)> {
+        let result = loop {
+            let (receipt, block) = match self.wait_for_inclusion().await {
+                Ok(inclusion) => inclusion,
+                Err(err) => break Err(err),
+            };
 
-        let unsigned_tx = |i: usize| Body::Tx { hash: hashes[i] };
-
-        let bundle = SendBundleParams {
-            body: vec![
-                tx(0),
-                tx(1),
-                unsigned_tx(2),
-                Body::Bundle(Box::new(SendBundleParams {
-                    body: vec![tx(3), tx(4), unsigned_tx(5)],
-                    ..Default::default()
-                })),
-                tx(6),
-            ],
-            ..Default::default()
+            match self
+                .provider
+                .wait_for_confirmations(std::slice::from_ref(&receipt), confirmations)
+                .await
+            {
+                Err(Error::Reorged(..)) if on_reorg == OnReorg::Resume => {
+                    debug!(?self.hash, %block, "transaction reorged out, waiting for it to land again");
+                }
+                Err(err) => break Err(err),
+                Ok(()) => break Ok((receipt, block)),
+            }
         };
 
-        assert!(bundle.body.hashes().eq(hashes));
+        track_inclusion(self.tracker.as_deref(), &result);
+        untrack_settled(
+            self.relay.as_ref(),
+            Some(Outstanding::Transaction(self.hash)),
+            &result,
+        );
+        alert_revert(
+            self.alerter.as_deref(),
+            &result,
+            Alert::TransactionReverted { hash: self.hash },
+        )
+        .await;
+        result
     }
 }
 
-/// Iterator over the hashes of a bundle body.
-/// See [`HahsIter`] for usage.
-pub struct BodyHashIterator<'lt> {
-    stack: Vec<Iter<'lt, Body<'lt>>>,
-}
+/// Records the outcome of waiting for a bundle or transaction in `tracker`, if any.
+#[cfg(feature = "wait")]
+fn track_inclusion<T>(tracker: Option<&SubmissionTracker>, result: &Result<T>) {
+    let Some(tracker) = tracker else {
+        return;
+    };
 
-impl<'lt> BodyHashIterator<'lt> {
-    pub fn new(bodies: &'lt [Body]) -> Self {
-        BodyHashIterator {
-            stack: vec![bodies.iter()],
+    match result {
+        Ok(_) => tracker.record(Outcome::Included),
+        Err(Error::BundleRevert(_) | Error::TransactionRevert(_)) => {
+            tracker.record(Outcome::Reverted);
+        }
+        Err(Error::BundleTimeout(..) | Error::TransactionTimeout(..)) => {
+            tracker.record(Outcome::TimedOut);
         }
+        Err(_) => {}
     }
 }
 
-impl<'a> Iterator for BodyHashIterator<'a> {
-    type Item = TxHash;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        while let Some(bodies) = self.stack.last_mut() {
-            match bodies.next() {
-                Some(body) => match body {
-                    Body::Tx { hash } => return Some(*hash),
-                    Body::Signed { tx, .. } => return Some(keccak256(tx).into()),
-                    Body::Bundle(bundle) => {
-                        self.stack.push(bundle.body.iter());
-                        continue;
-                    }
-                },
-                None => {
-                    self.stack.pop();
-                    continue;
-                }
-            }
-        }
-        None
+/// Stops tracking `order` in the orders of the client that sent it, if any, once waiting for it found that it landed,
+/// reverted or expired: there's nothing left to cancel.
+#[cfg(feature = "wait")]
+fn untrack_settled<T>(
+    relay: Option<&(Arc<MevShareRpcClient>, Orders)>,
+    order: Option<Outstanding>,
+    result: &Result<T>,
+) {
+    let (Some((_, orders)), Some(order)) = (relay, order) else {
+        return;
+    };
+
+    if matches!(
+        result,
+        Ok(_)
+            | Err(Error::BundleRevert(_)
+                | Error::TransactionRevert(_)
+                | Error::BundleTimeout(..)
+                | Error::TransactionTimeout(..))
+    ) {
+        guard::untrack(orders, &order);
     }
 }
 
-/// Trait for iterating over the hashes of a bundle.
-pub trait HashesIter {
-    fn hashes(&self) -> BodyHashIterator;
+/// Fires `alert` with `alerter`, if any, if waiting for a bundle or transaction found that it reverted.
+#[cfg(feature = "wait")]
+async fn alert_revert<T>(alerter: Option<&Alerter>, result: &Result<T>, alert: Alert) {
+    if let Some(alerter) = alerter
+        && let Err(Error::BundleRevert(_) | Error::TransactionRevert(_)) = result
+    {
+        alerter.fire(alert).await;
+    }
 }
 
-impl HashesIter for Vec<Body<'_>> {
-    fn hashes(&self) -> BodyHashIterator {
-        BodyHashIterator::new(self.as_slice())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wait")]
+    #[test]
+    fn test_cancel_signal() {
+        let signal = CancelSignal::default();
+        let hash = TxHash::repeat_byte(1);
+
+        let done = futures::executor::block_on(signal.unless_fired(hash, async { Ok(7) }));
+        assert_eq!(done.unwrap(), 7);
+
+        signal.fire();
+        // fired before the wait starts, or while waiting
+        let cancelled =
+            futures::executor::block_on(signal.unless_fired(hash, future::pending::<Result<()>>()));
+        assert!(matches!(cancelled, Err(Error::Cancelled(h)) if h == hash));
+        signal.fire();
     }
-}
 
-impl HashesIter for &[Body<'_>] {
-    fn hashes(&self) -> BodyHashIterator {
-        BodyHashIterator::new(self)
+    #[cfg(feature = "wait")]
+    #[tokio::test]
+    async fn test_cancel_landed_bundle() {
+        use crate::helpers::{json_rpc_response, mock_relay};
+
+        let (url, _) = mock_relay(|_| Some(json_rpc_response("null"))).await;
+        let rpc = MevShareRpcClient::new(
+            &url,
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+        );
+        let (provider, mock) = Provider::mocked();
+        let request = SendBundleParams::builder()
+            .body(vec![Body::Tx {
+                hash: TxHash::repeat_byte(1),
+            }])
+            .inclusion(10, None)
+            .replacement_uuid("uuid".to_owned())
+            .build();
+        let mut bundle = PendingBundle::new(TxHash::repeat_byte(2), request, Arc::new(provider));
+        bundle.relay = Some((Arc::new(rpc), Orders::default()));
+
+        let waiting = |bundle: &PendingBundle<Provider<MockProvider>>| {
+            bundle
+                .cancelled
+                .unless_fired(bundle.hash, future::pending::<Result<()>>())
+                .now_or_never()
+        };
+
+        // the bundle landed before the relay withdrew it: waiters still get its inclusion
+        mock.push(TransactionReceipt {
+            block_number: Some(U64::from(10)),
+            ..TransactionReceipt::default()
+        })
+        .unwrap();
+        assert!(matches!(
+            bundle.cancel().await,
+            Ok(BundleCancellation::TooLate(receipts)) if receipts.len() == 1
+        ));
+        assert!(waiting(&bundle).is_none());
+
+        mock.push(serde_json::Value::Null).unwrap();
+        assert!(matches!(
+            bundle.cancel().await,
+            Ok(BundleCancellation::Cancelled)
+        ));
+        assert!(matches!(waiting(&bundle), Some(Err(Error::Cancelled(_)))));
+    }
+
+    #[test]
+    fn test_transaction_status() {
+        let (provider, mock) = Provider::mocked();
+        let pending_tx = PendingTransaction::new(TxHash::repeat_byte(1), None, Arc::new(provider));
+        let status = |status: Option<u64>| {
+            mock.push(TransactionReceipt {
+                status: status.map(U64::from),
+                block_number: Some(U64::from(10)),
+                ..TransactionReceipt::default()
+            })
+            .unwrap();
+            futures::executor::block_on(pending_tx.status()).unwrap()
+        };
+
+        assert!(matches!(status(Some(1)), TransactionStatus::Included(..)));
+        assert!(matches!(status(Some(0)), TransactionStatus::Reverted(_)));
+        // no status, e.g. before Byzantium
+        assert!(matches!(status(None), TransactionStatus::Included(..)));
+    }
+
+    #[test]
+    fn test_owned() {
+        fn assert_static<T: 'static>() {}
+
+        // params read at runtime and pending bundles can outlive the function that built or sent them
+        assert_static::<SendBundleParams>();
+        assert_static::<SendTransactionParams>();
+        assert_static::<PendingBundle>();
+        assert_static::<PendingTransaction>();
+        assert_static::<crate::MevShareClient>();
+
+        let name = String::from("my-builder");
+        let builder = Builder::from_name(&name);
+        drop(name);
+        assert_eq!(builder, Builder::Other("my-builder".to_owned()));
     }
 }
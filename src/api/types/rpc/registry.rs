@@ -0,0 +1,67 @@
+use crate::api::types::{BuilderRegistry, BUILDER_REGISTRY_URL};
+use crate::{Error, Result};
+use futures::{stream, Stream};
+use futures_timer::Delay;
+use std::time::Duration;
+
+/// Downloads the builders currently registered with Flashbots from [`BUILDER_REGISTRY_URL`], to share bundles
+/// with builders registered after this release too.
+///
+/// # Example
+///
+/// ```
+/// let registry = fetch_builder_registry().await?;
+///
+/// let bundle = SendBundleParams::builder()
+///     .body(body)
+///     .inclusion(block, None)
+///     .privacy([Hint::Hash], registry.builders())
+///     .build();
+/// ```
+///
+/// # Errors
+///
+/// * [`Error::BuilderRegistry`] if the registry can't be downloaded or parsed.
+pub async fn fetch_builder_registry() -> Result<BuilderRegistry> {
+    fetch_builder_registry_from(BUILDER_REGISTRY_URL).await
+}
+
+/// Downloads the builder registry from `url` instead of [`BUILDER_REGISTRY_URL`], e.g. from a mirror.
+///
+/// # Errors
+///
+/// * [`Error::BuilderRegistry`] if the registry can't be downloaded or parsed.
+pub async fn fetch_builder_registry_from(url: &str) -> Result<BuilderRegistry> {
+    reqwest::get(url)
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(Error::BuilderRegistry)?
+        .json()
+        .await
+        .map_err(Error::BuilderRegistry)
+}
+
+/// Returns a [`Stream`] of the builder registry, downloaded right away, then every `interval`, e.g. to keep the
+/// builders of a long-running searcher up to date.
+///
+/// The stream yields the failed downloads too, and keeps going.
+///
+/// # Example
+///
+/// ```
+/// let mut registry = pin!(watch_builder_registry(Duration::from_secs(3600)));
+/// while let Some(update) = registry.next().await {
+///     match update {
+///         Ok(registry) => *builders.write().unwrap() = registry,
+///         Err(err) => warn!(%err, "keeping the previous builders"),
+///     }
+/// }
+/// ```
+pub fn watch_builder_registry(interval: Duration) -> impl Stream<Item = Result<BuilderRegistry>> {
+    stream::unfold(true, move |first| async move {
+        if !first {
+            Delay::new(interval).await;
+        }
+        Some((fetch_builder_registry().await, false))
+    })
+}
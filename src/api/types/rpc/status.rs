@@ -0,0 +1,218 @@
+use super::*;
+use crate::backend::OrderflowBackend;
+use crate::helpers::provider::{BlockStream, Waiter};
+use crate::{Error, Result};
+use ethers::prelude::*;
+use futures::{stream, Stream, StreamExt};
+use std::collections::VecDeque;
+
+/// A stage reached by a bundle on its way on-chain, see [`PendingBundle::watch_status`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BundleStatusEvent {
+    /// The relay received the bundle.
+    Received,
+    /// The relay simulated the bundle.
+    Simulated,
+    /// More builders considered the bundle for their blocks, `builders` of them so far.
+    Considered { builders: usize },
+    /// More builders included the bundle in a block they built, `builders` of them so far. Their blocks may still lose
+    /// the auction.
+    Sealed { builders: usize },
+    /// The bundle landed in the block.
+    Included(U64),
+    /// The bundle didn't land by its maximum block. The stats of its last target block tell how far it made it.
+    Dropped(Box<BundleStats>),
+}
+
+/// The stages `current` reached since `previous`, in order.
+fn transitions(previous: &BundleStats, current: &BundleStats) -> Vec<BundleStatusEvent> {
+    let mut events = Vec::new();
+
+    if previous.received_at.is_none() && current.received_at.is_some() {
+        events.push(BundleStatusEvent::Received);
+    }
+    if !previous.is_simulated && current.is_simulated {
+        events.push(BundleStatusEvent::Simulated);
+    }
+
+    let considered = current.considered_by_builders_at.len();
+    if considered > previous.considered_by_builders_at.len() {
+        events.push(BundleStatusEvent::Considered {
+            builders: considered,
+        });
+    }
+
+    let sealed = current.sealed_by_builders_at.len();
+    if sealed > previous.sealed_by_builders_at.len() {
+        events.push(BundleStatusEvent::Sealed { builders: sealed });
+    }
+
+    events
+}
+
+impl<M: Waiter> PendingBundle<M> {
+    /// Returns a [`Stream`] of the stages the bundle reaches, polling its stats from the Flashbots relay and looking
+    /// for it on-chain at each new block, e.g. to show it on a dashboard or to find out where a bundle that doesn't
+    /// land drops out.
+    ///
+    /// The stream ends after [`BundleStatusEvent::Included`], [`BundleStatusEvent::Dropped`] or an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pending_bundle = client.send_bundle(bundle).await?;
+    ///
+    /// let mut status = pin!(pending_bundle.watch_status());
+    /// while let Some(event) = status.next().await {
+    ///     info!(?pending_bundle.hash, ?event);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The stream yields, then ends with:
+    ///
+    /// * [`Error::InvalidParams`] if the bundle was not sent by a [`crate::MevShareClient`]: there is no relay to ask.
+    /// * [`Error::Rpc`] if a JSON-RPC request to the MEV-Share API fails.
+    /// * [`Error::Provider`] if the provider fails to subscribe to blocks or to fetch the receipts of the bundle.
+    pub fn watch_status(&self) -> impl Stream<Item = Result<BundleStatusEvent>> + '_ {
+        let watch = StatusWatch {
+            bundle: self,
+            blocks: None,
+            stats: BundleStats::default(),
+            events: VecDeque::new(),
+            finished: false,
+        };
+
+        stream::unfold(watch, StatusWatch::next)
+    }
+}
+
+/// State of a [`PendingBundle::watch_status`] stream.
+struct StatusWatch<'b, M> {
+    bundle: &'b PendingBundle<M>,
+    blocks: Option<BlockStream<'b>>,
+    stats: BundleStats,
+    events: VecDeque<BundleStatusEvent>,
+    finished: bool,
+}
+
+impl<M: Waiter> StatusWatch<'_, M> {
+    async fn next(mut self) -> Option<(Result<BundleStatusEvent>, Self)> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Some((Ok(event), self));
+            }
+            if self.finished {
+                return None;
+            }
+            if let Err(err) = self.poll().await {
+                self.finished = true;
+                return Some((Err(err), self));
+            }
+        }
+    }
+
+    /// Waits for the next block, then queues the stages the bundle reached.
+    async fn poll(&mut self) -> Result<()> {
+        let Some((rpc, _)) = &self.bundle.relay else {
+            return Err(Error::InvalidParams(format!(
+                "bundle {:?} was not sent by a client",
+                self.bundle.hash
+            )));
+        };
+
+        let blocks = match &mut self.blocks {
+            Some(blocks) => blocks,
+            None => self.blocks.insert(self.bundle.provider.new_blocks().await?),
+        };
+        let Some(number) = blocks.next().await else {
+            self.finished = true;
+            return Ok(());
+        };
+
+        if let Some(block) = self.landed_in().await? {
+            self.events.push_back(BundleStatusEvent::Included(block));
+            self.finished = true;
+            return Ok(());
+        }
+
+        let Inclusion { block, max_block } = self.bundle.request.inclusion;
+        let max_block = max_block.unwrap_or(block);
+        let target = (number + 1).clamp(block, max_block);
+
+        let stats = rpc.bundle_status(self.bundle.hash, target).await?;
+        self.events.extend(transitions(&self.stats, &stats));
+        self.stats = stats;
+
+        if number >= max_block {
+            self.events
+                .push_back(BundleStatusEvent::Dropped(Box::new(self.stats.clone())));
+            self.finished = true;
+        }
+
+        Ok(())
+    }
+
+    /// The block every transaction of the bundle landed in, if they did, together.
+    async fn landed_in(&self) -> Result<Option<U64>> {
+        let mut landed = None;
+
+        for hash in self.bundle.request.body.hashes() {
+            let Some(receipt) = self
+                .bundle
+                .provider
+                .get_transaction_receipt(hash)
+                .await
+                .map_err(Error::middleware)?
+            else {
+                return Ok(None);
+            };
+            if landed.is_some() && landed != receipt.block_number {
+                return Ok(None);
+            }
+            landed = receipt.block_number;
+        }
+
+        Ok(landed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitions() {
+        let builder = |second| BuilderTimestamp {
+            pubkey: Bytes::from(vec![second]),
+            timestamp: chrono::DateTime::from_timestamp(i64::from(second), 0).unwrap(),
+        };
+
+        let received = BundleStats {
+            received_at: Some(builder(1).timestamp),
+            ..BundleStats::default()
+        };
+        assert_eq!(
+            transitions(&BundleStats::default(), &received),
+            vec![BundleStatusEvent::Received]
+        );
+        assert!(transitions(&received, &received).is_empty());
+
+        let sealed = BundleStats {
+            is_simulated: true,
+            simulated_at: Some(builder(2).timestamp),
+            considered_by_builders_at: vec![builder(3), builder(4)],
+            sealed_by_builders_at: vec![builder(5)],
+            ..received.clone()
+        };
+        assert_eq!(
+            transitions(&received, &sealed),
+            vec![
+                BundleStatusEvent::Simulated,
+                BundleStatusEvent::Considered { builders: 2 },
+                BundleStatusEvent::Sealed { builders: 1 },
+            ]
+        );
+    }
+}
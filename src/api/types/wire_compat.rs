@@ -0,0 +1,406 @@
+//! Wire format of the requests and responses.
+//!
+//! The fixtures in `fixtures/` are hand-written, in the shape of the methods documented in the [Flashbots docs]: they
+//! are not captured from the relay, and their hashes, transactions and signatures are made up. They pin the JSON this
+//! crate sends and accepts: requests built by this crate must serialize to the same JSON, field names, casing and hex
+//! encoding included; responses must deserialize.
+//!
+//! The order of `hints` and `builders` is not significant: [`Privacy`] keeps them in sets.
+//!
+//! [Flashbots docs]: https://docs.flashbots.net/flashbots-auction/searchers/advanced/rpc-endpoint
+
+use super::*;
+use crate::client::MevShareRequest;
+use ethers::prelude::*;
+use mev_share_types::jsonrpc::JsonRpcResponseSuccess;
+use serde_json::Value;
+
+macro_rules! fixture {
+    ($name: literal) => {
+        include_str!(concat!("fixtures/", $name, ".json"))
+    };
+}
+
+/// Parses a fixture, sorting the privacy sets so that they can be compared.
+fn normalized(json: &str) -> Value {
+    let mut value: Value = serde_json::from_str(json).unwrap();
+    sort_privacy(&mut value);
+    value
+}
+
+fn sort_privacy(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if let ("hints" | "builders", Value::Array(items)) = (key.as_str(), &mut *value) {
+                    items.sort_by_key(ToString::to_string);
+                } else {
+                    sort_privacy(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(sort_privacy),
+        _ => {}
+    }
+}
+
+/// Serializes a request the way [`crate::api::rpc_client::MevShareRpcClient::post`] does.
+fn request(method: MevShareRequest, params: impl serde::Serialize) -> Value {
+    let request = JsonRpcRequest {
+        jsonrpc: "2.0",
+        id: 1,
+        method: method.as_method_name(),
+        params: serde_json::to_value(params).unwrap(),
+    };
+
+    normalized(&serde_json::to_string(&request).unwrap())
+}
+
+/// Checks that the params of the request fixture `json` survive a round trip through `P`.
+fn assert_round_trip<'de, P>(method: MevShareRequest, json: &'de str)
+where
+    P: serde::Deserialize<'de> + serde::Serialize,
+{
+    #[derive(serde::Deserialize)]
+    struct Request<P> {
+        params: P,
+    }
+
+    let parsed: Request<P> = serde_json::from_str(json).unwrap();
+    assert_eq!(request(method, parsed.params), normalized(json));
+}
+
+/// Made-up bytes in the shape of an EIP-1559 transaction, only ever passed through: they don't even decode.
+const SIGNED_TX: &str = "0x02f8730180843b9aca00852e90edd00082520894c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2880de0b6b3a764000080c001a0a5b4c3d2e1f00112233445566778899aabbccddeeff00112233445566778899a06b5a49382716f5e4d3c2b1a0f9e8d7c6b5a49382716f5e4d3c2b1a0f9e8d7c6";
+
+#[test]
+fn test_send_bundle() {
+    let params = SendBundleParams::builder()
+        .inclusion(0x8b_8da8, Some(U64::from(0x8b_8dab)))
+        .body(vec![
+            Body::tx(
+                "0x2ce3a5f1e5e9c4e2a0bf0d7a3b4f8bd0e3f3b4a4c4e8d8b7e0f0a5b6c7d8e9f0"
+                    .parse::<TxHash>()
+                    .unwrap(),
+            ),
+            Body::signed(SIGNED_TX.parse::<Bytes>().unwrap(), false),
+        ])
+        .validity(
+            vec![Refund {
+                body_idx: 0,
+                percent: 90,
+            }],
+            vec![RefundConfig {
+                address: "0x690b9a9e9aa1c9db991c7721a92d351db4fac990"
+                    .parse()
+                    .unwrap(),
+                percent: 100,
+            }],
+        )
+        .privacy(
+            [
+                Hint::Calldata,
+                Hint::ContractAddress,
+                Hint::Logs,
+                Hint::FunctionSelector,
+                Hint::Hash,
+                Hint::TxHash,
+            ],
+            Builder::KNOWN,
+        )
+        .metadata("my-wallet")
+        .build();
+
+    assert_eq!(
+        request(MevShareRequest::SendBundle, [params]),
+        normalized(fixture!("send_bundle"))
+    );
+    assert_round_trip::<[SendBundleParams; 1]>(
+        MevShareRequest::SendBundle,
+        fixture!("send_bundle"),
+    );
+
+    let response: JsonRpcResponse<SendBundleResponse> =
+        serde_json::from_str(fixture!("send_bundle_response")).unwrap();
+    assert!(matches!(
+        response,
+        JsonRpcResponse::Success(JsonRpcResponseSuccess { result, .. })
+            if result.bundle_hash == "0x5d8b3b6a2f0ce8e2a8d6d0b5a6b3f1e9c7d8f0a1b2c3d4e5f60718293a4b5c6d".parse().unwrap()
+    ));
+}
+
+#[test]
+fn test_send_private_transaction() {
+    let params = SendTransactionParams::builder()
+        .tx(SIGNED_TX.parse::<Bytes>().unwrap())
+        .max_block_number(0x8b_8dc0)
+        .preferences(
+            [
+                Hint::Calldata,
+                Hint::ContractAddress,
+                Hint::Logs,
+                Hint::FunctionSelector,
+                Hint::Hash,
+            ],
+            [Builder::Flashbots],
+        )
+        .build();
+
+    assert_eq!(
+        request(MevShareRequest::SendPrivateTransaction, [params]),
+        normalized(fixture!("send_private_transaction"))
+    );
+    assert_round_trip::<[SendTransactionParams; 1]>(
+        MevShareRequest::SendPrivateTransaction,
+        fixture!("send_private_transaction"),
+    );
+
+    let response: JsonRpcResponse<TxHash> =
+        serde_json::from_str(fixture!("send_private_transaction_response")).unwrap();
+    assert!(matches!(response, JsonRpcResponse::Success(_)));
+}
+
+#[test]
+fn test_sim_bundle() {
+    let bundle = SendBundleParams::builder()
+        .inclusion(0x8b_8da8, None)
+        .body(vec![Body::signed(
+            SIGNED_TX.parse::<Bytes>().unwrap(),
+            true,
+        )])
+        .build();
+    let options = SimulateBundleParams::builder()
+        .parent_block(0x8b_8da7)
+        .timeout(5_u64)
+        .build();
+
+    assert_eq!(
+        request(MevShareRequest::SimBundle, (bundle, options)),
+        normalized(fixture!("sim_bundle"))
+    );
+    assert_round_trip::<(SendBundleParams, SimulateBundleParams)>(
+        MevShareRequest::SimBundle,
+        fixture!("sim_bundle"),
+    );
+
+    let response: JsonRpcResponse<SimulateBundleResponse> =
+        serde_json::from_str(fixture!("sim_bundle_response")).unwrap();
+    let JsonRpcResponse::Success(JsonRpcResponseSuccess { result, .. }) = response else {
+        panic!("expected a successful simulation");
+    };
+    assert!(result.success);
+    assert_eq!(result.state_block, U64::from(0x8b_8da8));
+    assert_eq!(result.gas_used, U256::from(0xa620));
+    assert_eq!(result.logs.len(), 2);
+}
+
+#[test]
+fn test_call_bundle() {
+    let response: EthCallBundleResponse =
+        serde_json::from_str(fixture!("call_bundle_response")).unwrap();
+    assert_eq!(response.state_block_number, 5_221_585);
+    assert_eq!(
+        response.coinbase_diff,
+        U256::from(20_000_000_000_126_000_u64)
+    );
+    assert_eq!(response.results[0].gas_used, 21_000);
+    assert!(response.results[0].is_success());
+    assert!(!response.is_success());
+    assert_eq!(
+        response
+            .first_failure()
+            .and_then(|result| result.revert.as_deref()),
+        Some("Ownable: caller is not the owner")
+    );
+}
+
+#[test]
+fn test_replacement_uuid() {
+    let replacement_uuid = "5c9a1e73-0b1d-4f4e-9d6a-2f3b8c7e1a90";
+    let params = SendBundleParams::builder()
+        .inclusion(0x8b_8da8, None)
+        .body(vec![
+            Body::tx(
+                "0x2ce3a5f1e5e9c4e2a0bf0d7a3b4f8bd0e3f3b4a4c4e8d8b7e0f0a5b6c7d8e9f0"
+                    .parse::<TxHash>()
+                    .unwrap(),
+            ),
+            Body::signed(SIGNED_TX.parse::<Bytes>().unwrap(), false),
+        ])
+        .replacement_uuid(replacement_uuid)
+        .build();
+    assert_eq!(
+        request(MevShareRequest::SendBundle, [params]),
+        normalized(fixture!("send_bundle_replacement"))
+    );
+    assert_round_trip::<[SendBundleParams; 1]>(
+        MevShareRequest::SendBundle,
+        fixture!("send_bundle_replacement"),
+    );
+
+    let params = EthSendBundleParams::builder()
+        .txs(vec![SIGNED_TX.parse::<Bytes>().unwrap()])
+        .block_number(0x8b_8da8)
+        .replacement_uuid(replacement_uuid)
+        .build();
+    assert_eq!(
+        request(MevShareRequest::SendLegacyBundle, [params]),
+        normalized(fixture!("eth_send_bundle"))
+    );
+    assert_round_trip::<[EthSendBundleParams; 1]>(
+        MevShareRequest::SendLegacyBundle,
+        fixture!("eth_send_bundle"),
+    );
+}
+
+#[test]
+fn test_cancel() {
+    let params = CancelBundleParams {
+        replacement_uuid: "5c9a1e73-0b1d-4f4e-9d6a-2f3b8c7e1a90".to_owned(),
+    };
+    assert_eq!(
+        request(MevShareRequest::CancelBundle, [params]),
+        normalized(fixture!("cancel_bundle"))
+    );
+    assert_round_trip::<[CancelBundleParams; 1]>(
+        MevShareRequest::CancelBundle,
+        fixture!("cancel_bundle"),
+    );
+    assert_round_trip::<[CancelPrivateTransactionParams; 1]>(
+        MevShareRequest::CancelPrivateTransaction,
+        fixture!("cancel_private_transaction"),
+    );
+
+    let response: JsonRpcResponse<CancelPrivateTransactionResponse> =
+        serde_json::from_str(r#"{"jsonrpc":"2.0","id":1,"result":true}"#).unwrap();
+    assert!(matches!(
+        response,
+        JsonRpcResponse::Success(JsonRpcResponseSuccess { result, .. }) if result.is_cancelled()
+    ));
+}
+
+#[test]
+fn test_error_response() {
+    let response: JsonRpcResponse<TxHash> =
+        serde_json::from_str(fixture!("error_response")).unwrap();
+    assert!(matches!(response, JsonRpcResponse::Error(_)));
+}
+
+#[test]
+fn test_events() {
+    let event: MevShareEvent = serde_json::from_str(include_str!(
+        "../../../mev-share-types/src/fixtures/sse_event.json"
+    ))
+    .unwrap();
+    let tx = &event.txs.as_ref().unwrap()[0];
+    assert_eq!(tx.function_selector, Some([0x7f, 0xf3, 0x6a, 0xb5]));
+    assert_eq!(event.logs.unwrap().len(), 1);
+    assert_eq!(event.mev_gas_price, Some(U256::from(1_000_000_000)));
+
+    let history: Vec<EventHistory> = serde_json::from_str(fixture!("history")).unwrap();
+    assert_eq!(history[0].block, 9_146_624);
+    assert_eq!(history[0].hint.gas_used, Some(U256::from(10_000)));
+
+    let info: EventHistoryInfo = serde_json::from_str(fixture!("history_info")).unwrap();
+    assert_eq!(info.max_limit, 500);
+}
+
+#[test]
+fn test_stats() {
+    let stats: BundleStats = serde_json::from_str(fixture!("bundle_stats")).unwrap();
+    assert!(stats.is_simulated && stats.is_high_priority);
+    assert_eq!(stats.considered_by_builders_at.len(), 2);
+    assert_eq!(stats.sealed_by_builders_at[0].pubkey.len(), 48);
+    assert_eq!(
+        stats.simulation_latency(),
+        Some(chrono::Duration::milliseconds(67))
+    );
+    assert_eq!(
+        stats.first_sealed_at().unwrap().to_rfc3339(),
+        "2022-10-06T21:36:07.742+00:00"
+    );
+    assert_eq!(
+        stats.to_string(),
+        "simulated, considered by 2 builders, sealed by 1"
+    );
+
+    // bundles the relay doesn't know about yet
+    let unknown: BundleStats = serde_json::from_str(r#"{"isSimulated":false}"#).unwrap();
+    assert_eq!(unknown, BundleStats::default());
+    assert!(!unknown.is_sealed());
+
+    let stats: UserStats = serde_json::from_str(fixture!("user_stats")).unwrap();
+    assert_eq!(
+        stats.all_time_validator_payments,
+        U256::from(1_280_749_594_841_588_639_u64)
+    );
+    assert_eq!(stats.last_1d_gas_simulated, U256::from(2_731_770_076_u64));
+    assert!((stats.last_1d_validator_payments_eth() - 0.142_305_510_537_954_3).abs() < 1e-12);
+    assert_eq!(
+        serde_json::to_value(&stats).unwrap(),
+        serde_json::from_str::<Value>(fixture!("user_stats")).unwrap()
+    );
+    assert!(stats
+        .to_string()
+        .starts_with("high priority, validator payments 0.1423 / 1.2807 / 1.2807 ETH"));
+}
+
+#[test]
+fn test_fee_refunds() {
+    let refunds: FeeRefunds = serde_json::from_str(fixture!("fee_refunds")).unwrap();
+    assert_eq!(refunds.refunds[0].block_number, U64::from(17_010_474));
+    assert_eq!(
+        refunds.total(RefundStatus::Pending),
+        U256::from(20_000_000_000_000_000_u64)
+    );
+    assert_eq!(refunds.total(RefundStatus::Received), U256::exp10(16));
+    assert_eq!(refunds.cursor.as_deref(), Some("0x1038f2a"));
+
+    let unknown: FeeRefund = serde_json::from_value(serde_json::json!({
+        "hash": TxHash::zero(),
+        "amount": "0x0",
+        "blockNumber": "0x1",
+        "status": "clawed-back",
+        "recipient": Address::zero(),
+    }))
+    .unwrap();
+    assert_eq!(unknown.status, RefundStatus::Unknown);
+}
+
+#[test]
+fn test_fee_refunds_by_recipient() {
+    let params = GetFeeRefundsByRecipientParams::builder()
+        .recipient(
+            "0x73e54ac1ce2d2a66f3b2cf5b5ff23c2e3d0b4a8f"
+                .parse::<Address>()
+                .unwrap(),
+        )
+        .block_range_from(17_000_000)
+        .cursor("0x1038f2a")
+        .build();
+    assert_eq!(
+        request(MevShareRequest::GetFeeRefundsByRecipient, [params]),
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "flashbots_getFeeRefundsByRecipient",
+            "params": [{
+                "recipient": "0x73e54ac1ce2d2a66f3b2cf5b5ff23c2e3d0b4a8f",
+                "blockRangeFrom": "0x1036640",
+                "cursor": "0x1038f2a",
+            }],
+        })
+    );
+
+    let totals: FeeRefundTotals = serde_json::from_str(fixture!("fee_refund_totals")).unwrap();
+    assert_eq!(totals.max_block_number, U64::from(17_010_474));
+    assert_eq!(totals.total(), U256::from(30_000_000_000_000_000_u64));
+    assert_eq!(
+        totals.to_string(),
+        "0.020000000000000000 ETH pending, 0.010000000000000000 ETH received up to block 17010474"
+    );
+    assert_eq!(
+        serde_json::to_value(&totals).unwrap(),
+        serde_json::from_str::<Value>(fixture!("fee_refund_totals")).unwrap()
+    );
+}
@@ -0,0 +1,182 @@
+//! Order-flow providers the client sends its transactions and bundles to.
+//!
+//! The Flashbots MEV-Share relay is the default. Other providers, e.g. bloXroute, Eden or a builder's private API,
+//! speak their own wire formats for the same concepts: an adapter implementing [`OrderflowBackend`] translates the
+//! MEV-Share params to the provider API, and [`crate::MevShareClient::with_backend`] plugs it into the client, keeping
+//! budgets, pending bundles and inclusion waits unchanged.
+//!
+//! # Example
+//!
+//! ```
+//! struct MyBuilder { http: reqwest::Client }
+//!
+//! #[async_trait]
+//! impl OrderflowBackend for MyBuilder {
+//!     fn name(&self) -> &str {
+//!         "my-builder"
+//!     }
+//!
+//!     async fn send_bundle(&self, params: SendBundleParams) -> Result<SendBundleResponse> {
+//!         let txs = params.body.iter().filter_map(|body| match body {
+//!             Body::Signed { tx, .. } => Some(tx),
+//!             _ => None,
+//!         });
+//!         ...
+//!     }
+//!
+//!     ...
+//! }
+//!
+//! let client = MevShareClient::new(auth_wallet, provider).await?.with_backend(MyBuilder::new());
+//! ```
+//!
+//! The `bloxroute` feature provides a [`BloxrouteBackend`] for the bloXroute MEV API.
+
+use crate::api::rpc_client::MevShareRpcClient;
+use crate::api::types::{
+    BundleStats, SendBundleParams, SendBundleResponse, SendTransactionParams, SimulateBundleParams,
+    SimulateBundleResponse,
+};
+use crate::client::MevShareRequest;
+use crate::Result;
+use async_trait::async_trait;
+use ethers::types::{TxHash, U64};
+use serde_json::json;
+
+#[cfg(feature = "bloxroute")]
+mod bloxroute;
+#[cfg(feature = "bloxroute")]
+pub use bloxroute::{BloxrouteBackend, BLOXROUTE_URL};
+
+/// A private order-flow provider: where transactions and bundles are sent, simulated and tracked.
+///
+/// Errors specific to the provider can be surfaced with [`crate::Error::Backend`].
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait OrderflowBackend: Send + Sync {
+    /// Name of the provider, for logs.
+    fn name(&self) -> &str;
+
+    /// Sends a signed transaction privately, and returns its hash.
+    async fn send_private_transaction(&self, params: SendTransactionParams) -> Result<TxHash>;
+
+    /// Sends a bundle, and returns its hash.
+    async fn send_bundle(&self, params: SendBundleParams) -> Result<SendBundleResponse>;
+
+    /// Simulates a bundle, overriding the block state with `options`.
+    async fn simulate_bundle(
+        &self,
+        params: SendBundleParams,
+        options: SimulateBundleParams,
+    ) -> Result<SimulateBundleResponse>;
+
+    /// Returns what the provider knows about the bundle `bundle_hash` targeting `block`.
+    async fn bundle_status(&self, bundle_hash: TxHash, block: U64) -> Result<BundleStats>;
+}
+
+/// The Flashbots MEV-Share relay, speaking the `mev_` and `eth_` JSON-RPC methods.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl OrderflowBackend for MevShareRpcClient {
+    fn name(&self) -> &'static str {
+        "flashbots"
+    }
+
+    async fn send_private_transaction(&self, params: SendTransactionParams) -> Result<TxHash> {
+        Ok(self
+            .post(MevShareRequest::SendPrivateTransaction, [params])
+            .await?)
+    }
+
+    async fn send_bundle(&self, params: SendBundleParams) -> Result<SendBundleResponse> {
+        Ok(self.post(MevShareRequest::SendBundle, [params]).await?)
+    }
+
+    async fn simulate_bundle(
+        &self,
+        params: SendBundleParams,
+        options: SimulateBundleParams,
+    ) -> Result<SimulateBundleResponse> {
+        Ok(self
+            .post(MevShareRequest::SimBundle, (params, options))
+            .await?)
+    }
+
+    async fn bundle_status(&self, bundle_hash: TxHash, block: U64) -> Result<BundleStats> {
+        Ok(self
+            .post(
+                MevShareRequest::GetBundleStats,
+                [json!({ "bundleHash": bundle_hash, "blockNumber": block })],
+            )
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    /// A provider that refuses every order, like a builder API would for an unknown searcher.
+    struct Rejecting;
+
+    #[async_trait]
+    impl OrderflowBackend for Rejecting {
+        fn name(&self) -> &'static str {
+            "rejecting"
+        }
+
+        async fn send_private_transaction(&self, _: SendTransactionParams) -> Result<TxHash> {
+            Err(Error::Backend(
+                self.name().to_owned(),
+                "unknown searcher".into(),
+            ))
+        }
+
+        async fn send_bundle(&self, _: SendBundleParams) -> Result<SendBundleResponse> {
+            Err(Error::Backend(
+                self.name().to_owned(),
+                "unknown searcher".into(),
+            ))
+        }
+
+        async fn simulate_bundle(
+            &self,
+            _: SendBundleParams,
+            _: SimulateBundleParams,
+        ) -> Result<SimulateBundleResponse> {
+            Err(Error::Backend(
+                self.name().to_owned(),
+                "unknown searcher".into(),
+            ))
+        }
+
+        async fn bundle_status(&self, _: TxHash, _: U64) -> Result<BundleStats> {
+            Err(Error::Backend(
+                self.name().to_owned(),
+                "unknown searcher".into(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backend_errors() {
+        let backend: Box<dyn OrderflowBackend> = Box::new(Rejecting);
+
+        let err = backend
+            .send_bundle(SendBundleParams::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "rejecting error: unknown searcher");
+
+        let relay = MevShareRpcClient::new(
+            "http://127.0.0.1:1",
+            ethers::signers::LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+        );
+        assert_eq!(relay.name(), "flashbots");
+        assert!(matches!(
+            relay.bundle_status(TxHash::zero(), U64::one()).await,
+            Err(Error::Rpc(_))
+        ));
+    }
+}
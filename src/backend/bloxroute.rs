@@ -0,0 +1,245 @@
+use super::OrderflowBackend;
+use crate::api::types::{
+    Body, BundleStats, EthCallBundleResponse, JsonRpcRequest, JsonRpcResponse, SendBundleParams,
+    SendBundleResponse, SendTransactionParams, SimulateBundleParams, SimulateBundleResponse,
+};
+use crate::{Error, Result};
+use async_trait::async_trait;
+use ethers::types::{TxHash, U256, U64};
+use ethers::utils::{hex, keccak256};
+use reqwest::header::AUTHORIZATION;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// URL of the bloXroute MEV API.
+pub const BLOXROUTE_URL: &str = "https://mev.api.blxrbdn.com";
+
+/// The bloXroute MEV API, speaking the `blxr_` JSON-RPC methods and authenticated with the `Authorization` header of
+/// a bloXroute account instead of a Flashbots signature.
+///
+/// Bundles are forwarded to all the builders bloXroute submits to: the [`crate::bundle::Privacy`] of the bundle
+/// doesn't apply. bloXroute can't backrun the transactions of the MEV-Share event stream either, so the bundles can
+/// only contain signed transactions.
+///
+/// # Example
+///
+/// ```
+/// let client = MevShareClient::new(auth_wallet, provider)
+///     .await?
+///     .with_backend(BloxrouteBackend::new(std::env::var("BLOXROUTE_AUTH_HEADER")?));
+/// ```
+pub struct BloxrouteBackend {
+    url: String,
+    auth_header: String,
+    http: reqwest::Client,
+    request_id: AtomicI32,
+}
+
+impl BloxrouteBackend {
+    /// The bloXroute MEV API at [`BLOXROUTE_URL`], authenticated with `auth_header`, as shown in the bloXroute
+    /// account portal.
+    pub fn new(auth_header: impl Into<String>) -> Self {
+        Self {
+            url: BLOXROUTE_URL.to_owned(),
+            auth_header: auth_header.into(),
+            http: reqwest::Client::new(),
+            request_id: AtomicI32::new(1),
+        }
+    }
+
+    /// Sends the requests to `url` instead of [`BLOXROUTE_URL`], e.g. to a regional gateway.
+    #[must_use]
+    pub fn with_url(self, url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..self
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: self.request_id.fetch_add(1, Ordering::Relaxed),
+            method,
+            params,
+        };
+
+        let response = self
+            .http
+            .post(&self.url)
+            .header(AUTHORIZATION, &self.auth_header)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| self.error(err))?;
+
+        match response.json().await.map_err(|err| self.error(err))? {
+            JsonRpcResponse::Success(success) => Ok(success.result),
+            JsonRpcResponse::Error(err) => Err(self.error(format!("{:?}", err.error))),
+        }
+    }
+
+    fn error(&self, err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Error {
+        Error::Backend(self.name().to_owned(), err.into())
+    }
+}
+
+/// Signed transactions of `params`, nested bundles flattened, hex-encoded without prefix as bloXroute expects them,
+/// and the hashes of those allowed to revert.
+fn transactions(params: &SendBundleParams) -> Result<(Vec<String>, Vec<TxHash>)> {
+    let mut txs = Vec::new();
+    let mut reverting_hashes = Vec::new();
+
+    for body in &params.body {
+        match body {
+            Body::Signed { tx, can_revert } => {
+                if *can_revert {
+                    reverting_hashes.push(keccak256(tx).into());
+                }
+                txs.push(hex::encode(tx));
+            }
+            Body::Bundle(bundle) => {
+                let (nested_txs, nested_reverting_hashes) = transactions(bundle)?;
+                txs.extend(nested_txs);
+                reverting_hashes.extend(nested_reverting_hashes);
+            }
+            Body::Tx { hash } => {
+                return Err(Error::InvalidParams(format!(
+                    "bloXroute doesn't accept transaction hashes in bundles, got {hash:?}"
+                )))
+            }
+        }
+    }
+
+    Ok((txs, reverting_hashes))
+}
+
+/// Params of `blxr_submit_bundle` for `params`.
+fn submit_bundle_params(params: &SendBundleParams) -> Result<Value> {
+    let (transaction, reverting_hashes) = transactions(params)?;
+
+    let mut submission = json!({
+        "transaction": transaction,
+        "block_number": format!("{:#x}", params.inclusion.block),
+        "reverting_hashes": reverting_hashes,
+        "mev_builders": { "all": "" },
+    });
+    if let Some(uuid) = &params.replacement_uuid {
+        submission["uuid"] = json!(uuid);
+    }
+
+    Ok(submission)
+}
+
+/// Response of `blxr_private_tx`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrivateTxResponse {
+    tx_hash: String,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl OrderflowBackend for BloxrouteBackend {
+    fn name(&self) -> &'static str {
+        "bloxroute"
+    }
+
+    async fn send_private_transaction(&self, params: SendTransactionParams) -> Result<TxHash> {
+        let response: PrivateTxResponse = self
+            .call(
+                "blxr_private_tx",
+                json!({ "transaction": hex::encode(&params.tx) }),
+            )
+            .await?;
+
+        response
+            .tx_hash
+            .trim_start_matches("0x")
+            .parse()
+            .map_err(|err| self.error(format!("invalid transaction hash: {err}")))
+    }
+
+    async fn send_bundle(&self, params: SendBundleParams) -> Result<SendBundleResponse> {
+        self.call("blxr_submit_bundle", submit_bundle_params(&params)?)
+            .await
+    }
+
+    async fn simulate_bundle(
+        &self,
+        params: SendBundleParams,
+        options: SimulateBundleParams,
+    ) -> Result<SimulateBundleResponse> {
+        let (transaction, _) = transactions(&params)?;
+
+        let mut simulation = json!({
+            "transaction": transaction,
+            "block_number": format!("{:#x}", options.block_number.unwrap_or(params.inclusion.block)),
+            "state_block_number": options
+                .parent_block
+                .map_or_else(|| "latest".to_owned(), |block| format!("{block:#x}")),
+        });
+        if let Some(timestamp) = options.timestamp {
+            simulation["timestamp"] = json!(timestamp.as_u64());
+        }
+
+        let response: EthCallBundleResponse = self.call("blxr_simulate_bundle", simulation).await?;
+
+        Ok(SimulateBundleResponse {
+            success: response.is_success(),
+            error: response
+                .first_failure()
+                .and_then(|failure| failure.revert.clone().or_else(|| failure.error.clone())),
+            state_block: response.state_block_number.into(),
+            mev_gas_price: response.bundle_gas_price,
+            profit: response.coinbase_diff,
+            refundable_value: U256::zero(),
+            gas_used: response.total_gas_used.into(),
+            logs: Vec::new(),
+        })
+    }
+
+    async fn bundle_status(&self, _: TxHash, _: U64) -> Result<BundleStats> {
+        Err(self.error("bloXroute doesn't report bundle stats"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::Bytes;
+
+    #[test]
+    fn test_submit_bundle_params() {
+        let nested = SendBundleParams {
+            body: vec![Body::signed(Bytes::from(vec![0x02, 0x02]), true)],
+            ..SendBundleParams::default()
+        };
+        let mut bundle = SendBundleParams {
+            body: vec![
+                Body::signed(Bytes::from(vec![0x02, 0x01]), false),
+                Body::Bundle(Box::new(nested)),
+            ],
+            replacement_uuid: Some("2e5bd2f4-3a4c-4f35-9bc7-5a0b5a4a6f1c".to_owned()),
+            ..SendBundleParams::default()
+        };
+        bundle.inclusion.block = 100.into();
+
+        let params = submit_bundle_params(&bundle).unwrap();
+        assert_eq!(params["transaction"], json!(["0201", "0202"]));
+        assert_eq!(params["block_number"], "0x64");
+        assert_eq!(
+            params["reverting_hashes"],
+            json!([TxHash::from(keccak256([0x02, 0x02]))])
+        );
+        assert_eq!(params["uuid"], "2e5bd2f4-3a4c-4f35-9bc7-5a0b5a4a6f1c");
+
+        bundle.body.push(Body::tx(TxHash::zero()));
+        assert!(matches!(
+            submit_bundle_params(&bundle),
+            Err(Error::InvalidParams(_))
+        ));
+    }
+}
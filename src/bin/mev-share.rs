@@ -0,0 +1,391 @@
+//! `mev-share`: one-off interactions with the MEV-Share relay from the command line.
+//!
+//! Built with the `cli` feature:
+//!
+//! ```sh
+//! cargo install mev-share-rs --features cli
+//!
+//! export AUTH_PRIVATE_KEY=0x... PROVIDER_URL=wss://...
+//! # or AUTH_KEYSTORE=keys/auth.json AUTH_KEYSTORE_PASSWORD=...
+//! # or AUTH_MNEMONIC="..." AUTH_DERIVATION_PATH="m/44'/60'/0'/0/3"
+//! mev-share send-tx 0x02f8... --hint calldata --hint logs --wait
+//! mev-share send-bundle bundle.json --wait
+//! mev-share watch --to 0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2
+//! mev-share history --last-hours 1 > hints.jsonl
+//! mev-share serve --listen 127.0.0.1:8546 # with the `server` feature
+//! ```
+//!
+//! Results are printed to stdout as JSON, one object per line, so they can be piped into `jq`.
+
+use clap::{Args, Parser, Subcommand};
+use ethers::prelude::*;
+use futures::{StreamExt, TryStreamExt};
+use mev_share_rs::bundle::{Builder, Hint, Privacy, SendBundleParams, SimulateBundleParams};
+use mev_share_rs::history::GetEventHistoryParams;
+use mev_share_rs::stream::EventFilter;
+use mev_share_rs::transaction::{Preferences, SendTransactionParams};
+use mev_share_rs::{wallet, MevShareClient};
+use serde::de::value::{Error as ValueError, StrDeserializer};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::path::PathBuf;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(name = "mev-share", version, about)]
+struct Cli {
+    /// Private key used to authenticate with the relay.
+    #[arg(
+        long,
+        env = "AUTH_PRIVATE_KEY",
+        hide_env_values = true,
+        required_unless_present_any = ["auth_keystore", "auth_mnemonic"]
+    )]
+    auth_private_key: Option<LocalWallet>,
+
+    /// Encrypted JSON keystore of the key used to authenticate with the relay, instead of `--auth-private-key`.
+    #[arg(long, env = "AUTH_KEYSTORE", conflicts_with = "auth_private_key")]
+    auth_keystore: Option<PathBuf>,
+
+    /// Password of `--auth-keystore`.
+    #[arg(
+        long,
+        env = "AUTH_KEYSTORE_PASSWORD",
+        hide_env_values = true,
+        default_value = ""
+    )]
+    auth_keystore_password: String,
+
+    /// BIP-39 mnemonic the key used to authenticate with the relay is derived from, instead of `--auth-private-key`.
+    #[arg(
+        long,
+        env = "AUTH_MNEMONIC",
+        hide_env_values = true,
+        conflicts_with_all = ["auth_private_key", "auth_keystore"]
+    )]
+    auth_mnemonic: Option<String>,
+
+    /// Derivation path of the key of `--auth-mnemonic`.
+    #[arg(long, env = "AUTH_DERIVATION_PATH", default_value = "m/44'/60'/0'/0/0")]
+    auth_derivation_path: String,
+
+    /// Websocket RPC endpoint, used to find the chain and to wait for inclusion.
+    #[arg(long, env = "PROVIDER_URL")]
+    provider_url: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Send a signed transaction with `eth_sendPrivateTransaction`.
+    SendTx {
+        /// The signed transaction, hex encoded.
+        tx: Bytes,
+
+        /// Maximum block number for the transaction to be included in.
+        #[arg(long)]
+        max_block_number: Option<u64>,
+
+        #[command(flatten)]
+        privacy: PrivacyArgs,
+
+        /// Wait for the transaction to be included.
+        #[arg(long)]
+        wait: bool,
+    },
+
+    /// Send the bundle in a JSON file with `mev_sendBundle`.
+    SendBundle {
+        /// Bundle file: JSON `mev_sendBundle` params, see `SendBundleParams::to_json_file`.
+        file: PathBuf,
+
+        /// Wait for the bundle to be included.
+        #[arg(long)]
+        wait: bool,
+    },
+
+    /// Simulate the bundle in a JSON file with `mev_simBundle`.
+    Simulate {
+        /// Bundle file: JSON `mev_sendBundle` params, see `SendBundleParams::to_json_file`.
+        file: PathBuf,
+
+        /// Block used for simulation state. Defaults to the latest block.
+        #[arg(long)]
+        parent_block: Option<u64>,
+
+        /// Simulation timeout, in seconds.
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+
+    /// Print the hints of the SSE event stream.
+    Watch {
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+
+    /// Export historical hints.
+    History {
+        #[command(flatten)]
+        range: HistoryRange,
+    },
+
+    /// Summarize historical hints.
+    Stats {
+        #[command(flatten)]
+        range: HistoryRange,
+
+        /// Number of most targeted contracts to list.
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+
+    /// Serve the client over a local websocket JSON-RPC API, see `mev_share_rs::server`.
+    #[cfg(feature = "server")]
+    Serve {
+        /// Address to listen on. Anybody that can connect sends orders with your authentication key.
+        #[arg(long, default_value = "127.0.0.1:8546")]
+        listen: std::net::SocketAddr,
+    },
+}
+
+#[derive(Args)]
+struct PrivacyArgs {
+    /// Hint to share, e.g. `calldata`. Can be repeated; defaults to the relay defaults.
+    #[arg(long = "hint", value_parser = parse_hint)]
+    hints: Vec<Hint>,
+
+    /// Builder to share with, e.g. `flashbots`. Can be repeated; defaults to the relay defaults.
+    #[arg(long = "builder")]
+    builders: Vec<String>,
+}
+
+#[derive(Args)]
+struct FilterArgs {
+    /// Only print hints with a transaction to this address. Can be repeated.
+    #[arg(long)]
+    to: Vec<Address>,
+
+    /// Only print hints with a transaction calling this 4byte selector. Can be repeated.
+    #[arg(long, value_parser = parse_selector)]
+    selector: Vec<Selector>,
+
+    /// Only print hints disclosing logs.
+    #[arg(long)]
+    with_logs: bool,
+}
+
+#[derive(Args)]
+struct HistoryRange {
+    /// First block of the range.
+    #[arg(long)]
+    block_start: Option<u64>,
+
+    /// Last block of the range.
+    #[arg(long)]
+    block_end: Option<u64>,
+
+    /// Only the hints of the last N hours, instead of a block range.
+    #[arg(long, conflicts_with_all = ["block_start", "block_end"])]
+    last_hours: Option<u64>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let provider = Provider::<Ws>::connect(&cli.provider_url).await?;
+    let auth_wallet = match (cli.auth_private_key, cli.auth_keystore, cli.auth_mnemonic) {
+        (Some(wallet), _, _) => wallet,
+        (None, Some(keystore), _) => wallet::from_keystore(keystore, &cli.auth_keystore_password)?,
+        (None, None, Some(mnemonic)) => {
+            wallet::from_mnemonic(&mnemonic, &cli.auth_derivation_path)?
+        }
+        (None, None, None) => unreachable!("enforced by clap"),
+    };
+
+    let client = MevShareClient::new(auth_wallet, provider).await?;
+
+    match cli.command {
+        Command::SendTx {
+            tx,
+            max_block_number,
+            privacy,
+            wait,
+        } => {
+            let preferences =
+                (!privacy.hints.is_empty() || !privacy.builders.is_empty()).then(|| Preferences {
+                    fast: true,
+                    privacy: Privacy::new(
+                        privacy.hints,
+                        privacy.builders.iter().map(|name| parse_builder(name)),
+                    ),
+                    validity: None,
+                });
+
+            let params = SendTransactionParams {
+                tx,
+                max_block_number: max_block_number.map(Into::into),
+                preferences,
+            };
+
+            let pending = client.send_private_transaction(params).await?;
+            eprintln!("{pending}");
+
+            if wait {
+                let (receipt, block) = pending.inclusion().await?;
+                print_json(&Inclusion {
+                    hash: receipt.transaction_hash,
+                    block,
+                })?;
+            } else {
+                print_json(&pending.hash)?;
+            }
+        }
+
+        Command::SendBundle { file, wait } => {
+            let params = SendBundleParams::from_json_file(file)?;
+
+            let pending = client.send_bundle(params).await?;
+            eprintln!("{pending}");
+
+            if wait {
+                let hash = pending.hash;
+                let (_, block) = pending.inclusion().await?;
+                print_json(&Inclusion { hash, block })?;
+            } else {
+                print_json(&pending.hash)?;
+            }
+        }
+
+        Command::Simulate {
+            file,
+            parent_block,
+            timeout,
+        } => {
+            let params = SendBundleParams::from_json_file(file)?;
+
+            let simulation = client
+                .simulate_bundle(
+                    params,
+                    SimulateBundleParams {
+                        parent_block: parent_block.map(Into::into),
+                        timeout,
+                        ..Default::default()
+                    },
+                )
+                .await?;
+
+            eprintln!("{simulation}");
+            print_json(&simulation)?;
+        }
+
+        Command::Watch { filter } => {
+            let filter = EventFilter::from(filter);
+            let mut events = Box::pin(client.subscribe_bundles());
+            while let Some(event) = events.next().await {
+                let event = event?;
+                if filter.matches(&event) {
+                    print_json(&event)?;
+                }
+            }
+        }
+
+        Command::History { range } => {
+            let mut events = Box::pin(client.event_history_stream(range.into()));
+            while let Some(event) = events.try_next().await? {
+                print_json(&event)?;
+            }
+        }
+
+        Command::Stats { range, top } => {
+            let summary = client.summarize_event_history(range.into()).await?;
+
+            print_json(&Stats {
+                hints: summary.hints,
+                first_block: summary.first_block,
+                last_block: summary.last_block,
+                hints_per_block: summary.hints_per_block(),
+                calldata_share: summary.calldata_share(),
+                function_selector_share: summary.function_selector_share(),
+                logs_share: summary.logs_share(),
+                median_gas_used: summary.gas_used_percentile(50.0),
+                top_targets: summary.top_targets(top),
+            })?;
+        }
+
+        #[cfg(feature = "server")]
+        Command::Serve { listen } => {
+            mev_share_rs::server::Server::new(client)
+                .serve(listen)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+impl From<FilterArgs> for EventFilter {
+    fn from(args: FilterArgs) -> Self {
+        Self {
+            to: args.to,
+            selectors: args.selector,
+            with_logs: args.with_logs,
+        }
+    }
+}
+
+impl From<HistoryRange> for GetEventHistoryParams {
+    fn from(range: HistoryRange) -> Self {
+        match range.last_hours {
+            Some(hours) => GetEventHistoryParams::last_hours(hours),
+            None => GetEventHistoryParams {
+                block_start: range.block_start,
+                block_end: range.block_end,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Inclusion {
+    hash: TxHash,
+    block: U64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Stats {
+    hints: u64,
+    first_block: Option<u64>,
+    last_block: Option<u64>,
+    hints_per_block: f64,
+    calldata_share: f64,
+    function_selector_share: f64,
+    logs_share: f64,
+    median_gas_used: Option<U256>,
+    top_targets: Vec<(Address, u64)>,
+}
+
+fn print_json(value: &impl Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string(value)?);
+    Ok(())
+}
+
+fn parse_hint(name: &str) -> std::result::Result<Hint, String> {
+    Hint::deserialize(StrDeserializer::<ValueError>::new(name)).map_err(|err| err.to_string())
+}
+
+/// Known builders by their relay name, anything else as [`Builder::Other`].
+fn parse_builder(name: &str) -> Builder {
+    Builder::from_name(name)
+}
+
+fn parse_selector(selector: &str) -> std::result::Result<Selector, String> {
+    let bytes: Bytes = selector.parse().map_err(|err| format!("{err}"))?;
+    Selector::try_from(bytes.as_ref()).map_err(|_| format!("expected 4 bytes, got {}", bytes.len()))
+}
@@ -0,0 +1,777 @@
+//! A synchronous façade over [`crate::MevShareClient`], for scripts and tools that aren't async.
+//!
+//! Enabled by the `blocking` feature. Like `reqwest::blocking`, the client drives its own tokio runtime,
+//! so it must not be used from within an async context.
+//!
+//! # Example
+//!
+//! ```
+//! use mev_share_rs::blocking::MevShareClient;
+//!
+//! let client = MevShareClient::connect(auth_wallet, "wss://rpc.example/api_key")?;
+//!
+//! let (receipt, block) = client
+//!     .send_private_transaction(SendTransactionParams::builder().tx(signed_tx).build())?
+//!     .inclusion()?;
+//! ```
+//!
+//! Clients on other providers, e.g. a `Provider<Http>`, wrap an async client with [`crate::MevShareClient::blocking`]:
+//!
+//! ```
+//! let provider = Provider::<Http>::try_from("https://rpc.example/api_key")?;
+//! let client = mev_share_rs::MevShareClient::new_with_chain_id(auth_wallet, provider, Chain::Mainnet)?
+//!     .with_timeout(Duration::from_secs(2))
+//!     .blocking()?;
+//!
+//! let pending_bundle = client.send_bundle(bundle)?;
+//! ```
+
+use crate::api::types::{
+    BundleStats, BundleStatusEvent, EthCallBundleParams, EthCallBundleResponse,
+    EthSendBundleParams, FeeRefundTotals, FeeRefunds, GetFeeRefundsByRecipientParams, Privacy,
+    RelayLimits, SendBundleParams, SendBundleResponse, SendTransactionParams, SimulateBundleParams,
+    SimulateBundleResponse, TransactionProgress,
+};
+#[cfg(feature = "history")]
+use crate::api::types::{EventHistory, EventHistoryInfo, GetEventHistoryParams};
+use crate::backend::OrderflowBackend;
+use crate::budget::Budget;
+use crate::dedup::DedupStore;
+use crate::helpers::provider::Waiter;
+#[cfg(feature = "history")]
+use crate::history::HintSummary;
+use crate::signer::{AuthScheme, AuthSigner};
+use crate::{MevShareNetwork, NetworkTable, Result};
+use ethers::prelude::*;
+use futures::{future, TryStreamExt};
+use reqwest::header::HeaderMap;
+use std::fmt::{Debug, Display};
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// Blocking counterpart of [`crate::MevShareClient`].
+///
+/// Clones share the runtime, and everything [`crate::MevShareClient`] clones share, e.g. to send from several threads.
+pub struct MevShareClient<M = Provider<Ws>> {
+    // declared before `runtime`, so that the websocket connection is closed while the runtime is still alive
+    inner: crate::MevShareClient<M>,
+    // shared with the pending bundles and transactions, which may outlive the client
+    runtime: Arc<Runtime>,
+}
+
+impl<M> Clone for MevShareClient<M> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+}
+
+impl MevShareClient {
+    /// Connects to the websocket `provider_url` and initializes a client for the chain it serves.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Io`] if the runtime can't be started.
+    /// * [`crate::Error::Provider`] if the connection to `provider_url` fails or the `chain_id` can't be retrieved.
+    /// * [`crate::Error::UnsupportedNetwork`] if the `chain_id` is not supported by the MEV-Share client.
+    pub fn connect(auth_wallet: impl Into<AuthSigner>, provider_url: &str) -> Result<Self> {
+        let runtime = runtime()?;
+
+        let inner = runtime.block_on(async {
+            let provider = Provider::<Ws>::connect(provider_url).await?;
+
+            crate::MevShareClient::new(auth_wallet, provider).await
+        })?;
+
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Connects to the websocket `provider_url` and initializes a client on `network`, see
+    /// [`crate::MevShareClient::new_with_network`].
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Io`] if the runtime can't be started.
+    /// * [`crate::Error::Provider`] if the connection to `provider_url` fails.
+    pub fn connect_with_network(
+        auth_wallet: impl Into<AuthSigner>,
+        provider_url: &str,
+        network: MevShareNetwork,
+    ) -> Result<Self> {
+        let runtime = runtime()?;
+
+        let provider = runtime.block_on(Provider::<Ws>::connect(provider_url))?;
+        let inner = crate::MevShareClient::new_with_network(auth_wallet, provider, network);
+
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Connects to the websocket `provider_url` and initializes a client on the network of the chain it serves among
+    /// `networks`, see [`crate::MevShareClient::new_with_networks`].
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Io`] if the runtime can't be started.
+    /// * [`crate::Error::Provider`] if the connection to `provider_url` fails or the `chain_id` can't be retrieved.
+    /// * [`crate::Error::UnsupportedNetwork`] if `networks` has no network for the `chain_id`.
+    pub fn connect_with_networks(
+        auth_wallet: impl Into<AuthSigner>,
+        provider_url: &str,
+        networks: &NetworkTable,
+    ) -> Result<Self> {
+        let runtime = runtime()?;
+
+        let inner = runtime.block_on(async {
+            let provider = Provider::<Ws>::connect(provider_url).await?;
+
+            crate::MevShareClient::new_with_networks(auth_wallet, provider, networks).await
+        })?;
+
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// See [`crate::MevShareClient::switch_network`]; the websocket `provider_url` serves the new network.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Provider`] if the connection to `provider_url` fails.
+    /// * Same as [`crate::MevShareClient::switch_network`].
+    pub fn switch_network(
+        &mut self,
+        chain_id: impl TryInto<crate::MevShareNetwork, Error = impl Into<crate::Error>>,
+        provider_url: &str,
+    ) -> Result<()> {
+        let provider = self
+            .runtime
+            .block_on(Provider::<Ws>::connect(provider_url))?;
+        self.inner.switch_network(chain_id, provider)
+    }
+}
+
+impl<M: Waiter> MevShareClient<M> {
+    /// Wraps `client`, e.g. one on a `Provider<Http>`, see [`crate::MevShareClient::blocking`].
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Io`] if the runtime can't be started.
+    pub fn from_async(client: crate::MevShareClient<M>) -> Result<Self> {
+        Ok(Self {
+            inner: client,
+            runtime: Arc::new(runtime()?),
+        })
+    }
+
+    /// See [`crate::MevShareClient::with_budget`].
+    #[must_use]
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.inner = self.inner.with_budget(budget);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_tenant_budget`].
+    #[must_use]
+    pub fn with_tenant_budget(mut self, budget: Budget) -> Self {
+        self.inner = self.inner.with_tenant_budget(budget);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_dedup_store`].
+    #[must_use]
+    pub fn with_dedup_store(mut self, store: impl DedupStore + 'static, ttl: Duration) -> Self {
+        self.inner = self.inner.with_dedup_store(store, ttl);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_api_url`].
+    #[must_use]
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.inner = self.inner.with_api_url(api_url);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_stream_url`].
+    #[must_use]
+    pub fn with_stream_url(mut self, stream_url: impl Into<String>) -> Self {
+        self.inner = self.inner.with_stream_url(stream_url);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_relay_limits`].
+    #[must_use]
+    pub fn with_relay_limits(mut self, limits: RelayLimits) -> Self {
+        self.inner = self.inner.with_relay_limits(limits);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_bundle_hash_verification`].
+    #[must_use]
+    pub fn with_bundle_hash_verification(mut self) -> Self {
+        self.inner = self.inner.with_bundle_hash_verification();
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_default_privacy`].
+    #[must_use]
+    pub fn with_default_privacy(mut self, privacy: Privacy) -> Self {
+        self.inner = self.inner.with_default_privacy(privacy);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_submission_tracker`].
+    #[must_use]
+    pub fn with_submission_tracker(
+        mut self,
+        tracker: std::sync::Arc<crate::tracker::SubmissionTracker>,
+    ) -> Self {
+        self.inner = self.inner.with_submission_tracker(tracker);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_alerter`].
+    #[must_use]
+    pub fn with_alerter(mut self, alerter: std::sync::Arc<crate::alert::Alerter>) -> Self {
+        self.inner = self.inner.with_alerter(alerter);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_backend`].
+    #[must_use]
+    pub fn with_backend(mut self, backend: impl OrderflowBackend + 'static) -> Self {
+        self.inner = self.inner.with_backend(backend);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_auth_scheme`].
+    #[must_use]
+    pub fn with_auth_scheme(mut self, auth: impl AuthScheme + 'static) -> Self {
+        self.inner = self.inner.with_auth_scheme(auth);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_user_agent`].
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::InvalidHeader`] if `user_agent` is not a valid header value.
+    pub fn with_user_agent(mut self, user_agent: &str) -> Result<Self> {
+        self.inner = self.inner.with_user_agent(user_agent)?;
+        Ok(self)
+    }
+
+    /// See [`crate::MevShareClient::with_headers`].
+    #[must_use]
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.inner = self.inner.with_headers(headers);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_http_client`].
+    #[must_use]
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.inner = self.inner.with_http_client(http);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_timeout`].
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.with_timeout(timeout);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_retry_policy`].
+    #[must_use]
+    pub fn with_retry_policy(mut self, policy: crate::retry::RetryPolicy) -> Self {
+        self.inner = self.inner.with_retry_policy(policy);
+        self
+    }
+
+    /// See [`crate::MevShareClient::with_layer`].
+    #[must_use]
+    pub fn with_layer(mut self, layer: impl crate::layer::RpcLayer + 'static) -> Self {
+        self.inner = self.inner.with_layer(layer);
+        self
+    }
+
+    /// See [`crate::MevShareClient::network`].
+    #[must_use]
+    pub fn network(&self) -> &crate::MevShareNetwork {
+        self.inner.network()
+    }
+
+    /// See [`crate::MevShareClient::health_check`].
+    #[must_use]
+    pub fn health_check(&self) -> crate::health::HealthReport {
+        self.runtime.block_on(self.inner.health_check())
+    }
+
+    /// See [`crate::MevShareClient::last_response`].
+    #[must_use]
+    pub fn last_response(&self) -> Option<crate::ResponseMeta> {
+        self.inner.last_response()
+    }
+
+    /// Returns the underlying async client, e.g. to build a [`crate::guard::CancelGuard`].
+    #[must_use]
+    pub fn as_async(&self) -> &crate::MevShareClient<M> {
+        &self.inner
+    }
+
+    /// See [`crate::MevShareClient::resolve_name`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::resolve_name`].
+    pub fn resolve_name(&self, name: impl Into<NameOrAddress>) -> Result<Address> {
+        self.runtime.block_on(self.inner.resolve_name(name))
+    }
+
+    /// See [`crate::MevShareClient::send_private_transaction`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::send_private_transaction`].
+    pub fn send_private_transaction(
+        &self,
+        params: SendTransactionParams,
+    ) -> Result<PendingTransaction<M>> {
+        let inner = self
+            .runtime
+            .block_on(self.inner.send_private_transaction(params))?;
+
+        Ok(PendingTransaction {
+            inner,
+            runtime: Arc::clone(&self.runtime),
+        })
+    }
+
+    /// See [`crate::MevShareClient::send_private_transaction_until`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::send_private_transaction_until`].
+    pub fn send_private_transaction_until(
+        &self,
+        params: SendTransactionParams,
+        timeout: Duration,
+        on_progress: impl FnMut(TransactionProgress<'_>),
+    ) -> Result<(TransactionReceipt, U64)> {
+        self.runtime
+            .block_on(
+                self.inner
+                    .send_private_transaction_until(params, timeout, on_progress),
+            )
+    }
+
+    /// See [`crate::MevShareClient::send_bundle`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::send_bundle`].
+    pub fn send_bundle(&self, params: SendBundleParams) -> Result<PendingBundle<M>> {
+        let inner = self.runtime.block_on(self.inner.send_bundle(params))?;
+
+        Ok(PendingBundle {
+            inner,
+            runtime: Arc::clone(&self.runtime),
+        })
+    }
+
+    /// See [`crate::MevShareClient::send_bundle_with_timeout`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::send_bundle_with_timeout`].
+    pub fn send_bundle_with_timeout(
+        &self,
+        params: SendBundleParams,
+        timeout: Duration,
+    ) -> Result<PendingBundle<M>> {
+        let inner = self
+            .runtime
+            .block_on(self.inner.send_bundle_with_timeout(params, timeout))?;
+
+        Ok(PendingBundle {
+            inner,
+            runtime: Arc::clone(&self.runtime),
+        })
+    }
+
+    /// See [`crate::MevShareClient::send_legacy_bundle`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::send_legacy_bundle`].
+    pub fn send_legacy_bundle(&self, params: EthSendBundleParams) -> Result<SendBundleResponse> {
+        self.runtime.block_on(self.inner.send_legacy_bundle(params))
+    }
+
+    /// See [`crate::MevShareClient::get_user_stats`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::get_user_stats`].
+    pub fn get_user_stats(&self, block: impl Into<U64>) -> Result<crate::stats::UserStats> {
+        self.runtime.block_on(self.inner.get_user_stats(block))
+    }
+
+    /// See [`crate::MevShareClient::cancel_private_transaction`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::cancel_private_transaction`].
+    pub fn cancel_private_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> Result<crate::transaction::CancelPrivateTransactionResponse> {
+        self.runtime
+            .block_on(self.inner.cancel_private_transaction(tx_hash))
+    }
+
+    /// See [`crate::MevShareClient::cancel_bundle`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::cancel_bundle`].
+    pub fn cancel_bundle(&self, replacement_uuid: impl Into<String>) -> Result<()> {
+        self.runtime
+            .block_on(self.inner.cancel_bundle(replacement_uuid))
+    }
+
+    /// See [`crate::MevShareClient::call_bundle`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::call_bundle`].
+    pub fn call_bundle(&self, params: EthCallBundleParams) -> Result<EthCallBundleResponse> {
+        self.runtime.block_on(self.inner.call_bundle(params))
+    }
+
+    /// See [`crate::MevShareClient::batch`]; send the batch with [`Self::send_batch`].
+    #[must_use]
+    pub fn batch(&self) -> crate::Batch<'_, M> {
+        self.inner.batch()
+    }
+
+    /// Sends a batch started with [`Self::batch`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::Batch::send`].
+    pub fn send_batch(&self, batch: crate::Batch<'_, M>) -> Result<crate::BatchResponse> {
+        self.runtime.block_on(batch.send())
+    }
+
+    /// See [`crate::MevShareClient::simulate_bundle`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::simulate_bundle`].
+    pub fn simulate_bundle(
+        &self,
+        bundle_params: SendBundleParams,
+        sim_options: SimulateBundleParams,
+    ) -> Result<SimulateBundleResponse> {
+        self.runtime
+            .block_on(self.inner.simulate_bundle(bundle_params, sim_options))
+    }
+
+    /// See [`crate::MevShareClient::simulate_bundle_with_timeout`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::simulate_bundle_with_timeout`].
+    pub fn simulate_bundle_with_timeout(
+        &self,
+        bundle_params: SendBundleParams,
+        sim_options: SimulateBundleParams,
+        timeout: Duration,
+    ) -> Result<SimulateBundleResponse> {
+        self.runtime
+            .block_on(
+                self.inner
+                    .simulate_bundle_with_timeout(bundle_params, sim_options, timeout),
+            )
+    }
+
+    /// See [`crate::MevShareClient::get_bundle_stats`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::get_bundle_stats`].
+    pub fn get_bundle_stats(
+        &self,
+        bundle_hash: TxHash,
+        block: impl Into<U64>,
+    ) -> Result<BundleStats> {
+        self.runtime
+            .block_on(self.inner.get_bundle_stats(bundle_hash, block))
+    }
+
+    /// See [`crate::MevShareClient::bundle_status`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::bundle_status`].
+    pub fn bundle_status(&self, bundle_hash: TxHash, block: impl Into<U64>) -> Result<BundleStats> {
+        self.runtime
+            .block_on(self.inner.bundle_status(bundle_hash, block))
+    }
+
+    /// See [`crate::MevShareClient::fee_refunds_by_bundle`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::fee_refunds_by_bundle`].
+    pub fn fee_refunds_by_bundle(&self, bundle_hash: TxHash) -> Result<FeeRefunds> {
+        self.runtime
+            .block_on(self.inner.fee_refunds_by_bundle(bundle_hash))
+    }
+
+    /// See [`crate::MevShareClient::fee_refunds_by_block`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::fee_refunds_by_block`].
+    pub fn fee_refunds_by_block(&self, block: impl Into<U64>) -> Result<FeeRefunds> {
+        self.runtime
+            .block_on(self.inner.fee_refunds_by_block(block))
+    }
+
+    /// See [`crate::MevShareClient::fee_refunds_by_recipient`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::fee_refunds_by_recipient`].
+    pub fn fee_refunds_by_recipient(
+        &self,
+        params: GetFeeRefundsByRecipientParams,
+    ) -> Result<FeeRefunds> {
+        self.runtime
+            .block_on(self.inner.fee_refunds_by_recipient(params))
+    }
+
+    /// See [`crate::MevShareClient::fee_refund_totals`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::fee_refund_totals`].
+    pub fn fee_refund_totals(&self, recipient: Address) -> Result<FeeRefundTotals> {
+        self.runtime
+            .block_on(self.inner.fee_refund_totals(recipient))
+    }
+
+    /// See [`crate::MevShareClient::get_fee_refunds`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::get_fee_refunds`].
+    pub fn get_fee_refunds(&self, recipient: Address) -> Result<FeeRefunds> {
+        self.runtime.block_on(self.inner.get_fee_refunds(recipient))
+    }
+}
+
+#[cfg(feature = "history")]
+impl<M: Waiter> MevShareClient<M> {
+    /// See [`crate::MevShareClient::get_event_history_info`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::get_event_history_info`].
+    pub fn get_event_history_info(&self) -> Result<EventHistoryInfo> {
+        self.runtime.block_on(self.inner.get_event_history_info())
+    }
+
+    /// See [`crate::MevShareClient::get_event_history`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::get_event_history`].
+    pub fn get_event_history(&self, params: GetEventHistoryParams) -> Result<Vec<EventHistory>> {
+        self.runtime.block_on(self.inner.get_event_history(params))
+    }
+
+    /// Collects every past event matching `params`, paging through [`Self::get_event_history`].
+    ///
+    /// See [`crate::MevShareClient::event_history_stream`]; mind the size of the range you're asking for.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Rest`] if a network GET request to the MEV-Share API fails.
+    pub fn event_history(&self, params: GetEventHistoryParams) -> Result<Vec<EventHistory>> {
+        self.runtime
+            .block_on(self.inner.event_history_stream(params).try_collect())
+    }
+
+    /// See [`crate::MevShareClient::summarize_event_history`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::MevShareClient::summarize_event_history`].
+    pub fn summarize_event_history(&self, params: GetEventHistoryParams) -> Result<HintSummary> {
+        self.runtime
+            .block_on(self.inner.summarize_event_history(params))
+    }
+}
+
+/// Blocking counterpart of [`crate::transaction::PendingTransaction`].
+pub struct PendingTransaction<M = Provider<Ws>> {
+    inner: crate::transaction::PendingTransaction<M>,
+    runtime: Arc<Runtime>,
+}
+
+impl<M> Deref for PendingTransaction<M> {
+    type Target = crate::transaction::PendingTransaction<M>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<M> Display for PendingTransaction<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
+}
+
+impl<M> Debug for PendingTransaction<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+impl<M: Waiter> PendingTransaction<M> {
+    /// Blocks until the transaction is included. See [`crate::transaction::PendingTransaction::inclusion`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::transaction::PendingTransaction::inclusion`].
+    pub fn inclusion(&self) -> Result<(TransactionReceipt, U64)> {
+        self.runtime.block_on(self.inner.inclusion())
+    }
+
+    /// See [`crate::transaction::PendingTransaction::cancel`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::transaction::PendingTransaction::cancel`].
+    pub fn cancel(&self) -> Result<crate::transaction::CancelPrivateTransactionResponse> {
+        self.runtime.block_on(self.inner.cancel())
+    }
+}
+
+/// Blocking counterpart of [`crate::bundle::PendingBundle`].
+pub struct PendingBundle<M = Provider<Ws>> {
+    inner: crate::bundle::PendingBundle<M>,
+    runtime: Arc<Runtime>,
+}
+
+impl<M> Deref for PendingBundle<M> {
+    type Target = crate::bundle::PendingBundle<M>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<M> Display for PendingBundle<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
+}
+
+impl<M> Debug for PendingBundle<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+impl<M: Waiter> PendingBundle<M> {
+    /// Blocks until the bundle is included. See [`crate::bundle::PendingBundle::inclusion`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::bundle::PendingBundle::inclusion`].
+    pub fn inclusion(self) -> Result<(Vec<TransactionReceipt>, U64)> {
+        self.runtime.block_on(self.inner.inclusion())
+    }
+
+    /// See [`crate::bundle::PendingBundle::cancel`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::bundle::PendingBundle::cancel`].
+    pub fn cancel(&self) -> Result<crate::bundle::BundleCancellation> {
+        self.runtime.block_on(self.inner.cancel())
+    }
+
+    /// See [`crate::bundle::PendingBundle::diagnose`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::bundle::PendingBundle::diagnose`].
+    pub fn diagnose(&self) -> Result<crate::relay_data::InclusionDiagnosis> {
+        self.runtime.block_on(self.inner.diagnose())
+    }
+
+    /// Blocks until the bundle lands or is dropped, calling `on_event` with each stage it reaches. See
+    /// [`crate::bundle::PendingBundle::watch_status`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`crate::bundle::PendingBundle::watch_status`].
+    pub fn watch_status(&self, mut on_event: impl FnMut(BundleStatusEvent)) -> Result<()> {
+        self.runtime
+            .block_on(self.inner.watch_status().try_for_each(|event| {
+                on_event(event);
+                future::ok(())
+            }))
+    }
+}
+
+/// Starts the runtime of a client: the websocket connection, if any, is driven by a background task, so keep a worker
+/// thread around to run it between calls.
+fn runtime() -> Result<Runtime> {
+    Ok(tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .enable_all()
+        .build()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::LocalWallet;
+
+    #[test]
+    fn test_from_async() {
+        let (provider, _) = Provider::mocked();
+        let client = crate::MevShareClient::new_with_chain_id(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+            5_u64,
+        )
+        .unwrap()
+        .with_api_url("http://localhost:8080")
+        .blocking()
+        .unwrap();
+
+        // the settings of the async client carry over
+        assert_eq!(client.network().chain_id(), 5);
+        assert_eq!(client.clone().network().api_url(), "http://localhost:8080");
+        assert_eq!(client.as_async().network().chain_id(), 5);
+    }
+}
@@ -0,0 +1,540 @@
+use crate::api::types::Body;
+use crate::{Error, Result};
+use ethers::types::{Bytes, Transaction, U256};
+use ethers::utils::rlp;
+use instant::Instant;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use typed_builder::TypedBuilder;
+
+/// Limits on what a [`crate::MevShareClient`] is allowed to submit, as a safety net against runaway strategies.
+///
+/// Only the signed transactions in a submission count towards the limits: transactions referenced by hash belong to somebody else.
+///
+/// # Example
+///
+/// ```
+/// let client = MevShareClient::new(auth_wallet, provider)
+///     .await?
+///     .with_budget(
+///         Budget::builder()
+///             .window(Duration::from_secs(60 * 60))
+///             .max_value(parse_ether("0.5")?)
+///             .max_in_flight(4)
+///             .build(),
+///     );
+///
+/// match client.send_bundle(bundle_request).await {
+///     Err(Error::BudgetExceeded(limit)) => warn!(%limit, "not sending"),
+///     result => result?.inclusion().await?,
+/// }
+/// ```
+#[derive(Clone, Debug, TypedBuilder)]
+#[builder(field_defaults(default, setter(strip_option)))]
+pub struct Budget {
+    /// Rolling window over which gas and value are accounted. Defaults to one hour.
+    #[builder(default = Duration::from_secs(60 * 60), setter(!strip_option))]
+    pub window: Duration,
+    /// Maximum gas that can be submitted within `window`.
+    pub max_gas: Option<U256>,
+    /// Maximum ETH value (in wei) that can be submitted within `window`: transferred value plus the maximum gas fees.
+    pub max_value: Option<U256>,
+    /// Maximum number of bundles waiting for inclusion at the same time.
+    pub max_in_flight: Option<usize>,
+    /// Maximum number of transactions and bundles that can be submitted within `window`.
+    pub max_submissions: Option<usize>,
+}
+
+/// The limit of a [`Budget`] that a submission would have exceeded. See [`Error::BudgetExceeded`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BudgetLimit {
+    /// [`Budget::max_gas`] would have been exceeded.
+    Gas {
+        spent: U256,
+        requested: U256,
+        max: U256,
+    },
+    /// [`Budget::max_value`] would have been exceeded.
+    Value {
+        spent: U256,
+        requested: U256,
+        max: U256,
+    },
+    /// [`Budget::max_in_flight`] bundles are already waiting for inclusion.
+    InFlight { max: usize },
+    /// [`Budget::max_submissions`] were already submitted within the window.
+    Submissions { max: usize },
+}
+
+impl Display for BudgetLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gas {
+                spent,
+                requested,
+                max,
+            } => {
+                write!(f, "gas: spent {spent} + requested {requested} > max {max}")
+            }
+            Self::Value {
+                spent,
+                requested,
+                max,
+            } => {
+                write!(
+                    f,
+                    "value: spent {spent} + requested {requested} > max {max} wei"
+                )
+            }
+            Self::InFlight { max } => write!(f, "{max} bundles already in flight"),
+            Self::Submissions { max } => write!(f, "{max} submissions already within the window"),
+        }
+    }
+}
+
+/// Gas and value of a submission.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Spend {
+    gas: U256,
+    value: U256,
+}
+
+impl Spend {
+    /// Computes the spend of a single signed transaction.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Rlp`] if `tx` can't be decoded.
+    /// * [`Error::InvalidParams`] if its value and maximum gas fees add up to more than a `U256`.
+    pub fn of_transaction(tx: &Bytes) -> Result<Self> {
+        let tx: Transaction = rlp::decode(tx)?;
+        let fee_per_gas = tx.max_fee_per_gas.or(tx.gas_price).unwrap_or_default();
+        let value = tx
+            .gas
+            .checked_mul(fee_per_gas)
+            .and_then(|fees| fees.checked_add(tx.value))
+            .ok_or_else(|| {
+                Error::InvalidParams(format!(
+                    "value and fees of transaction {:?} overflow",
+                    tx.hash
+                ))
+            })?;
+
+        Ok(Self { gas: tx.gas, value })
+    }
+
+    /// Adds up `self` and `other`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidParams`] if the gas or the value overflows.
+    fn checked_add(self, other: Self) -> Result<Self> {
+        match (
+            self.gas.checked_add(other.gas),
+            self.value.checked_add(other.value),
+        ) {
+            (Some(gas), Some(value)) => Ok(Self { gas, value }),
+            _ => Err(Error::InvalidParams(
+                "gas or value of the transactions overflow".to_owned(),
+            )),
+        }
+    }
+
+    /// Computes the spend of several signed transactions, e.g. of a legacy bundle.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Rlp`] if any of `txs` can't be decoded.
+    /// * [`Error::InvalidParams`] if their gas or value add up to more than a `U256`.
+    pub fn of_transactions(txs: &[Bytes]) -> Result<Self> {
+        txs.iter().try_fold(Self::default(), |total, tx| {
+            total.checked_add(Self::of_transaction(tx)?)
+        })
+    }
+
+    /// Computes the spend of the signed transactions in a bundle body, including nested bundles.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Rlp`] if any of the transactions can't be decoded.
+    /// * [`Error::InvalidParams`] if their gas or value add up to more than a `U256`.
+    pub fn of_body(body: &[Body]) -> Result<Self> {
+        body.iter().try_fold(Self::default(), |total, body| {
+            let spend = match body {
+                Body::Tx { .. } => Self::default(),
+                Body::Signed { tx, .. } => Self::of_transaction(tx)?,
+                Body::Bundle(bundle) => Self::of_body(&bundle.body)?,
+            };
+
+            total.checked_add(spend)
+        })
+    }
+}
+
+/// What has been submitted within the window of a [`Budget`]. See [`crate::MevShareClient::tenant_usage`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BudgetUsage {
+    /// Number of transactions and bundles submitted.
+    pub submissions: usize,
+    /// Gas submitted.
+    pub gas: U256,
+    /// ETH value (in wei) submitted: transferred value plus the maximum gas fees.
+    pub value: U256,
+    /// Number of bundles waiting for inclusion.
+    pub in_flight: usize,
+}
+
+impl BudgetUsage {
+    /// [`Self::value`] in USD, at `eth_usd` dollars per ETH, see [`crate::price`].
+    #[must_use]
+    pub fn value_usd(&self, eth_usd: f64) -> f64 {
+        crate::price::wei_to_usd(self.value, eth_usd)
+    }
+}
+
+/// Enforces a [`Budget`] at send time.
+#[derive(Debug)]
+pub(crate) struct BudgetLimiter {
+    budget: Budget,
+    /// Tenant whose submissions are limited, see [`TenantLimiters`]. `None` for the client-wide budget.
+    tenant: Option<String>,
+    spent: Mutex<VecDeque<(u64, Instant, Spend)>>,
+    next_id: AtomicU64,
+    in_flight: Arc<AtomicUsize>,
+}
+
+/// A submission accounted for by a [`BudgetLimiter`], that can be given back via [`BudgetLimiter::refund`].
+#[derive(Clone, Copy)]
+pub(crate) struct Reservation(u64);
+
+/// A slot taken among the [`Budget::max_in_flight`] bundles, released on drop.
+#[derive(Debug)]
+pub struct InFlightPermit(Arc<AtomicUsize>);
+
+impl Drop for InFlightPermit {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl BudgetLimiter {
+    pub fn new(budget: Budget) -> Self {
+        Self {
+            budget,
+            tenant: None,
+            spent: Mutex::default(),
+            next_id: AtomicU64::default(),
+            in_flight: Arc::default(),
+        }
+    }
+
+    /// Accounts for `spend` if it fits in the budget.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::BudgetExceeded`] if it doesn't.
+    pub fn reserve(&self, spend: Spend) -> Result<Reservation> {
+        let now = Instant::now();
+        let mut history = self.spent.lock().unwrap_or_else(PoisonError::into_inner);
+        let total = self.prune(&mut history, now);
+
+        if let Some(max) = self.budget.max_submissions
+            && history.len() >= max
+        {
+            return Err(self.exceeded(BudgetLimit::Submissions { max }));
+        }
+
+        if let Some(max) = self.budget.max_gas
+            && total.gas.saturating_add(spend.gas) > max
+        {
+            return Err(self.exceeded(BudgetLimit::Gas {
+                spent: total.gas,
+                requested: spend.gas,
+                max,
+            }));
+        }
+
+        if let Some(max) = self.budget.max_value
+            && total.value.saturating_add(spend.value) > max
+        {
+            return Err(self.exceeded(BudgetLimit::Value {
+                spent: total.value,
+                requested: spend.value,
+                max,
+            }));
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        history.push_back((id, now, spend));
+
+        Ok(Reservation(id))
+    }
+
+    /// Returns what has been submitted within the window.
+    pub fn usage(&self) -> BudgetUsage {
+        let mut history = self.spent.lock().unwrap_or_else(PoisonError::into_inner);
+        let total = self.prune(&mut history, Instant::now());
+
+        BudgetUsage {
+            submissions: history.len(),
+            gas: total.gas,
+            value: total.value,
+            in_flight: self.in_flight.load(Ordering::Acquire),
+        }
+    }
+
+    /// Forgets the submissions that fell out of the window, and returns the total of the remaining ones.
+    fn prune(&self, history: &mut VecDeque<(u64, Instant, Spend)>, now: Instant) -> Spend {
+        while let Some((_, at, _)) = history.front()
+            && now.duration_since(*at) > self.budget.window
+        {
+            history.pop_front();
+        }
+
+        history
+            .iter()
+            .fold(Spend::default(), |total, (_, _, spend)| Spend {
+                gas: total.gas.saturating_add(spend.gas),
+                value: total.value.saturating_add(spend.value),
+            })
+    }
+
+    /// The error of a submission that would exceed `limit`.
+    fn exceeded(&self, limit: BudgetLimit) -> Error {
+        match &self.tenant {
+            Some(tenant) => Error::QuotaExceeded(tenant.clone(), limit),
+            None => Error::BudgetExceeded(limit),
+        }
+    }
+
+    /// Gives back a reservation, e.g. because the relay rejected the submission.
+    pub fn refund(&self, reservation: Reservation) {
+        self.spent
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|(id, _, _)| *id != reservation.0);
+    }
+
+    /// Takes one of the [`Budget::max_in_flight`] slots.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::BudgetExceeded`] if they're all taken.
+    pub fn acquire_in_flight(&self) -> Result<InFlightPermit> {
+        let max = self.budget.max_in_flight.unwrap_or(usize::MAX);
+
+        self.in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .map_err(|_| self.exceeded(BudgetLimit::InFlight { max }))?;
+
+        Ok(InFlightPermit(self.in_flight.clone()))
+    }
+}
+
+/// Reservations taken by [`admit_bundle`], with the limiter each one belongs to.
+pub(crate) type Reservations = Vec<(Arc<BudgetLimiter>, Reservation)>;
+
+/// Admits a bundle of `spend` through every limiter in `limiters`, or through none of them.
+///
+/// # Errors
+///
+/// * [`Error::BudgetExceeded`] or [`Error::QuotaExceeded`] if any limiter rejects it.
+pub(crate) fn admit_bundle(
+    limiters: &[Arc<BudgetLimiter>],
+    spend: Spend,
+) -> Result<(Vec<InFlightPermit>, Reservations)> {
+    let mut permits = Vec::with_capacity(limiters.len());
+    let mut reservations = Vec::with_capacity(limiters.len());
+
+    for limiter in limiters {
+        let admitted = limiter
+            .acquire_in_flight()
+            .and_then(|permit| Ok((permit, limiter.reserve(spend)?)));
+
+        match admitted {
+            Ok((permit, reservation)) => {
+                permits.push(permit);
+                reservations.push((Arc::clone(limiter), reservation));
+            }
+            Err(err) => {
+                refund_all(reservations);
+                return Err(err);
+            }
+        }
+    }
+
+    Ok((permits, reservations))
+}
+
+/// Gives back the reservations of [`admit_bundle`], e.g. because the relay rejected the bundle.
+pub(crate) fn refund_all(reservations: Reservations) {
+    for (limiter, reservation) in reservations {
+        limiter.refund(reservation);
+    }
+}
+
+/// Enforces a [`Budget`] separately for each tenant. See [`crate::MevShareClient::with_tenant_budget`].
+#[derive(Debug)]
+pub(crate) struct TenantLimiters {
+    budget: Budget,
+    tenants: Mutex<HashMap<String, Arc<BudgetLimiter>>>,
+}
+
+impl TenantLimiters {
+    pub fn new(budget: Budget) -> Self {
+        Self {
+            budget,
+            tenants: Mutex::default(),
+        }
+    }
+
+    /// Returns the limiter of `tenant`, starting it on its first submission.
+    pub fn get(&self, tenant: &str) -> Arc<BudgetLimiter> {
+        self.tenants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(tenant.to_owned())
+            .or_insert_with(|| {
+                Arc::new(BudgetLimiter {
+                    tenant: Some(tenant.to_owned()),
+                    ..BudgetLimiter::new(self.budget.clone())
+                })
+            })
+            .clone()
+    }
+
+    /// Returns what `tenant` submitted within the window, if it ever submitted anything.
+    pub fn usage(&self, tenant: &str) -> Option<BudgetUsage> {
+        self.tenants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(tenant)
+            .map(|limiter| limiter.usage())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_limiter() {
+        let limiter = BudgetLimiter::new(
+            Budget::builder()
+                .max_gas(U256::from(100))
+                .max_in_flight(1)
+                .build(),
+        );
+
+        let spend = Spend {
+            gas: U256::from(60),
+            value: U256::zero(),
+        };
+
+        let reservation = limiter.reserve(spend).unwrap();
+        assert!(matches!(
+            limiter.reserve(spend),
+            Err(Error::BudgetExceeded(BudgetLimit::Gas { .. }))
+        ));
+
+        limiter.refund(reservation);
+        assert!(limiter.reserve(spend).is_ok());
+
+        let permit = limiter.acquire_in_flight().unwrap();
+        assert!(matches!(
+            limiter.acquire_in_flight(),
+            Err(Error::BudgetExceeded(BudgetLimit::InFlight { max: 1 }))
+        ));
+
+        drop(permit);
+        assert!(limiter.acquire_in_flight().is_ok());
+    }
+
+    #[test]
+    fn test_spend_overflow() {
+        use ethers::signers::{LocalWallet, Signer};
+        use ethers::types::transaction::eip2718::TypedTransaction;
+        use ethers::types::TransactionRequest;
+
+        let wallet = LocalWallet::new(&mut ethers::core::rand::thread_rng()).with_chain_id(1_u64);
+        let signed = |gas: U256, gas_price: U256, value: U256| {
+            let tx: TypedTransaction = TransactionRequest::new()
+                .to(wallet.address())
+                .nonce(0)
+                .chain_id(1)
+                .gas(gas)
+                .gas_price(gas_price)
+                .value(value)
+                .into();
+            tx.rlp_signed(&wallet.sign_transaction_sync(&tx).unwrap())
+        };
+
+        let fees = signed(U256::MAX, U256::from(2), U256::zero());
+        assert!(matches!(
+            Spend::of_transaction(&fees),
+            Err(Error::InvalidParams(_))
+        ));
+
+        // each transaction fits, but not both
+        let value = signed(U256::zero(), U256::zero(), U256::MAX);
+        assert_eq!(Spend::of_transaction(&value).unwrap().value, U256::MAX);
+        assert!(matches!(
+            Spend::of_transactions(&[value.clone(), value.clone()]),
+            Err(Error::InvalidParams(_))
+        ));
+        assert!(matches!(
+            Spend::of_body(&[
+                Body::Signed {
+                    tx: value.clone(),
+                    can_revert: false
+                },
+                Body::Signed {
+                    tx: value,
+                    can_revert: false
+                },
+            ]),
+            Err(Error::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_tenant_limiters() {
+        let tenants = TenantLimiters::new(Budget::builder().max_submissions(1).build());
+        let client = Arc::new(BudgetLimiter::new(
+            Budget::builder().max_gas(U256::from(100)).build(),
+        ));
+
+        let spend = Spend {
+            gas: U256::from(60),
+            value: U256::zero(),
+        };
+
+        let alice = tenants.get("alice");
+        let (_, reservations) = admit_bundle(&[client.clone(), alice.clone()], spend).unwrap();
+        assert_eq!(reservations.len(), 2);
+        assert!(matches!(
+            admit_bundle(&[client.clone(), alice.clone()], spend),
+            Err(Error::BudgetExceeded(BudgetLimit::Gas { .. }))
+        ));
+
+        refund_all(reservations);
+        let (_, reservations) = admit_bundle(&[client.clone(), alice.clone()], spend).unwrap();
+        refund_all(reservations.into_iter().take(1).collect());
+
+        // alice's submission is still accounted for, and rejected by her quota rather than the client budget
+        assert!(matches!(
+            admit_bundle(&[client.clone(), alice.clone()], spend),
+            Err(Error::QuotaExceeded(tenant, BudgetLimit::Submissions { max: 1 })) if tenant == "alice"
+        ));
+        assert_eq!(client.usage().submissions, 0);
+        assert_eq!(tenants.usage("alice").unwrap().submissions, 1);
+
+        assert!(admit_bundle(&[client.clone(), tenants.get("bob")], spend).is_ok());
+        assert_eq!(tenants.usage("carol"), None);
+    }
+}
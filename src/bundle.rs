@@ -0,0 +1,14 @@
+//! Types to send and simulate bundles with [`crate::MevShareClient::send_bundle`] and
+//! [`crate::MevShareClient::simulate_bundle`], or in the legacy format with
+//! [`crate::MevShareClient::send_legacy_bundle`] and [`crate::MevShareClient::call_bundle`].
+
+pub use crate::api::types::{
+    attribute_block, fetch_builder_registry, fetch_builder_registry_from, verify_adjacency,
+    watch_builder_registry, AdjacencyReport, AdjacencyViolation, Body, BodyHashIterator, Builder,
+    BuilderAttribution, BuilderRegistration, BuilderRegistry, Builders, BundleCancellation,
+    BundleLogs, BundleStatusEvent, CancelBundleParams, EthCallBundleParams, EthCallBundleResponse,
+    EthCallBundleTxResult, EthSendBundleParams, HashesIter, Hint, Inclusion, KnownBuilder,
+    Metadata, OnReorg, PendingBundle, Privacy, Refund, RefundConfig, RelayLimit, RelayLimits,
+    SendBundleParams, SendBundleResponse, SimulateBundleParams, SimulateBundleResponse, Validity,
+    BUILDER_REGISTRY_URL, KNOWN_BUILDERS, SUPPORTED_VERSIONS,
+};
@@ -1,30 +1,140 @@
-use crate::api::networks::MevShareNetwork;
+#[cfg(feature = "stream")]
+use crate::alert::StreamWatch;
+use crate::alert::{Alert, Alerter};
+use crate::api::batch::Batch;
+use crate::api::networks::{Capability, MevShareNetwork, NetworkTable};
+#[cfg(feature = "history")]
 use crate::api::rest_client::RestClient;
-use crate::api::rpc_client::MevShareRpcClient;
+use crate::api::rpc_client::{MevShareRpcClient, ResponseMeta};
 use crate::api::types::PendingTransaction;
 use crate::api::types::*;
+use crate::backend::OrderflowBackend;
+use crate::budget::{Budget, BudgetLimiter, BudgetUsage, TenantLimiters};
+use crate::dedup::DedupStore;
+use crate::ens::EnsResolver;
+#[cfg(feature = "stream")]
 use crate::error::JsonError;
+use crate::guard::{self, CancelGuard, Orders, Outstanding};
+#[cfg(feature = "wait")]
+use crate::helpers::cancellation_of;
+#[cfg(feature = "wait")]
+use crate::helpers::provider::BlockStream;
 use crate::helpers::provider::Waiter;
-use crate::{Result, SendBundleParams, SendTransactionParams};
+use crate::helpers::within;
+#[cfg(feature = "history")]
+use crate::history::{InfoCache, HISTORY_INFO_TTL};
+use crate::layer::RpcLayer;
+use crate::retry::RetryPolicy;
+use crate::signer::{AuthScheme, AuthSigner};
+use crate::tracker::{Outcome, SubmissionTracker};
+use crate::{Error, Result};
 use ethers::prelude::*;
+#[cfg(feature = "wait")]
+use ethers::utils::rlp;
+#[cfg(feature = "stream")]
+use futures::{future, Stream, StreamExt};
+#[cfg(feature = "wait")]
+use instant::Instant;
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+#[cfg(feature = "stream")]
 use reqwest_eventsource::{Event, EventSource};
 use serde_json::json;
-use tokio_stream::{Stream, StreamExt};
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(feature = "wait")]
+use tracing::info;
+#[cfg(feature = "stream")]
 use tracing::trace;
 
-pub struct MevShareClient<'a> {
-    provider: Provider<Ws>,
+mod admission;
+mod api;
+mod builder;
+mod health;
+#[cfg(feature = "history")]
+mod history;
+mod network;
+
+#[cfg(feature = "stream")]
+pub use api::EventStream;
+pub use api::MevShareApi;
+pub use builder::MevShareClientBuilder;
+pub use network::NetworkOverride;
+
+/// Client of the MEV-Share relay and event stream, see [`Self::new`].
+///
+/// Cheap to clone, and `Send + Sync`: share one client across tasks by cloning it.
+///
+/// # Example
+///
+/// ```
+/// let client = MevShareClient::new(auth_wallet, provider).await?;
+///
+/// let sender = tokio::spawn({
+///     let client = client.clone();
+///     async move { client.send_bundle(bundle).await?.inclusion().await }
+/// });
+/// let mut events = client.subscribe_bundles();
+/// ```
+pub struct MevShareClient<M = Provider<Ws>> {
+    provider: Arc<M>,
     network: MevShareNetwork,
-    rpc: MevShareRpcClient<'a>,
-    rest: RestClient,
+    rpc: Arc<MevShareRpcClient>,
+    backend: Option<Arc<dyn OrderflowBackend>>,
+    #[cfg(feature = "history")]
+    rest: Arc<RestClient>,
+    orders: Orders,
+    budget: Option<Arc<BudgetLimiter>>,
+    tenants: Option<Arc<TenantLimiters>>,
+    dedup: Option<(Arc<dyn DedupStore>, Duration)>,
+    limits: Option<RelayLimits>,
+    verify_bundle_hash: bool,
+    default_privacy: Option<Privacy>,
+    tracker: Option<Arc<SubmissionTracker>>,
+    alerter: Option<Arc<Alerter>>,
+    ens: Arc<EnsResolver<Arc<M>>>,
+    #[cfg(feature = "history")]
+    history_info: Arc<InfoCache>,
+    #[cfg(all(feature = "stream", feature = "tokio"))]
+    stream_buffer: usize,
 }
 
-impl MevShareClient<'_> {
+/// Clones share the connections, the budgets, the dedup store and the tracked orders of the client: spawn tasks with
+/// clones of one client rather than with clients of their own.
+impl<M> Clone for MevShareClient<M> {
+    fn clone(&self) -> Self {
+        Self {
+            provider: Arc::clone(&self.provider),
+            network: self.network.clone(),
+            rpc: Arc::clone(&self.rpc),
+            backend: self.backend.clone(),
+            #[cfg(feature = "history")]
+            rest: Arc::clone(&self.rest),
+            orders: Arc::clone(&self.orders),
+            budget: self.budget.clone(),
+            tenants: self.tenants.clone(),
+            dedup: self.dedup.clone(),
+            limits: self.limits,
+            verify_bundle_hash: self.verify_bundle_hash,
+            default_privacy: self.default_privacy.clone(),
+            tracker: self.tracker.clone(),
+            alerter: self.alerter.clone(),
+            ens: Arc::clone(&self.ens),
+            #[cfg(feature = "history")]
+            history_info: Arc::clone(&self.history_info),
+            #[cfg(all(feature = "stream", feature = "tokio"))]
+            stream_buffer: self.stream_buffer,
+        }
+    }
+}
+
+impl<M: Waiter> MevShareClient<M> {
     /// Initializes a [`MevShareClient`].
     ///
     /// If you already have a `chain_id`, you can use [`Self::new_with_chain_id`], which is not async because it avoids the network trip.
     /// `chain_id` is needed to infer which MEV-Share endpoint (e.g. mainnet or goerli) to query.
     ///
+    /// Requests are signed by `auth_wallet`, a [`LocalWallet`] or a [`crate::signer::RemoteSigner`].
+    ///
     /// # Example
     ///
     /// ```
@@ -35,37 +145,739 @@ impl MevShareClient<'_> {
     ///
     /// * [`crate::Error::Provider`] if the `provider` fails to retrieve a `chain_id`.
     /// * [`crate::Error::UnsupportedNetwork`] if the `chain_id` is not supported by the MEV-Share client.
-    pub async fn new(auth_wallet: LocalWallet, provider: Provider<Ws>) -> Result<Self> {
-        let chain_id = provider.get_chainid().await?;
+    pub async fn new(auth_wallet: impl Into<AuthSigner>, provider: M) -> Result<Self> {
+        let chain_id = provider.get_chainid().await.map_err(Error::middleware)?;
         Self::new_with_chain_id(auth_wallet, provider, chain_id)
     }
 
     /// Initializes a [`MevShareClient`].
     ///
+    /// `chain_id` is a [`Chain`], or a chain id as `u64` or [`U256`].
+    ///
     /// # Example
     ///
     /// ```
     /// // no need to await here
-    /// let client = MevShareProvider::new_with_chain_id(auth_wallte, provider, chain_id)?;
+    /// let client = MevShareProvider::new_with_chain_id(auth_wallte, provider, Chain::Mainnet)?;
     /// ```
     ///
     /// # Errors
     ///
     /// * [`crate::Error::UnsupportedNetwork`] if the `chain_id` is not supported by the MEV-Share client.
     pub fn new_with_chain_id(
-        auth_wallet: LocalWallet,
-        provider: Provider<Ws>,
-        chain_id: U256,
+        auth_wallet: impl Into<AuthSigner>,
+        provider: M,
+        chain_id: impl TryInto<MevShareNetwork, Error = impl Into<Error>>,
     ) -> Result<Self> {
-        let network = MevShareNetwork::try_from(chain_id)?;
-        let rest_url = format!("{}/api/v1", network.stream_url.trim_end_matches('/'));
+        Ok(Self::new_with_network(
+            auth_wallet,
+            provider,
+            chain_id.try_into().map_err(Into::into)?,
+        ))
+    }
 
-        Ok(Self {
-            rpc: MevShareRpcClient::new(network.api_url, auth_wallet),
-            rest: RestClient::new(rest_url),
+    /// Initializes a [`MevShareClient`] on the network of the chain served by `provider` among `networks`, e.g. a table
+    /// extended with other chains from the environment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let networks = NetworkTable::from_env()?;
+    /// let client = MevShareClient::new_with_networks(auth_wallet, provider, &networks).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Provider`] if the `provider` fails to retrieve a `chain_id`.
+    /// * [`crate::Error::UnsupportedNetwork`] if `networks` has no network for the `chain_id`.
+    pub async fn new_with_networks(
+        auth_wallet: impl Into<AuthSigner>,
+        provider: M,
+        networks: &NetworkTable,
+    ) -> Result<Self> {
+        let network = networks.get(provider.get_chainid().await.map_err(Error::middleware)?)?;
+        Ok(Self::new_with_network(auth_wallet, provider, network))
+    }
+
+    /// Initializes a [`MevShareClient`] on `network`, e.g. a [`MevShareNetwork::custom`] one to use a self-hosted
+    /// matchmaker instead of the Flashbots endpoints of the chain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let network = MevShareNetwork::custom(Chain::Mainnet, "https://relay.example.org", "https://mev-share.example.org");
+    /// let client = MevShareClient::new_with_network(auth_wallet, provider, network);
+    /// ```
+    #[must_use]
+    pub fn new_with_network(
+        auth_wallet: impl Into<AuthSigner>,
+        provider: M,
+        network: MevShareNetwork,
+    ) -> Self {
+        let provider = Arc::new(provider);
+        Self {
+            rpc: Arc::new(MevShareRpcClient::new(network.api_url(), auth_wallet)),
+            backend: None,
+            #[cfg(feature = "history")]
+            rest: Arc::new(RestClient::new(network::history_url(&network))),
+            ens: Arc::new(EnsResolver::new(Arc::clone(&provider))),
             provider,
             network,
-        })
+            orders: Orders::default(),
+            budget: None,
+            tenants: None,
+            dedup: None,
+            limits: None,
+            verify_bundle_hash: false,
+            default_privacy: None,
+            tracker: None,
+            alerter: None,
+            #[cfg(feature = "history")]
+            history_info: Arc::new(InfoCache::new(HISTORY_INFO_TTL)),
+            #[cfg(all(feature = "stream", feature = "tokio"))]
+            stream_buffer: 0,
+        }
+    }
+
+    /// The MEV-Share network the client was resolved to, from the chain id of the provider.
+    #[must_use]
+    pub fn network(&self) -> &MevShareNetwork {
+        &self.network
+    }
+
+    /// Resolves the ENS `name` through the provider, caching the address for the life of the client, e.g. to pay
+    /// refunds to `treasury.eth`. Hex addresses are passed through.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let refund = RefundConfig { address: client.resolve_name("treasury.eth").await?, percent: 100 };
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Provider`] if the name has no resolver or address, or the provider fails.
+    pub async fn resolve_name(&self, name: impl Into<NameOrAddress>) -> Result<Address> {
+        self.ens.resolve(name).await
+    }
+
+    /// The number of the latest block, from the provider.
+    async fn block_number(&self) -> Result<U64> {
+        self.provider
+            .get_block_number()
+            .await
+            .map_err(Error::middleware)
+    }
+
+    /// Enforces `budget` on every transaction and bundle sent by this client.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_budget(Budget::builder().max_gas(U256::from(5_000_000)).max_in_flight(4).build());
+    /// ```
+    #[must_use]
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.budget = Some(Arc::new(BudgetLimiter::new(budget)));
+        self
+    }
+
+    /// Enforces `budget` separately for each tenant, on top of the client [`Budget`], for backends that submit bundles
+    /// on behalf of many users.
+    ///
+    /// Bundles are attributed to the tenant in their [`SendBundleParams::metadata`] `origin_id`; bundles without one
+    /// only count against the client budget.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_tenant_budget(Budget::builder().max_submissions(10).max_in_flight(2).build());
+    ///
+    /// let bundle = SendBundleParams::builder()
+    ///     .body(body)
+    ///     .inclusion(block, None)
+    ///     .metadata(user_id)
+    ///     .build();
+    ///
+    /// match client.send_bundle(bundle).await {
+    ///     Err(Error::QuotaExceeded(tenant, limit)) => return Err(TooManyRequests(tenant, limit)),
+    ///     result => result?,
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_tenant_budget(mut self, budget: Budget) -> Self {
+        self.tenants = Some(Arc::new(TenantLimiters::new(budget)));
+        self
+    }
+
+    /// Returns what `tenant` submitted within the window of the [`Self::with_tenant_budget`] budget, if anything.
+    #[must_use]
+    pub fn tenant_usage(&self, tenant: &str) -> Option<BudgetUsage> {
+        self.tenants.as_ref()?.usage(tenant)
+    }
+
+    /// Claims every bundle in `store` for `ttl` before sending it, and refuses to send it again until then, even after a
+    /// restart. See [`crate::dedup`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_dedup_store(SqliteDedupStore::open("submitted.sqlite")?, Duration::from_secs(5 * 60));
+    /// ```
+    #[must_use]
+    pub fn with_dedup_store(mut self, store: impl DedupStore + 'static, ttl: Duration) -> Self {
+        self.dedup = Some((Arc::new(store), ttl));
+        self
+    }
+
+    /// Checks every bundle against the structural `limits` of the relay before sending it, failing with
+    /// [`crate::Error::RelayLimit`] rather than a generic relay rejection. See [`SendBundleParams::check_limits`].
+    ///
+    /// If [`RelayLimits::max_block_distance`] is set, sending a bundle fetches the current block number first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_relay_limits(RelayLimits::FLASHBOTS);
+    /// ```
+    #[must_use]
+    pub fn with_relay_limits(mut self, limits: RelayLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Checks that the bundle hash returned by the relay for [`Self::send_bundle`] is the one computed locally, see
+    /// [`SendBundleParams::bundle_hash`], e.g. to catch a proxy or a relay that rewrites bundles.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_bundle_hash_verification();
+    /// ```
+    #[must_use]
+    pub fn with_bundle_hash_verification(mut self) -> Self {
+        self.verify_bundle_hash = true;
+        self
+    }
+
+    /// Shares the bundles sent with [`Self::send_bundle`] without a [`SendBundleParams::privacy`] of their own
+    /// according to `privacy`, rather than to the relay defaults.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider).await?;
+    /// let privacy = Privacy::default_for(client.network());
+    /// let client = client.with_default_privacy(privacy);
+    /// ```
+    #[must_use]
+    pub fn with_default_privacy(mut self, privacy: Privacy) -> Self {
+        self.default_privacy = Some(privacy);
+        self
+    }
+
+    /// Records the outcomes of the bundles and transactions sent by this client in `tracker`, see [`crate::tracker`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let tracker = Arc::new(SubmissionTracker::default());
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_submission_tracker(tracker.clone());
+    /// ```
+    #[must_use]
+    pub fn with_submission_tracker(mut self, tracker: Arc<SubmissionTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Records `outcome` in the [`Self::with_submission_tracker`] tracker, if any.
+    fn track(&self, outcome: Outcome) {
+        if let Some(tracker) = &self.tracker {
+            tracker.record(outcome);
+        }
+    }
+
+    /// Notifies `alerter` of reverted submissions, budget refusals and event stream disconnections, see
+    /// [`crate::alert`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let alerter = Alerter::new(AlertRules::default()).with_webhook(Webhook::new(webhook_url));
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_alerter(Arc::new(alerter));
+    /// ```
+    #[must_use]
+    pub fn with_alerter(mut self, alerter: Arc<Alerter>) -> Self {
+        self.alerter = Some(alerter);
+        self
+    }
+
+    /// Fires [`Alert::BudgetExceeded`] with the [`Self::with_alerter`] alerter, if any, if `err` is a budget refusal.
+    async fn alert_budget(&self, err: &Error) {
+        let Some(alerter) = &self.alerter else {
+            return;
+        };
+
+        let (tenant, limit) = match err {
+            Error::BudgetExceeded(limit) => (None, limit),
+            Error::QuotaExceeded(tenant, limit) => (Some(tenant.clone()), limit),
+            _ => return,
+        };
+
+        alerter
+            .fire(Alert::BudgetExceeded {
+                tenant,
+                limit: limit.clone(),
+            })
+            .await;
+    }
+
+    /// Sends transactions and bundles to `backend` instead of the Flashbots relay, e.g. an adapter for another
+    /// order-flow provider, see [`crate::backend`].
+    ///
+    /// The event stream, the history API and cancellations still go to the Flashbots relay.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_backend(BloxrouteBackend::new(auth_header));
+    /// ```
+    #[must_use]
+    pub fn with_backend(mut self, backend: impl OrderflowBackend + 'static) -> Self {
+        self.backend = Some(Arc::new(backend));
+        self
+    }
+
+    /// The order-flow provider transactions and bundles are sent to: the [`Self::with_backend`] one, if any, or the
+    /// Flashbots relay.
+    fn backend(&self) -> &dyn OrderflowBackend {
+        match &self.backend {
+            Some(backend) => backend.as_ref(),
+            None => &*self.rpc,
+        }
+    }
+
+    /// Sends `params` to the order-flow provider, giving up after the client default timeout, if any.
+    async fn backend_send_private_transaction(
+        &self,
+        params: SendTransactionParams,
+    ) -> Result<TxHash> {
+        match &self.backend {
+            // the relay client times out on its own
+            None => self.rpc.send_private_transaction(params).await,
+            Some(backend) => within(self.rpc.timeout(), backend.send_private_transaction(params))
+                .await
+                .map_err(Error::Timeout)?,
+        }
+    }
+
+    /// Sends `params` to the order-flow provider, giving up after `timeout` rather than the client default.
+    async fn backend_send_bundle(
+        &self,
+        params: SendBundleParams,
+        timeout: Option<Duration>,
+    ) -> Result<SendBundleResponse> {
+        match &self.backend {
+            None => Ok(self
+                .rpc
+                .post_with_timeout(MevShareRequest::SendBundle, [params], timeout)
+                .await?),
+            Some(backend) => within(timeout, backend.send_bundle(params))
+                .await
+                .map_err(Error::Timeout)?,
+        }
+    }
+
+    /// Simulates `params` with the order-flow provider, giving up after `timeout` rather than the client default.
+    async fn backend_simulate_bundle(
+        &self,
+        params: SendBundleParams,
+        options: SimulateBundleParams,
+        timeout: Option<Duration>,
+    ) -> Result<SimulateBundleResponse> {
+        match &self.backend {
+            None => Ok(self
+                .rpc
+                .post_with_timeout(MevShareRequest::SimBundle, (params, options), timeout)
+                .await?),
+            Some(backend) => within(timeout, backend.simulate_bundle(params, options))
+                .await
+                .map_err(Error::Timeout)?,
+        }
+    }
+
+    /// Returns what the order-flow provider knows about the bundle `bundle_hash` targeting `block`: on the Flashbots relay,
+    /// whether it was simulated, considered by builders and sealed into a block.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    /// * [`crate::Error::Backend`] if a [`Self::with_backend`] provider fails.
+    pub async fn bundle_status(
+        &self,
+        bundle_hash: TxHash,
+        block: impl Into<U64>,
+    ) -> Result<BundleStats> {
+        self.backend()
+            .bundle_status(bundle_hash, block.into())
+            .await
+    }
+
+    /// Returns what the Flashbots relay knows about the bundle `bundle_hash` targeting `block`, with
+    /// `flashbots_getBundleStatsV2`: when it was received and simulated, which builders considered it, and which sealed
+    /// it into a block. Unlike [`Self::bundle_status`], always asks the Flashbots relay, even with
+    /// [`Self::with_backend`].
+    ///
+    /// The stats tell at which stage a bundle that didn't land dropped out, see [`BundleStats`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pending_bundle = client.send_bundle(bundle).await?;
+    /// if let Err(err) = pending_bundle.inclusion().await {
+    ///     let stats = client.get_bundle_stats(pending_bundle.hash, block).await?;
+    ///     warn!(%err, %stats, "bundle didn't land");
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    pub async fn get_bundle_stats(
+        &self,
+        bundle_hash: TxHash,
+        block: impl Into<U64>,
+    ) -> Result<BundleStats> {
+        self.rpc.bundle_status(bundle_hash, block.into()).await
+    }
+
+    /// Returns the reputation of the searcher signing the requests, as of `block`: whether it has high priority access to
+    /// the relay, and what it paid validators and had simulated over the last day, week and since its first submission.
+    ///
+    /// The stats are fed to the [`Self::with_alerter`] alerter, if any, which notifies when high priority is lost.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let block = provider.get_block_number().await?;
+    /// let stats = client.get_user_stats(block).await?;
+    /// info!(stats.is_high_priority, paid = stats.last_7d_validator_payments_eth(), "last week");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    pub async fn get_user_stats(&self, block: impl Into<U64>) -> Result<UserStats> {
+        let stats: UserStats = self
+            .rpc
+            .post(
+                MevShareRequest::GetUserStats,
+                [json!({ "blockNumber": block.into() })],
+            )
+            .await?;
+
+        if let Some(alerter) = &self.alerter {
+            alerter.observe_user_stats(&stats).await;
+        }
+
+        Ok(stats)
+    }
+
+    /// Returns the gas fee refunds Flashbots owes, or paid, for the bundle `bundle_hash`.
+    ///
+    /// Refunds are computed once the block is final; until then, [`RefundRule`] estimates them.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UnsupportedOnNetwork`] if the relay of the network doesn't pay refunds.
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    pub async fn fee_refunds_by_bundle(&self, bundle_hash: TxHash) -> Result<FeeRefunds> {
+        self.network.require(Capability::FeeRefunds)?;
+
+        Ok(self
+            .rpc
+            .post(
+                MevShareRequest::GetFeeRefundsByBundle,
+                [json!({ "bundle_hash": bundle_hash })],
+            )
+            .await?)
+    }
+
+    /// Returns the gas fee refunds Flashbots owes, or paid, for the bundles and transactions that landed in `block`.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UnsupportedOnNetwork`] if the relay of the network doesn't pay refunds.
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    pub async fn fee_refunds_by_block(&self, block: impl Into<U64>) -> Result<FeeRefunds> {
+        self.network.require(Capability::FeeRefunds)?;
+
+        Ok(self
+            .rpc
+            .post(
+                MevShareRequest::GetFeeRefundsByBlock,
+                [json!({ "block_number": block.into() })],
+            )
+            .await?)
+    }
+
+    /// Returns the gas fee refunds Flashbots owes, or paid, to `params.recipient`, one page at a time: resume from
+    /// [`FeeRefunds::cursor`], or use [`Self::get_fee_refunds`] to list them all.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UnsupportedOnNetwork`] if the relay of the network doesn't pay refunds.
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    pub async fn fee_refunds_by_recipient(
+        &self,
+        params: GetFeeRefundsByRecipientParams,
+    ) -> Result<FeeRefunds> {
+        self.network.require(Capability::FeeRefunds)?;
+
+        Ok(self
+            .rpc
+            .post(MevShareRequest::GetFeeRefundsByRecipient, [params])
+            .await?)
+    }
+
+    /// Returns the gas fee refunds `recipient` has accrued so far, pending and paid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let totals = client.fee_refund_totals(searcher).await?;
+    /// info!(%totals, "accrued {} ETH", format_ether(totals.total()));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UnsupportedOnNetwork`] if the relay of the network doesn't pay refunds.
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    pub async fn fee_refund_totals(&self, recipient: Address) -> Result<FeeRefundTotals> {
+        self.network.require(Capability::FeeRefunds)?;
+
+        Ok(self
+            .rpc
+            .post(
+                MevShareRequest::GetFeeRefundTotalsByRecipient,
+                [json!({ "recipient": recipient })],
+            )
+            .await?)
+    }
+
+    /// Returns every gas fee refund Flashbots owes, or paid, to `recipient`, following the pages of
+    /// [`Self::fee_refunds_by_recipient`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let refunds = client.get_fee_refunds(searcher).await?;
+    /// info!(pending = %format_ether(refunds.total(RefundStatus::Pending)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UnsupportedOnNetwork`] if the relay of the network doesn't pay refunds.
+    /// * [`crate::Error::Rpc`] if any JSON-RPC request to the MEV-Share API fails.
+    pub async fn get_fee_refunds(&self, recipient: Address) -> Result<FeeRefunds> {
+        let mut refunds = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let params = GetFeeRefundsByRecipientParams {
+                recipient,
+                block_range_from: None,
+                block_range_to: None,
+                cursor: cursor.clone(),
+            };
+            let page = self.fee_refunds_by_recipient(params).await?;
+            let done = page.refunds.is_empty() || page.cursor.is_none() || page.cursor == cursor;
+            refunds.extend(page.refunds);
+
+            if done {
+                return Ok(FeeRefunds {
+                    refunds,
+                    cursor: None,
+                });
+            }
+            cursor = page.cursor;
+        }
+    }
+
+    /// Authenticates the requests to the relay with `auth` instead of the `X-Flashbots-Signature` of the auth wallet,
+    /// e.g. for a gateway expecting another header or a MAC, see [`AuthScheme`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_auth_scheme(GatewayAuth::new(api_key, secret));
+    /// ```
+    #[must_use]
+    pub fn with_auth_scheme(mut self, auth: impl AuthScheme + 'static) -> Self {
+        Arc::make_mut(&mut self.rpc).set_auth_scheme(Arc::new(auth));
+        self
+    }
+
+    /// Runs `layer` around every JSON-RPC request to the relay, e.g. to log, measure or rewrite them; layers added
+    /// later run within the ones added before. See [`crate::layer`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_layer(RequestLog)
+    ///     .with_layer(Metrics::new(registry));
+    /// ```
+    #[must_use]
+    pub fn with_layer(mut self, layer: impl RpcLayer + 'static) -> Self {
+        Arc::make_mut(&mut self.rpc).push_layer(Arc::new(layer));
+        self
+    }
+
+    /// Returns the metadata of the last response of the relay: its request id and the rate limit of the searcher, to
+    /// throttle before the relay does. When requests are concurrent, the last one to complete.
+    ///
+    /// Errors carry the metadata of their own response, see [`crate::Error::response_meta`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pending_bundle = client.send_bundle(bundle).await?;
+    /// if let Some(rate_limit) = client.last_response().and_then(|meta| meta.rate_limit)
+    ///     && rate_limit.is_exhausted()
+    /// {
+    ///     tokio::time::sleep(rate_limit.reset.unwrap_or(Duration::from_secs(1))).await;
+    /// }
+    /// ```
+    #[must_use]
+    pub fn last_response(&self) -> Option<ResponseMeta> {
+        self.rpc.last_response()
+    }
+
+    /// Identifies this client with `user_agent` on every request to the MEV-Share API, instead of `mev-share-rs/<version>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_user_agent("my-searcher/0.3")?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::InvalidHeader`] if `user_agent` is not a valid header value.
+    pub fn with_user_agent(self, user_agent: &str) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_str(user_agent)?);
+        Ok(self.with_headers(headers))
+    }
+
+    /// Sends `headers` with every request to the relay and to the history API, e.g. an API key or routing headers required
+    /// by a proxy in front of the relay. Headers already set are overwritten.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("X-Api-Key", HeaderValue::from_str(&config.api_key)?);
+    ///
+    /// let client = MevShareClient::new(auth_wallet, provider).await?.with_headers(headers);
+    /// ```
+    #[must_use]
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        #[cfg(feature = "history")]
+        Arc::make_mut(&mut self.rest)
+            .headers_mut()
+            .extend(headers.clone());
+        Arc::make_mut(&mut self.rpc).headers_mut().extend(headers);
+        self
+    }
+
+    /// Sends the requests to the relay and to the history API, and opens the event stream, with `http` rather than a
+    /// default [`reqwest::Client`], e.g. to go through a proxy, trust custom TLS roots or tune the connection pool.
+    ///
+    /// The [`Self::cancel_guard`] cancels through `http` too, including on drop: drop it from a multi-threaded runtime,
+    /// or outside any, for the connections of the pool to keep being driven meanwhile.
+    ///
+    /// With the `compression` feature, `http` decompresses the history responses and the event stream unless it was
+    /// built with `no_gzip` or `no_brotli`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let http = reqwest::Client::builder()
+    ///     .proxy(reqwest::Proxy::https("http://proxy.internal:3128")?)
+    ///     .pool_max_idle_per_host(4)
+    ///     .build()?;
+    /// let client = MevShareClient::new(auth_wallet, provider).await?.with_http_client(http);
+    /// ```
+    #[must_use]
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        #[cfg(feature = "history")]
+        Arc::make_mut(&mut self.rest).set_http_client(http.clone());
+        Arc::make_mut(&mut self.rpc).set_http_client(http);
+        self
+    }
+
+    /// Fails the requests to the relay, and to the history API, that get no response within `timeout`, with
+    /// [`crate::Error::Timeout`]; by default, the client waits forever. A [`Self::with_backend`] provider is given as
+    /// long to send and simulate.
+    ///
+    /// Applies to each request, from signing to the last byte of the response, rather than to the whole call, e.g.
+    /// not to waiting for inclusion; the event stream is not affected. Override it for a single bundle with
+    /// [`Self::send_bundle_with_timeout`] and [`Self::simulate_bundle_with_timeout`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_timeout(Duration::from_secs(2));
+    /// ```
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        #[cfg(feature = "history")]
+        Arc::make_mut(&mut self.rest).set_timeout(Some(timeout));
+        Arc::make_mut(&mut self.rpc).set_timeout(Some(timeout));
+        self
+    }
+
+    /// Retries the requests to the relay that fail transiently, e.g. on a connection reset or a `503`, as `policy`
+    /// says; by default, they are not retried. Sending transactions and bundles is only retried if
+    /// [`RetryPolicy::retry_sends`], see [`crate::retry`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_timeout(Duration::from_secs(2))
+    ///     .with_retry_policy(RetryPolicy::builder().max_attempts(4).build());
+    /// ```
+    #[must_use]
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        Arc::make_mut(&mut self.rpc).set_retry_policy(Some(policy));
+        self
     }
 
     /// Starts listening to the MEV-Share event stream.
@@ -82,23 +894,82 @@ impl MevShareClient<'_> {
     /// # Returns
     ///
     ///  A stream of [`MevShareEvent`]s.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields [`crate::Error::UnsupportedOnNetwork`], then ends, if the network serves no event stream.
+    #[cfg(feature = "stream")]
     pub fn subscribe_bundles(&self) -> impl Stream<Item = Result<MevShareEvent>> + '_ {
-        EventSource::get(self.network.stream_url).filter_map(move |event| match event {
-                    Ok(Event::Open) => None,
-                    Ok(Event::Message(msg)) => {
-                trace!(%msg.data);
-
-                Some(
-                    serde_json::from_str(&msg.data)
-                        .map_err(|source| JsonError::Deserialization {
-                            text: msg.data,
-                            source,
-                        })
-                        .map_err(Into::into),
-                )
+        if let Err(err) = self.network.require(Capability::EventStream) {
+            return future::Either::Left(futures::stream::once(future::ready(Err(err.into()))));
+        }
+
+        let request = self
+            .rpc
+            .http_client()
+            .get(self.network.stream_url())
+            .headers(self.rpc.headers().clone());
+
+        // bodiless GET requests can always be cloned to reconnect, so the fallback is never hit
+        let events = EventSource::new(request)
+            .unwrap_or_else(|_| EventSource::get(self.network.stream_url()));
+
+        let alerter = self.alerter.clone();
+        let mut watch = StreamWatch::default();
+
+        let events = events.filter_map(move |event| {
+            let alert = alerter.as_ref().and_then(|alerter| {
+                let threshold = alerter.rules().stream_disconnected?;
+                Some((alerter.clone(), watch.observe(event.is_ok(), threshold)?))
+            });
+
+            let item = match event {
+                Ok(Event::Open) => None,
+                Ok(Event::Message(msg)) => {
+                    trace!(%msg.data);
+
+                    Some(
+                        serde_json::from_str(&msg.data)
+                            .map_err(|source| JsonError::Deserialization {
+                                text: msg.data,
+                                source,
+                            })
+                            .map_err(Into::into),
+                    )
+                }
+                Err(err) => Some(Err(err.into())),
+            };
+
+            match alert {
+                None => future::Either::Left(future::ready(item)),
+                Some((alerter, alert)) => future::Either::Right(Box::pin(async move {
+                    alerter.fire(alert).await;
+                    item
+                })),
             }
-            Err(err) => Some(Err(err.into())),
-        })
+        });
+
+        #[cfg(feature = "tokio")]
+        let events = crate::helpers::read_ahead(events, self.stream_buffer);
+
+        future::Either::Right(events)
+    }
+
+    /// Reads up to `capacity` events of the [`Self::subscribe_bundles`] streams ahead of their consumer, from a task of
+    /// their own, so that a slow handler doesn't fall behind the event stream. Unbuffered by default.
+    ///
+    /// Streams must then be subscribed to from within a tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider).await?.with_stream_buffer(1024);
+    /// ```
+    #[cfg(all(feature = "stream", feature = "tokio"))]
+    #[must_use]
+    pub fn with_stream_buffer(mut self, capacity: usize) -> Self {
+        self.stream_buffer = capacity;
+        self
     }
 
     /// Sends a private transaction with MEV hints to Flashbots MEV-Share.
@@ -124,8 +995,8 @@ impl MevShareClient<'_> {
     ///     .tx(tx.rlp_signed(&sender_wallet.sign_transaction_sync(&tx)?))
     ///     .max_block_number(current_block + 20)
     ///     .preferences(
-    ///         Some(set![Hint::Hash, Calldata, Logs, ContractAddress, FunctionSelector]),
-    ///         Some(set![Builder::Flashbots]),
+    ///         [Hint::Hash, Hint::Calldata, Hint::Logs, Hint::ContractAddress, Hint::FunctionSelector],
+    ///         [Builder::Flashbots],
     ///     )
     ///     .build();
     ///
@@ -149,7 +1020,11 @@ impl MevShareClient<'_> {
     ///
     /// # Errors
     ///
+    /// * [`crate::Error::BudgetExceeded`] if sending the transaction would exceed the client [`Budget`].
+    /// * [`crate::Error::Rlp`] if a [`Budget`] is set and the transaction can't be decoded.
+    /// * [`crate::Error::InvalidParams`] if a [`Budget`] is set and the value and fees of the transaction overflow.
     /// * [`crate::Error::Rpc`] if the network request to the MEV-Share API fails.
+    /// * [`crate::Error::Backend`] if a [`Self::with_backend`] provider fails.
     /// * [`crate::Error::Provider`] if `self.provider` fails to get the [`TransactionReceipt`] or subscribing to blocks to wait for it.
     /// * [`crate::Error::TransactionTimeout`] if the transaction is not included in a block before `params.max_block_number` or 25[^1] blocks.
     /// * [`crate::Error::TransactionRevert`] if the transaction reverts.
@@ -157,20 +1032,293 @@ impl MevShareClient<'_> {
     /// [^1]: See [flashbots docs](https://docs.flashbots.net/flashbots-auction/searchers/advanced/private-transaction).
     pub async fn send_private_transaction(
         &self,
-        params: SendTransactionParams<'_>,
-    ) -> Result<PendingTransaction> {
-        let max_block_number = params.max_block_number;
+        params: SendTransactionParams,
+    ) -> Result<PendingTransaction<M>> {
+        let max_block_number = params.max_block_number;
+        let admission = self.admit_transaction(&params.tx).await?;
+
+        self.track(Outcome::Sent);
+        let hash = match self.backend_send_private_transaction(params).await {
+            Ok(hash) => hash,
+            Err(err) => {
+                self.reject(admission);
+                return Err(err);
+            }
+        };
+        self.accept(admission, hash);
+
+        let mut pending_tx =
+            PendingTransaction::new(hash, max_block_number, Arc::clone(&self.provider));
+        pending_tx.tracker.clone_from(&self.tracker);
+        pending_tx.alerter.clone_from(&self.alerter);
+        pending_tx.relay = Some((Arc::clone(&self.rpc), self.orders.clone()));
+
+        Ok(pending_tx)
+    }
+
+    /// Sends an end user's signed transaction the way Flashbots Protect does, sharing [`PROTECT_HINTS`] with every builder
+    /// of [`Builder::KNOWN`] and paying the MEV refund out to `refund`, e.g. the user, or the user and the wallet.
+    ///
+    /// Meant for wallets: poll the returned [`PendingTransaction::status`] to update the UI.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pending_tx = client
+    ///     .send_protected_transaction(
+    ///         signed_tx,
+    ///         [
+    ///             RefundConfig { address: user, percent: 90 },
+    ///             RefundConfig { address: wallet_treasury, percent: 10 },
+    ///         ],
+    ///     )
+    ///     .await?;
+    ///
+    /// while let TransactionStatus::Pending = pending_tx.status().await? {
+    ///     render_spinner(&pending_tx);
+    ///     sleep(Duration::from_secs(4)).await;
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::InvalidRefund`] if the `refund` percentages add up to more than 100.
+    /// * Same as [`Self::send_private_transaction`].
+    pub async fn send_protected_transaction(
+        &self,
+        tx: impl Into<Bytes>,
+        refund: impl IntoIterator<Item = RefundConfig>,
+    ) -> Result<PendingTransaction<M>> {
+        let refund: Vec<_> = refund.into_iter().collect();
+
+        let total: u32 = refund.iter().map(|refund| refund.percent).sum();
+        if total > 100 {
+            return Err(Error::InvalidRefund(total));
+        }
+
+        let params = SendTransactionParams {
+            tx: tx.into(),
+            max_block_number: None,
+            preferences: Some(Preferences {
+                fast: true,
+                privacy: Privacy::new(PROTECT_HINTS, Builder::KNOWN),
+                validity: (!refund.is_empty()).then_some(TransactionValidity { refund }),
+            }),
+        };
+
+        self.send_private_transaction(params).await
+    }
+
+    /// Keeps an end user's transaction alive until it lands or `timeout` elapses, re-submitting it with a fresh
+    /// `max_block_number` whenever the previous submission expires, and reporting every state change to `on_progress`.
+    ///
+    /// Each submission is valid for as many blocks as `params.max_block_number` is ahead of the current block, or
+    /// [`TX_WAIT_MAX_BLOCKS`] if unset. Each submission counts against the client [`Budget`], if any.
+    ///
+    /// Requires the `wait` feature (enabled by default).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let (receipt, block) = client
+    ///     .send_private_transaction_until(params, Duration::from_secs(300), |progress| match progress {
+    ///         TransactionProgress::Submitted { max_block, .. } => ui.show(format!("sent, valid until block {max_block}")),
+    ///         TransactionProgress::Pending(block) => ui.show(format!("still pending at block {block}")),
+    ///         TransactionProgress::Included(_, block) => ui.show(format!("included in block {block}")),
+    ///         TransactionProgress::Failed(err) => ui.show(format!("failed: {err}")),
+    ///     })
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::TransactionTimeout`] if the transaction is not included before `timeout`.
+    /// * [`crate::Error::TransactionRevert`] if the transaction reverts.
+    /// * [`crate::Error::BlockStreamEnded`] if the provider stops producing blocks before the transaction lands.
+    /// * [`crate::Error::Provider`] if `self.provider` fails to subscribe to blocks or to get the [`TransactionReceipt`].
+    /// * Same as [`Self::send_private_transaction`].
+    #[cfg(feature = "wait")]
+    pub async fn send_private_transaction_until(
+        &self,
+        params: SendTransactionParams,
+        timeout: Duration,
+        mut on_progress: impl FnMut(TransactionProgress<'_>),
+    ) -> Result<(TransactionReceipt, U64)> {
+        // subscribe first, so that no block is missed between the submission and the first check
+        let result = match self.provider.new_blocks().await {
+            Ok(blocks) => {
+                self.keep_alive(blocks, params, timeout, &mut on_progress)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+
+        match &result {
+            Ok((receipt, block)) => on_progress(TransactionProgress::Included(receipt, *block)),
+            Err(err) => on_progress(TransactionProgress::Failed(err)),
+        }
+
+        result
+    }
+
+    /// Cancels the pending private transaction `tx` by privately sending a 0-value self-transfer from `sender` with the
+    /// same nonce and higher fees, then waits to see which of the two lands.
+    ///
+    /// Unlike `eth_cancelPrivateTransaction`, which only withdraws the transaction from the relay, this also stops
+    /// builders that already received it, as the nonce can only be used once.
+    ///
+    /// Requires the `wait` feature (enabled by default).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// match client.cancel_by_replacement(&signed_tx, &sender_wallet).await? {
+    ///     ReplacementOutcome::Cancelled(_) => println!("cancelled"),
+    ///     ReplacementOutcome::TooLate(receipt) => println!("already landed: {:?}", receipt.transaction_hash),
+    /// }
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - The signed transaction to cancel.
+    /// * `sender` - The wallet that signed `tx`.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Rlp`] if `tx` can't be decoded.
+    /// * [`crate::Error::SenderMismatch`] if `tx` was not signed by `sender`.
+    /// * [`crate::Error::Wallet`] if the cancellation can't be signed.
+    /// * [`crate::Error::TransactionTimeout`] if neither transaction lands within [`TX_WAIT_MAX_BLOCKS`].
+    /// * [`crate::Error::BlockStreamEnded`] if the provider stops producing blocks before either transaction lands.
+    /// * [`crate::Error::Provider`] if `self.provider` fails to subscribe to blocks or to get the [`TransactionReceipt`]s.
+    /// * Same as [`Self::send_private_transaction`].
+    #[cfg(feature = "wait")]
+    pub async fn cancel_by_replacement(
+        &self,
+        tx: &Bytes,
+        sender: &LocalWallet,
+    ) -> Result<ReplacementOutcome> {
+        let mut original: ethers::types::Transaction = rlp::decode(tx)?;
+        let from = original
+            .recover_from_mut()
+            .map_err(|_| rlp::DecoderError::Custom("invalid transaction signature"))?;
+        if from != sender.address() {
+            return Err(Error::SenderMismatch(from, sender.address()));
+        }
+
+        let cancellation = cancellation_of(&original);
+        let signature = sender.sign_transaction_sync(&cancellation)?;
+
+        let blocks = self.provider.new_blocks().await?;
+        let max_block = self.block_number().await? + TX_WAIT_MAX_BLOCKS;
+
+        let cancellation = self
+            .send_private_transaction(
+                SendTransactionParams::builder()
+                    .tx(cancellation.rlp_signed(&signature))
+                    .max_block_number(max_block)
+                    .build(),
+            )
+            .await?;
+        info!(original = ?original.hash, cancellation = ?cancellation.hash, "replacing transaction");
+
+        self.replacement_outcome(blocks, original.hash, cancellation.hash, max_block)
+            .await
+    }
+
+    /// Checks, on every block of `blocks` up to `max_block`, whether `cancellation` or `original` landed. See
+    /// [`Self::cancel_by_replacement`].
+    #[cfg(feature = "wait")]
+    async fn replacement_outcome(
+        &self,
+        mut blocks: BlockStream<'_>,
+        original: TxHash,
+        cancellation: TxHash,
+        max_block: U64,
+    ) -> Result<ReplacementOutcome> {
+        use futures::StreamExt;
+
+        loop {
+            if let Some(receipt) = self
+                .provider
+                .get_transaction_receipt(cancellation)
+                .await
+                .map_err(Error::middleware)?
+            {
+                return Ok(ReplacementOutcome::Cancelled(Box::new(receipt)));
+            }
+            if let Some(receipt) = self
+                .provider
+                .get_transaction_receipt(original)
+                .await
+                .map_err(Error::middleware)?
+            {
+                return Ok(ReplacementOutcome::TooLate(Box::new(receipt)));
+            }
+
+            let Some(block) = blocks.next().await else {
+                return Err(Error::BlockStreamEnded);
+            };
+            if block > max_block {
+                return Err(Error::TransactionTimeout(cancellation, block));
+            }
+        }
+    }
+
+    /// See [`Self::send_private_transaction_until`].
+    #[cfg(feature = "wait")]
+    async fn keep_alive(
+        &self,
+        mut blocks: BlockStream<'_>,
+        mut params: SendTransactionParams,
+        timeout: Duration,
+        on_progress: &mut impl FnMut(TransactionProgress<'_>),
+    ) -> Result<(TransactionReceipt, U64)> {
+        use futures::StreamExt;
 
-        let hash: TxHash = self
-            .rpc
-            .post(MevShareRequest::SendPrivateTransaction, [params])
-            .await?;
+        let deadline = Instant::now() + timeout;
+        let current_block = self.block_number().await?;
+        let window = params
+            .max_block_number
+            .map_or(U64::from(TX_WAIT_MAX_BLOCKS), |max_block| {
+                max_block.saturating_sub(current_block).max(U64::one())
+            });
 
-        Ok(PendingTransaction::new(
-            hash,
-            max_block_number,
-            &self.provider,
-        ))
+        let mut max_block = current_block + window;
+        params.max_block_number = Some(max_block);
+        let mut pending = self.send_private_transaction(params.clone()).await?;
+        on_progress(TransactionProgress::Submitted {
+            hash: pending.hash,
+            max_block,
+        });
+
+        while let Some(number) = blocks.next().await {
+            match pending.status().await.map_err(Error::middleware)? {
+                TransactionStatus::Included(receipt, block) => return Ok((*receipt, block)),
+                TransactionStatus::Reverted(receipt) => {
+                    return Err(Error::TransactionRevert(*receipt))
+                }
+                TransactionStatus::Pending | TransactionStatus::Expired => {}
+            }
+
+            if Instant::now() >= deadline {
+                return Err(Error::TransactionTimeout(pending.hash, number));
+            }
+
+            on_progress(TransactionProgress::Pending(number));
+
+            if number >= max_block {
+                max_block = number + window;
+                params.max_block_number = Some(max_block);
+                pending = self.send_private_transaction(params.clone()).await?;
+                on_progress(TransactionProgress::Submitted {
+                    hash: pending.hash,
+                    max_block,
+                });
+            }
+        }
+
+        Err(Error::BlockStreamEnded)
     }
 
     /// Sends a bundle to mev-share.
@@ -193,8 +1341,8 @@ impl MevShareClient<'_> {
     ///     ])
     ///     .inclusion(current_block + 1, Some(current_block + 1 + 20))
     ///     .privacy(
-    ///         Some(set![Hash, Calldata, Logs, FunctionSelector, ContractAddress]),
-    ///         Some(set![Flashbots, Builder::Custom("my own builder")]),
+    ///         [Hint::Hash, Hint::Calldata, Hint::Logs, Hint::FunctionSelector, Hint::ContractAddress],
+    ///         [Builder::Flashbots, Builder::Other("my own builder".to_owned())],
     ///     )
     ///     .build();
     ///
@@ -218,33 +1366,264 @@ impl MevShareClient<'_> {
     ///
     /// # Errors
     ///
+    /// * [`crate::Error::RelayLimit`] if the bundle exceeds the limits of the relay, see [`Self::with_relay_limits`].
+    /// * [`crate::Error::BudgetExceeded`] if sending the bundle would exceed the client [`Budget`].
+    /// * [`crate::Error::QuotaExceeded`] if sending the bundle would exceed the budget of its tenant, see [`Self::with_tenant_budget`].
+    /// * [`crate::Error::Rlp`] if a [`Budget`] is set and any signed transaction in the bundle can't be decoded.
+    /// * [`crate::Error::InvalidParams`] if a [`Budget`] is set and the gas or value of the bundle overflow.
+    /// * [`crate::Error::DuplicateSubmission`] if the bundle was already sent, see [`Self::with_dedup_store`].
+    /// * [`crate::Error::BundleHashMismatch`] if the relay accepted the bundle under another hash than the one computed
+    ///   locally, see [`Self::with_bundle_hash_verification`]. What it reserved is given back, but the bundle may be
+    ///   live at the relay: withdraw it with [`Self::cancel_bundle`] if it was sent with a replacement UUID.
     /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    /// * [`crate::Error::Backend`] if a [`Self::with_backend`] provider fails.
+    /// * [`crate::Error::Timeout`] if the order-flow provider does not respond within [`Self::with_timeout`].
     /// * [`crate::Error::Provider`] if `self.provider` fails to get the [`TransactionReceipt`] for the transactions that or subscribing to blocks to wait for it.
     /// * [`crate::Error::BundleTimeout`] if the bundle is not included in a block before `params.inclusion.max_block`.
     /// * [`crate::Error::BundleRevert`] if any transaction in the bundle reverts.
     /// * [`crate::Error::BundleDiscard`] if the bundle was not included as a whole but some of the transactions in its body were included
     /// (before `params.inclusion.max_block`, otherwise [`crate::Error::BundleTimeout`] will be returned instead).
-    pub async fn send_bundle<'lt>(
-        &'lt self,
-        params: SendBundleParams<'lt>,
-    ) -> Result<PendingBundle> {
-        let send_bundle_response: SendBundleResponse = self
-            .rpc
-            .post(MevShareRequest::SendBundle, [params.clone()])
-            .await?;
+    pub async fn send_bundle(&self, params: SendBundleParams) -> Result<PendingBundle<M>> {
+        self.send_bundle_within(params, self.rpc.timeout()).await
+    }
+
+    /// Like [`Self::send_bundle`], but gives up on the order-flow provider after `timeout` rather than the client
+    /// default, see [`Self::with_timeout`]; e.g. shorter, for a bundle only worth sending this block.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pending_bundle = client
+    ///     .send_bundle_with_timeout(bundle_request, Duration::from_millis(500))
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Timeout`] if the order-flow provider does not respond within `timeout`.
+    /// * Same as [`Self::send_bundle`] otherwise.
+    pub async fn send_bundle_with_timeout(
+        &self,
+        params: SendBundleParams,
+        timeout: Duration,
+    ) -> Result<PendingBundle<M>> {
+        self.send_bundle_within(params, Some(timeout)).await
+    }
+
+    async fn send_bundle_within(
+        &self,
+        mut params: SendBundleParams,
+        timeout: Option<Duration>,
+    ) -> Result<PendingBundle<M>> {
+        let admission = self.admit_bundle(&mut params).await?;
+
+        self.track(Outcome::Sent);
+        let send_bundle_response = match self.backend_send_bundle(params.clone(), timeout).await {
+            Ok(response) => response,
+            Err(err) => {
+                self.reject(admission);
+                return Err(err);
+            }
+        };
+
+        // checked before accepting it, not to keep the budget and the dedup claim for a bundle the caller doesn't get
+        if self.verify_bundle_hash && params.bundle_hash() != send_bundle_response.bundle_hash {
+            self.reject(admission);
+            return Err(Error::BundleHashMismatch(
+                params.bundle_hash(),
+                send_bundle_response.bundle_hash,
+            ));
+        }
+        let permits = self.accept(admission, send_bundle_response.bundle_hash);
 
-        Ok(PendingBundle::new(
+        let mut pending_bundle = PendingBundle::new(
             send_bundle_response.bundle_hash,
             params,
-            &self.provider,
-        ))
+            Arc::clone(&self.provider),
+        );
+        pending_bundle.permits = permits;
+        pending_bundle.tracker.clone_from(&self.tracker);
+        pending_bundle.alerter.clone_from(&self.alerter);
+        pending_bundle.relay = Some((Arc::clone(&self.rpc), self.orders.clone()));
+
+        Ok(pending_bundle)
+    }
+
+    /// Sends a bundle in the legacy `eth_sendBundle` format, e.g. to builders that don't accept `mev_sendBundle`.
+    ///
+    /// The transactions count against the client [`Budget`], if any. Bundles sent with a replacement UUID are tracked
+    /// by the [`Self::cancel_guard`], if any. Always sent to the Flashbots relay, even with [`Self::with_backend`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let bundle = EthSendBundleParams::builder()
+    ///     .txs(vec![signed_tx_1, signed_tx_2])
+    ///     .block_number(block + 1)
+    ///     .build();
+    ///
+    /// let response = client.send_legacy_bundle(bundle).await?;
+    /// debug!(?response.bundle_hash, "relay accepted the bundle");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::BudgetExceeded`] if sending the bundle would exceed the client [`Budget`].
+    /// * [`crate::Error::Rlp`] if a [`Budget`] is set and any transaction of the bundle can't be decoded.
+    /// * [`crate::Error::InvalidParams`] if a [`Budget`] is set and the gas or value of the bundle overflow.
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    pub async fn send_legacy_bundle(
+        &self,
+        params: EthSendBundleParams,
+    ) -> Result<SendBundleResponse> {
+        let admission = self.admit_legacy_bundle(&params).await?;
+
+        self.track(Outcome::Sent);
+        let response: SendBundleResponse = match self
+            .rpc
+            .post(MevShareRequest::SendLegacyBundle, [params])
+            .await
+        {
+            Ok(response) => response,
+            Err(err) => {
+                self.reject(admission);
+                return Err(err.into());
+            }
+        };
+        self.accept(admission, response.bundle_hash);
+
+        Ok(response)
+    }
+
+    /// Withdraws the private transaction `tx_hash` from the relay with `eth_cancelPrivateTransaction`, if it hasn't
+    /// landed yet.
+    ///
+    /// Builders that already received the transaction may still include it; [`Self::cancel_by_replacement`] also
+    /// stops them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pending_tx = client.send_private_transaction(params).await?;
+    ///
+    /// if client.cancel_private_transaction(pending_tx.hash).await?.is_cancelled() {
+    ///     info!(?pending_tx.hash, "withdrawn");
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    pub async fn cancel_private_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> Result<CancelPrivateTransactionResponse> {
+        let response: CancelPrivateTransactionResponse = self
+            .rpc
+            .post(
+                MevShareRequest::CancelPrivateTransaction,
+                [CancelPrivateTransactionParams { tx_hash }],
+            )
+            .await?;
+
+        if response.is_cancelled() {
+            guard::untrack(&self.orders, &Outstanding::Transaction(tx_hash));
+        }
+
+        Ok(response)
+    }
+
+    /// Withdraws the bundles sent with `replacement_uuid`, with [`Self::send_bundle`] or [`Self::send_legacy_bundle`],
+    /// using `eth_cancelBundle`.
+    ///
+    /// Builders that already received the bundles may still include them: cancel early, or send a replacement instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let replacement_uuid = Uuid::new_v4().to_string();
+    /// let bundle = SendBundleParams::builder()
+    ///     .body(body)
+    ///     .inclusion(block + 1, None)
+    ///     .replacement_uuid(replacement_uuid.clone())
+    ///     .build();
+    ///
+    /// client.send_bundle(bundle).await?;
+    /// // the opportunity is gone
+    /// client.cancel_bundle(replacement_uuid).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    pub async fn cancel_bundle(&self, replacement_uuid: impl Into<String>) -> Result<()> {
+        let replacement_uuid = replacement_uuid.into();
+
+        let _: serde_json::Value = self
+            .rpc
+            .post(
+                MevShareRequest::CancelBundle,
+                [CancelBundleParams {
+                    replacement_uuid: replacement_uuid.clone(),
+                }],
+            )
+            .await?;
+        guard::untrack(&self.orders, &Outstanding::Bundle(replacement_uuid));
+
+        Ok(())
+    }
+
+    /// Simulates a legacy bundle with `eth_callBundle`, e.g. to check it before [`Self::send_legacy_bundle`], since
+    /// bundles that fail on-chain simulation hurt the searcher reputation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let simulation = client.call_bundle(EthCallBundleParams::from(&bundle)).await?;
+    /// if let Some(failure) = simulation.first_failure() {
+    ///     warn!(?failure.tx_hash, ?failure.revert, "bundle would fail");
+    /// } else {
+    ///     client.send_legacy_bundle(bundle).await?;
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails.
+    pub async fn call_bundle(&self, params: EthCallBundleParams) -> Result<EthCallBundleResponse> {
+        Ok(self.rpc.post(MevShareRequest::CallBundle, [params]).await?)
+    }
+
+    /// Starts a [`Batch`] of requests, sent to the MEV-Share API as a single signed JSON-RPC request, e.g. to send the
+    /// same bundle targeting several blocks, or to simulate and send it in one round trip.
+    ///
+    /// Bundles and transactions sent in a batch count against the [`Budget`]s and go through the relay limits and the
+    /// dedup store like the ones sent one by one: if any is refused, none of the batch is sent. They are always sent to
+    /// the Flashbots relay, so a client with a [`Self::with_backend`] backend can only batch simulations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut batch = client.batch();
+    /// let simulation = batch.call_bundle(EthCallBundleParams::from(&bundle))?;
+    /// let sent = batch.send_legacy_bundle(bundle)?;
+    ///
+    /// let response = batch.send().await?;
+    /// if response.get(simulation)?.is_success() {
+    ///     debug!(bundle_hash = ?response.get(sent)?.bundle_hash, "relay accepted the bundle");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn batch(&self) -> Batch<'_, M> {
+        Batch::new(self)
     }
 
     /// Simulates a bundle specified by `params`.
     ///
     /// Bundles containing pending transactions (specified by `{hash}` instead of `{tx}` in `params.body`) may
     /// only be simulated after those transactions have landed on chain. If the bundle contains
-    /// pending transactions, this method will wait for the transactions to land before simulating.
+    /// pending transactions, this method will wait for the transactions to land before simulating (requires the `wait` feature,
+    /// enabled by default; otherwise the bundle is simulated as is).
     ///
     /// # Example
     ///
@@ -276,144 +1655,554 @@ impl MevShareClient<'_> {
     /// # Errors
     ///
     /// * [`crate::Error::Rpc`] if any JSON-RPC request to the MEV-Share API fails.
+    /// * [`crate::Error::Backend`] if a [`Self::with_backend`] provider fails.
+    /// * [`crate::Error::Timeout`] if the order-flow provider does not respond within [`Self::with_timeout`].
     /// * [`crate::Error::Provider`] if the provider can't subscribe to the blocks to wait for the unsigned
     /// transactions to land, or fetch the transactions.
     ///
     /// For a more comprehensive example, see [`crate::MevShareClient::send_bundle`].
     pub async fn simulate_bundle(
         &self,
-        mut bundle_params: SendBundleParams<'_>,
-        mut sim_options: SimulateBundleParams,
+        bundle_params: SendBundleParams,
+        sim_options: SimulateBundleParams,
     ) -> Result<SimulateBundleResponse> {
-        if let Some(Body::Tx { hash }) = bundle_params.body.first() {
-            // hash must appear on-chain before simulation is possible
-            let (tx, block_number) = self
-                .provider
-                .wait_for_tx(*hash, bundle_params.inclusion.block + TX_WAIT_MAX_BLOCKS)
-                .await?;
-
-            // replace hash with signed tx
-                            let mut body = bundle_params.body.clone();
-                            body[0] = Body::Signed {
-                                tx: tx.rlp(),
-                                can_revert: false,
-                            };
-
-            bundle_params = SendBundleParams {
-                body,
-                        ..bundle_params.clone()
-            };
-
-            sim_options = SimulateBundleParams {
-                        parent_block: sim_options.parent_block.or(Some(block_number - 1)),
-                        ..sim_options.clone()
-        };
-        }
-
-        self.rpc
-            .post(
-                MevShareRequest::SimBundle,
-                json!([bundle_params, sim_options]),
-            )
+        self.simulate_bundle_within(bundle_params, sim_options, self.rpc.timeout())
             .await
-            .map_err(Into::into)
     }
 
-    /// Gets information about the event history endpoint.
+    /// Like [`Self::simulate_bundle`], but gives up on the order-flow provider after `timeout` rather than the client
+    /// default, see [`Self::with_timeout`]; e.g. longer, for a bundle of many transactions. Waiting for pending
+    /// transactions of the bundle to land does not count against `timeout`.
     ///
     /// # Example
     ///
     /// ```
-    /// let client = MevShareClient::new_with_chain_id(
-    ///     config.auth_wallet.clone(),
-    ///     config.provider.clone(),
-    ///     U256::one(), // EventHistory seems to be only supported on mainnet
-    /// )?;
-    /// let event_history_info = client.get_event_history_info().await?;
+    /// let simulation_result = client
+    ///     .simulate_bundle_with_timeout(bundle_request, sim_options, Duration::from_secs(10))
+    ///     .await?;
+    /// ```
     ///
-    /// let mut page = 0;
-    /// let mut done = false;
+    /// # Errors
     ///
-    /// while !done {
-    ///     let events = client
-    ///         .get_event_history(
-    ///             GetEventHistoryParams::builder()
-    ///                 .limit(event_history_info.max_limit)
-    ///                 .offset(page * event_history_info.max_limit)
-    ///                 .block_start(event_history_info.min_block)
-    ///                 .build(),
-    ///         )
-    ///         .await?;
+    /// * [`crate::Error::Timeout`] if the order-flow provider does not respond within `timeout`.
+    /// * Same as [`Self::simulate_bundle`] otherwise.
+    pub async fn simulate_bundle_with_timeout(
+        &self,
+        bundle_params: SendBundleParams,
+        sim_options: SimulateBundleParams,
+        timeout: Duration,
+    ) -> Result<SimulateBundleResponse> {
+        self.simulate_bundle_within(bundle_params, sim_options, Some(timeout))
+            .await
+    }
+
+    async fn simulate_bundle_within(
+        &self,
+        bundle_params: SendBundleParams,
+        sim_options: SimulateBundleParams,
+        timeout: Option<Duration>,
+    ) -> Result<SimulateBundleResponse> {
+        #[cfg(feature = "wait")]
+        let (bundle_params, sim_options) = self.land_first_leg(bundle_params, sim_options).await?;
+
+        let response = self
+            .backend_simulate_bundle(bundle_params, sim_options, timeout)
+            .await?;
+
+        if response.success {
+            self.track(Outcome::Simulated);
+        }
+
+        Ok(response)
+    }
+
+    /// Waits for the transaction hash leading a bundle to land, and replaces it with the signed transaction:
+    /// hashes must appear on-chain before simulation is possible.
+    #[cfg(feature = "wait")]
+    async fn land_first_leg(
+        &self,
+        bundle_params: SendBundleParams,
+        sim_options: SimulateBundleParams,
+    ) -> Result<(SendBundleParams, SimulateBundleParams)> {
+        let Some(Body::Tx { hash }) = bundle_params.body.first() else {
+            return Ok((bundle_params, sim_options));
+        };
+
+        let (tx, block_number) = self
+            .provider
+            .wait_for_tx(*hash, bundle_params.inclusion.block + TX_WAIT_MAX_BLOCKS)
+            .await?;
+
+        // replace hash with signed tx
+        let mut body = bundle_params.body.clone();
+        body[0] = Body::Signed {
+            tx: tx.rlp(),
+            can_revert: false,
+        };
+
+        Ok((
+            SendBundleParams {
+                body,
+                ..bundle_params
+            },
+            SimulateBundleParams {
+                parent_block: sim_options.parent_block.or(Some(block_number - 1)),
+                ..sim_options
+            },
+        ))
+    }
+
+    /// Returns a [`CancelGuard`] that cancels the orders issued by this client when dropped or on shutdown.
     ///
-    ///     for event in &events {
-    ///         if let Some(txs) = &event.hint.txs && !txs.is_empty() {
-    ///             debug!(?event);
-    ///             debug!(?txs);
-    ///             break;
-    ///         }
-    ///     }
+    /// Private transactions sent after this call are tracked by the guard automatically.
     ///
-    ///     for event in &events {
-    ///         if let Some(logs) = &event.hint.logs && !logs.is_empty() {
-    ///             debug!(?logs);
-    ///             done = true;
-    ///             break;
-    ///         }
-    ///     }
+    /// # Example
     ///
-    ///     page += 1;
-    /// }
     /// ```
+    /// let guard = client.cancel_guard();
+    /// let pending_tx = client.send_private_transaction(tx_request).await?;
     ///
-    /// # Errors
-    ///
-    /// * [`crate::Error::Rest`] if the network GET request to the MEV-Share API fails.
-    pub async fn get_event_history_info(&self) -> Result<EventHistoryInfo> {
-        self.rest.get("history/info").await.map_err(Into::into)
+    /// // `pending_tx` gets cancelled if the bot crashes before it lands
+    /// pending_tx.inclusion().await?;
+    /// guard.forget(&Outstanding::Transaction(pending_tx.hash));
+    /// ```
+    #[must_use]
+    pub fn cancel_guard(&self) -> CancelGuard {
+        CancelGuard::new(self.rpc.detached(), self.orders.clone())
     }
 
-    /// Gets past events that were broadcast via the SSE event stream.
+    /// Returns a [`crate::blocking::MevShareClient`] sending through this client, with a runtime of its own, for code
+    /// that isn't async.
+    ///
+    /// Like the client it returns, must not be called from within an async context. A websocket [`Provider`] keeps
+    /// being driven by the runtime it was connected from, which must outlive the blocking client: outside an async
+    /// application, prefer [`crate::blocking::MevShareClient::connect`], or a provider over HTTP.
     ///
     /// # Example
     ///
     /// ```
-    /// let info = client.get_event_history_info().await?;
-    /// pritnln!("min_block={}, max_limit={}", info.min_block, info.max_limit);
+    /// let provider = Provider::<Http>::try_from("https://rpc.example/api_key")?;
+    /// let client = MevShareClient::new_with_chain_id(auth_wallet, provider, Chain::Mainnet)?.blocking()?;
+    ///
+    /// let (receipt, block) = client
+    ///     .send_private_transaction(SendTransactionParams::builder().tx(signed_tx).build())?
+    ///     .inclusion()?;
     /// ```
     ///
     /// # Errors
     ///
-    /// * [`crate::Error::Rest`] if the network GET request to the MEV-Share API fails.
-    ///
-    /// For a more comprehensive example, see [`crate::MevShareClient::get_event_history_info`].
-    pub async fn get_event_history(
-        &self,
-        params: GetEventHistoryParams,
-    ) -> Result<Vec<EventHistory>> {
-        self.rest
-            .get_with_params("history", params)
-            .await
-            .map_err(Into::into)
+    /// * [`Error::Io`] if the runtime can't be started.
+    #[cfg(feature = "blocking")]
+    pub fn blocking(self) -> Result<crate::blocking::MevShareClient<M>> {
+        crate::blocking::MevShareClient::from_async(self)
     }
-
 }
 
+#[derive(Clone, Copy, Debug)]
 pub enum MevShareRequest {
     SendPrivateTransaction,
     SendBundle,
+    SendLegacyBundle,
+    CallBundle,
     SimBundle,
     GetUserStats,
     GetBundleStats,
+    CancelPrivateTransaction,
+    CancelBundle,
+    GetFeeRefundsByBundle,
+    GetFeeRefundsByBlock,
+    GetFeeRefundsByRecipient,
+    GetFeeRefundTotalsByRecipient,
 }
 
 impl MevShareRequest {
-    pub fn as_method_name(&self) -> &str {
-        match &self {
+    pub fn as_method_name(self) -> &'static str {
+        match self {
             Self::SendPrivateTransaction => "eth_sendPrivateTransaction",
             Self::SendBundle => "mev_sendBundle",
+            Self::SendLegacyBundle => "eth_sendBundle",
+            Self::CallBundle => "eth_callBundle",
             Self::SimBundle => "mev_simBundle",
             Self::GetUserStats => "flashbots_getUserStatsV2",
             Self::GetBundleStats => "flashbots_getBundleStatsV2",
+            Self::CancelPrivateTransaction => "eth_cancelPrivateTransaction",
+            Self::CancelBundle => "eth_cancelBundle",
+            Self::GetFeeRefundsByBundle => "flashbots_getFeeRefundsByBundle",
+            Self::GetFeeRefundsByBlock => "flashbots_getFeeRefundsByBlock",
+            Self::GetFeeRefundsByRecipient => "flashbots_getFeeRefundsByRecipient",
+            Self::GetFeeRefundTotalsByRecipient => "flashbots_getFeeRefundTotalsByRecipient",
+        }
+    }
+
+    /// Whether sending the request twice has the same effect as sending it once, so that it is safe to retry.
+    pub fn is_idempotent(self) -> bool {
+        !matches!(
+            self,
+            Self::SendPrivateTransaction | Self::SendBundle | Self::SendLegacyBundle
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clone() {
+        fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+        assert_shareable::<MevShareClient>();
+
+        let (provider, _) = Provider::mocked();
+        let client = MevShareClient::new_with_network(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+            MevShareNetwork::try_from(5_u64).unwrap(),
+        );
+
+        // clones track the orders of the client, from any thread
+        let clone = client.clone();
+        std::thread::spawn(move || {
+            let guard = clone.cancel_guard();
+            guard.track_bundle("replacement-uuid");
+            guard.disarm();
+        })
+        .join()
+        .unwrap();
+
+        let guard = client.cancel_guard();
+        assert_eq!(
+            guard.outstanding(),
+            vec![Outstanding::Bundle("replacement-uuid".to_owned())]
+        );
+        guard.disarm();
+
+        // but are reconfigured on their own
+        let proxied = client.clone().with_api_url("http://localhost:8080");
+        assert_eq!(proxied.network().api_url(), "http://localhost:8080");
+        assert_eq!(
+            client.network().api_url(),
+            "https://relay-goerli.flashbots.net"
+        );
+    }
+
+    #[cfg(feature = "wait")]
+    #[test]
+    fn test_replacement_block_stream_ended() {
+        use futures::StreamExt;
+
+        let (provider, mock) = Provider::mocked();
+        // neither the original nor the cancellation landed
+        mock.push(serde_json::Value::Null).unwrap();
+        mock.push(serde_json::Value::Null).unwrap();
+        let client = MevShareClient::new_with_network(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+            MevShareNetwork::try_from(5_u64).unwrap(),
+        );
+
+        let outcome = futures::executor::block_on(client.replacement_outcome(
+            futures::stream::empty().boxed(),
+            TxHash::repeat_byte(1),
+            TxHash::repeat_byte(2),
+            100.into(),
+        ));
+        assert!(matches!(outcome, Err(Error::BlockStreamEnded)));
+    }
+
+    #[cfg(feature = "wait")]
+    #[tokio::test]
+    async fn test_keep_alive_block_stream_ended() {
+        use crate::helpers::{json_rpc_response, mock_relay};
+        use futures::StreamExt;
+
+        let (url, _) = mock_relay(|_| {
+            Some(json_rpc_response(&format!(
+                "\"{:?}\"",
+                TxHash::repeat_byte(1)
+            )))
+        })
+        .await;
+        let (provider, mock) = Provider::mocked();
+        mock.push(U64::from(10)).unwrap();
+        let client = MevShareClient::new_with_network(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+            MevShareNetwork::try_from(5_u64).unwrap(),
+        )
+        .with_api_url(url);
+
+        let mut submitted = Vec::new();
+        let result = client
+            .keep_alive(
+                futures::stream::empty().boxed(),
+                SendTransactionParams::builder().tx(Bytes::new()).build(),
+                Duration::from_secs(60),
+                &mut |progress| {
+                    if let TransactionProgress::Submitted { hash, max_block } = progress {
+                        submitted.push((hash, max_block));
+                    }
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::BlockStreamEnded)));
+        assert_eq!(
+            submitted,
+            [(TxHash::repeat_byte(1), U64::from(10 + TX_WAIT_MAX_BLOCKS))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dedup_store_failures() {
+        use crate::helpers::{http_response, json_rpc_response, mock_relay};
+
+        /// A store that claims, then fails to record or release anything.
+        struct FailingStore;
+
+        impl DedupStore for FailingStore {
+            fn claim(&self, _: TxHash, _: u64, _: u64) -> Result<bool> {
+                Ok(true)
+            }
+
+            fn confirm(&self, _: TxHash, _: TxHash) -> Result<()> {
+                Err(Error::InvalidParams("store down".to_owned()))
+            }
+
+            fn release(&self, _: TxHash) -> Result<()> {
+                Err(Error::InvalidParams("store down".to_owned()))
+            }
+        }
+
+        let (url, _) = mock_relay(|n| {
+            Some(match n {
+                0 => json_rpc_response(&format!(
+                    r#"{{"bundleHash":"{:?}"}}"#,
+                    TxHash::repeat_byte(1)
+                )),
+                _ => http_response("503 Service Unavailable", ""),
+            })
+        })
+        .await;
+        let (provider, _) = Provider::mocked();
+        let client = MevShareClient::new_with_network(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+            MevShareNetwork::try_from(5_u64).unwrap(),
+        )
+        .with_api_url(url)
+        .with_dedup_store(FailingStore, Duration::from_secs(60));
+
+        let bundle = |block: u64| {
+            SendBundleParams::builder()
+                .body(vec![Body::Tx {
+                    hash: TxHash::repeat_byte(2),
+                }])
+                .inclusion(block, None)
+                .build()
+        };
+
+        // the relay accepted the bundle: it is pending whatever the store does
+        let pending_bundle = client.send_bundle(bundle(1)).await.unwrap();
+        assert_eq!(pending_bundle.hash, TxHash::repeat_byte(1));
+
+        // the relay refused it: its error is the one returned
+        assert!(matches!(
+            client.send_bundle(bundle(2)).await,
+            Err(Error::Rpc(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_bundle_hash_mismatch() {
+        use crate::helpers::{json_rpc_response, mock_relay};
+
+        let (url, _) = mock_relay(|_| {
+            Some(json_rpc_response(&format!(
+                r#"{{"bundleHash":"{:?}"}}"#,
+                TxHash::repeat_byte(1)
+            )))
+        })
+        .await;
+        let (provider, _) = Provider::mocked();
+        let client = MevShareClient::new_with_network(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+            MevShareNetwork::try_from(5_u64).unwrap(),
+        )
+        .with_api_url(url)
+        .with_bundle_hash_verification()
+        .with_budget(Budget::builder().max_submissions(1).build());
+
+        let bundle = SendBundleParams::builder()
+            .body(vec![Body::Tx {
+                hash: TxHash::repeat_byte(2),
+            }])
+            .inclusion(1, None)
+            .build();
+
+        // the budget is given back: the second send fails the same way, not over budget
+        for _ in 0..2 {
+            assert!(matches!(
+                client.send_bundle(bundle.clone()).await,
+                Err(Error::BundleHashMismatch(_, returned)) if returned == TxHash::repeat_byte(1)
+            ));
+        }
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_compressed_responses() {
+        use crate::helpers::mock_relay;
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let gzipped = |content_type: &str, body: &str| {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(body.as_bytes()).unwrap();
+            let body = encoder.finish().unwrap();
+
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: {content_type}\r\n\
+                 Content-Encoding: gzip\r\nContent-Length: {}\r\n\r\n",
+                body.len()
+            )
+            .into_bytes();
+            response.extend(body);
+            response
+        };
+        let info = json!({
+            "minBlock": 1,
+            "maxBlock": 10,
+            "minTimestamp": 0,
+            "maxTimestamp": 0,
+            "count": 3,
+            "maxLimit": 2,
+        });
+        let event = json!({ "hash": TxHash::repeat_byte(1), "logs": null, "txs": null });
+
+        // /history/info, then the event stream, reconnected to as many times as needed
+        let (url, mut requests) = mock_relay(move |n| {
+            Some(match n {
+                0 => gzipped("application/json", &info.to_string()),
+                _ => gzipped("text/event-stream", &format!("data: {event}\n\n")),
+            })
+        })
+        .await;
+        let (provider, _) = Provider::mocked();
+        let client = MevShareClient::new_with_network(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+            MevShareNetwork::try_from(1_u64).unwrap(),
+        )
+        .with_stream_url(url);
+
+        assert_eq!(client.get_event_history_info().await.unwrap().count, 3);
+        assert!(requests
+            .recv()
+            .await
+            .unwrap()
+            .contains("accept-encoding: gzip"));
+
+        // with the default client, then with an injected one
+        let injected = client.clone().with_http_client(reqwest::Client::new());
+        for client in [&client, &injected] {
+            let event = client.subscribe_bundles().next().await.unwrap().unwrap();
+            assert_eq!(event.hash, TxHash::repeat_byte(1));
         }
+        assert!(requests
+            .recv()
+            .await
+            .unwrap()
+            .contains("accept-encoding: gzip"));
+    }
+
+    #[tokio::test]
+    async fn test_untracked_without_guard() {
+        use crate::helpers::{json_rpc_response, mock_relay};
+
+        let (url, _) = mock_relay(|_| {
+            Some(json_rpc_response(&format!(
+                "\"{:?}\"",
+                TxHash::repeat_byte(1)
+            )))
+        })
+        .await;
+        let (provider, _) = Provider::mocked();
+        let client = MevShareClient::new_with_network(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+            MevShareNetwork::try_from(5_u64).unwrap(),
+        )
+        .with_api_url(url);
+
+        // clones and pending transactions share the orders, but nobody can cancel them without a guard
+        let clone = client.clone();
+        let pending_tx = clone
+            .send_private_transaction(SendTransactionParams::builder().tx(Bytes::new()).build())
+            .await
+            .unwrap();
+
+        let guard = client.cancel_guard();
+        assert!(guard.outstanding().is_empty());
+
+        // from then on they are tracked
+        clone
+            .send_private_transaction(SendTransactionParams::builder().tx(Bytes::new()).build())
+            .await
+            .unwrap();
+        assert_eq!(
+            guard.outstanding(),
+            vec![Outstanding::Transaction(pending_tx.hash)]
+        );
+        guard.disarm();
+    }
+
+    #[tokio::test]
+    async fn test_protected_transaction_builders() {
+        use crate::helpers::{json_rpc_response, mock_relay};
+
+        let (url, mut requests) = mock_relay(|_| {
+            Some(json_rpc_response(&format!(
+                "\"{:?}\"",
+                TxHash::repeat_byte(1)
+            )))
+        })
+        .await;
+        let (provider, _) = Provider::mocked();
+        let client = MevShareClient::new_with_network(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+            MevShareNetwork::try_from(5_u64).unwrap(),
+        )
+        .with_api_url(url);
+
+        client
+            .send_protected_transaction(Bytes::new(), [])
+            .await
+            .unwrap();
+
+        let request = requests.recv().await.unwrap();
+        let (_, body) = request.split_once("\r\n\r\n").unwrap();
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        let mut builders: Vec<_> = body["params"][0]["preferences"]["privacy"]["builders"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|builder| builder.as_str().unwrap().to_owned())
+            .collect();
+        builders.sort();
+
+        let mut known: Vec<_> = Builder::KNOWN
+            .iter()
+            .map(|builder| {
+                serde_json::to_value(builder)
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_lowercase()
+            })
+            .collect();
+        known.sort();
+        assert_eq!(builders, known);
     }
 }
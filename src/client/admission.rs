@@ -0,0 +1,294 @@
+use super::MevShareClient;
+use crate::api::batch::{BatchId, BatchResponse, BatchedSend};
+use crate::api::rpc_client::MevShareRpcClient;
+use crate::api::types::{
+    EthSendBundleParams, JsonRpcRequest, Metadata, SendBundleParams, SendBundleResponse,
+};
+use crate::budget::{self, BudgetLimiter, InFlightPermit, Reservations, Spend};
+use crate::dedup;
+use crate::guard::{self, Outstanding};
+use crate::helpers::provider::Waiter;
+use crate::helpers::system_now;
+use crate::tracker::Outcome;
+use crate::{Error, Result};
+use ethers::types::{Bytes, TxHash};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use tracing::warn;
+
+/// What an order reserved on its way to the relay: kept with [`MevShareClient::accept`] if the relay accepts it,
+/// given back with [`MevShareClient::reject`] otherwise.
+///
+/// Shared by the single sends and the sends of a [`crate::Batch`], so that both go through the same budgets, limits
+/// and dedup store, and are tracked the same way.
+#[must_use]
+pub(crate) struct Admission {
+    reservations: Reservations,
+    permits: Vec<InFlightPermit>,
+    /// Key claimed in the dedup store.
+    claim: Option<TxHash>,
+    order: Order,
+}
+
+/// The kind of order admitted, to track it once accepted.
+enum Order {
+    Transaction,
+    Bundle { replacement_uuid: Option<String> },
+}
+
+impl<M: Waiter> MevShareClient<M> {
+    /// Admits the bundle `params`: applies the default privacy, checks the relay limits, reserves it against the
+    /// client and tenant budgets, and claims it in the dedup store.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::RelayLimit`] if the bundle exceeds the [`Self::with_relay_limits`] limits.
+    /// * [`Error::BudgetExceeded`] or [`Error::QuotaExceeded`] if it would exceed a budget.
+    /// * [`Error::DuplicateSubmission`] if the dedup store already has it.
+    pub(crate) async fn admit_bundle(&self, params: &mut SendBundleParams) -> Result<Admission> {
+        self.apply_default_privacy(params);
+
+        if let Some(limits) = &self.limits {
+            let current_block = match limits.max_block_distance {
+                Some(_) => Some(self.block_number().await?),
+                None => None,
+            };
+            params.check_limits(limits, current_block)?;
+        }
+
+        let tenant = match (&self.tenants, &params.metadata) {
+            (
+                Some(tenants),
+                Some(Metadata {
+                    origin_id: Some(tenant),
+                }),
+            ) => Some(tenants.get(tenant)),
+            _ => None,
+        };
+
+        let limiters: Vec<Arc<BudgetLimiter>> = self.budget.iter().cloned().chain(tenant).collect();
+        let (permits, reservations) = if limiters.is_empty() {
+            (Vec::new(), Vec::new())
+        } else {
+            match budget::admit_bundle(&limiters, Spend::of_body(&params.body)?) {
+                Ok(admitted) => admitted,
+                Err(err) => {
+                    self.alert_budget(&err).await;
+                    return Err(err);
+                }
+            }
+        };
+
+        let claim = match self.claim(params) {
+            Ok(claim) => claim,
+            Err(err) => {
+                budget::refund_all(reservations);
+                return Err(err);
+            }
+        };
+
+        Ok(Admission {
+            reservations,
+            permits,
+            claim,
+            order: Order::Bundle {
+                replacement_uuid: params.replacement_uuid.clone(),
+            },
+        })
+    }
+
+    /// Admits the legacy bundle `params`, reserving its transactions against the client budget.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::BudgetExceeded`] if it would exceed the client budget.
+    /// * [`Error::Rlp`] if a budget is set and a transaction can't be decoded.
+    /// * [`Error::InvalidParams`] if a budget is set and the gas or value of the bundle overflow.
+    pub(crate) async fn admit_legacy_bundle(
+        &self,
+        params: &EthSendBundleParams,
+    ) -> Result<Admission> {
+        Ok(Admission {
+            reservations: self.reserve(Spend::of_transactions(&params.txs)).await?,
+            permits: Vec::new(),
+            claim: None,
+            order: Order::Bundle {
+                replacement_uuid: params.replacement_uuid.clone(),
+            },
+        })
+    }
+
+    /// Admits the private transaction `tx`, reserving it against the client budget.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::BudgetExceeded`] if it would exceed the client budget.
+    /// * [`Error::Rlp`] if a budget is set and `tx` can't be decoded.
+    /// * [`Error::InvalidParams`] if a budget is set and the value and fees of `tx` overflow.
+    pub(crate) async fn admit_transaction(&self, tx: &Bytes) -> Result<Admission> {
+        Ok(Admission {
+            reservations: self.reserve(Spend::of_transaction(tx)).await?,
+            permits: Vec::new(),
+            claim: None,
+            order: Order::Transaction,
+        })
+    }
+
+    /// Keeps what `admission` reserved for an order the relay accepted as `hash`, tracks the order, and returns the
+    /// in-flight permits it holds.
+    ///
+    /// The order is live at the relay whatever the [`Self::with_dedup_store`] store does: failing to record it there
+    /// is only logged.
+    pub(crate) fn accept(&self, admission: Admission, hash: TxHash) -> Vec<InFlightPermit> {
+        self.track(Outcome::Accepted);
+        if let Some((store, _)) = &self.dedup
+            && let Some(key) = admission.claim
+            && let Err(err) = store.confirm(key, hash)
+        {
+            warn!(?key, ?hash, %err, "failed to record the submission in the dedup store");
+        }
+
+        match admission.order {
+            Order::Transaction => guard::track(&self.orders, Outstanding::Transaction(hash)),
+            Order::Bundle {
+                replacement_uuid: Some(replacement_uuid),
+            } => guard::track(&self.orders, Outstanding::Bundle(replacement_uuid)),
+            Order::Bundle {
+                replacement_uuid: None,
+            } => {}
+        }
+
+        admission.permits
+    }
+
+    /// Gives back what `admission` reserved for an order the relay refused, or that wasn't sent.
+    ///
+    /// Failing to release the claim in the [`Self::with_dedup_store`] store is only logged, not to hide why the order
+    /// was refused: the claim then expires with its TTL.
+    pub(crate) fn reject(&self, admission: Admission) {
+        budget::refund_all(admission.reservations);
+        if let Some((store, _)) = &self.dedup
+            && let Some(key) = admission.claim
+            && let Err(err) = store.release(key)
+        {
+            warn!(?key, %err, "failed to release the submission in the dedup store");
+        }
+    }
+
+    /// Sends `requests` as a batch, once `sends`, the bundles and transactions among them, are all admitted. See
+    /// [`crate::Batch::send`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidParams`] if there are `sends` and the client has a [`Self::with_backend`] backend.
+    /// * Same as the `admit_*` functions if a send is refused; nothing is sent then.
+    /// * [`Error::Rpc`] if the JSON-RPC request to the MEV-Share API fails as a whole.
+    pub(crate) async fn send_batch(
+        &self,
+        requests: &[JsonRpcRequest<'_>],
+        sends: Vec<(i32, BatchedSend)>,
+    ) -> Result<BatchResponse> {
+        if !sends.is_empty() && self.backend.is_some() {
+            return Err(Error::InvalidParams(
+                "bundles and transactions can't be batched through a backend".to_owned(),
+            ));
+        }
+
+        let mut admissions = Vec::with_capacity(sends.len());
+        for (id, send) in sends {
+            let is_transaction = matches!(send, BatchedSend::Transaction(_));
+            let admitted = match send {
+                BatchedSend::Bundle(mut params) => self.admit_bundle(&mut params).await,
+                BatchedSend::LegacyBundle(params) => self.admit_legacy_bundle(&params).await,
+                BatchedSend::Transaction(tx) => self.admit_transaction(&tx).await,
+            };
+
+            match admitted {
+                Ok(admission) => admissions.push((id, is_transaction, admission)),
+                Err(err) => {
+                    for (_, _, admission) in admissions {
+                        self.reject(admission);
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
+        for _ in &admissions {
+            self.track(Outcome::Sent);
+        }
+        let response = match self.rpc.post_batch(requests).await {
+            Ok(response) => response,
+            Err(err) => {
+                for (_, _, admission) in admissions {
+                    self.reject(admission);
+                }
+                return Err(err.into());
+            }
+        };
+
+        let mut permits = Vec::new();
+        for (id, is_transaction, admission) in admissions {
+            let hash = if is_transaction {
+                response.get(BatchId::<TxHash>::new(id))
+            } else {
+                response
+                    .get(BatchId::<SendBundleResponse>::new(id))
+                    .map(|response| response.bundle_hash)
+            };
+
+            match hash {
+                Ok(hash) => permits.extend(self.accept(admission, hash)),
+                Err(_) => self.reject(admission),
+            }
+        }
+
+        Ok(response.with_permits(permits))
+    }
+
+    /// Sets the privacy of `params` to the [`Self::with_default_privacy`] one, if it has none.
+    pub(crate) fn apply_default_privacy(&self, params: &mut SendBundleParams) {
+        if params.privacy.is_none() {
+            params.privacy.clone_from(&self.default_privacy);
+        }
+    }
+
+    /// The JSON-RPC client of the Flashbots relay.
+    pub(crate) fn rpc(&self) -> &MevShareRpcClient {
+        &self.rpc
+    }
+
+    /// Reserves `spend` against the client budget, if any.
+    async fn reserve(&self, spend: Result<Spend>) -> Result<Reservations> {
+        let Some(budget) = &self.budget else {
+            return Ok(Vec::new());
+        };
+
+        match budget.reserve(spend?) {
+            Ok(reservation) => Ok(vec![(Arc::clone(budget), reservation)]),
+            Err(err) => {
+                self.alert_budget(&err).await;
+                Err(err)
+            }
+        }
+    }
+
+    /// Claims `params` in the [`Self::with_dedup_store`] store, if any, and returns the claimed key.
+    fn claim(&self, params: &SendBundleParams) -> Result<Option<TxHash>> {
+        let Some((store, ttl)) = &self.dedup else {
+            return Ok(None);
+        };
+
+        let key = dedup::submission_key(params)?;
+        let now = system_now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        if !store.claim(key, now, now + ttl.as_secs())? {
+            return Err(Error::DuplicateSubmission(key));
+        }
+
+        Ok(Some(key))
+    }
+}
@@ -0,0 +1,307 @@
+use super::MevShareClient;
+#[cfg(feature = "stream")]
+use crate::api::types::MevShareEvent;
+use crate::api::types::{
+    BundleStats, PendingBundle, PendingTransaction, SendBundleParams, SendTransactionParams,
+    SimulateBundleParams, SimulateBundleResponse, UserStats,
+};
+#[cfg(feature = "history")]
+use crate::api::types::{EventHistory, EventHistoryInfo, GetEventHistoryParams};
+use crate::helpers::provider::Waiter;
+use crate::Result;
+use async_trait::async_trait;
+use ethers::providers::{Provider, Ws};
+use ethers::types::{TxHash, U64};
+#[cfg(all(feature = "stream", not(target_arch = "wasm32")))]
+use futures::stream::BoxStream;
+#[cfg(all(feature = "stream", target_arch = "wasm32"))]
+use futures::stream::LocalBoxStream;
+#[cfg(feature = "stream")]
+use futures::stream::StreamExt;
+
+/// Stream of the events of [`MevShareApi::subscribe_bundles`].
+#[cfg(all(feature = "stream", not(target_arch = "wasm32")))]
+pub type EventStream<'a> = BoxStream<'a, Result<MevShareEvent>>;
+
+/// Stream of the events of [`MevShareApi::subscribe_bundles`].
+#[cfg(all(feature = "stream", target_arch = "wasm32"))]
+pub type EventStream<'a> = LocalBoxStream<'a, Result<MevShareEvent>>;
+
+/// What a searcher does with MEV-Share: send and simulate orders, stream and query the hints, and check its stats.
+///
+/// Implemented by [`MevShareClient`]. Code taking a `&impl MevShareApi`, or an `Arc<dyn MevShareApi>`, rather than the
+/// client can be unit tested against a mock returning canned responses, without a relay. Pending bundles and
+/// transactions are built with [`PendingBundle::new`] and [`PendingTransaction::new`], e.g. on top of a
+/// [`ethers::providers::MockProvider`].
+///
+/// The methods behave like the [`MevShareClient`] ones of the same name, see their documentation.
+///
+/// # Example
+///
+/// ```
+/// async fn backrun(api: &impl MevShareApi, event: MevShareEvent, backrun: Body) -> Result<TxHash> {
+///     let bundle = SendBundleParams::builder()
+///         .body(vec![Body::Tx { hash: event.hash }, backrun])
+///         .inclusion(block, None)
+///         .build();
+///     let simulation = api.simulate_bundle(bundle.clone(), SimulateBundleParams::default()).await?;
+///     ...
+///     Ok(api.send_bundle(bundle).await?.hash)
+/// }
+///
+/// struct MockApi { sent: Mutex<Vec<SendBundleParams>>, provider: Arc<Provider<MockProvider>> }
+///
+/// #[async_trait]
+/// impl MevShareApi<Provider<MockProvider>> for MockApi {
+///     async fn send_bundle(&self, params: SendBundleParams) -> Result<PendingBundle<Provider<MockProvider>>> {
+///         self.sent.lock().unwrap().push(params.clone());
+///         Ok(PendingBundle::new(TxHash::zero(), params, self.provider.clone()))
+///     }
+///
+///     ...
+/// }
+/// ```
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait MevShareApi<M = Provider<Ws>>: Send + Sync {
+    /// Sends a bundle, see [`MevShareClient::send_bundle`].
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`MevShareClient::send_bundle`].
+    async fn send_bundle(&self, params: SendBundleParams) -> Result<PendingBundle<M>>;
+
+    /// Sends a signed transaction privately, see [`MevShareClient::send_private_transaction`].
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`MevShareClient::send_private_transaction`].
+    async fn send_private_transaction(
+        &self,
+        params: SendTransactionParams,
+    ) -> Result<PendingTransaction<M>>;
+
+    /// Simulates a bundle, see [`MevShareClient::simulate_bundle`].
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`MevShareClient::simulate_bundle`].
+    async fn simulate_bundle(
+        &self,
+        bundle_params: SendBundleParams,
+        sim_options: SimulateBundleParams,
+    ) -> Result<SimulateBundleResponse>;
+
+    /// Streams the hints of the MEV-Share event stream, see [`MevShareClient::subscribe_bundles`].
+    #[cfg(feature = "stream")]
+    fn subscribe_bundles(&self) -> EventStream<'_>;
+
+    /// Returns the bounds of the event history, see [`MevShareClient::get_event_history_info`].
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`MevShareClient::get_event_history_info`].
+    #[cfg(feature = "history")]
+    async fn get_event_history_info(&self) -> Result<EventHistoryInfo>;
+
+    /// Returns past hints of the event stream, see [`MevShareClient::get_event_history`].
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`MevShareClient::get_event_history`].
+    #[cfg(feature = "history")]
+    async fn get_event_history(&self, params: GetEventHistoryParams) -> Result<Vec<EventHistory>>;
+
+    /// Returns the reputation of the searcher as of `block`, see [`MevShareClient::get_user_stats`].
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`MevShareClient::get_user_stats`].
+    async fn get_user_stats(&self, block: U64) -> Result<UserStats>;
+
+    /// Returns what the relay knows about the bundle `bundle_hash` targeting `block`, see
+    /// [`MevShareClient::get_bundle_stats`].
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`MevShareClient::get_bundle_stats`].
+    async fn get_bundle_stats(&self, bundle_hash: TxHash, block: U64) -> Result<BundleStats>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M: Waiter> MevShareApi<M> for MevShareClient<M> {
+    async fn send_bundle(&self, params: SendBundleParams) -> Result<PendingBundle<M>> {
+        self.send_bundle(params).await
+    }
+
+    async fn send_private_transaction(
+        &self,
+        params: SendTransactionParams,
+    ) -> Result<PendingTransaction<M>> {
+        self.send_private_transaction(params).await
+    }
+
+    async fn simulate_bundle(
+        &self,
+        bundle_params: SendBundleParams,
+        sim_options: SimulateBundleParams,
+    ) -> Result<SimulateBundleResponse> {
+        self.simulate_bundle(bundle_params, sim_options).await
+    }
+
+    #[cfg(all(feature = "stream", not(target_arch = "wasm32")))]
+    fn subscribe_bundles(&self) -> EventStream<'_> {
+        self.subscribe_bundles().boxed()
+    }
+
+    #[cfg(all(feature = "stream", target_arch = "wasm32"))]
+    fn subscribe_bundles(&self) -> EventStream<'_> {
+        self.subscribe_bundles().boxed_local()
+    }
+
+    #[cfg(feature = "history")]
+    async fn get_event_history_info(&self) -> Result<EventHistoryInfo> {
+        self.get_event_history_info().await
+    }
+
+    #[cfg(feature = "history")]
+    async fn get_event_history(&self, params: GetEventHistoryParams) -> Result<Vec<EventHistory>> {
+        self.get_event_history(params).await
+    }
+
+    async fn get_user_stats(&self, block: U64) -> Result<UserStats> {
+        self.get_user_stats(block).await
+    }
+
+    async fn get_bundle_stats(&self, bundle_hash: TxHash, block: U64) -> Result<BundleStats> {
+        self.get_bundle_stats(bundle_hash, block).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::{Body, Inclusion};
+    use crate::Error;
+    use ethers::providers::MockProvider;
+    use ethers::types::U256;
+    use std::sync::{Arc, Mutex};
+
+    type Mocked = Provider<MockProvider>;
+
+    /// Accepts and records every bundle; bundles simulate with a profit of 1 wei per transaction after the first.
+    struct MockApi {
+        provider: Arc<Mocked>,
+        sent: Mutex<Vec<SendBundleParams>>,
+    }
+
+    #[async_trait]
+    impl MevShareApi<Mocked> for MockApi {
+        async fn send_bundle(&self, params: SendBundleParams) -> Result<PendingBundle<Mocked>> {
+            self.sent.lock().unwrap().push(params.clone());
+            Ok(PendingBundle::new(
+                TxHash::repeat_byte(1),
+                params,
+                self.provider.clone(),
+            ))
+        }
+
+        async fn send_private_transaction(
+            &self,
+            _: SendTransactionParams,
+        ) -> Result<PendingTransaction<Mocked>> {
+            Err(Error::InvalidParams("not mocked".to_owned()))
+        }
+
+        async fn simulate_bundle(
+            &self,
+            bundle_params: SendBundleParams,
+            _: SimulateBundleParams,
+        ) -> Result<SimulateBundleResponse> {
+            Ok(SimulateBundleResponse {
+                success: true,
+                error: None,
+                state_block: 1.into(),
+                mev_gas_price: U256::zero(),
+                profit: (bundle_params.body.len() - 1).into(),
+                refundable_value: U256::zero(),
+                gas_used: 21_000.into(),
+                logs: Vec::new(),
+            })
+        }
+
+        #[cfg(feature = "stream")]
+        fn subscribe_bundles(&self) -> EventStream<'_> {
+            futures::stream::empty().boxed()
+        }
+
+        #[cfg(feature = "history")]
+        async fn get_event_history_info(&self) -> Result<EventHistoryInfo> {
+            Err(Error::InvalidParams("not mocked".to_owned()))
+        }
+
+        #[cfg(feature = "history")]
+        async fn get_event_history(&self, _: GetEventHistoryParams) -> Result<Vec<EventHistory>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_user_stats(&self, _: U64) -> Result<UserStats> {
+            Err(Error::InvalidParams("not mocked".to_owned()))
+        }
+
+        async fn get_bundle_stats(&self, _: TxHash, _: U64) -> Result<BundleStats> {
+            Err(Error::InvalidParams("not mocked".to_owned()))
+        }
+    }
+
+    /// Code under test: sends `body` only if it simulates with a profit.
+    async fn send_if_profitable<M>(
+        api: &dyn MevShareApi<M>,
+        body: Vec<Body>,
+    ) -> Result<Option<TxHash>> {
+        let bundle = SendBundleParams {
+            body,
+            inclusion: Inclusion {
+                block: 1.into(),
+                max_block: None,
+            },
+            ..Default::default()
+        };
+        let simulation = api
+            .simulate_bundle(bundle.clone(), SimulateBundleParams::default())
+            .await?;
+        if simulation.profit.is_zero() {
+            return Ok(None);
+        }
+        Ok(Some(api.send_bundle(bundle).await?.hash))
+    }
+
+    #[test]
+    fn test_mock() {
+        fn assert_api<M: Waiter>(_: &dyn MevShareApi<M>) {}
+        let (provider, _) = Provider::mocked();
+        let client = MevShareClient::new_with_network(
+            ethers::signers::LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider.clone(),
+            crate::MevShareNetwork::try_from(5_u64).unwrap(),
+        );
+        assert_api(&client);
+
+        let api = MockApi {
+            provider: Arc::new(provider),
+            sent: Mutex::new(Vec::new()),
+        };
+        let tx = |hash| Body::Tx {
+            hash: TxHash::repeat_byte(hash),
+        };
+
+        let unprofitable = futures::executor::block_on(send_if_profitable(&api, vec![tx(2)]));
+        assert_eq!(unprofitable.unwrap(), None);
+        assert!(api.sent.lock().unwrap().is_empty());
+
+        let profitable = futures::executor::block_on(send_if_profitable(&api, vec![tx(2), tx(3)]));
+        assert_eq!(profitable.unwrap(), Some(TxHash::repeat_byte(1)));
+        assert_eq!(api.sent.lock().unwrap().len(), 1);
+    }
+}
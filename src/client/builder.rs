@@ -0,0 +1,290 @@
+use super::MevShareClient;
+use crate::api::networks::{MevShareNetwork, NetworkTable};
+use crate::api::types::Privacy;
+use crate::helpers::provider::Waiter;
+use crate::layer::RpcLayer;
+use crate::retry::RetryPolicy;
+use crate::signer::AuthSigner;
+use crate::{Error, Result};
+use ethers::providers::{Provider, Ws};
+use reqwest::header::HeaderMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Configures a [`MevShareClient`] in one place, see [`MevShareClient::builder`].
+///
+/// Every setting is optional: by default, the client is the one [`MevShareClient::new`] returns, on the Flashbots
+/// network of the chain served by the provider.
+///
+/// # Example
+///
+/// ```
+/// let client = MevShareClient::builder(auth_wallet, provider)
+///     .api_url("https://relay-proxy.internal")
+///     .default_privacy(Privacy::new([Hint::Hash], Builder::KNOWN))
+///     .user_agent("my-searcher/0.3")
+///     .timeout(Duration::from_secs(2))
+///     .retry_policy(RetryPolicy::default())
+///     .stream_buffer(1024)
+///     .build()
+///     .await?;
+/// ```
+pub struct MevShareClientBuilder<M = Provider<Ws>> {
+    auth: AuthSigner,
+    provider: M,
+    network: Option<MevShareNetwork>,
+    networks: Option<NetworkTable>,
+    api_url: Option<String>,
+    stream_url: Option<String>,
+    default_privacy: Option<Privacy>,
+    user_agent: Option<String>,
+    headers: HeaderMap,
+    http: Option<reqwest::Client>,
+    timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    layers: Vec<Arc<dyn RpcLayer>>,
+    #[cfg(all(feature = "stream", feature = "tokio"))]
+    stream_buffer: usize,
+}
+
+impl<M: Waiter> MevShareClient<M> {
+    /// Returns a [`MevShareClientBuilder`] to configure a client signing its requests with `auth_wallet` and going
+    /// on-chain through `provider`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::builder(auth_wallet, provider)
+    ///     .network(MevShareNetwork::try_from(Chain::Goerli)?)
+    ///     .build()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn builder(auth_wallet: impl Into<AuthSigner>, provider: M) -> MevShareClientBuilder<M> {
+        MevShareClientBuilder {
+            auth: auth_wallet.into(),
+            provider,
+            network: None,
+            networks: None,
+            api_url: None,
+            stream_url: None,
+            default_privacy: None,
+            user_agent: None,
+            headers: HeaderMap::new(),
+            http: None,
+            timeout: None,
+            retry_policy: None,
+            layers: Vec::new(),
+            #[cfg(all(feature = "stream", feature = "tokio"))]
+            stream_buffer: 0,
+        }
+    }
+}
+
+impl<M: Waiter> MevShareClientBuilder<M> {
+    /// Uses `network`, rather than the network of the chain served by the provider. See
+    /// [`MevShareClient::new_with_network`].
+    #[must_use]
+    pub fn network(mut self, network: MevShareNetwork) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// Looks the network of the chain served by the provider up in `networks`, rather than among the Flashbots ones.
+    /// See [`MevShareClient::new_with_networks`].
+    #[must_use]
+    pub fn networks(mut self, networks: NetworkTable) -> Self {
+        self.networks = Some(networks);
+        self
+    }
+
+    /// Sends the requests to the relay JSON-RPC API at `api_url`. See [`MevShareClient::with_api_url`].
+    #[must_use]
+    pub fn api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = Some(api_url.into());
+        self
+    }
+
+    /// Streams events, and queries the event history, from `stream_url`. See [`MevShareClient::with_stream_url`].
+    #[must_use]
+    pub fn stream_url(mut self, stream_url: impl Into<String>) -> Self {
+        self.stream_url = Some(stream_url.into());
+        self
+    }
+
+    /// Shares the bundles sent without privacy settings as `privacy` says. See
+    /// [`MevShareClient::with_default_privacy`].
+    #[must_use]
+    pub fn default_privacy(mut self, privacy: Privacy) -> Self {
+        self.default_privacy = Some(privacy);
+        self
+    }
+
+    /// Identifies the client as `user_agent`. See [`MevShareClient::with_user_agent`].
+    #[must_use]
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sends `headers` with every request. See [`MevShareClient::with_headers`].
+    #[must_use]
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Sends the requests with `http`, e.g. to go through a proxy. See [`MevShareClient::with_http_client`].
+    #[must_use]
+    pub fn http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = Some(http);
+        self
+    }
+
+    /// Fails the requests that get no response within `timeout`. See [`MevShareClient::with_timeout`].
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retries the requests failing transiently as `policy` says. See [`MevShareClient::with_retry_policy`].
+    #[must_use]
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Runs `layer` around every JSON-RPC request, within the layers added before. See
+    /// [`MevShareClient::with_layer`].
+    #[must_use]
+    pub fn layer(mut self, layer: impl RpcLayer + 'static) -> Self {
+        self.layers.push(Arc::new(layer));
+        self
+    }
+
+    /// Reads up to `capacity` events ahead of their consumer. See [`MevShareClient::with_stream_buffer`].
+    #[cfg(all(feature = "stream", feature = "tokio"))]
+    #[must_use]
+    pub fn stream_buffer(mut self, capacity: usize) -> Self {
+        self.stream_buffer = capacity;
+        self
+    }
+
+    /// Builds the client, fetching the chain id from the provider unless a [`Self::network`] is set.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Provider`] if the provider fails to retrieve the chain id.
+    /// * [`Error::UnsupportedNetwork`] if the chain id has no network, among the [`Self::networks`] if set.
+    /// * [`Error::InvalidHeader`] if the [`Self::user_agent`] is not a valid header value.
+    pub async fn build(self) -> Result<MevShareClient<M>> {
+        let network = match self.network {
+            Some(network) => network,
+            None => {
+                let chain_id = self
+                    .provider
+                    .get_chainid()
+                    .await
+                    .map_err(Error::middleware)?;
+                match &self.networks {
+                    Some(networks) => networks.get(chain_id)?,
+                    None => MevShareNetwork::try_from(chain_id)?,
+                }
+            }
+        };
+
+        let mut client = MevShareClient::new_with_network(self.auth, self.provider, network);
+        if let Some(api_url) = self.api_url {
+            client = client.with_api_url(api_url);
+        }
+        if let Some(stream_url) = self.stream_url {
+            client = client.with_stream_url(stream_url);
+        }
+        if let Some(privacy) = self.default_privacy {
+            client = client.with_default_privacy(privacy);
+        }
+        if let Some(http) = self.http {
+            client = client.with_http_client(http);
+        }
+        if let Some(timeout) = self.timeout {
+            client = client.with_timeout(timeout);
+        }
+        if let Some(policy) = self.retry_policy {
+            client = client.with_retry_policy(policy);
+        }
+        for layer in self.layers {
+            Arc::make_mut(&mut client.rpc).push_layer(layer);
+        }
+        if !self.headers.is_empty() {
+            client = client.with_headers(self.headers);
+        }
+        if let Some(user_agent) = self.user_agent {
+            client = client.with_user_agent(&user_agent)?;
+        }
+        #[cfg(all(feature = "stream", feature = "tokio"))]
+        {
+            client = client.with_stream_buffer(self.stream_buffer);
+        }
+
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::{Builder, Hint};
+    use ethers::providers::MockProvider;
+    use ethers::signers::LocalWallet;
+
+    fn builder() -> MevShareClientBuilder<Provider<MockProvider>> {
+        let (provider, mock) = Provider::mocked();
+        mock.push(ethers::types::U64::from(5)).unwrap();
+        MevShareClient::builder(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+        )
+    }
+
+    #[test]
+    fn test_builder() {
+        let client = futures::executor::block_on(
+            builder()
+                .api_url("http://localhost:8080")
+                .default_privacy(Privacy::new([Hint::Hash], [Builder::Flashbots]))
+                .user_agent("my-searcher/0.3")
+                .timeout(Duration::from_secs(2))
+                .retry_policy(RetryPolicy::default())
+                .build(),
+        )
+        .unwrap();
+
+        // the chain id comes from the provider
+        assert_eq!(client.network().chain_id(), 5);
+        assert_eq!(client.network().api_url(), "http://localhost:8080");
+        assert_eq!(
+            client.network().stream_url(),
+            "https://mev-share-goerli.flashbots.net"
+        );
+        assert_eq!(
+            client.rpc.headers()[reqwest::header::USER_AGENT],
+            "my-searcher/0.3"
+        );
+        assert!(client.default_privacy.is_some());
+        assert_eq!(client.rpc.timeout(), Some(Duration::from_secs(2)));
+
+        let network = MevShareNetwork::custom(100_u64, "http://relay", "http://stream");
+        let client = futures::executor::block_on(builder().network(network).build()).unwrap();
+        assert_eq!(client.network().chain_id(), 100);
+
+        assert!(matches!(
+            futures::executor::block_on(builder().networks(NetworkTable::empty()).build()),
+            Err(Error::UnsupportedNetwork(_))
+        ));
+        assert!(matches!(
+            futures::executor::block_on(builder().user_agent("bad\nagent").build()),
+            Err(Error::InvalidHeader(_))
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+use super::{MevShareClient, MevShareRequest};
+use crate::api::types::UserStats;
+use crate::health::{Check, HealthReport, HEALTH_CHECK_TIMEOUT};
+use crate::helpers::provider::Waiter;
+use crate::RpcError;
+use ethers::providers::Middleware;
+use futures::future::{self, Either};
+use futures_timer::Delay;
+use instant::Instant;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use serde_json::json;
+use std::future::Future;
+use std::pin::pin;
+use std::time::Duration;
+
+/// Runs `future`, giving up after [`HEALTH_CHECK_TIMEOUT`]; returns its output and how long it took.
+async fn timeout<T>(future: impl Future<Output = T>) -> Option<(T, Duration)> {
+    let started = Instant::now();
+    match future::select(pin!(future), Delay::new(HEALTH_CHECK_TIMEOUT)).await {
+        Either::Left((output, _)) => Some((output, started.elapsed())),
+        Either::Right(_) => None,
+    }
+}
+
+fn timed_out() -> Check {
+    Check::Failed(format!("no answer within {HEALTH_CHECK_TIMEOUT:?}"))
+}
+
+impl<M: Waiter> MevShareClient<M> {
+    /// Checks that the provider, the relay JSON-RPC endpoint and the SSE event stream answer, and that the relay
+    /// accepts the signature of the requests, e.g. at startup or from a readiness probe. See [`crate::health`].
+    ///
+    /// The signature is checked with a `flashbots_getUserStatsV2` request, which sends nothing; the checks run
+    /// concurrently, each failing after [`HEALTH_CHECK_TIMEOUT`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let report = client.health_check().await;
+    /// info!(%report, healthy = report.is_healthy(), "MEV-Share health");
+    /// ```
+    pub async fn health_check(&self) -> HealthReport {
+        let ((provider, rpc, auth), stream) =
+            future::join(self.check_relay(), self.check_stream()).await;
+
+        HealthReport {
+            provider,
+            rpc,
+            auth,
+            stream,
+        }
+    }
+
+    /// Checks the provider, then the relay and the signature of the requests with the current block.
+    async fn check_relay(&self) -> (Check, Check, Check) {
+        let (block, provider) = match timeout(self.provider.get_block_number()).await {
+            Some((Ok(block), latency)) => (block, Check::Ok { latency }),
+            Some((Err(err), _)) => {
+                return (
+                    Check::Failed(err.to_string()),
+                    Check::Skipped,
+                    Check::Skipped,
+                )
+            }
+            None => return (timed_out(), Check::Skipped, Check::Skipped),
+        };
+
+        let stats = self.rpc.post::<UserStats, _>(
+            MevShareRequest::GetUserStats,
+            [json!({ "blockNumber": block })],
+        );
+
+        let (rpc, auth) = match timeout(stats).await {
+            Some((Ok(_), latency)) => (Check::Ok { latency }, Check::Ok { latency }),
+            // the relay answered, but rejected the request
+            Some((Err(err @ RpcError::Response(..)), latency)) => {
+                (Check::Ok { latency }, Check::Failed(err.to_string()))
+            }
+            Some((Err(err @ RpcError::Http(..)), latency))
+                if err
+                    .response_meta()
+                    .is_some_and(|meta| matches!(meta.status, 401 | 403)) =>
+            {
+                (Check::Ok { latency }, Check::Failed(err.to_string()))
+            }
+            // the request could not even be signed
+            Some((
+                Err(err @ (RpcError::Signing(_) | RpcError::RemoteSigning(_) | RpcError::Auth(_))),
+                _,
+            )) => (Check::Skipped, Check::Failed(err.to_string())),
+            Some((Err(err), _)) => (Check::Failed(err.to_string()), Check::Skipped),
+            None => (timed_out(), Check::Skipped),
+        };
+
+        (provider, rpc, auth)
+    }
+
+    /// Opens the event stream, and closes it as soon as the response headers are in.
+    async fn check_stream(&self) -> Check {
+        let request = self
+            .rpc
+            .http_client()
+            .get(self.network.stream_url())
+            .headers(self.rpc.headers().clone())
+            .header(ACCEPT, "text/event-stream")
+            .send();
+
+        match timeout(request).await {
+            Some((Ok(response), latency)) => {
+                let content_type = response
+                    .headers()
+                    .get(CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default();
+
+                if !response.status().is_success() {
+                    Check::Failed(format!("HTTP {}", response.status()))
+                } else if !content_type.starts_with("text/event-stream") {
+                    Check::Failed(format!("not an event stream: {content_type:?}"))
+                } else {
+                    Check::Ok { latency }
+                }
+            }
+            Some((Err(err), _)) => Check::Failed(err.to_string()),
+            None => timed_out(),
+        }
+    }
+}
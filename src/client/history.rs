@@ -0,0 +1,243 @@
+use super::MevShareClient;
+use crate::api::networks::Capability;
+use crate::api::types::{EventHistory, EventHistoryInfo, GetEventHistoryParams, MevShareEvent};
+use crate::helpers::provider::Waiter;
+use crate::history::{self, HintOutcome, HintSummary, InfoCache};
+use crate::Result;
+use futures::stream::{Stream, StreamExt};
+use std::pin::pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+impl<M: Waiter> MevShareClient<M> {
+    /// Reuses the `/history/info` response for `ttl` (default: [`crate::history::HISTORY_INFO_TTL`]) instead of fetching it on every call.
+    ///
+    /// See [`Self::get_event_history_info`].
+    #[must_use]
+    pub fn with_history_info_ttl(mut self, ttl: Duration) -> Self {
+        self.history_info = Arc::new(InfoCache::new(ttl));
+        self
+    }
+
+    /// Gets information about the event history endpoint.
+    ///
+    /// The response is cached for [`crate::history::HISTORY_INFO_TTL`], see [`Self::with_history_info_ttl`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new_with_chain_id(
+    ///     config.auth_wallet.clone(),
+    ///     config.provider.clone(),
+    ///     Chain::Mainnet, // EventHistory seems to be only supported on mainnet
+    /// )?;
+    /// let event_history_info = client.get_event_history_info().await?;
+    ///
+    /// let mut page = 0;
+    /// let mut done = false;
+    ///
+    /// while !done {
+    ///     let events = client
+    ///         .get_event_history(
+    ///             GetEventHistoryParams::builder()
+    ///                 .limit(event_history_info.max_limit)
+    ///                 .offset(page * event_history_info.max_limit)
+    ///                 .block_start(event_history_info.min_block)
+    ///                 .build(),
+    ///         )
+    ///         .await?;
+    ///
+    ///     for event in &events {
+    ///         if let Some(txs) = &event.hint.txs && !txs.is_empty() {
+    ///             debug!(?event);
+    ///             debug!(?txs);
+    ///             break;
+    ///         }
+    ///     }
+    ///
+    ///     for event in &events {
+    ///         if let Some(logs) = &event.hint.logs && !logs.is_empty() {
+    ///             debug!(?logs);
+    ///             done = true;
+    ///             break;
+    ///         }
+    ///     }
+    ///
+    ///     page += 1;
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UnsupportedOnNetwork`] if the network serves no event history.
+    /// * [`crate::Error::Rest`] if the network GET request to the MEV-Share API fails.
+    pub async fn get_event_history_info(&self) -> Result<EventHistoryInfo> {
+        self.network.require(Capability::EventHistory)?;
+
+        if let Some(info) = self.history_info.get() {
+            return Ok(info);
+        }
+
+        let info: EventHistoryInfo = self.rest.get("history/info").await?;
+        self.history_info.set(info.clone());
+
+        Ok(info)
+    }
+
+    /// Gets past events that were broadcast via the SSE event stream.
+    ///
+    /// `params` are clamped to the bounds advertised by [`Self::get_event_history_info`] (see [`GetEventHistoryParams::clamp`]),
+    /// so out-of-bounds requests return the available events rather than being rejected by the relay.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let info = client.get_event_history_info().await?;
+    /// pritnln!("min_block={}, max_limit={}", info.min_block, info.max_limit);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UnsupportedOnNetwork`] if the network serves no event history.
+    /// * [`crate::Error::Rest`] if the network GET request to the MEV-Share API fails.
+    ///
+    /// For a more comprehensive example, see [`crate::MevShareClient::get_event_history_info`].
+    pub async fn get_event_history(
+        &self,
+        params: GetEventHistoryParams,
+    ) -> Result<Vec<EventHistory>> {
+        let params = params.clamp(&self.get_event_history_info().await?);
+
+        self.rest
+            .get_with_params("history", params)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Streams past events that were broadcast via the SSE event stream, paging through [`Self::get_event_history`] internally.
+    ///
+    /// Pages are at most as large as the `max_limit` advertised by [`Self::get_event_history_info`] (or `params.limit`, if smaller),
+    /// and requests are retried with exponential backoff when the relay rate-limits them.
+    /// The stream ends when the relay runs out of events matching `params`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut events = pin!(client.event_history_stream(
+    ///     GetEventHistoryParams::builder()
+    ///         .block_start(info.min_block)
+    ///         .build()
+    /// ));
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     let event = event?;
+    ///     println!("block={} hash={:?}", event.block, event.hint.hash);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Each item fails with [`crate::Error::Rest`] if a network GET request to the MEV-Share API fails; the stream ends after an error.
+    /// The first one fails with [`crate::Error::InvalidParams`] if the page size, `params.limit` or the relay's
+    /// `max_limit`, is 0.
+    pub fn event_history_stream(
+        &self,
+        params: GetEventHistoryParams,
+    ) -> impl Stream<Item = Result<EventHistory>> + '_ {
+        history::paginate(self, params)
+    }
+
+    /// Replays past events matching `params` as an event stream, re-emitting them at their original relative timestamps
+    /// divided by `speed`, so that strategies can be backtested against real hint timing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // replay the last 1000 blocks, 10x faster than real time
+    /// let mut events = pin!(client.replay_event_history(
+    ///     GetEventHistoryParams::builder()
+    ///         .block_start(current_block - 1000)
+    ///         .build(),
+    ///     10.0,
+    /// ));
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     strategy.on_event(event?).await?;
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Each item fails with [`crate::Error::Rest`] if a network GET request to the MEV-Share API fails; the stream ends after an error.
+    /// The first one fails with [`crate::Error::InvalidParams`] if the page size, `params.limit` or the relay's
+    /// `max_limit`, is 0.
+    ///
+    /// # Panics
+    ///
+    /// If `speed` is not strictly positive.
+    pub fn replay_event_history(
+        &self,
+        params: GetEventHistoryParams,
+        speed: f64,
+    ) -> impl Stream<Item = Result<MevShareEvent>> + '_ {
+        history::replay(self.event_history_stream(params), speed)
+    }
+
+    /// Streams past events matching `params`, each joined with the on-chain outcome of the hinted transaction:
+    /// whether and where it landed, with what gas, and whether a backrun followed it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut outcomes = pin!(client.event_history_outcomes(params));
+    ///
+    /// while let Some(outcome) = outcomes.next().await {
+    ///     let outcome = outcome?;
+    ///     if let Some(landing) = &outcome.landing && landing.backrun.is_some() {
+    ///         println!("{:?} got backrun in block {}", outcome.event.hint.hash, landing.block);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Each item fails with:
+    /// * [`crate::Error::Rest`] if a network GET request to the MEV-Share API fails; the stream ends after an error.
+    /// * [`crate::Error::Provider`] if looking up the outcome of the event fails.
+    pub fn event_history_outcomes(
+        &self,
+        params: GetEventHistoryParams,
+    ) -> impl Stream<Item = Result<HintOutcome>> + '_ {
+        history::outcomes(&*self.provider, self.event_history_stream(params))
+    }
+
+    /// Computes summary statistics over the past events matching `params`: hints per block, share of hints disclosing
+    /// calldata or logs, gas used distribution and most targeted contracts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let summary = client
+    ///     .summarize_event_history(GetEventHistoryParams::builder().block_start(current_block - 7200).build())
+    ///     .await?;
+    ///
+    /// println!("median gas used: {:?}", summary.gas_used_percentile(50.0));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Rest`] if a network GET request to the MEV-Share API fails.
+    pub async fn summarize_event_history(
+        &self,
+        params: GetEventHistoryParams,
+    ) -> Result<HintSummary> {
+        let mut events = pin!(self.event_history_stream(params));
+        let mut summary = HintSummary::default();
+
+        while let Some(event) = events.next().await {
+            summary.add(&event?);
+        }
+
+        Ok(summary)
+    }
+}
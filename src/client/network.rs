@@ -0,0 +1,158 @@
+use super::MevShareClient;
+use crate::api::networks::MevShareNetwork;
+use crate::ens::EnsResolver;
+use crate::helpers::provider::Waiter;
+use crate::{Error, Result};
+use ethers::providers::{Provider, Ws};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+/// Base URL of the event history API of `network`.
+#[cfg(feature = "history")]
+pub(super) fn history_url(network: &MevShareNetwork) -> String {
+    format!("{}/api/v1", network.stream_url().trim_end_matches('/'))
+}
+
+impl<M: Waiter> MevShareClient<M> {
+    /// Switches the client to the MEV-Share network of `chain_id`, served by `provider`, keeping the rest of its
+    /// configuration: signer, headers, budget, dedup store, tracker, alerter and so on.
+    ///
+    /// The relay and event stream endpoints are re-resolved: the next requests and [`Self::subscribe_bundles`] streams
+    /// go to the new network, while streams already open keep streaming from the previous one. A custom
+    /// [`crate::backend::OrderflowBackend`] is left as is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// client.switch_network(Chain::Goerli, goerli_provider)?;
+    /// let pending_bundle = client.send_bundle(bundle).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UnsupportedNetwork`] if the `chain_id` is not supported by the MEV-Share client, in which case
+    ///   the client is left on its current network.
+    pub fn switch_network(
+        &mut self,
+        chain_id: impl TryInto<MevShareNetwork, Error = impl Into<Error>>,
+        provider: M,
+    ) -> Result<()> {
+        self.set_network(chain_id.try_into().map_err(Into::into)?, Arc::new(provider));
+        Ok(())
+    }
+
+    /// Switches the client to the MEV-Share network of `chain_id`, served by `provider`, until the returned guard is
+    /// dropped, then switches it back. See [`Self::switch_network`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// {
+    ///     let goerli = client.with_network(Chain::Goerli, goerli_provider)?;
+    ///     goerli.send_bundle(test_bundle).await?.inclusion().await?;
+    /// }
+    /// // back on mainnet
+    /// client.send_bundle(bundle).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Self::switch_network`].
+    pub fn with_network(
+        &mut self,
+        chain_id: impl TryInto<MevShareNetwork, Error = impl Into<Error>>,
+        provider: M,
+    ) -> Result<NetworkOverride<'_, M>> {
+        let network = chain_id.try_into().map_err(Into::into)?;
+        let previous = (self.network.clone(), Arc::clone(&self.provider));
+        self.set_network(network, Arc::new(provider));
+
+        Ok(NetworkOverride {
+            client: self,
+            previous: Some(previous),
+        })
+    }
+
+    /// Sends the requests to the relay JSON-RPC API at `api_url` instead of the one of the network, e.g. to go through a
+    /// private relay proxy while streaming events from the official endpoint.
+    ///
+    /// Overrides are dropped by [`Self::switch_network`], which resolves both endpoints of the new network.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_api_url("https://relay-proxy.internal");
+    /// ```
+    #[must_use]
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.network = self.network.with_api_url(api_url);
+        Arc::make_mut(&mut self.rpc).set_base_url(self.network.api_url());
+        self
+    }
+
+    /// Streams events, and queries the event history, from `stream_url` instead of the endpoint of the network.
+    ///
+    /// Overrides are dropped by [`Self::switch_network`], which resolves both endpoints of the new network.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client = MevShareClient::new(auth_wallet, provider)
+    ///     .await?
+    ///     .with_stream_url("https://mev-share-mirror.example.org");
+    /// ```
+    #[must_use]
+    pub fn with_stream_url(mut self, stream_url: impl Into<String>) -> Self {
+        self.network = self.network.with_stream_url(stream_url);
+        #[cfg(feature = "history")]
+        {
+            Arc::make_mut(&mut self.rest).set_base_url(history_url(&self.network));
+            self.history_info = Arc::new(self.history_info.empty_like());
+        }
+        self
+    }
+
+    fn set_network(&mut self, network: MevShareNetwork, provider: Arc<M>) {
+        Arc::make_mut(&mut self.rpc).set_base_url(network.api_url());
+        #[cfg(feature = "history")]
+        {
+            Arc::make_mut(&mut self.rest).set_base_url(history_url(&network));
+            self.history_info = Arc::new(self.history_info.empty_like());
+        }
+        self.ens = Arc::new(EnsResolver::new(Arc::clone(&provider)));
+        self.provider = provider;
+        self.network = network;
+    }
+}
+
+/// A client temporarily switched to another network, see [`MevShareClient::with_network`].
+///
+/// Dereferences to the client; switches it back to its previous network when dropped.
+pub struct NetworkOverride<'c, M: Waiter = Provider<Ws>> {
+    client: &'c mut MevShareClient<M>,
+    previous: Option<(MevShareNetwork, Arc<M>)>,
+}
+
+impl<M: Waiter> Deref for NetworkOverride<'_, M> {
+    type Target = MevShareClient<M>;
+
+    fn deref(&self) -> &Self::Target {
+        self.client
+    }
+}
+
+impl<M: Waiter> DerefMut for NetworkOverride<'_, M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.client
+    }
+}
+
+impl<M: Waiter> Drop for NetworkOverride<'_, M> {
+    fn drop(&mut self) {
+        if let Some((network, provider)) = self.previous.take() {
+            self.client.set_network(network, provider);
+        }
+    }
+}
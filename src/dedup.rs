@@ -0,0 +1,248 @@
+//! Remembering which bundles were already submitted, across restarts, so that a bot coming back up doesn't send the
+//! opportunities it already has in flight a second time.
+//!
+//! Before sending a bundle, a client [with a store](crate::MevShareClient::with_dedup_store) claims its
+//! [`submission_key`] for a time to live, and fails with [`crate::Error::DuplicateSubmission`] if it is already claimed.
+//! The claim is released if the relay rejects the bundle, and records the bundle hash otherwise.
+//!
+//! The `dedup` feature provides [`SqliteDedupStore`], a store in a local `SQLite` database; other stores, e.g. shared by
+//! several replicas in Redis, implement [`DedupStore`].
+//!
+//! # Example
+//!
+//! ```
+//! let client = MevShareClient::new(auth_wallet, provider)
+//!     .await?
+//!     .with_dedup_store(SqliteDedupStore::open("submitted.sqlite")?, Duration::from_secs(5 * 60));
+//!
+//! match client.send_bundle(bundle).await {
+//!     Err(Error::DuplicateSubmission(key)) => debug!(?key, "already in flight"),
+//!     result => { result?; }
+//! }
+//! ```
+
+use crate::api::types::SendBundleParams;
+use crate::Result;
+use ethers::types::TxHash;
+use ethers::utils::keccak256;
+#[cfg(feature = "dedup")]
+use rusqlite::{params, Connection, OptionalExtension};
+#[cfg(feature = "dedup")]
+use std::path::Path;
+#[cfg(feature = "dedup")]
+use std::sync::{Mutex, PoisonError};
+
+/// Identifies the opportunity a bundle captures: the keccak hash of its body and target block.
+///
+/// Resending the same body for a later block, e.g. because it didn't land, is a new submission.
+///
+/// # Errors
+///
+/// * [`crate::Error::Json`] if the body can't be serialized.
+pub fn submission_key(params: &SendBundleParams) -> Result<TxHash> {
+    let json = serde_json::to_vec(&(&params.body, params.inclusion.block))?;
+    Ok(keccak256(json).into())
+}
+
+/// Where submissions are claimed; times are unix timestamps, in seconds.
+pub trait DedupStore: Send + Sync {
+    /// Claims `key` until `expires_at`, unless it is already claimed by a submission that has not expired.
+    ///
+    /// Returns whether `key` was claimed.
+    ///
+    /// # Errors
+    ///
+    /// If the store can't be read or written, e.g. `Error::Sqlite` for a [`SqliteDedupStore`]. The submission is not
+    /// sent then.
+    fn claim(&self, key: TxHash, now: u64, expires_at: u64) -> Result<bool>;
+
+    /// Records the bundle hash returned by the relay for the submission claimed with `key`.
+    ///
+    /// # Errors
+    ///
+    /// If the store can't be written. The client only logs it: the bundle is live at the relay, and its
+    /// `PendingBundle` is returned nonetheless.
+    fn confirm(&self, key: TxHash, bundle_hash: TxHash) -> Result<()>;
+
+    /// Releases `key`, e.g. because the relay rejected the bundle, so that it can be submitted again.
+    ///
+    /// # Errors
+    ///
+    /// If the store can't be written: `key` stays claimed until it expires. The client only logs it, and returns why
+    /// the bundle was rejected.
+    fn release(&self, key: TxHash) -> Result<()>;
+}
+
+#[cfg(feature = "dedup")]
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS submissions (
+        key         TEXT PRIMARY KEY,
+        bundle_hash TEXT,
+        expires_at  INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS submissions_expires_at ON submissions (expires_at);
+    CREATE TABLE IF NOT EXISTS pnl (
+        bundle_hash     TEXT PRIMARY KEY,
+        strategy        TEXT NOT NULL,
+        block           INTEGER NOT NULL,
+        received        TEXT NOT NULL,
+        gas_paid        TEXT NOT NULL,
+        coinbase_paid   TEXT NOT NULL,
+        refund_owed     TEXT NOT NULL,
+        refund_received TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS pnl_strategy ON pnl (strategy);
+";
+
+/// A [`DedupStore`] in a local `SQLite` database, which is also a [`crate::pnl::PnlStore`].
+#[cfg(feature = "dedup")]
+pub struct SqliteDedupStore {
+    pub(crate) connection: Mutex<Connection>,
+}
+
+#[cfg(feature = "dedup")]
+impl SqliteDedupStore {
+    /// Opens (or creates) the store database at `path`.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Sqlite`] if the database can't be opened or initialized.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Opens a transient, in-memory store, e.g. for tests.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Sqlite`] if the database can't be initialized.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Returns the bundle hash recorded for the submission `key`, if it was confirmed and has not expired at `now`.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Sqlite`] if the store can't be queried.
+    pub fn bundle_hash(&self, key: TxHash, now: u64) -> Result<Option<TxHash>> {
+        let bundle_hash: Option<Option<String>> = self
+            .connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .query_row(
+                "SELECT bundle_hash FROM submissions WHERE key = ?1 AND expires_at > ?2",
+                params![format!("{key:?}"), now],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(bundle_hash
+            .flatten()
+            .and_then(|bundle_hash| bundle_hash.parse().ok()))
+    }
+}
+
+#[cfg(feature = "dedup")]
+impl DedupStore for SqliteDedupStore {
+    fn claim(&self, key: TxHash, now: u64, expires_at: u64) -> Result<bool> {
+        let connection = self
+            .connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        // expired claims are dropped lazily, on the next claim
+        connection.execute(
+            "DELETE FROM submissions WHERE expires_at <= ?1",
+            params![now],
+        )?;
+        let claimed = connection.execute(
+            "INSERT OR IGNORE INTO submissions (key, expires_at) VALUES (?1, ?2)",
+            params![format!("{key:?}"), expires_at],
+        )?;
+
+        Ok(claimed == 1)
+    }
+
+    fn confirm(&self, key: TxHash, bundle_hash: TxHash) -> Result<()> {
+        self.connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .execute(
+                "UPDATE submissions SET bundle_hash = ?2 WHERE key = ?1",
+                params![format!("{key:?}"), format!("{bundle_hash:?}")],
+            )?;
+        Ok(())
+    }
+
+    fn release(&self, key: TxHash) -> Result<()> {
+        self.connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .execute(
+                "DELETE FROM submissions WHERE key = ?1",
+                params![format!("{key:?}")],
+            )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::Body;
+
+    #[test]
+    fn test_submission_key() {
+        let bundle = |block: u64| {
+            SendBundleParams::builder()
+                .inclusion(block, None)
+                .body(vec![Body::tx(TxHash::zero())])
+                .build()
+        };
+
+        assert_eq!(
+            submission_key(&bundle(100)).unwrap(),
+            submission_key(&bundle(100)).unwrap()
+        );
+        assert_ne!(
+            submission_key(&bundle(100)).unwrap(),
+            submission_key(&bundle(101)).unwrap()
+        );
+    }
+
+    #[cfg(feature = "dedup")]
+    #[test]
+    fn test_sqlite_dedup_store() {
+        let path = std::env::temp_dir().join(format!("dedup-{}.sqlite", std::process::id()));
+        let (key, bundle_hash) = (TxHash::random(), TxHash::random());
+
+        {
+            let store = SqliteDedupStore::open(&path).unwrap();
+            assert!(store.claim(key, 1_000, 1_300).unwrap());
+            store.confirm(key, bundle_hash).unwrap();
+        }
+
+        // a restarted bot sees the submission of the previous run
+        let store = SqliteDedupStore::open(&path).unwrap();
+        assert!(!store.claim(key, 1_100, 1_400).unwrap());
+        assert_eq!(store.bundle_hash(key, 1_100).unwrap(), Some(bundle_hash));
+
+        // until it expires
+        assert!(store.claim(key, 1_300, 1_600).unwrap());
+        assert_eq!(store.bundle_hash(key, 1_300).unwrap(), None);
+
+        store.release(key).unwrap();
+        assert!(store.claim(key, 1_300, 1_600).unwrap());
+
+        drop(store);
+        std::fs::remove_file(path).unwrap();
+    }
+}
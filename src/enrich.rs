@@ -0,0 +1,221 @@
+//! Enriching events with the full transaction once it lands, to analyze what their hints didn't disclose.
+//!
+//! An [`Enricher`] waits, through the provider, for the transaction hinted by a [`MevShareEvent`] to land, and fetches
+//! the actual [`Transaction`] and [`TransactionReceipt`]. The resulting [`EnrichedEvent`] compares the hint to the
+//! transaction with [`EnrichedEvent::disclosure`].
+//!
+//! Only events of a single transaction are enriched: the hash of a bundle event isn't the hash of any of its
+//! transactions, so there is nothing to look up.
+//!
+//! # Example
+//!
+//! ```
+//! let enricher = Enricher::new(provider.clone());
+//! let mut enriched = enricher.enrich_stream(client.subscribe_bundles(), 16);
+//!
+//! while let Some(event) = enriched.next().await {
+//!     let disclosure = event?.disclosure();
+//!     info!(%disclosure, "landed");
+//! }
+//! ```
+
+use crate::api::types::{MevShareEvent, TX_WAIT_MAX_BLOCKS};
+use crate::helpers::provider::Waiter;
+use crate::{Error, Result};
+use ethers::prelude::*;
+use futures::{future, Stream, StreamExt};
+use std::fmt::Display;
+
+/// A [`MevShareEvent`] along with the transaction it hinted at, as it landed, see [`crate::enrich`].
+#[derive(Clone, Debug)]
+pub struct EnrichedEvent {
+    /// The event, as streamed.
+    pub event: MevShareEvent,
+    /// The transaction, as it landed.
+    pub transaction: Transaction,
+    /// The receipt of the transaction.
+    pub receipt: TransactionReceipt,
+}
+
+impl EnrichedEvent {
+    /// Hash of the transaction.
+    #[must_use]
+    pub fn hash(&self) -> TxHash {
+        self.transaction.hash
+    }
+
+    /// What the hints of the event disclosed, compared to the transaction.
+    #[must_use]
+    pub fn disclosure(&self) -> Disclosure {
+        let hint = self.event.transactions().first();
+
+        Disclosure {
+            to: hint.is_some_and(|hint| hint.to.is_some()),
+            function_selector: hint.is_some_and(|hint| hint.function_selector.is_some()),
+            call_data: hint.is_some_and(|hint| hint.call_data.is_some()),
+            logs_hinted: self.event.logs().len(),
+            logs_emitted: self.receipt.logs.len(),
+        }
+    }
+}
+
+/// What the hints of an event disclosed about the transaction, see [`EnrichedEvent::disclosure`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Disclosure {
+    /// Whether the recipient was disclosed.
+    pub to: bool,
+    /// Whether the function selector was disclosed.
+    pub function_selector: bool,
+    /// Whether the whole calldata was disclosed.
+    pub call_data: bool,
+    /// Number of logs disclosed.
+    pub logs_hinted: usize,
+    /// Number of logs the transaction emitted.
+    pub logs_emitted: usize,
+}
+
+impl Disclosure {
+    /// Number of logs emitted, but not disclosed.
+    #[must_use]
+    pub fn logs_undisclosed(&self) -> usize {
+        self.logs_emitted.saturating_sub(self.logs_hinted)
+    }
+
+    /// Whether the hints disclosed everything there is to know for a backrun: recipient, calldata and logs.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.to && self.call_data && self.logs_undisclosed() == 0
+    }
+}
+
+impl Display for Disclosure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hidden = [
+            (!self.to).then_some("to"),
+            (!self.function_selector).then_some("function selector"),
+            (!self.call_data).then_some("calldata"),
+        ];
+        let hidden = hidden.into_iter().flatten().collect::<Vec<_>>();
+
+        if hidden.is_empty() {
+            f.write_str("all fields")?;
+        } else {
+            write!(f, "hid {}", hidden.join(", "))?;
+        }
+        write!(f, ", {}/{} logs", self.logs_hinted, self.logs_emitted)
+    }
+}
+
+/// Fetches the landed transaction of events, see [`crate::enrich`].
+#[derive(Clone, Debug)]
+pub struct Enricher {
+    provider: Provider<Ws>,
+    max_blocks: u64,
+}
+
+impl Enricher {
+    /// Waits for transactions to land for [`TX_WAIT_MAX_BLOCKS`], as long as the relay tries to include them.
+    #[must_use]
+    pub fn new(provider: Provider<Ws>) -> Self {
+        Self {
+            provider,
+            max_blocks: TX_WAIT_MAX_BLOCKS,
+        }
+    }
+
+    /// Waits for transactions to land for `max_blocks` instead.
+    #[must_use]
+    pub fn with_max_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_blocks = max_blocks;
+        self
+    }
+
+    /// Waits for the transaction of `event` to land, and fetches it along with its receipt.
+    ///
+    /// Returns `None` if the event is a bundle, or if the transaction didn't land in time.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Provider`] if the provider fails.
+    pub async fn enrich(&self, event: MevShareEvent) -> Result<Option<EnrichedEvent>> {
+        let Some((hash, ..)) = event.as_transaction() else {
+            return Ok(None);
+        };
+
+        let max_block = self.provider.get_block_number().await? + self.max_blocks;
+        let receipt = match self.provider.wait_for_tx_receipt(hash, max_block).await {
+            Ok((receipt, _)) => receipt,
+            Err(Error::TransactionTimeout(..)) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        // `None` if reorged out since
+        let Some(transaction) = self.provider.get_transaction(hash).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(EnrichedEvent {
+            event,
+            transaction,
+            receipt,
+        }))
+    }
+
+    /// Enriches the events of `events`, e.g. [`crate::MevShareClient::subscribe_bundles`], waiting for up to
+    /// `concurrency` transactions at once.
+    ///
+    /// Events are yielded as their transaction lands, not in the order they were streamed; events that aren't
+    /// enriched are skipped. Errors of `events` are passed through.
+    pub fn enrich_stream<'a>(
+        &'a self,
+        events: impl Stream<Item = Result<MevShareEvent>> + 'a,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<EnrichedEvent>> + 'a {
+        events
+            .map(move |event| async move {
+                match event {
+                    Ok(event) => self.enrich(event).await.transpose(),
+                    Err(err) => Some(Err(err)),
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .filter_map(future::ready)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disclosure() {
+        let event: MevShareEvent = serde_json::from_str(include_str!(
+            "../mev-share-types/src/fixtures/sse_event.json"
+        ))
+        .unwrap();
+        let receipt = TransactionReceipt {
+            logs: vec![Log::default(), Log::default()],
+            ..Default::default()
+        };
+        let mut enriched = EnrichedEvent {
+            event,
+            transaction: Transaction::default(),
+            receipt,
+        };
+
+        let disclosure = enriched.disclosure();
+        assert!(disclosure.to && disclosure.function_selector && disclosure.call_data);
+        assert_eq!(disclosure.logs_undisclosed(), 1);
+        assert!(!disclosure.is_full());
+        assert_eq!(disclosure.to_string(), "all fields, 1/2 logs");
+
+        enriched.event.txs = None;
+        enriched.event.logs = None;
+        let disclosure = enriched.disclosure();
+        assert_eq!(disclosure.logs_undisclosed(), 2);
+        assert_eq!(
+            disclosure.to_string(),
+            "hid to, function selector, calldata, 0/2 logs"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+//! Resolving ENS names into addresses, so that refund recipients and other addresses of a config can be written as
+//! `treasury.eth` rather than hex.
+//!
+//! Names are resolved through the provider when the params are built, and cached for the life of the resolver: a
+//! name is resolved once, however many bundles pay a refund to it. Hex addresses are passed through as is.
+//!
+//! # Example
+//!
+//! ```
+//! let ens = EnsResolver::new(provider.clone());
+//!
+//! let pending_tx = client
+//!     .send_protected_transaction(
+//!         signed_tx,
+//!         [ens.refund_config("user.eth", 90).await?, ens.refund_config("wallet.eth", 10).await?],
+//!     )
+//!     .await?;
+//! ```
+//!
+//! The client resolves names the same way with [`crate::MevShareClient::resolve_name`].
+
+use crate::api::types::RefundConfig;
+use crate::helpers::provider::Waiter;
+use crate::{Error, Result};
+use ethers::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+/// Resolves ENS names through a provider, caching the addresses, see [`crate::ens`].
+#[derive(Debug)]
+pub struct EnsResolver<M = Provider<Ws>> {
+    provider: M,
+    cache: Mutex<HashMap<String, Address>>,
+}
+
+impl<M: Waiter> EnsResolver<M> {
+    /// Resolves names through `provider`.
+    #[must_use]
+    pub fn new(provider: M) -> Self {
+        Self {
+            provider,
+            cache: Mutex::default(),
+        }
+    }
+
+    /// Resolves `name`, e.g. `"vitalik.eth"`, `"0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"` or an [`Address`], into an
+    /// address.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Provider`] if the name has no resolver or address, or the provider fails.
+    pub async fn resolve(&self, name: impl Into<NameOrAddress>) -> Result<Address> {
+        let name = match name.into() {
+            NameOrAddress::Address(address) => return Ok(address),
+            NameOrAddress::Name(name) => name,
+        };
+
+        if let Some(address) = self.cached(&name) {
+            return Ok(address);
+        }
+
+        let address = self
+            .provider
+            .resolve_name(&name)
+            .await
+            .map_err(Error::middleware)?;
+        self.cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name, address);
+
+        Ok(address)
+    }
+
+    /// A [`RefundConfig`] paying `percent` of the refund to `name`, resolved.
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Self::resolve`].
+    pub async fn refund_config(
+        &self,
+        name: impl Into<NameOrAddress>,
+        percent: u32,
+    ) -> Result<RefundConfig> {
+        Ok(RefundConfig {
+            address: self.resolve(name).await?,
+            percent,
+        })
+    }
+
+    /// The address `name` resolved to earlier, if any.
+    #[must_use]
+    pub fn cached(&self, name: &str) -> Option<Address> {
+        self.cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .copied()
+    }
+}
@@ -1,11 +1,15 @@
 use std::backtrace::Backtrace;
 
-use crate::api::types::JsonRpcResponseError;
+use crate::api::networks::Capability;
+use crate::api::rpc_client::ResponseMeta;
+use crate::api::types::{JsonRpcResponseError, RelayLimit};
+use crate::budget::BudgetLimit;
 use ethers::{
     providers::ProviderError,
-    types::{TransactionReceipt, TxHash, U256, U64},
+    types::{Address, TransactionReceipt, TxHash, U256, U64},
 };
 use reqwest::header::InvalidHeaderValue;
+use std::time::Duration;
 use thiserror::Error;
 
 /// The crate `Error` type.
@@ -20,29 +24,122 @@ pub enum Error {
     #[error("Bundle dropped: only partially appeared onchain. Receipts: {0:?}")]
     BundleDiscard(Vec<TransactionReceipt>),
 
+    #[error("Transactions {0:?} included in block {1} were reorged out")]
+    Reorged(Vec<TxHash>, U64),
+
+    #[error("Bundle or transaction {0:?} was cancelled")]
+    Cancelled(TxHash),
+
     #[error("Transaction {0:?} did not appaear on-chain before maxBlock: {1}")]
     TransactionTimeout(TxHash, U64),
 
+    #[error(
+        "The stream of new blocks ended, e.g. because the websocket of the provider disconnected"
+    )]
+    BlockStreamEnded,
+
     #[error("Transaction {0:?} reverterd")]
     TransactionRevert(TransactionReceipt),
 
+    #[error("Budget exceeded: {0}")]
+    BudgetExceeded(BudgetLimit),
+
+    #[error("Quota of tenant {0:?} exceeded: {1}")]
+    QuotaExceeded(String, BudgetLimit),
+
+    #[error("Refund percentages add up to {0}%, more than 100%")]
+    InvalidRefund(u32),
+
+    #[error("Invalid params: {0}")]
+    InvalidParams(String),
+
+    #[error("Invalid X-Flashbots-Signature header: {0}")]
+    InvalidSignatureHeader(String),
+
+    #[error("Bundle exceeds a relay limit: {0}")]
+    RelayLimit(RelayLimit),
+
+    #[error("Bundle {0:?} already submitted, and not expired yet")]
+    DuplicateSubmission(TxHash),
+
+    #[error("Relay returned bundle hash {1:?}, expected {0:?}")]
+    BundleHashMismatch(TxHash, TxHash),
+
+    #[error("Wallet {1:?} can't sign for the sender of the transaction, {0:?}")]
+    SenderMismatch(Address, Address),
+
     #[error("UnsupportedNetwork: {0}")]
     UnsupportedNetwork(U256),
 
+    #[error("{0} is not available on chain {1}")]
+    UnsupportedOnNetwork(Capability, u64),
+
+    #[error("Price unavailable: {0}")]
+    Price(String),
+
+    #[error("Invalid header value: {0}")]
+    InvalidHeader(#[from] InvalidHeaderValue),
+
     #[error(transparent)]
     Json(#[from] JsonError),
 
     #[error(transparent)]
     Provider(#[from] ProviderError),
 
+    #[error(transparent)]
+    Middleware(Box<dyn std::error::Error + Send + Sync>),
+
+    #[cfg(feature = "stream")]
     #[error(transparent)]
     EventSource(#[from] reqwest_eventsource::Error),
 
     #[error(transparent)]
-    Rpc(#[from] RpcError),
+    Rpc(RpcError),
+
+    #[error("No response within {0:?}")]
+    Timeout(Duration),
+
+    #[error("Builder registry unavailable: {0}")]
+    BuilderRegistry(reqwest::Error),
+
+    #[error("Relay data API {0} error: {1}")]
+    RelayData(String, reqwest::Error),
+
+    #[error("{0} error: {1}")]
+    Backend(String, Box<dyn std::error::Error + Send + Sync>),
+
+    #[cfg(feature = "history")]
+    #[error(transparent)]
+    Rest(RestError),
+
+    #[cfg(feature = "server")]
+    #[error(transparent)]
+    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Rlp(#[from] ethers::utils::rlp::DecoderError),
+
+    #[error(transparent)]
+    Wallet(#[from] ethers::signers::WalletError),
+
+    #[cfg(any(feature = "indexer", feature = "dedup"))]
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
 
+    #[cfg(feature = "kafka")]
     #[error(transparent)]
-    Rest(#[from] RestError),
+    Kafka(#[from] rdkafka::error::KafkaError),
+
+    #[cfg(feature = "nats")]
+    #[error(transparent)]
+    Nats(#[from] async_nats::PublishError),
+
+    #[cfg(feature = "redis")]
+    #[error(transparent)]
+    Redis(#[from] redis::RedisError),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -51,18 +148,120 @@ pub enum RpcError {
     Json(#[from] JsonError),
 
     #[error("Error: {0:?}")]
-    Response(JsonRpcResponseError),
+    Response(JsonRpcResponseError, ResponseMeta),
+
+    #[error("HTTP {}: {1}", .0.status)]
+    Http(ResponseMeta, String),
+
+    #[error("No response to request {0} of the batch")]
+    MissingResponse(i32),
 
     #[error(transparent)]
     Signing(#[from] ethers::signers::WalletError),
 
+    #[error("Remote signer error: {0:?}")]
+    RemoteSigning(JsonRpcResponseError),
+
+    #[error(transparent)]
+    InvalidSignature(#[from] ethers::types::SignatureError),
+
+    #[error("Authentication error: {0}")]
+    Auth(Box<dyn std::error::Error + Send + Sync>),
+
     #[error(transparent)]
     InvalidHeader(#[from] InvalidHeaderValue),
 
     #[error(transparent)]
     Network(#[from] reqwest::Error),
+
+    #[error("No response within {0:?}")]
+    Timeout(Duration),
+}
+
+/// Timeouts surface as [`Error::Timeout`], whichever API timed out.
+impl From<RpcError> for Error {
+    fn from(err: RpcError) -> Self {
+        match err {
+            RpcError::Timeout(timeout) => Self::Timeout(timeout),
+            err => Self::Rpc(err),
+        }
+    }
+}
+
+#[cfg(feature = "history")]
+impl From<RestError> for Error {
+    fn from(err: RestError) -> Self {
+        match err {
+            RestError::Timeout(timeout) => Self::Timeout(timeout),
+            err => Self::Rest(err),
+        }
+    }
 }
 
+impl From<mev_share_types::Error> for Error {
+    fn from(err: mev_share_types::Error) -> Self {
+        use mev_share_types::Error as TypesError;
+
+        match err {
+            TypesError::InvalidRefund(percent) => Self::InvalidRefund(percent),
+            TypesError::InvalidParams(reason) => Self::InvalidParams(reason),
+            TypesError::RelayLimit(limit) => Self::RelayLimit(limit),
+            TypesError::UnsupportedNetwork(chain_id) => Self::UnsupportedNetwork(chain_id),
+            TypesError::UnsupportedOnNetwork(capability, chain_id) => {
+                Self::UnsupportedOnNetwork(capability, chain_id)
+            }
+            TypesError::Json(err) => Self::Json(err.into()),
+            TypesError::Io(err) => Self::Io(err),
+        }
+    }
+}
+
+impl Error {
+    /// Metadata of the relay response that caused the error, if the relay responded, e.g. to quote its request id in
+    /// a support issue or to back off for its `Retry-After`.
+    #[must_use]
+    pub fn response_meta(&self) -> Option<&ResponseMeta> {
+        match self {
+            Self::Rpc(err) => err.response_meta(),
+            _ => None,
+        }
+    }
+
+    /// Wraps an error of the [`ethers::providers::Middleware`] of the client: as [`Error::Provider`] if it is a
+    /// [`ProviderError`], e.g. for a bare [`ethers::providers::Provider`], as [`Error::Middleware`] otherwise.
+    pub(crate) fn middleware(err: impl std::error::Error + Send + Sync + 'static) -> Self {
+        let err: Box<dyn std::error::Error + Send + Sync> = Box::new(err);
+        match err.downcast::<ProviderError>() {
+            Ok(err) => Self::Provider(*err),
+            Err(err) => Self::Middleware(err),
+        }
+    }
+}
+
+impl RpcError {
+    /// Metadata of the relay response that caused the error, if the relay responded.
+    #[must_use]
+    pub fn response_meta(&self) -> Option<&ResponseMeta> {
+        match self {
+            Self::Response(_, meta) | Self::Http(meta, _) => Some(meta),
+            _ => None,
+        }
+    }
+
+    /// Whether the request may succeed if sent again: the relay was unreachable, reset the connection, did not respond
+    /// in time, or failed it with a `5xx` or `429` HTTP status without a JSON-RPC error. See [`crate::retry`].
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Timeout(_) => true,
+            Self::Network(err) => err.is_request() || err.is_timeout() || err.is_body(),
+            Self::Http(meta, _) => meta.status >= 500 || meta.status == 429,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "history")]
 #[derive(thiserror::Error, Debug)]
 pub enum RestError {
     #[error(transparent)]
@@ -73,6 +272,9 @@ pub enum RestError {
 
     #[error(transparent)]
     Network(#[from] reqwest::Error),
+
+    #[error("No response within {0:?}")]
+    Timeout(Duration),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -103,6 +305,7 @@ macro_rules! impl_from_serde_json_error {
 
 impl_from_serde_json_error!(Error);
 impl_from_serde_json_error!(RpcError);
+#[cfg(feature = "history")]
 impl_from_serde_json_error!(RestError);
 
 /// The crate `Result` type.
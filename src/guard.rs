@@ -0,0 +1,238 @@
+use crate::api::rpc_client::MevShareRpcClient;
+use crate::api::types::{CancelBundleParams, CancelPrivateTransactionParams};
+use crate::client::MevShareRequest;
+use crate::Result;
+use ethers::types::TxHash;
+use futures::future::join_all;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tracing::*;
+
+/// An order submitted to the relay that can still be withdrawn.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Outstanding {
+    /// A private transaction, cancelled via `eth_cancelPrivateTransaction`.
+    Transaction(TxHash),
+    /// A bundle submitted with a replacement UUID, cancelled via `eth_cancelBundle`.
+    Bundle(String),
+}
+
+/// Orders issued by a [`crate::MevShareClient`], shared with its clones, its pending orders and its [`CancelGuard`]s.
+pub(crate) type Orders = Arc<TrackedOrders>;
+
+/// The outstanding orders of a [`crate::MevShareClient`], and how many [`CancelGuard`]s are alive to cancel them.
+#[derive(Debug, Default)]
+pub(crate) struct TrackedOrders {
+    orders: Mutex<HashSet<Outstanding>>,
+    guards: AtomicUsize,
+}
+
+impl TrackedOrders {
+    fn lock(&self) -> MutexGuard<'_, HashSet<Outstanding>> {
+        self.orders.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Records `order` in `orders`, but only while a [`CancelGuard`] is alive: without a guard there is nobody to cancel it.
+pub(crate) fn track(orders: &Orders, order: Outstanding) {
+    if orders.guards.load(Ordering::Acquire) > 0 {
+        orders.lock().insert(order);
+    }
+}
+
+/// Stops tracking `order`, e.g. because it was cancelled or landed.
+pub(crate) fn untrack(orders: &Orders, order: &Outstanding) {
+    orders.lock().remove(order);
+}
+
+/// Cancels the orders issued by a [`crate::MevShareClient`] when dropped or when the process is asked to shut down,
+/// so that a crashing bot doesn't leave stale orders at the relay.
+///
+/// Cancelling on drop and on shutdown signals requires the `tokio` feature (enabled by default); without it, call
+/// [`CancelGuard::cancel_all`] explicitly from your own executor.
+///
+/// Obtain one via [`crate::MevShareClient::cancel_guard`]: from then on, every private transaction sent by the client is tracked.
+/// Bundles can only be withdrawn by their replacement UUID: those sent with one are tracked too, others can't be
+/// cancelled. Bundles sent by other means can be registered with [`CancelGuard::track_bundle`].
+///
+/// # Example
+///
+/// ```
+/// let guard = client.cancel_guard();
+///
+/// tokio::select! {
+///     result = run_strategy(&client) => result?,
+///     // withdraw everything on SIGINT/SIGTERM
+///     result = guard.cancel_on_shutdown() => result?,
+/// }
+/// ```
+pub struct CancelGuard {
+    rpc: MevShareRpcClient,
+    orders: Orders,
+    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+    armed: bool,
+}
+
+impl CancelGuard {
+    pub(crate) fn new(rpc: MevShareRpcClient, orders: Orders) -> Self {
+        orders.guards.fetch_add(1, Ordering::AcqRel);
+        Self {
+            rpc,
+            orders,
+            armed: true,
+        }
+    }
+
+    /// Registers a bundle, by its replacement UUID, to be cancelled along with the tracked transactions.
+    pub fn track_bundle(&self, replacement_uuid: impl Into<String>) {
+        self.orders
+            .lock()
+            .insert(Outstanding::Bundle(replacement_uuid.into()));
+    }
+
+    /// Stops tracking `order`, e.g. because it landed on-chain.
+    pub fn forget(&self, order: &Outstanding) {
+        untrack(&self.orders, order);
+    }
+
+    /// Returns the orders that would be cancelled right now.
+    #[must_use]
+    pub fn outstanding(&self) -> Vec<Outstanding> {
+        self.orders.lock().iter().cloned().collect()
+    }
+
+    /// Prevents the guard from cancelling anything when dropped.
+    pub fn disarm(mut self) {
+        self.armed = false;
+    }
+
+    /// Cancels every outstanding order.
+    ///
+    /// Cancellations are best-effort: orders that already landed or expired are rejected by the relay,
+    /// so failures are only logged.
+    ///
+    /// # Returns
+    ///
+    /// The orders that the relay accepted to cancel.
+    pub async fn cancel_all(&self) -> Vec<Outstanding> {
+        cancel_all(&self.rpc, &self.orders).await
+    }
+
+    /// Waits for `SIGINT` (or `SIGTERM` on unix) and then cancels every outstanding order.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Io`] if the signal handlers can't be installed.
+    #[cfg(feature = "tokio")]
+    pub async fn cancel_on_shutdown(&self) -> Result<Vec<Outstanding>> {
+        shutdown_signal().await?;
+        info!("shutdown requested, cancelling outstanding orders");
+        Ok(self.cancel_all().await)
+    }
+}
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        self.orders.guards.fetch_sub(1, Ordering::AcqRel);
+
+        #[cfg(feature = "tokio")]
+        self.cancel_on_drop();
+    }
+}
+
+impl CancelGuard {
+    #[cfg(feature = "tokio")]
+    fn cancel_on_drop(&self) {
+        if !self.armed || self.orders.lock().is_empty() {
+            return;
+        }
+
+        // `Drop` can't be async, and we may be dropped from within a runtime that we must not block on:
+        // run the cancellations to completion on a dedicated thread, with a fresh connection pool unless one was
+        // injected.
+        let rpc = self.rpc.detached();
+        let orders = &self.orders;
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                match tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(runtime) => {
+                        runtime.block_on(cancel_all(&rpc, orders));
+                    }
+                    Err(err) => error!(
+                        ?err,
+                        "failed to start a runtime to cancel outstanding orders"
+                    ),
+                }
+            });
+        });
+    }
+}
+
+async fn cancel_all(rpc: &MevShareRpcClient, orders: &Orders) -> Vec<Outstanding> {
+    let orders: Vec<_> = orders.lock().drain().collect();
+
+    let results = join_all(orders.iter().map(|order| cancel(rpc, order))).await;
+
+    orders
+        .into_iter()
+        .zip(results)
+        .filter_map(|(order, result)| match result {
+            Ok(()) => Some(order),
+            Err(err) => {
+                debug!(?order, ?err, "failed to cancel order");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Withdraws `order` from the relay.
+pub(crate) async fn cancel(rpc: &MevShareRpcClient, order: &Outstanding) -> Result<()> {
+    match order {
+        Outstanding::Transaction(tx_hash) => {
+            let _: Value = rpc
+                .post(
+                    MevShareRequest::CancelPrivateTransaction,
+                    [CancelPrivateTransactionParams { tx_hash: *tx_hash }],
+                )
+                .await?;
+        }
+        Outstanding::Bundle(replacement_uuid) => {
+            let _: Value = rpc
+                .post(
+                    MevShareRequest::CancelBundle,
+                    [CancelBundleParams {
+                        replacement_uuid: replacement_uuid.clone(),
+                    }],
+                )
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "tokio")]
+async fn shutdown_signal() -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut terminate = signal(SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result,
+            _ = terminate.recv() => Ok(()),
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await
+    }
+}
@@ -0,0 +1,106 @@
+//! Checking that the relay and the event stream are reachable and accept the client credentials, e.g. at startup or
+//! from a readiness probe. See [`crate::MevShareClient::health_check`].
+//!
+//! # Example
+//!
+//! ```
+//! let report = client.health_check().await;
+//! if !report.is_healthy() {
+//!     error!(%report, "MEV-Share is not usable");
+//!     std::process::exit(1);
+//! }
+//! ```
+
+use std::fmt::Display;
+use std::time::Duration;
+
+/// How long each check waits for an answer before failing.
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of one check of a [`HealthReport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// Passed, answering in `latency`.
+    Ok { latency: Duration },
+    /// Failed, for the reason given.
+    Failed(String),
+    /// Not run, because a check it depends on failed.
+    Skipped,
+}
+
+impl Check {
+    /// Whether the check passed.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Self::Ok { .. })
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ok { latency } => write!(f, "ok ({latency:.0?})"),
+            Self::Failed(reason) => write!(f, "failed: {reason}"),
+            Self::Skipped => f.write_str("skipped"),
+        }
+    }
+}
+
+/// See [`crate::MevShareClient::health_check`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthReport {
+    /// The provider answers `eth_blockNumber`.
+    pub provider: Check,
+    /// The relay JSON-RPC endpoint answers.
+    pub rpc: Check,
+    /// The relay accepts the signature of the requests.
+    pub auth: Check,
+    /// The SSE event stream accepts connections.
+    pub stream: Check,
+}
+
+impl HealthReport {
+    /// Whether every check passed.
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        [&self.provider, &self.rpc, &self.auth, &self.stream]
+            .iter()
+            .all(|check| check.is_ok())
+    }
+}
+
+impl Display for HealthReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "provider {}, rpc {}, auth {}, stream {}",
+            self.provider, self.rpc, self.auth, self.stream
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_report() {
+        let ok = Check::Ok {
+            latency: Duration::from_millis(42),
+        };
+        let mut report = HealthReport {
+            provider: ok.clone(),
+            rpc: ok.clone(),
+            auth: ok.clone(),
+            stream: ok,
+        };
+        assert!(report.is_healthy());
+
+        report.auth = Check::Failed("invalid signature".to_owned());
+        assert!(!report.is_healthy());
+        assert_eq!(
+            report.to_string(),
+            "provider ok (42ms), rpc ok (42ms), auth failed: invalid signature, stream ok (42ms)"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Eip1559TransactionRequest, Transaction, TransactionRequest, U256};
+
+/// Fee increase, in percent, of a cancellation over the transaction it replaces: nodes and builders require at least 10%.
+const CANCELLATION_FEE_BUMP: u64 = 10;
+
+/// Builds a 0-value self-transfer taking the nonce of `tx`, with fees high enough to replace it.
+///
+/// `tx.from` must be set, e.g. with [`Transaction::recover_from_mut`].
+pub fn cancellation_of(tx: &Transaction) -> TypedTransaction {
+    let bump = |fee: Option<U256>| {
+        let fee = fee.unwrap_or_default();
+        fee + fee * CANCELLATION_FEE_BUMP / 100 + 1
+    };
+
+    match tx.transaction_type.map(|kind| kind.as_u64()) {
+        Some(2) => {
+            let mut cancellation = Eip1559TransactionRequest::new()
+                .from(tx.from)
+                .to(tx.from)
+                .value(0)
+                .nonce(tx.nonce)
+                .gas(21_000)
+                .max_fee_per_gas(bump(tx.max_fee_per_gas))
+                .max_priority_fee_per_gas(bump(tx.max_priority_fee_per_gas));
+            cancellation.chain_id = tx.chain_id.map(|id| id.as_u64().into());
+            cancellation.into()
+        }
+        _ => {
+            let mut cancellation = TransactionRequest::new()
+                .from(tx.from)
+                .to(tx.from)
+                .value(0)
+                .nonce(tx.nonce)
+                .gas(21_000)
+                .gas_price(bump(tx.gas_price));
+            cancellation.chain_id = tx.chain_id.map(|id| id.as_u64().into());
+            cancellation.into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::utils::rlp;
+
+    #[test]
+    fn test_cancellation_of() {
+        let wallet = LocalWallet::new(&mut ethers::core::rand::thread_rng()).with_chain_id(1_u64);
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(ethers::types::Address::repeat_byte(0x11))
+            .value(1_000)
+            .nonce(7)
+            .gas(100_000)
+            .max_fee_per_gas(100)
+            .max_priority_fee_per_gas(10)
+            .chain_id(1)
+            .into();
+        let signed = tx.rlp_signed(&wallet.sign_transaction_sync(&tx).unwrap());
+        let mut tx: Transaction = rlp::decode(&signed).unwrap();
+        tx.recover_from_mut().unwrap();
+
+        let cancellation = cancellation_of(&tx);
+
+        assert_eq!(cancellation.from(), Some(&wallet.address()));
+        assert_eq!(cancellation.to_addr(), Some(&wallet.address()));
+        assert_eq!(cancellation.nonce(), Some(&7.into()));
+        assert_eq!(cancellation.value(), Some(&0.into()));
+        assert_eq!(cancellation.chain_id(), Some(1.into()));
+        let TypedTransaction::Eip1559(cancellation) = cancellation else {
+            panic!("expected an EIP-1559 transaction");
+        };
+        assert_eq!(cancellation.max_fee_per_gas, Some(111.into()));
+        assert_eq!(cancellation.max_priority_fee_per_gas, Some(12.into()));
+    }
+}
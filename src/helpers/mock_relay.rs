@@ -0,0 +1,52 @@
+//! A local HTTP server standing in for the relay in tests.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+/// Answers the `n`-th request, from 0, with the raw HTTP response `respond(n)` returns, or never if it returns `None`.
+///
+/// Returns the URL of the relay, and the requests it got, headers included and lowercased.
+pub(crate) async fn mock_relay<R: AsRef<[u8]> + Send + 'static>(
+    respond: impl Fn(u32) -> Option<R> + Send + 'static,
+) -> (String, UnboundedReceiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (requests, received) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        // connections left unanswered are kept open, for the client to time out
+        let mut unanswered = Vec::new();
+        for n in 0_u32.. {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                return;
+            };
+            let mut request = [0; 4096];
+            let len = socket.read(&mut request).await.unwrap();
+            _ = requests.send(String::from_utf8_lossy(&request[..len]).to_lowercase());
+
+            match respond(n) {
+                Some(response) => socket.write_all(response.as_ref()).await.unwrap(),
+                None => unanswered.push(socket),
+            }
+        }
+    });
+
+    (url, received)
+}
+
+/// A `200 OK` response to a JSON-RPC request, with the JSON `result`.
+pub(crate) fn json_rpc_response(result: &str) -> String {
+    http_response(
+        "200 OK",
+        &format!(r#"{{"jsonrpc":"2.0","id":1,"result":{result}}}"#),
+    )
+}
+
+/// A response with `status`, e.g. `"503 Service Unavailable"`, and `body`.
+pub(crate) fn http_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+}
@@ -1,4 +1,17 @@
+#[cfg(feature = "wait")]
+mod cancellation;
+#[cfg(test)]
+mod mock_relay;
 pub mod provider;
-mod selector;
+#[cfg(all(feature = "stream", feature = "tokio"))]
+mod read_ahead;
+mod time;
 
-pub use selector::SelectorDeserializer;
+#[cfg(feature = "wait")]
+pub use cancellation::cancellation_of;
+pub use mev_share_types::helpers::{system_now, DecimalU256};
+#[cfg(test)]
+pub(crate) use mock_relay::{http_response, json_rpc_response, mock_relay};
+#[cfg(all(feature = "stream", feature = "tokio"))]
+pub(crate) use read_ahead::read_ahead;
+pub(crate) use time::within;
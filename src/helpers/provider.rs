@@ -1,14 +1,57 @@
 use crate::{Error, Result};
+use async_trait::async_trait;
 use ethers::prelude::*;
-use futures::future::try_join_all;
+use futures::future::{self, try_join_all};
+#[cfg(not(target_arch = "wasm32"))]
+use futures::stream::BoxStream;
+#[cfg(target_arch = "wasm32")]
+use futures::stream::LocalBoxStream;
+use futures::stream::{self, Stream};
+use futures_timer::Delay;
+use std::any::Any;
 use tracing::*;
 
-/// A helper module for waiting on transactions and bundles inclusion.
-/// 
-/// Internally used to implement [`crate::PendingBundle::inclusion`] and [`crate::PendingTransaction::inclusion`].
-/// 
-// TODO: Looks like some of this is already in `ethers_provider::FilterWatcher`, so maybe we can use that internally?
-pub trait Waiter {
+/// Stream of the numbers of new blocks, see [`Waiter::new_blocks`]. Not `Send` on `wasm32`, where the provider
+/// futures aren't either.
+#[cfg(not(target_arch = "wasm32"))]
+pub type BlockStream<'a> = BoxStream<'a, U64>;
+
+/// Stream of the numbers of new blocks, see [`Waiter::new_blocks`]. Not `Send` on `wasm32`, where the provider
+/// futures aren't either.
+#[cfg(target_arch = "wasm32")]
+pub type BlockStream<'a> = LocalBoxStream<'a, U64>;
+
+/// Boxes `blocks` into a [`BlockStream`].
+#[cfg(not(target_arch = "wasm32"))]
+fn boxed<'a>(blocks: impl Stream<Item = U64> + Send + 'a) -> BlockStream<'a> {
+    blocks.boxed()
+}
+
+/// Boxes `blocks` into a [`BlockStream`].
+#[cfg(target_arch = "wasm32")]
+fn boxed<'a>(blocks: impl Stream<Item = U64> + 'a) -> BlockStream<'a> {
+    blocks.boxed_local()
+}
+
+/// The [`Middleware`] the client goes on-chain through: a [`Provider`] over a websocket, HTTP or IPC, or a stack of
+/// middlewares on top of one, e.g. a `SignerMiddleware<Provider<Http>, LocalWallet>`.
+///
+/// Implemented for every middleware with `'static` provider and error types. New blocks are watched with
+/// `eth_subscribe` when the provider at the bottom of the stack is a [`Provider<Ws>`], and by polling
+/// `eth_blockNumber` every [`Provider::get_interval`] otherwise: set a shorter interval with [`Provider::interval`] to
+/// notice inclusions sooner over HTTP.
+///
+/// Internally used to implement [`crate::bundle::PendingBundle::inclusion`] and [`crate::transaction::PendingTransaction::inclusion`].
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait Waiter: Middleware<Provider: 'static, Error: 'static> {
+    /// Returns a [`futures::Stream`] of the numbers of the blocks produced from now on.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Provider`] if the provider fails to subscribe to blocks or to fetch the current block number.
+    async fn new_blocks(&self) -> Result<BlockStream<'_>>;
+
     /// Waits for a transaction to be included in a block.
     ///
     /// # Arguments
@@ -19,6 +62,11 @@ pub trait Waiter {
     /// # Returns
     ///
     /// A tuple containing the [`Transaction`] and the block number it was included in.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::TransactionTimeout`] if the transaction is not included in a block before `max_block`.
+    /// * [`Error::BlockStreamEnded`] if the provider stops producing blocks before then.
     async fn wait_for_tx(&self, hash: TxHash, max_block: U64) -> Result<(Transaction, U64)>;
 
     /// Waits for a transaction to be included in a block.
@@ -31,6 +79,10 @@ pub trait Waiter {
     /// # Returns
     ///
     /// A tuple containing the [`TransactionReceipt`] and the block number it was included in.
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Self::wait_for_tx`].
     async fn wait_for_tx_receipt(
         &self,
         hash: TxHash,
@@ -48,39 +100,79 @@ pub trait Waiter {
     /// # Returns
     ///
     /// A tuple containing the [`TransactionReceipt`]s and the block number it was included in.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::BundleTimeout`] if the bundle is not included in a block before `max_block`.
+    /// * [`Error::BlockStreamEnded`] if the provider stops producing blocks before then.
     async fn wait_for_bundle(
         &self,
         hash: TxHash,
         txs: Vec<TxHash>,
         max_block: U64,
     ) -> Result<(Vec<TransactionReceipt>, U64)>;
+
+    /// Waits for `confirmations` blocks on top of the block the transactions of `receipts` were included in, checking
+    /// at every block that they still are.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Reorged`] if a receipt disappears or moves to another block, i.e. the block was reorged out.
+    /// * [`Error::BlockStreamEnded`] if the provider stops producing blocks before then.
+    async fn wait_for_confirmations(
+        &self,
+        receipts: &[TransactionReceipt],
+        confirmations: u64,
+    ) -> Result<()>;
 }
 
 macro_rules! wait_for_tx {
     ($hash: ident, $max_block: ident, $provider: ident, $get_tx: ident) => {
-        if let Some(tx) = $provider.$get_tx($hash).await? {
+        if let Some(tx) = $provider.$get_tx($hash).await.map_err(Error::middleware)? {
             let block = tx.block_number.unwrap();
             return Ok((tx, block));
         }
 
-        let mut block_subscription = $provider.subscribe_blocks().await?;
-        while let Some(block) = block_subscription.next().await {
-            if let Some(tx) = $provider.$get_tx($hash).await? {
-                return Ok((tx, block.number.unwrap()));
-            }
+        let blocks = $provider.new_blocks().await?;
+        return wait_for_tx_over(blocks, $hash, $max_block, |hash| async move {
+            $provider.$get_tx(hash).await.map_err(Error::middleware)
+        })
+        .await;
+    };
+}
 
-            let block_number = block.number.unwrap();
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M> Waiter for M
+where
+    M: Middleware<Provider: 'static, Error: 'static>,
+{
+    /// See [`Waiter::new_blocks`]
+    async fn new_blocks(&self) -> Result<BlockStream<'_>> {
+        let provider = self.provider();
+        if let Some(ws) = (provider as &(dyn Any + Send + Sync)).downcast_ref::<Provider<Ws>>() {
+            let blocks = ws.subscribe_blocks().await?;
+            return Ok(boxed(
+                blocks.filter_map(|block| future::ready(block.number)),
+            ));
+        }
 
-            if block_number >= $max_block {
-                return Err(Error::TransactionTimeout($hash, block_number));
+        let interval = provider.get_interval();
+        let latest = provider.get_block_number().await?;
+        let blocks = stream::unfold(latest, move |latest| async move {
+            loop {
+                Delay::new(interval).await;
+                match provider.get_block_number().await {
+                    Ok(block) if block > latest => return Some((block, block)),
+                    Ok(_) => {}
+                    Err(err) => warn!(%err, "failed to poll the block number"),
+                }
             }
-        }
+        });
 
-        unreachable!("at each iteration, block number increases")
-    };
-}
+        Ok(boxed(blocks))
+    }
 
-impl Waiter for Provider<Ws> {
     /// See [`Waiter::wait_for_tx`]
     #[instrument(skip(self))]
     async fn wait_for_tx(&self, hash: TxHash, max_block: U64) -> Result<(Transaction, U64)> {
@@ -105,36 +197,142 @@ impl Waiter for Provider<Ws> {
         txs: Vec<TxHash>,
         max_block: U64,
     ) -> Result<(Vec<TransactionReceipt>, U64)> {
-        // checks whether the bundle has landed
-        macro_rules! check_inclusion {
-            () => {
-                let receipts = fetch_receipts(self, &txs).await?;
-                if receipts.len() > 0 {
-                    let block = receipts.first().expect("len() > 0").block_number.unwrap();
-                    return Ok((receipts, block));
-                }
-            };
-        }
-
         // in case it's already landed
-        check_inclusion!();
+        if let Some(inclusion) = check_inclusion(self, hash, &txs).await? {
+            return Ok(inclusion);
+        }
 
         // subscribe to blocks up to max_block and check for bundle to land
-        let mut block_subscription = self.subscribe_blocks().await?;
-        while let Some(block) = block_subscription.next().await {
-            check_inclusion!();
+        let blocks = self.new_blocks().await?;
+        wait_for_bundle_over(self, blocks, hash, txs, max_block).await
+    }
 
-            if let Some(block) = block.number && block > max_block {
-                return Err(Error::BundleTimeout(txs, block));
-            }
+    /// See [`Waiter::wait_for_confirmations`]
+    #[instrument(skip(self, receipts))]
+    async fn wait_for_confirmations(
+        &self,
+        receipts: &[TransactionReceipt],
+        confirmations: u64,
+    ) -> Result<()> {
+        let Some(included) = receipts.iter().filter_map(|r| r.block_number).max() else {
+            return Ok(());
+        };
+        let confirmed = included + confirmations;
+
+        // subscribe first, not to miss a block between the check and the subscription
+        let blocks = self.new_blocks().await?;
+
+        check_receipts(self, receipts).await?;
+        if self.get_block_number().await.map_err(Error::middleware)? >= confirmed {
+            return Ok(());
         }
 
-        unreachable!("at each iteration, block number increases")
+        wait_for_confirmations_over(self, blocks, receipts, confirmed).await
     }
 }
 
-async fn fetch_receipts(
-    provider: &Provider<Ws>,
+/// Checks at each of `blocks` whether the transaction `hash`, fetched with `get_tx`, landed, until `max_block`.
+async fn wait_for_tx_over<T, F>(
+    mut blocks: BlockStream<'_>,
+    hash: TxHash,
+    max_block: U64,
+    get_tx: impl Fn(TxHash) -> F,
+) -> Result<(T, U64)>
+where
+    F: std::future::Future<Output = Result<Option<T>>>,
+{
+    while let Some(block_number) = blocks.next().await {
+        if let Some(tx) = get_tx(hash).await? {
+            return Ok((tx, block_number));
+        }
+
+        if block_number >= max_block {
+            return Err(Error::TransactionTimeout(hash, block_number));
+        }
+    }
+
+    Err(Error::BlockStreamEnded)
+}
+
+/// Checks at each of `blocks` whether the bundle `hash` of `txs` landed, until `max_block`.
+async fn wait_for_bundle_over<M: Waiter>(
+    provider: &M,
+    mut blocks: BlockStream<'_>,
+    hash: TxHash,
+    txs: Vec<TxHash>,
+    max_block: U64,
+) -> Result<(Vec<TransactionReceipt>, U64)> {
+    while let Some(block) = blocks.next().await {
+        if let Some(inclusion) = check_inclusion(provider, hash, &txs).await? {
+            return Ok(inclusion);
+        }
+
+        if block > max_block {
+            return Err(Error::BundleTimeout(txs, block));
+        }
+    }
+
+    Err(Error::BlockStreamEnded)
+}
+
+/// Checks at each of `blocks` that the transactions of `receipts` are still included, until `confirmed`.
+async fn wait_for_confirmations_over<M: Waiter>(
+    provider: &M,
+    mut blocks: BlockStream<'_>,
+    receipts: &[TransactionReceipt],
+    confirmed: U64,
+) -> Result<()> {
+    while let Some(block) = blocks.next().await {
+        check_receipts(provider, receipts).await?;
+
+        if block >= confirmed {
+            return Ok(());
+        }
+    }
+
+    Err(Error::BlockStreamEnded)
+}
+
+/// Checks whether the bundle `hash` of `txs` has landed, returning its receipts and block if so.
+async fn check_inclusion<M: Waiter>(
+    provider: &M,
+    hash: TxHash,
+    txs: &[TxHash],
+) -> Result<Option<(Vec<TransactionReceipt>, U64)>> {
+    let receipts = fetch_receipts(provider, txs).await?;
+    if receipts.is_empty() {
+        return Ok(None);
+    }
+
+    let block = receipts.first().expect("not empty").block_number.unwrap();
+    debug!(?hash, %block, "bundle landed");
+    Ok(Some((receipts, block)))
+}
+
+/// Checks that the transactions of `receipts` are still included in the same blocks.
+async fn check_receipts<M: Waiter>(provider: &M, receipts: &[TransactionReceipt]) -> Result<()> {
+    let hashes: Vec<TxHash> = receipts.iter().map(|r| r.transaction_hash).collect();
+    let current = try_join_all(
+        hashes
+            .iter()
+            .map(|tx| provider.get_transaction_receipt(*tx)),
+    )
+    .await
+    .map_err(Error::middleware)?;
+
+    for (receipt, current) in receipts.iter().zip(current) {
+        if current.map(|current| current.block_hash) != Some(receipt.block_hash) {
+            let block = receipt.block_number.unwrap_or_default();
+            warn!(?receipt.transaction_hash, %block, "reorged out");
+            return Err(Error::Reorged(hashes, block));
+        }
+    }
+
+    Ok(())
+}
+
+async fn fetch_receipts<M: Waiter>(
+    provider: &M,
     hashes: &[TxHash],
 ) -> Result<Vec<TransactionReceipt>> {
     let receipts = try_join_all(
@@ -142,7 +340,8 @@ async fn fetch_receipts(
             .iter()
             .map(|tx| provider.get_transaction_receipt(*tx)),
     )
-    .await?
+    .await
+    .map_err(Error::middleware)?
     .into_iter()
     .flatten()
     .collect::<Vec<_>>();
@@ -154,7 +353,7 @@ async fn fetch_receipts(
         Err(Error::BundleDiscard(receipts))
     } else if receipts
         .iter()
-        .filter(|r| r.status.unwrap() != U64::one())
+        .filter(|r| r.status.is_some_and(|status| status.is_zero()))
         .count()
         > 0
     {
@@ -165,3 +364,74 @@ async fn fetch_receipts(
         Ok(receipts)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_polled_blocks() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(Duration::from_millis(1));
+
+        // responses are popped from the back: the current block, then a poll with no new block, then new blocks
+        for block in [103_u64, 101, 100, 100] {
+            mock.push(U64::from(block)).unwrap();
+        }
+
+        let blocks = futures::executor::block_on(async {
+            let blocks = provider.new_blocks().await.unwrap();
+            blocks.take(2).collect::<Vec<_>>().await
+        });
+        assert_eq!(blocks, vec![U64::from(101), U64::from(103)]);
+
+        assert!(matches!(
+            Error::middleware(ProviderError::CustomError("down".to_owned())),
+            Error::Provider(_)
+        ));
+    }
+
+    #[test]
+    fn test_block_stream_ended() {
+        let (provider, mock) = Provider::mocked();
+        let hash = TxHash::repeat_byte(1);
+        let receipt = TransactionReceipt {
+            transaction_hash: hash,
+            block_number: Some(U64::from(100)),
+            ..TransactionReceipt::default()
+        };
+        // the websocket disconnects, or the polling stops, two blocks before the deadline
+        let blocks = || boxed(stream::iter([U64::from(100), U64::from(101)]));
+
+        futures::executor::block_on(async {
+            let not_landed = |_| async { Ok(None::<TransactionReceipt>) };
+            assert!(matches!(
+                wait_for_tx_over(blocks(), hash, U64::from(103), not_landed).await,
+                Err(Error::BlockStreamEnded)
+            ));
+
+            for _ in 0..2 {
+                mock.push(serde_json::Value::Null).unwrap();
+            }
+            assert!(matches!(
+                wait_for_bundle_over(&provider, blocks(), hash, vec![hash], U64::from(103)).await,
+                Err(Error::BlockStreamEnded)
+            ));
+
+            for _ in 0..2 {
+                mock.push(receipt.clone()).unwrap();
+            }
+            assert!(matches!(
+                wait_for_confirmations_over(
+                    &provider,
+                    blocks(),
+                    std::slice::from_ref(&receipt),
+                    U64::from(103)
+                )
+                .await,
+                Err(Error::BlockStreamEnded)
+            ));
+        });
+    }
+}
@@ -0,0 +1,52 @@
+use futures::channel::mpsc;
+use futures::future::Either;
+use futures::{SinkExt, Stream, StreamExt};
+
+/// Reads `stream` ahead of its consumer, from a task of its own, buffering up to `capacity` items, e.g. not to fall
+/// behind the event stream while handling an event. Returns `stream` as is if `capacity` is 0.
+///
+/// Must be called from within a tokio runtime if `capacity` is not 0. The task ends with `stream`, or once the
+/// returned stream is dropped.
+pub fn read_ahead<S>(stream: S, capacity: usize) -> impl Stream<Item = S::Item>
+where
+    S: Stream + Send + 'static,
+    S::Item: Send,
+{
+    if capacity == 0 {
+        return Either::Left(stream);
+    }
+
+    // the channel holds `capacity` items, plus one per sender
+    let (mut sender, receiver) = mpsc::channel(capacity - 1);
+    tokio::spawn(async move {
+        let mut stream = std::pin::pin!(stream);
+        while let Some(item) = stream.next().await {
+            if sender.send(item).await.is_err() {
+                // the consumer is gone
+                break;
+            }
+        }
+    });
+
+    Either::Right(receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+
+    #[tokio::test]
+    async fn test_read_ahead() {
+        let items: Vec<_> = read_ahead(stream::iter(0..10), 0).collect().await;
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+
+        let items: Vec<_> = read_ahead(stream::iter(0..10), 3).collect().await;
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+
+        // the reading task stops with the consumer
+        let mut items = Box::pin(read_ahead(stream::iter(0..), 1));
+        assert_eq!(items.next().await, Some(0));
+        drop(items);
+    }
+}
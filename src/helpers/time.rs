@@ -0,0 +1,20 @@
+use futures::future::{self, Either};
+use futures_timer::Delay;
+use std::future::Future;
+use std::pin::pin;
+use std::time::Duration;
+
+/// Runs `future`, giving up after `timeout` if any with `Err(timeout)`. Unlike a `tokio` timeout, works on any
+/// runtime, and in the browser.
+pub(crate) async fn within<T>(
+    timeout: Option<Duration>,
+    future: impl Future<Output = T>,
+) -> Result<T, Duration> {
+    let Some(timeout) = timeout else {
+        return Ok(future.await);
+    };
+    match future::select(pin!(future), Delay::new(timeout)).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(timeout),
+    }
+}
@@ -0,0 +1,237 @@
+use super::stream::{get_with_backoff, page_limit};
+use crate::api::types::{EventHistory, GetEventHistoryParams};
+use crate::{MevShareClient, Result, Waiter};
+use ethers::providers::{Provider, Ws};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::PathBuf;
+use tracing::*;
+
+/// Position of a [`HistoryCrawler`] in the event history: the next page starts at the `offset`-th event of `block`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint {
+    pub block: u64,
+    pub offset: u32,
+}
+
+/// Persists the [`Checkpoint`] of a [`HistoryCrawler`] across restarts.
+pub trait CheckpointStore {
+    /// Loads the last saved checkpoint, if any.
+    ///
+    /// # Errors
+    ///
+    /// If the checkpoint exists but can't be read.
+    fn load(&self) -> Result<Option<Checkpoint>>;
+
+    /// Saves `checkpoint`, replacing the previous one.
+    ///
+    /// # Errors
+    ///
+    /// If the checkpoint can't be written.
+    fn save(&self, checkpoint: &Checkpoint) -> Result<()>;
+}
+
+/// A [`CheckpointStore`] backed by a JSON file.
+#[derive(Clone, Debug)]
+pub struct FileCheckpoint {
+    path: PathBuf,
+}
+
+impl FileCheckpoint {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl CheckpointStore for FileCheckpoint {
+    fn load(&self) -> Result<Option<Checkpoint>> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn save(&self, checkpoint: &Checkpoint) -> Result<()> {
+        // write-then-rename, so that a crash never leaves a truncated checkpoint behind
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(checkpoint)?)?;
+        std::fs::rename(tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Walks the whole event history, or the block range of the given params, one page at a time, saving a [`Checkpoint`]
+/// after each page so that a restarted crawl resumes where the previous one stopped.
+///
+/// Pages are handed to the handler before the checkpoint is saved: after a crash, the last page may be delivered twice.
+///
+/// # Example
+///
+/// ```
+/// let indexer = Indexer::open("hints.sqlite")?;
+///
+/// let crawled = HistoryCrawler::new(&client, FileCheckpoint::new("crawl.json"))
+///     .crawl(|events| async {
+///         for event in &events {
+///             indexer.insert_history(event)?;
+///         }
+///         Ok(())
+///     })
+///     .await?;
+/// ```
+pub struct HistoryCrawler<'c, S, M = Provider<Ws>> {
+    client: &'c MevShareClient<M>,
+    store: S,
+    params: GetEventHistoryParams,
+}
+
+impl<'c, S: CheckpointStore, M: Waiter> HistoryCrawler<'c, S, M> {
+    pub fn new(client: &'c MevShareClient<M>, store: S) -> Self {
+        Self {
+            client,
+            store,
+            params: GetEventHistoryParams::default(),
+        }
+    }
+
+    /// Restricts the crawl to the block/timestamp range of `params`. `limit` and `offset` are managed by the crawler.
+    #[must_use]
+    pub fn with_params(self, params: GetEventHistoryParams) -> Self {
+        Self { params, ..self }
+    }
+
+    /// Crawls from the saved checkpoint (or the beginning of the range) until the end of the history, passing each page to `handle`.
+    ///
+    /// # Returns
+    ///
+    /// The number of events crawled.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::InvalidParams`] if the page size, the `limit` of the params or the relay's `max_limit`, is 0.
+    /// * [`crate::Error::Rest`] if fetching a page fails.
+    /// * Any error returned by `handle` or by the [`CheckpointStore`]: the checkpoint is left at the last handled page.
+    pub async fn crawl<F, Fut>(&self, mut handle: F) -> Result<u64>
+    where
+        F: FnMut(Vec<EventHistory>) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let info = self.client.get_event_history_info().await?;
+        let limit = page_limit(self.params.limit, info.max_limit)?;
+
+        let mut checkpoint = match self.store.load()? {
+            Some(checkpoint) => {
+                debug!(?checkpoint, "resuming crawl");
+                checkpoint
+            }
+            None => Checkpoint {
+                block: self.params.block_start.unwrap_or(info.min_block),
+                offset: 0,
+            },
+        };
+
+        let mut crawled = 0;
+
+        loop {
+            let params = GetEventHistoryParams {
+                block_start: Some(checkpoint.block),
+                limit: Some(limit),
+                offset: Some(checkpoint.offset),
+                ..self.params.clone()
+            };
+
+            let events = get_with_backoff(self.client, params).await?;
+            let count = events.len();
+
+            let next = next_checkpoint(checkpoint, &events);
+            handle(events).await?;
+
+            self.store.save(&next)?;
+            checkpoint = next;
+            crawled += count as u64;
+
+            trace!(?checkpoint, crawled, "crawled page");
+
+            if count < limit as usize {
+                break;
+            }
+        }
+
+        info!(crawled, ?checkpoint, "crawl complete");
+        Ok(crawled)
+    }
+}
+
+/// Moves `checkpoint` past `events`, re-anchoring it at the last block seen so that offsets stay small.
+fn next_checkpoint(checkpoint: Checkpoint, events: &[EventHistory]) -> Checkpoint {
+    let Some(last) = events.last() else {
+        return checkpoint;
+    };
+
+    let in_last_block = events
+        .iter()
+        .rev()
+        .take_while(|event| event.block == last.block)
+        .count();
+    let in_last_block = u32::try_from(in_last_block).unwrap_or(u32::MAX);
+
+    if last.block == checkpoint.block {
+        Checkpoint {
+            block: checkpoint.block,
+            offset: checkpoint.offset + in_last_block,
+        }
+    } else {
+        Checkpoint {
+            block: last.block,
+            offset: in_last_block,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::EventHint;
+
+    fn event(block: u64) -> EventHistory {
+        EventHistory {
+            block,
+            timestamp: 0,
+            hint: EventHint {
+                txs: None,
+                hash: ethers::types::TxHash::default(),
+                logs: None,
+                gas_used: None,
+                mev_gas_price: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_next_checkpoint() {
+        let start = Checkpoint {
+            block: 10,
+            offset: 2,
+        };
+
+        assert_eq!(next_checkpoint(start, &[]), start);
+
+        assert_eq!(
+            next_checkpoint(start, &[event(10), event(10)]),
+            Checkpoint {
+                block: 10,
+                offset: 4
+            }
+        );
+
+        assert_eq!(
+            next_checkpoint(start, &[event(10), event(11), event(12), event(12)]),
+            Checkpoint {
+                block: 12,
+                offset: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use crate::api::types::EventHistoryInfo;
+use instant::Instant;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// Default time a `/history/info` response is reused for.
+pub const HISTORY_INFO_TTL: Duration = Duration::from_secs(60);
+
+/// Caches the `/history/info` response for a limited time.
+#[derive(Debug)]
+pub(crate) struct InfoCache {
+    ttl: Duration,
+    cached: Mutex<Option<(Instant, EventHistoryInfo)>>,
+}
+
+impl InfoCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            cached: Mutex::default(),
+        }
+    }
+
+    /// Returns the cached info, unless it expired.
+    pub fn get(&self) -> Option<EventHistoryInfo> {
+        match &*self.cached.lock().unwrap_or_else(PoisonError::into_inner) {
+            Some((fetched_at, info)) if fetched_at.elapsed() < self.ttl => Some(info.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn set(&self, info: EventHistoryInfo) {
+        *self.cached.lock().unwrap_or_else(PoisonError::into_inner) = Some((Instant::now(), info));
+    }
+
+    /// An empty cache with the same TTL, e.g. when the client switches networks: its clones, still on the previous
+    /// network, keep theirs.
+    pub fn empty_like(&self) -> Self {
+        Self::new(self.ttl)
+    }
+}
@@ -0,0 +1,30 @@
+//! Querying, crawling, replaying and analyzing the hints history exposed by the MEV-Share API,
+//! see [`crate::MevShareClient::get_event_history`].
+
+//!
+//! Everything that queries the API requires the `history` feature (enabled by default).
+
+#[cfg(feature = "history")]
+mod crawler;
+#[cfg(feature = "history")]
+mod info_cache;
+mod outcome;
+#[cfg(feature = "history")]
+mod replay;
+#[cfg(feature = "history")]
+mod stream;
+mod summary;
+
+pub use crate::api::types::{EventHint, EventHistory, EventHistoryInfo, GetEventHistoryParams};
+#[cfg(feature = "history")]
+pub use crawler::*;
+#[cfg(feature = "history")]
+pub(crate) use info_cache::InfoCache;
+#[cfg(feature = "history")]
+pub use info_cache::HISTORY_INFO_TTL;
+pub use outcome::*;
+#[cfg(feature = "history")]
+pub use replay::*;
+#[cfg(feature = "history")]
+pub(crate) use stream::paginate;
+pub use summary::*;
@@ -0,0 +1,116 @@
+use crate::api::types::EventHistory;
+use crate::helpers::provider::Waiter;
+use crate::{Error, Result};
+use ethers::prelude::*;
+use futures::stream::{Stream, StreamExt};
+
+/// Maximum number of hints whose outcome is looked up concurrently by [`outcomes`].
+const CONCURRENCY: usize = 8;
+
+/// A historical hint joined with what happened on-chain. See [`join_outcome`].
+#[derive(Clone, Debug)]
+pub struct HintOutcome {
+    /// The hint, as returned by [`crate::MevShareClient::get_event_history`].
+    pub event: EventHistory,
+    /// Where and how the hinted transaction landed, if it did.
+    pub landing: Option<Landing>,
+}
+
+/// See [`HintOutcome::landing`].
+#[derive(Clone, Debug)]
+pub struct Landing {
+    /// Block the transaction was included in.
+    pub block: U64,
+    /// Hash of that block.
+    pub block_hash: H256,
+    /// Position of the transaction in the block.
+    pub transaction_index: U64,
+    /// Number of blocks between the hint broadcast and the inclusion.
+    pub blocks_after_hint: u64,
+    /// Gas used by the transaction.
+    pub gas_used: Option<U256>,
+    /// Price paid per unit of gas.
+    pub effective_gas_price: Option<U256>,
+    /// Whether the transaction succeeded.
+    pub success: bool,
+    /// The transaction right after it in the block, when sent by somebody else: most likely a backrun.
+    pub backrun: Option<TxHash>,
+}
+
+/// Looks up whether the transaction hinted by `event` landed, where, with what gas, and whether a backrun followed it.
+///
+/// Only transaction hints can be joined: bundle hashes never have a receipt, so their [`HintOutcome::landing`] is always `None`.
+///
+/// # Errors
+///
+/// * [`crate::Error::Provider`] if any of the lookups fails.
+pub async fn join_outcome<M: Waiter>(provider: &M, event: EventHistory) -> Result<HintOutcome> {
+    let Some(receipt) = provider
+        .get_transaction_receipt(event.hint.hash)
+        .await
+        .map_err(Error::middleware)?
+    else {
+        return Ok(HintOutcome {
+            event,
+            landing: None,
+        });
+    };
+
+    let (Some(block), Some(block_hash)) = (receipt.block_number, receipt.block_hash) else {
+        // pending receipts have no block yet
+        return Ok(HintOutcome {
+            event,
+            landing: None,
+        });
+    };
+
+    let backrun = match provider
+        .get_block(block_hash)
+        .await
+        .map_err(Error::middleware)?
+    {
+        Some(including_block) => {
+            match including_block
+                .transactions
+                .get(receipt.transaction_index.as_usize() + 1)
+            {
+                Some(next) => provider
+                    .get_transaction(*next)
+                    .await
+                    .map_err(Error::middleware)?
+                    .filter(|next| next.from != receipt.from)
+                    .map(|next| next.hash),
+                None => None,
+            }
+        }
+        None => None,
+    };
+
+    let landing = Landing {
+        block,
+        block_hash,
+        transaction_index: receipt.transaction_index,
+        blocks_after_hint: block.as_u64().saturating_sub(event.block),
+        gas_used: receipt.gas_used,
+        effective_gas_price: receipt.effective_gas_price,
+        success: receipt.status == Some(U64::one()),
+        backrun,
+    };
+
+    Ok(HintOutcome {
+        event,
+        landing: Some(landing),
+    })
+}
+
+/// Joins each of `events` with its on-chain outcome, preserving their order. See [`join_outcome`].
+///
+/// See [`crate::MevShareClient::event_history_outcomes`].
+pub fn outcomes<'s, M: Waiter>(
+    provider: &'s M,
+    events: impl Stream<Item = Result<EventHistory>> + 's,
+) -> impl Stream<Item = Result<HintOutcome>> + 's {
+    events
+        .map(move |event| async move { join_outcome(provider, event?).await })
+        .buffered(CONCURRENCY)
+}
@@ -0,0 +1,44 @@
+use crate::api::types::{EventHistory, MevShareEvent};
+use crate::Result;
+use futures::stream::{Stream, StreamExt};
+use futures_timer::Delay;
+use instant::Instant;
+use std::time::Duration;
+
+/// Re-emits historical `events` as [`MevShareEvent`]s, spaced out like they were originally broadcast.
+///
+/// The first event is emitted right away; each following one when `(timestamp - first timestamp) / speed` seconds have elapsed.
+/// A `speed` of `1.0` replays in real time, `f64::INFINITY` as fast as `events` can be fetched.
+///
+/// See [`crate::MevShareClient::replay_event_history`].
+///
+/// # Panics
+///
+/// If `speed` is not strictly positive.
+pub fn replay<'s>(
+    events: impl Stream<Item = Result<EventHistory>> + 's,
+    speed: f64,
+) -> impl Stream<Item = Result<MevShareEvent>> + 's {
+    assert!(speed > 0.0, "replay speed must be positive, got {speed}");
+
+    // wall-clock instant and timestamp of the first event
+    let mut origin: Option<(Instant, u64)> = None;
+
+    events.then(move |event| {
+        let deadline = event.as_ref().ok().map(|event| {
+            let (start, first_timestamp) =
+                *origin.get_or_insert_with(|| (Instant::now(), event.timestamp));
+
+            #[allow(clippy::cast_precision_loss)]
+            let elapsed = event.timestamp.saturating_sub(first_timestamp) as f64 / speed;
+            start + Duration::from_secs_f64(elapsed)
+        });
+
+        async move {
+            if let Some(deadline) = deadline {
+                Delay::new(deadline.saturating_duration_since(Instant::now())).await;
+            }
+            event.map(Into::into)
+        }
+    })
+}
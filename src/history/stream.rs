@@ -0,0 +1,189 @@
+use crate::api::types::{EventHistory, GetEventHistoryParams};
+use crate::error::RestError;
+use crate::{Error, MevShareClient, Result, Waiter};
+use futures::stream::{self, Stream, TryStreamExt};
+use futures_timer::Delay;
+use reqwest::StatusCode;
+use std::time::Duration;
+use tracing::*;
+
+/// Maximum number of attempts to fetch a page when the relay rate-limits us.
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Initial wait before retrying a rate-limited request, doubled at each attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Paging state of [`paginate`].
+struct Page {
+    params: GetEventHistoryParams,
+    limit: u32,
+    done: bool,
+}
+
+/// Streams the events matching `params`, fetching one page of at most `/history/info`'s `max_limit` events at a time.
+///
+/// See [`crate::MevShareClient::event_history_stream`].
+pub(crate) fn paginate<'c, M: Waiter>(
+    client: &'c MevShareClient<M>,
+    params: GetEventHistoryParams,
+) -> impl Stream<Item = Result<EventHistory>> + 'c {
+    stream::try_unfold(None, move |page| next_page(client, params.clone(), page))
+        .map_ok(|events| stream::iter(events.into_iter().map(Ok)))
+        .try_flatten()
+}
+
+/// Fetches the events of `page`, returning the state for the next one. The first page is set up from `params`.
+async fn next_page<M: Waiter>(
+    client: &MevShareClient<M>,
+    params: GetEventHistoryParams,
+    page: Option<Page>,
+) -> Result<Option<(Vec<EventHistory>, Option<Page>)>> {
+    let mut page = match page {
+        Some(page) if page.done => return Ok(None),
+        Some(page) => page,
+        None => {
+            let max_limit = client.get_event_history_info().await?.max_limit;
+            let limit = page_limit(params.limit, max_limit)?;
+
+            Page {
+                params: GetEventHistoryParams {
+                    limit: Some(limit),
+                    offset: Some(params.offset.unwrap_or_default()),
+                    ..params
+                },
+                limit,
+                done: false,
+            }
+        }
+    };
+
+    let events = get_with_backoff(client, page.params.clone()).await?;
+    trace!(offset = ?page.params.offset, count = events.len(), "fetched event history page");
+
+    // a short page means there's nothing left
+    page.done = events.len() < page.limit as usize;
+    page.params.offset = page
+        .params
+        .offset
+        .map(|offset| offset + u32::try_from(events.len()).unwrap_or(u32::MAX));
+
+    Ok(Some((events, Some(page))))
+}
+
+/// Number of events to fetch per page: `limit`, at most the relay's `max_limit`.
+///
+/// # Errors
+///
+/// * [`Error::InvalidParams`] if it is 0: every page would be empty, yet never short, so the pagination would never end.
+pub(crate) fn page_limit(limit: Option<u32>, max_limit: u32) -> Result<u32> {
+    match limit.map_or(max_limit, |limit| limit.min(max_limit)) {
+        0 => Err(Error::InvalidParams(format!(
+            "event history pages of 0 events, with limit {limit:?} and max_limit {max_limit}"
+        ))),
+        limit => Ok(limit),
+    }
+}
+
+/// Fetches a page of events, backing off exponentially while the relay answers with `429 Too Many Requests`.
+pub(crate) async fn get_with_backoff<M: Waiter>(
+    client: &MevShareClient<M>,
+    params: GetEventHistoryParams,
+) -> Result<Vec<EventHistory>> {
+    let mut backoff = INITIAL_BACKOFF;
+
+    for attempt in 1.. {
+        match client.get_event_history(params.clone()).await {
+            Err(Error::Rest(RestError::Network(err)))
+                if err.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+                    && attempt < MAX_ATTEMPTS =>
+            {
+                debug!(?backoff, attempt, "rate limited by the relay, backing off");
+                Delay::new(backoff).await;
+                backoff *= 2;
+            }
+            result => return result,
+        }
+    }
+
+    unreachable!("the last attempt always returns")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::networks::MevShareNetwork;
+    use crate::api::types::EventHint;
+    use crate::helpers::{http_response, mock_relay};
+    use ethers::prelude::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_page_limit() {
+        assert_eq!(page_limit(None, 500).unwrap(), 500);
+        assert_eq!(page_limit(Some(100), 500).unwrap(), 100);
+        assert_eq!(page_limit(Some(1000), 500).unwrap(), 500);
+
+        assert!(matches!(
+            page_limit(Some(0), 500),
+            Err(Error::InvalidParams(_))
+        ));
+        assert!(matches!(page_limit(None, 0), Err(Error::InvalidParams(_))));
+    }
+
+    #[tokio::test]
+    async fn test_paginate() {
+        let event = |block| EventHistory {
+            block,
+            timestamp: 0,
+            hint: EventHint {
+                txs: None,
+                hash: TxHash::repeat_byte(1),
+                logs: None,
+                gas_used: None,
+                mev_gas_price: None,
+            },
+        };
+        let info = json!({
+            "minBlock": 1,
+            "maxBlock": 10,
+            "minTimestamp": 0,
+            "maxTimestamp": 0,
+            "count": 3,
+            "maxLimit": 2,
+        });
+
+        // /history/info, then a full page, then a short one
+        let (url, mut requests) = mock_relay(move |n| {
+            let body = match n {
+                0 => info.clone(),
+                1 => json!([event(1), event(2)]),
+                2 => json!([event(3)]),
+                _ => return None,
+            };
+            Some(http_response("200 OK", &body.to_string()))
+        })
+        .await;
+        let (provider, _) = Provider::mocked();
+        let client = MevShareClient::new_with_network(
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            provider,
+            MevShareNetwork::try_from(1_u64).unwrap(),
+        )
+        .with_stream_url(url);
+
+        let events: Vec<_> = paginate(&client, GetEventHistoryParams::default())
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(
+            events.iter().map(|event| event.block).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+
+        assert!(requests.recv().await.unwrap().contains("/history/info"));
+        assert!(requests.recv().await.unwrap().contains("offset=0"));
+        assert!(requests.recv().await.unwrap().contains("offset=2"));
+        // the short page is the last one
+        assert!(requests.try_recv().is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use crate::api::types::EventHistory;
+use ethers::types::{Address, U256};
+use std::collections::{HashMap, HashSet};
+
+/// Summary statistics over a set of historical hints: how many hints per block, how much they disclose,
+/// how much gas they use, and which contracts they target.
+///
+/// Build one with [`crate::MevShareClient::summarize_event_history`], or incrementally via [`HintSummary::add`] / [`Extend`].
+///
+/// # Example
+///
+/// ```
+/// let summary = client
+///     .summarize_event_history(GetEventHistoryParams::builder().block_start(current_block - 7200).build())
+///     .await?;
+///
+/// println!("{:.2} hints/block, {:.0}% with calldata", summary.hints_per_block(), summary.calldata_share() * 100.0);
+/// for (contract, hints) in summary.top_targets(10) {
+///     println!("{contract:?}: {hints}");
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HintSummary {
+    /// Number of hints.
+    pub hints: u64,
+    /// Lowest block of the hints.
+    pub first_block: Option<u64>,
+    /// Highest block of the hints.
+    pub last_block: Option<u64>,
+    /// Number of hints disclosing the calldata of at least a transaction.
+    pub with_calldata: u64,
+    /// Number of hints disclosing the function selector of at least a transaction.
+    pub with_function_selector: u64,
+    /// Number of hints disclosing logs.
+    pub with_logs: u64,
+    blocks: HashSet<u64>,
+    gas_used: Vec<U256>,
+    targets: HashMap<Address, u64>,
+}
+
+impl HintSummary {
+    /// Accounts for `event`.
+    pub fn add(&mut self, event: &EventHistory) {
+        let hint = &event.hint;
+        let txs = hint.txs.as_deref().unwrap_or_default();
+
+        self.hints += 1;
+        self.first_block = Some(
+            self.first_block
+                .map_or(event.block, |block| block.min(event.block)),
+        );
+        self.last_block = Some(
+            self.last_block
+                .map_or(event.block, |block| block.max(event.block)),
+        );
+        self.blocks.insert(event.block);
+
+        if txs.iter().any(|tx| tx.call_data.is_some()) {
+            self.with_calldata += 1;
+        }
+
+        if txs.iter().any(|tx| tx.function_selector.is_some()) {
+            self.with_function_selector += 1;
+        }
+
+        if hint.logs.as_ref().is_some_and(|logs| !logs.is_empty()) {
+            self.with_logs += 1;
+        }
+
+        if let Some(gas_used) = hint.gas_used {
+            self.gas_used.push(gas_used);
+        }
+
+        for to in txs.iter().filter_map(|tx| tx.to) {
+            *self.targets.entry(to).or_default() += 1;
+        }
+    }
+
+    /// Average number of hints per block over the `first_block..=last_block` range.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn hints_per_block(&self) -> f64 {
+        match (self.first_block, self.last_block) {
+            (Some(first), Some(last)) => self.hints as f64 / (last - first + 1) as f64,
+            _ => 0.0,
+        }
+    }
+
+    /// Number of distinct blocks with at least a hint.
+    #[must_use]
+    pub fn blocks_with_hints(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Share of hints disclosing calldata, in `[0, 1]`.
+    #[must_use]
+    pub fn calldata_share(&self) -> f64 {
+        self.share(self.with_calldata)
+    }
+
+    /// Share of hints disclosing a function selector, in `[0, 1]`.
+    #[must_use]
+    pub fn function_selector_share(&self) -> f64 {
+        self.share(self.with_function_selector)
+    }
+
+    /// Share of hints disclosing logs, in `[0, 1]`.
+    #[must_use]
+    pub fn logs_share(&self) -> f64 {
+        self.share(self.with_logs)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn share(&self, count: u64) -> f64 {
+        if self.hints == 0 {
+            0.0
+        } else {
+            count as f64 / self.hints as f64
+        }
+    }
+
+    /// The `percentile`-th (in `[0, 100]`) gas used among the hints that disclose it.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn gas_used_percentile(&self, percentile: f64) -> Option<U256> {
+        if self.gas_used.is_empty() {
+            return None;
+        }
+
+        let mut gas_used = self.gas_used.clone();
+        gas_used.sort_unstable();
+
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * (gas_used.len() - 1) as f64).round();
+        gas_used.get(rank as usize).copied()
+    }
+
+    /// The `n` contracts targeted by the most hints, with their number of hints.
+    #[must_use]
+    pub fn top_targets(&self, n: usize) -> Vec<(Address, u64)> {
+        let mut targets: Vec<_> = self
+            .targets
+            .iter()
+            .map(|(to, count)| (*to, *count))
+            .collect();
+        targets.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        targets.truncate(n);
+        targets
+    }
+}
+
+impl<'e> Extend<&'e EventHistory> for HintSummary {
+    fn extend<T: IntoIterator<Item = &'e EventHistory>>(&mut self, events: T) {
+        for event in events {
+            self.add(event);
+        }
+    }
+}
+
+impl<'e> FromIterator<&'e EventHistory> for HintSummary {
+    fn from_iter<T: IntoIterator<Item = &'e EventHistory>>(events: T) -> Self {
+        let mut summary = Self::default();
+        summary.extend(events);
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::{EventHint, Transaction};
+
+    #[test]
+    fn test_summary() {
+        let target = Address::repeat_byte(1);
+
+        let event = |block, gas_used: u64, call_data: bool| EventHistory {
+            block,
+            timestamp: 0,
+            hint: EventHint {
+                txs: Some(vec![Transaction {
+                    to: Some(target),
+                    function_selector: None,
+                    call_data: call_data.then(Default::default),
+                }]),
+                hash: ethers::types::TxHash::default(),
+                logs: None,
+                gas_used: Some(gas_used.into()),
+                mev_gas_price: None,
+            },
+        };
+
+        let events = [
+            event(10, 100, true),
+            event(10, 300, false),
+            event(13, 200, false),
+            event(13, 400, true),
+        ];
+        let summary: HintSummary = events.iter().collect();
+
+        assert_eq!(summary.hints, 4);
+        assert_eq!(summary.blocks_with_hints(), 2);
+        assert!((summary.hints_per_block() - 1.0).abs() < f64::EPSILON);
+        assert!((summary.calldata_share() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(summary.gas_used_percentile(0.0), Some(100.into()));
+        assert_eq!(summary.gas_used_percentile(100.0), Some(400.into()));
+        assert_eq!(summary.top_targets(5), vec![(target, 4)]);
+    }
+}
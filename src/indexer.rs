@@ -0,0 +1,416 @@
+use crate::api::types::{EventHistory, GetEventHistoryParams, MevShareEvent, Transaction};
+use crate::{MevShareClient, Result, Waiter};
+use ethers::types::{Address, Bytes, Log, Selector, TxHash, U256};
+use futures::StreamExt;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::pin::pin;
+use std::sync::{Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::*;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS hints (
+        hash          TEXT PRIMARY KEY,
+        block         INTEGER,
+        timestamp     INTEGER NOT NULL,
+        gas_used      TEXT,
+        mev_gas_price TEXT
+    );
+    CREATE TABLE IF NOT EXISTS hint_txs (
+        hash              TEXT NOT NULL REFERENCES hints (hash),
+        idx               INTEGER NOT NULL,
+        to_address        TEXT,
+        function_selector TEXT,
+        call_data         TEXT,
+        PRIMARY KEY (hash, idx)
+    );
+    CREATE TABLE IF NOT EXISTS hint_logs (
+        hash    TEXT NOT NULL REFERENCES hints (hash),
+        idx     INTEGER NOT NULL,
+        address TEXT NOT NULL,
+        log     TEXT NOT NULL,
+        PRIMARY KEY (hash, idx)
+    );
+    CREATE INDEX IF NOT EXISTS hints_block ON hints (block);
+    CREATE INDEX IF NOT EXISTS hint_txs_to_address ON hint_txs (to_address);
+    CREATE INDEX IF NOT EXISTS hint_txs_function_selector ON hint_txs (function_selector);
+    CREATE INDEX IF NOT EXISTS hint_logs_address ON hint_logs (address);
+";
+
+/// A hint stored by the [`Indexer`].
+#[derive(Clone, Debug)]
+pub struct IndexedHint {
+    /// Transaction or bundle hash.
+    pub hash: TxHash,
+    /// Block the hint was broadcast in; unknown for hints ingested from the live stream.
+    pub block: Option<u64>,
+    /// Unix timestamp at which the hint was broadcast, or received for hints ingested from the live stream.
+    pub timestamp: u64,
+    /// Transactions disclosed by the hint.
+    pub txs: Vec<Transaction>,
+    /// Logs disclosed by the hint.
+    pub logs: Vec<Log>,
+    /// Gas used by the transaction or bundle.
+    pub gas_used: Option<U256>,
+    /// Change in coinbase value divided by gas used.
+    pub mev_gas_price: Option<U256>,
+}
+
+/// Ingests MEV-Share hints, from the live stream and/or the history endpoint, into a local `SQLite` database
+/// indexed by block, address and function selector.
+///
+/// # Example
+///
+/// ```
+/// let indexer = Indexer::open("hints.sqlite")?;
+///
+/// // backfill, then keep up with the live stream
+/// indexer.ingest_history(&client, GetEventHistoryParams::builder().block_start(from_block).build()).await?;
+/// indexer.ingest_stream(&client).await?;
+///
+/// // somewhere else
+/// for hint in indexer.hints_by_selector([0x38, 0xed, 0x17, 0x39])? {
+///     println!("swapExactTokensForTokens in {:?}", hint.hash);
+/// }
+/// ```
+pub struct Indexer {
+    connection: Mutex<Connection>,
+}
+
+impl Indexer {
+    /// Opens (or creates) the index database at `path`.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Sqlite`] if the database can't be opened or initialized.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Opens a transient, in-memory index.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Sqlite`] if the database can't be initialized.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Stores a hint received from [`crate::MevShareClient::subscribe_bundles`], timestamped with the current time.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Sqlite`] if the hint can't be stored.
+    pub fn insert_event(&self, event: &MevShareEvent) -> Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        self.insert(&IndexedHint {
+            hash: event.hash,
+            block: None,
+            timestamp,
+            txs: event.txs.clone().unwrap_or_default(),
+            logs: event.logs.clone().unwrap_or_default(),
+            gas_used: event.gas_used,
+            mev_gas_price: event.mev_gas_price,
+        })
+    }
+
+    /// Stores a hint returned by [`crate::MevShareClient::get_event_history`].
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Sqlite`] if the hint can't be stored.
+    pub fn insert_history(&self, event: &EventHistory) -> Result<()> {
+        self.insert(&IndexedHint {
+            hash: event.hint.hash,
+            block: Some(event.block),
+            timestamp: event.timestamp,
+            txs: event.hint.txs.clone().unwrap_or_default(),
+            logs: event.hint.logs.clone().unwrap_or_default(),
+            gas_used: event.hint.gas_used,
+            mev_gas_price: event.hint.mev_gas_price,
+        })
+    }
+
+    fn insert(&self, hint: &IndexedHint) -> Result<()> {
+        let mut connection = self
+            .connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let tx = connection.transaction()?;
+        let hash = format!("{:?}", hint.hash);
+
+        // history knows the block and broadcast time: prefer it over what the live stream stored
+        tx.execute(
+            "INSERT INTO hints (hash, block, timestamp, gas_used, mev_gas_price) VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT (hash) DO UPDATE SET
+                block = coalesce(excluded.block, block),
+                timestamp = CASE WHEN excluded.block IS NULL THEN timestamp ELSE excluded.timestamp END",
+            params![
+                hash,
+                hint.block,
+                hint.timestamp,
+                hint.gas_used.map(|gas| gas.to_string()),
+                hint.mev_gas_price.map(|price| price.to_string()),
+            ],
+        )?;
+
+        for (idx, hint_tx) in hint.txs.iter().enumerate() {
+            tx.execute(
+                "INSERT OR IGNORE INTO hint_txs (hash, idx, to_address, function_selector, call_data) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    hash,
+                    idx,
+                    hint_tx.to.map(|to| format!("{to:?}")),
+                    hint_tx.function_selector.map(selector_to_hex),
+                    hint_tx.call_data.as_ref().map(ToString::to_string),
+                ],
+            )?;
+        }
+
+        for (idx, log) in hint.logs.iter().enumerate() {
+            tx.execute(
+                "INSERT OR IGNORE INTO hint_logs (hash, idx, address, log) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    hash,
+                    idx,
+                    format!("{:?}", log.address),
+                    serde_json::to_string(log)?
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Ingests the live event stream until it fails.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::EventSource`] if the stream fails.
+    /// * [`crate::Error::Sqlite`] if a hint can't be stored.
+    pub async fn ingest_stream<M: Waiter>(&self, client: &MevShareClient<M>) -> Result<()> {
+        let mut events = pin!(client.subscribe_bundles());
+
+        while let Some(event) = events.next().await {
+            let event = event?;
+            trace!(?event.hash, "indexing event");
+            self.insert_event(&event)?;
+        }
+
+        Ok(())
+    }
+
+    /// Ingests every historical event matching `params`.
+    ///
+    /// # Returns
+    ///
+    /// The number of ingested events.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Rest`] if fetching the history fails.
+    /// * [`crate::Error::Sqlite`] if a hint can't be stored.
+    pub async fn ingest_history<M: Waiter>(
+        &self,
+        client: &MevShareClient<M>,
+        params: GetEventHistoryParams,
+    ) -> Result<u64> {
+        let mut events = pin!(client.event_history_stream(params));
+        let mut count = 0;
+
+        while let Some(event) = events.next().await {
+            self.insert_history(&event?)?;
+            count += 1;
+        }
+
+        debug!(count, "indexed historical events");
+        Ok(count)
+    }
+
+    /// Returns the hints broadcast in `blocks`, ordered by block.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Sqlite`] if the query fails.
+    pub fn hints_in_blocks(&self, blocks: RangeInclusive<u64>) -> Result<Vec<IndexedHint>> {
+        self.query(
+            "SELECT hash FROM hints WHERE block BETWEEN ?1 AND ?2 ORDER BY block, timestamp",
+            params![blocks.start(), blocks.end()],
+        )
+    }
+
+    /// Returns the hints with a transaction to, or a log emitted by, `address`, ordered by time.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Sqlite`] if the query fails.
+    pub fn hints_by_address(&self, address: Address) -> Result<Vec<IndexedHint>> {
+        self.query(
+            "SELECT hash FROM hints WHERE hash IN (
+                SELECT hash FROM hint_txs WHERE to_address = ?1
+                UNION SELECT hash FROM hint_logs WHERE address = ?1
+             ) ORDER BY timestamp",
+            params![format!("{address:?}")],
+        )
+    }
+
+    /// Returns the hints with a transaction calling `selector`, ordered by time.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Sqlite`] if the query fails.
+    pub fn hints_by_selector(&self, selector: Selector) -> Result<Vec<IndexedHint>> {
+        self.query(
+            "SELECT hash FROM hints WHERE hash IN (
+                SELECT hash FROM hint_txs WHERE function_selector = ?1
+             ) ORDER BY timestamp",
+            params![selector_to_hex(selector)],
+        )
+    }
+
+    /// Returns the hint with the given transaction or bundle hash, if indexed.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Sqlite`] if the query fails.
+    pub fn hint(&self, hash: TxHash) -> Result<Option<IndexedHint>> {
+        let connection = self
+            .connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        load(&connection, &format!("{hash:?}"))
+    }
+
+    /// Runs `sql`, selecting hint hashes, and loads the matching hints.
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<IndexedHint>> {
+        let connection = self
+            .connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let hashes = connection
+            .prepare(sql)?
+            .query_map(params, |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        hashes
+            .iter()
+            .filter_map(|hash| load(&connection, hash).transpose())
+            .collect()
+    }
+}
+
+fn load(connection: &Connection, hash: &str) -> Result<Option<IndexedHint>> {
+    let Some((block, timestamp, gas_used, mev_gas_price)) = connection
+        .query_row(
+            "SELECT block, timestamp, gas_used, mev_gas_price FROM hints WHERE hash = ?1",
+            params![hash],
+            |row| {
+                Ok((
+                    row.get::<_, Option<u64>>(0)?,
+                    row.get::<_, u64>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            },
+        )
+        .optional()?
+    else {
+        return Ok(None);
+    };
+
+    let txs = connection
+        .prepare("SELECT to_address, function_selector, call_data FROM hint_txs WHERE hash = ?1 ORDER BY idx")?
+        .query_map(params![hash], |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        })?
+        .map(|row| {
+            let (to, function_selector, call_data) = row?;
+            Ok(Transaction {
+                to: to.and_then(|to| to.parse().ok()),
+                function_selector: function_selector.and_then(|selector| selector_from_hex(&selector)),
+                call_data: call_data.and_then(|data| data.parse::<Bytes>().ok()),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let logs = connection
+        .prepare("SELECT log FROM hint_logs WHERE hash = ?1 ORDER BY idx")?
+        .query_map(params![hash], |row| row.get::<_, String>(0))?
+        .map(|log| Ok(serde_json::from_str(&log?)?))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(IndexedHint {
+        hash: hash.parse().unwrap_or_default(),
+        block,
+        timestamp,
+        txs,
+        logs,
+        gas_used: gas_used.and_then(|gas| U256::from_dec_str(&gas).ok()),
+        mev_gas_price: mev_gas_price.and_then(|price| U256::from_dec_str(&price).ok()),
+    }))
+}
+
+fn selector_to_hex(selector: Selector) -> String {
+    format!("0x{}", ethers::utils::hex::encode(selector))
+}
+
+fn selector_from_hex(selector: &str) -> Option<Selector> {
+    ethers::utils::hex::decode(selector.trim_start_matches("0x"))
+        .ok()?
+        .try_into()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let indexer = Indexer::open_in_memory().unwrap();
+        let to = Address::repeat_byte(0x11);
+
+        let event = MevShareEvent {
+            hash: TxHash::repeat_byte(0xaa),
+            logs: None,
+            txs: Some(vec![Transaction {
+                to: Some(to),
+                function_selector: Some([0x38, 0xed, 0x17, 0x39]),
+                call_data: None,
+            }]),
+            mev_gas_price: None,
+            gas_used: Some(U256::from(21_000)),
+        };
+
+        indexer.insert_event(&event).unwrap();
+
+        let hints = indexer.hints_by_selector([0x38, 0xed, 0x17, 0x39]).unwrap();
+        assert_eq!(hints.len(), 1);
+        assert_eq!(hints[0].hash, event.hash);
+        assert_eq!(hints[0].block, None);
+        assert_eq!(hints[0].gas_used, event.gas_used);
+        assert_eq!(hints[0].txs[0].to, Some(to));
+
+        assert_eq!(indexer.hints_by_address(to).unwrap().len(), 1);
+        assert!(indexer.hints_in_blocks(0..=20_000_000).unwrap().is_empty());
+    }
+}
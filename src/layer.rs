@@ -0,0 +1,146 @@
+//! Hooks around every JSON-RPC request to the relay, e.g. to log, measure, or rewrite them.
+//!
+//! A [`RpcLayer`] gets each request on its way out, and hands it to the [`Next`] layers, the innermost of which
+//! authenticates the request and posts it; what it returns is what the next outer layer gets. Layers run once per
+//! attempt, retries included, and within the timeout of the request.
+//!
+//! Layers see the request before it is authenticated: changing its body changes what is signed. To authenticate
+//! requests differently altogether, implement [`crate::signer::AuthScheme`] instead.
+//!
+//! # Example
+//!
+//! ```
+//! use mev_share_rs::layer::{Next, RpcLayer, RpcRequest, RpcResponse};
+//!
+//! struct Metrics;
+//!
+//! #[async_trait]
+//! impl RpcLayer for Metrics {
+//!     async fn call(&self, request: RpcRequest, next: Next<'_>) -> Result<RpcResponse, RpcError> {
+//!         let method = request.method;
+//!         let started = Instant::now();
+//!         let response = next.run(request).await;
+//!         histogram!("relay_request_seconds", "method" => method).record(started.elapsed());
+//!         response
+//!     }
+//! }
+//!
+//! let client = MevShareClient::builder(auth_wallet, provider)
+//!     .layer(Metrics)
+//!     .build()
+//!     .await?;
+//! ```
+
+use crate::api::rpc_client::{MevShareRpcClient, ResponseMeta};
+use crate::error::RpcError;
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use std::sync::Arc;
+
+/// A request on its way to the relay.
+#[derive(Clone, Debug)]
+pub struct RpcRequest {
+    /// JSON-RPC method, or `"batch"` for a batch of requests.
+    pub method: &'static str,
+    /// JSON-RPC request, as sent.
+    pub body: String,
+    /// Headers sent with the request, on top of the authentication ones and `Content-Type`.
+    pub headers: HeaderMap,
+}
+
+/// The response of the relay to a [`RpcRequest`], whatever its status.
+#[derive(Clone, Debug)]
+pub struct RpcResponse {
+    /// JSON-RPC response, or whatever the relay, or a proxy in front of it, answered.
+    pub body: String,
+    /// HTTP status and headers of the response.
+    pub meta: ResponseMeta,
+}
+
+/// Hook around every JSON-RPC request to the relay, see [`crate::layer`].
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait RpcLayer: Send + Sync {
+    /// Handles `request`, usually by passing it on to `next`, changed or not, and returning its response.
+    ///
+    /// # Errors
+    ///
+    /// * [`RpcError`] if `next` fails, or to fail the request without sending it.
+    async fn call(&self, request: RpcRequest, next: Next<'_>) -> Result<RpcResponse, RpcError>;
+}
+
+/// The layers after the current one, then the relay.
+pub struct Next<'a> {
+    client: &'a MevShareRpcClient,
+    layers: &'a [Arc<dyn RpcLayer>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(client: &'a MevShareRpcClient, layers: &'a [Arc<dyn RpcLayer>]) -> Self {
+        Self { client, layers }
+    }
+
+    /// Passes `request` on to the next layer, or authenticates and posts it if there is none left.
+    ///
+    /// # Errors
+    ///
+    /// * [`RpcError`] if a layer fails, or the request can't be authenticated or sent.
+    pub async fn run(self, request: RpcRequest) -> Result<RpcResponse, RpcError> {
+        match self.layers.split_first() {
+            Some((layer, layers)) => layer.call(request, Next::new(self.client, layers)).await,
+            None => self.client.sign_and_post(request).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::MevShareRequest;
+    use crate::helpers::{json_rpc_response, mock_relay};
+    use ethers::signers::LocalWallet;
+    use ethers::types::TxHash;
+    use reqwest::header::HeaderValue;
+    use std::sync::Mutex;
+
+    /// Tags the requests with its name, and records the order it saw them in.
+    struct Tag(&'static str, Arc<Mutex<Vec<&'static str>>>);
+
+    #[async_trait]
+    impl RpcLayer for Tag {
+        async fn call(
+            &self,
+            mut request: RpcRequest,
+            next: Next<'_>,
+        ) -> Result<RpcResponse, RpcError> {
+            self.1.lock().unwrap().push(self.0);
+            request
+                .headers
+                .append("x-layer", HeaderValue::from_static(self.0));
+            next.run(request).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_layers() {
+        let (url, mut requests) = mock_relay(|_| Some(json_rpc_response("null"))).await;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut rpc = MevShareRpcClient::new(
+            &url,
+            LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+        );
+        rpc.push_layer(Arc::new(Tag("outer", seen.clone())));
+        rpc.push_layer(Arc::new(Tag("inner", seen.clone())));
+
+        rpc.post::<Option<TxHash>, _>(MevShareRequest::GetUserStats, [()])
+            .await
+            .unwrap();
+
+        assert_eq!(*seen.lock().unwrap(), ["outer", "inner"]);
+        let request = requests.recv().await.unwrap();
+        assert!(request.contains("x-layer: outer") && request.contains("x-layer: inner"));
+        // still authenticated
+        assert!(request.contains("x-flashbots-signature"));
+    }
+}
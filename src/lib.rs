@@ -30,7 +30,16 @@
 //! or you can provide it yourself:
 //!
 //! ```
-//! let client = MevShareClient::new_with_chain_id(auth_wallet, provider, 1)?;
+//! let client = MevShareClient::new_with_chain_id(auth_wallet, provider, Chain::Mainnet)?;
+//! ```
+//!
+//! The provider doesn't have to be a websocket one: any ethers [`Middleware`] works, e.g. a `Provider<Http>` or a
+//! middleware stack on top of one, in which case new blocks are polled for every [`Provider::get_interval`] to wait
+//! for inclusion. See [`Waiter`].
+//!
+//! ```
+//! let provider = Provider::<Http>::try_from("https://rpc.example/api_key")?.interval(Duration::from_secs(1));
+//! let client = MevShareClient::new(auth_wallet, provider).await?;
 //! ```
 //!
 //! ### Subscribing to MEV-Share events
@@ -76,8 +85,8 @@
 //!         .tx(tx.rlp_signed(&sender_wallet.sign_transaction_sync(&tx)?))
 //!         .max_block_number(current_block + 20)
 //!         .preferences(
-//!             Some(set![Hint::Hash, Calldata, Logs, ContractAddress, FunctionSelector]),
-//!             Some(BUILDERS.clone()),
+//!             [Hint::Hash, Hint::Calldata, Hint::Logs, Hint::ContractAddress, Hint::FunctionSelector],
+//!             Builder::KNOWN,
 //!         )
 //!         .build()
 //! ).await?;
@@ -122,19 +131,19 @@
 //!     SendBundleParams::builder()
 //!         .body(vec![
 //!             // a signed `TypedTransaction`
-//!             Signed { tx: tx1, can_revert: false },
-//!             Signed { tx: tx2, can_revert: false },
+//!             Body::Signed { tx: tx1, can_revert: false },
+//!             Body::Signed { tx: tx2, can_revert: false },
 //!             // a transaction we found in the mempool
-//!             Tx { hash: tx3 }
+//!             Body::Tx { hash: tx3 }
 //!         ])
 //!         .inclusion(current_block + 1, Some(current_block + 1 + 3)) // drop after 3 blocks
 //!         .privacy(
-//!             Some(set![Hint::Hash, Calldata, Logs, FunctionSelector, ContractAddress]),
-//!             Some(set![
+//!             [Hint::Hash, Hint::Calldata, Hint::Logs, Hint::FunctionSelector, Hint::ContractAddress],
+//!             [
 //!                 Builder::Flashbots,
 //!                 Builder::Rsync,
-//!                 Builder::Other("a non-flashbots builder")
-//!             ]),
+//!                 Builder::Other("a non-flashbots builder".to_owned())
+//!             ],
 //!         )
 //!         .build()
 //! ).await?;
@@ -197,18 +206,117 @@
 //! [`get_event_history`] and [`get_event_history_info`] allow you to query bundle submission history: check
 //! [`examples/historycal_stream_data`] for an example.
 //!
+//! Existing ethers code can send its transactions privately without changes by swapping its middleware for a
+//! [`middleware::MevShareMiddleware`].
+//!
 //! Finally, [`examples/send_backrun_bundle`] gives you an idea on how you can put all of the above to use to listen to transactions
 //! hints from the relayer and backrun those you're interested in.
 //!
+//! ## Features
+//!
+//! * `stream` (default): [`MevShareClient::subscribe_bundles`].
+//! * `history` (default): [`MevShareClient::get_event_history`] and the rest of the [`history`] API.
+//! * `wait` (default): waiting for pending bundles and transactions to land via the provider,
+//!   e.g. [`bundle::PendingBundle::inclusion`].
+//! * `tokio` (default): see below.
+//! * `compression`: gzip and brotli compressed history responses and event stream, where the server offers them, for
+//!   full-history crawls and high-volume stream consumption on constrained hosts. Not needed on `wasm`: browsers
+//!   decompress `fetch` responses themselves.
+//! * `blocking`: the [`blocking`] client.
+//! * `bloxroute`: sending to the bloXroute MEV API instead of the Flashbots relay, see [`backend`].
+//! * `indexer`: a local `SQLite` index of hints, see [`indexer`].
+//! * `dedup`: a `SQLite` store of submitted bundles, to not submit them twice across restarts, see [`dedup`], which
+//!   also keeps their [`pnl`].
+//! * `cli`: the `mev-share` command line tool, to send, simulate, watch and export hints without writing Rust.
+//! * `server`: a local websocket JSON-RPC [`server`] sharing one authenticated client with non-Rust services.
+//! * `kafka`, `nats`, `redis`: forwarding the event stream to an event bus, see [`sink`].
+//! * `mev-share-sse`: `From`/`TryFrom` conversions between the event and history types and those of Paradigm's
+//!   [`mev-share-sse`](https://docs.rs/mev-share-sse) crate, to migrate incrementally or mix the two.
+//! * `schema`: JSON Schema of the request and response types, and an `OpenRPC` document of the relay methods, see [`schema`].
+//! * `wasm`: browser support, see below.
+//!
+//! A service that only sends bundles can opt out of the defaults it doesn't need for a smaller dependency tree:
+//!
+//! ```toml
+//! mev-share-rs = { version = "0.1", default-features = false, features = ["wait"] }
+//! ```
+//!
+//! ## Async runtime
+//!
+//! The client doesn't spawn tasks nor use runtime-specific timers, so its futures and streams can be driven by any executor.
+//! Keep in mind that [`reqwest`] and the websocket [`Provider`] expect a tokio reactor underneath: on async-std or smol,
+//! wrap the calls in [`async_compat::Compat`].
+//!
+//! The `tokio` feature (enabled by default) is only needed for [`guard::CancelGuard`] to cancel orders when dropped
+//! or on shutdown signals.
+//!
+//! Non-async code can use the [`blocking`] client instead, behind the `blocking` feature: connect one directly, or turn
+//! an async client into one with [`MevShareClient::blocking`].
+//!
+//! ## WebAssembly
+//!
+//! Dapp frontends and browser extensions can build for `wasm32-unknown-unknown` with the `wasm` feature, and without
+//! `tokio`, `blocking` and `indexer`:
+//!
+//! ```toml
+//! mev-share-rs = { version = "0.1", default-features = false, features = ["wasm", "stream", "history", "wait"] }
+//! ```
+//!
+//! [`reqwest`] then sends requests with `fetch`, the SSE stream is read from the `fetch` response body, and timers and
+//! clocks are backed by the browser. The [`Provider`] has to be reachable from the browser too: a `Provider<Http>` is
+//! enough, as bundles and transactions then wait for their inclusion by polling the block number every
+//! [`Provider::get_interval`], rather than subscribing to new blocks over a websocket.
+//!
+//! ## Types without the client
+//!
+//! The request, response and event types, and their serialization, live in the [`mev_share_types`] crate, which
+//! depends on neither `reqwest` nor an event-source client. This crate re-exports them in its [`bundle`],
+//! [`transaction`], [`stream`], [`history`] and [`stats`] modules: depend on `mev-share-types` alone to send them over
+//! your own transport, or to serve the MEV-Share API relay-side.
+//!
+//! ```toml
+//! mev-share-types = { version = "0.1", features = ["schema"] }
+//! ```
+//!
 //! ## API reference
 //!
 //! See [`MevShareClient`].
 //!
+//! The [`prelude`] only brings the most common types into scope; the rest are grouped by domain:
+//!
+//! * [`bundle`]: sending and simulating bundles,
+//! * [`transaction`]: sending private transactions,
+//! * [`stream`]: events from the MEV-Share SSE stream,
+//! * [`history`]: querying and analyzing the hints history,
+//! * [`sink`]: forwarding events to Kafka, NATS or Redis streams,
+//! * [`stats`]: searcher and bundle statistics,
+//! * [`middleware`]: an ethers middleware that sends transactions privately,
+//! * [`wallet`]: loading wallets from encrypted keystores or deriving them from a mnemonic,
+//! * [`signer`]: authenticating with a key held by a remote signing service or with a custom [`signer::AuthScheme`], and
+//!   verifying signatures server-side,
+//! * [`backend`]: sending to other order-flow providers than the Flashbots relay,
+//! * [`relay`]: sending bundles to several builders at once, on top of the Flashbots relay,
+//! * [`relay_data`]: finding out from the MEV-Boost relays which builders won the blocks a bundle missed,
+//! * [`budget`], [`guard`] and [`dedup`]: safety nets around what the client submits,
+//! * [`pnl`]: profit and cost of the landed bundles, per strategy, refunds included,
+//! * [`price`]: valuing profits, budgets and profit and loss in USD with a Chainlink feed or another price source,
+//! * [`tracker`]: rolling statistics of the outcomes of the submissions, exported as metrics,
+//! * [`alert`]: notifying webhooks or callbacks of reverts, budget refusals, stream outages and reputation drops,
+//! * [`health`]: checking that the relay and the event stream are usable, e.g. from a readiness probe,
+//! * [`ens`]: resolving ENS names of refund recipients and other addresses,
+//! * [`signatures`]: resolving the function selectors disclosed by hints into signatures,
+//! * [`enrich`]: fetching the transaction of an event once it lands, to measure what its hints didn't disclose,
+//! * [`types`]: the `ethers` and `reqwest` types used by the above, re-exported.
+//!
 //! <!-- Links -->
 //!
 //! [`Signer`]: ethers::signers::Signer
 //! [`LocalWallet`]: ethers::signers::LocalWallet
 //! [`Provider`]: ethers::providers::Provider
+//! [`Provider::get_interval`]: ethers::providers::Provider::get_interval
+//! [`Middleware`]: ethers::providers::Middleware
+//! [`reqwest`]: https://docs.rs/reqwest
+//! [`async_compat::Compat`]: https://docs.rs/async-compat
 //! [`get_event_history`]: MevShareClient::get_event_history
 //! [`get_event_history_info`]: MevShareClient::get_event_history_info
 //! [`mev_sendBundle`]: https://docs.flashbots.net/flashbots-auction/searchers/advanced/rpc-endpoint#mev_sendbundle
@@ -243,11 +351,58 @@
     if_let_guard
 )]
 
+pub mod alert;
 mod api;
+pub mod backend;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod budget;
+pub mod bundle;
 mod client;
+pub mod dedup;
+#[cfg(feature = "wait")]
+pub mod enrich;
+pub mod ens;
 mod error;
+pub mod guard;
+pub mod health;
 mod helpers;
+pub mod history;
+#[cfg(feature = "indexer")]
+pub mod indexer;
+pub mod layer;
+pub mod middleware;
+pub mod pnl;
 pub mod prelude;
+pub mod price;
+pub mod relay;
+pub mod relay_data;
+pub mod retry;
+#[cfg(feature = "schema")]
+pub mod schema;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod signatures;
+pub mod signer;
+#[cfg(feature = "stream")]
+pub mod sink;
+pub mod stats;
+pub mod stream;
+pub mod tracker;
+pub mod transaction;
+pub mod types;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod wallet;
 
-pub use error::{Error, Result};
-pub use prelude::*;
+pub use api::batch::{Batch, BatchId, BatchResponse};
+pub use api::networks::{
+    Capability, MevShareNetwork, NetworkCapabilities, NetworkConfig, NetworkTable,
+};
+pub use api::rpc_client::{RateLimit, ResponseMeta};
+pub use client::{MevShareApi, MevShareClient, MevShareClientBuilder, NetworkOverride};
+pub use error::{Error, JsonError, Result, RpcError};
+pub use helpers::provider::Waiter;
+#[cfg(feature = "stream")]
+pub use client::EventStream;
+#[cfg(feature = "history")]
+pub use error::RestError;
@@ -0,0 +1,295 @@
+//! An [`ethers::providers::Middleware`] that sends transactions privately through MEV-Share.
+//!
+//! Swap it into an existing ethers stack and every transaction sent via `send_transaction` or `send_raw_transaction`
+//! goes to the relay with `eth_sendPrivateTransaction` instead of the public mempool. Every other call is forwarded
+//! to the inner middleware.
+//!
+//! # Example
+//!
+//! ```
+//! use mev_share_rs::middleware::MevShareMiddleware;
+//!
+//! let provider = SignerMiddleware::new(provider, wallet);
+//! let client = MevShareMiddleware::new(provider, auth_wallet, chain_id)?
+//!     .with_privacy([Hint::Calldata, Hint::TransactionHash], [])
+//!     .with_max_blocks(10);
+//!
+//! // unchanged ethers code, now private
+//! let receipt = contract.connect(Arc::new(client)).swap(amount).send().await?.await?;
+//! ```
+
+use crate::api::networks::MevShareNetwork;
+use crate::api::rpc_client::MevShareRpcClient;
+use crate::api::types::{Builder, Hint, Preferences, Preset, Privacy, SendTransactionParams};
+use crate::client::MevShareRequest;
+use crate::signer::{AuthScheme, AuthSigner};
+use async_trait::async_trait;
+use ethers::providers::{Middleware, MiddlewareError, PendingTransaction};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{BlockId, Bytes, TxHash};
+use std::fmt::Debug;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Routes the transactions sent through `inner` to MEV-Share. See the [module docs](self).
+pub struct MevShareMiddleware<M> {
+    inner: M,
+    rpc: MevShareRpcClient,
+    privacy: Privacy,
+    fast: bool,
+    max_blocks: Option<u64>,
+}
+
+impl<M: Middleware> MevShareMiddleware<M> {
+    /// Wraps `inner`, signing the relay requests with `auth_wallet`, a [`ethers::signers::LocalWallet`] or a [`crate::signer::RemoteSigner`].
+    ///
+    /// `chain_id`, a [`ethers::types::Chain`], or a chain id as `u64` or [`ethers::types::U256`], is needed to infer
+    /// which MEV-Share endpoint (e.g. mainnet or goerli) to send transactions to.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UnsupportedNetwork`] if the `chain_id` is not supported by the MEV-Share client.
+    pub fn new(
+        inner: M,
+        auth_wallet: impl Into<AuthSigner>,
+        chain_id: impl TryInto<MevShareNetwork, Error = impl Into<crate::Error>>,
+    ) -> crate::Result<Self> {
+        let network = chain_id.try_into().map_err(Into::into)?;
+
+        Ok(Self {
+            inner,
+            rpc: MevShareRpcClient::new(network.api_url(), auth_wallet),
+            privacy: Privacy::default(),
+            fast: true,
+            max_blocks: None,
+        })
+    }
+
+    /// Shares `hints` with `builders` for every transaction. Defaults to the relay defaults.
+    #[must_use]
+    pub fn with_privacy(
+        mut self,
+        hints: impl IntoIterator<Item = Hint>,
+        builders: impl IntoIterator<Item = Builder>,
+    ) -> Self {
+        self.privacy = Privacy::new(hints, builders);
+        self
+    }
+
+    /// Shares what `preset` shares for every transaction, with its speed preference, see [`Preset`].
+    #[must_use]
+    pub fn with_preset(mut self, preset: Preset) -> Self {
+        let Preferences { fast, privacy, .. } = preset.into();
+        self.fast = fast;
+        self.privacy = privacy;
+        self
+    }
+
+    /// Sets the `fast` preference of every transaction: with `true`, the relay shares it with every registered builder
+    /// right away. Defaults to `true`.
+    #[must_use]
+    pub fn with_fast(mut self, fast: bool) -> Self {
+        self.fast = fast;
+        self
+    }
+
+    /// Gives up on every transaction that is not included within `max_blocks` from the current block.
+    /// Defaults to the relay default of 25 blocks.
+    #[must_use]
+    pub fn with_max_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_blocks = Some(max_blocks);
+        self
+    }
+
+    /// Authenticates the requests to the relay with `auth` instead of the `X-Flashbots-Signature` of the auth wallet.
+    #[must_use]
+    pub fn with_auth_scheme(mut self, auth: impl AuthScheme + 'static) -> Self {
+        self.rpc.set_auth_scheme(Arc::new(auth));
+        self
+    }
+
+    /// Sends the signed transaction `tx` with `eth_sendPrivateTransaction`.
+    async fn send_private(&self, tx: Bytes) -> Result<TxHash, MevShareMiddlewareError<M>> {
+        let max_block_number = match self.max_blocks {
+            Some(max_blocks) => Some(
+                self.inner
+                    .get_block_number()
+                    .await
+                    .map_err(MevShareMiddlewareError::Middleware)?
+                    + max_blocks,
+            ),
+            None => None,
+        };
+
+        let params = SendTransactionParams {
+            tx,
+            max_block_number,
+            preferences: Some(Preferences {
+                fast: self.fast,
+                privacy: self.privacy.clone(),
+                validity: None,
+            }),
+        };
+
+        self.rpc
+            .post(MevShareRequest::SendPrivateTransaction, [params])
+            .await
+            .map_err(|err| MevShareMiddlewareError::Relay(Box::new(err.into())))
+    }
+}
+
+impl<M: Debug> Debug for MevShareMiddleware<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MevShareMiddleware")
+            .field("inner", &self.inner)
+            .field("privacy", &self.privacy)
+            .field("fast", &self.fast)
+            .field("max_blocks", &self.max_blocks)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M: Middleware> Middleware for MevShareMiddleware<M> {
+    type Error = MevShareMiddlewareError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Fills and signs `tx` through the inner middleware, then sends it privately.
+    ///
+    /// The inner middleware must be able to sign for the sender, e.g. a `SignerMiddleware`.
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<BlockId>,
+    ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
+        let mut tx = tx.into();
+
+        let from = tx
+            .from()
+            .copied()
+            .or_else(|| self.inner.default_sender())
+            .ok_or(MevShareMiddlewareError::MissingSender)?;
+        tx.set_from(from);
+
+        self.inner
+            .fill_transaction(&mut tx, block)
+            .await
+            .map_err(MevShareMiddlewareError::Middleware)?;
+
+        let signature = self
+            .inner
+            .sign_transaction(&tx, from)
+            .await
+            .map_err(MevShareMiddlewareError::Middleware)?;
+
+        let hash = self.send_private(tx.rlp_signed(&signature)).await?;
+
+        Ok(PendingTransaction::new(hash, self.provider()))
+    }
+
+    /// Sends the signed transaction `tx` privately.
+    async fn send_raw_transaction<'a>(
+        &'a self,
+        tx: Bytes,
+    ) -> Result<PendingTransaction<'a, Self::Provider>, Self::Error> {
+        let hash = self.send_private(tx).await?;
+
+        Ok(PendingTransaction::new(hash, self.provider()))
+    }
+}
+
+/// Errors of a [`MevShareMiddleware`].
+#[derive(Error, Debug)]
+pub enum MevShareMiddlewareError<M: Middleware> {
+    #[error(transparent)]
+    Middleware(M::Error),
+
+    #[error(transparent)]
+    Relay(Box<crate::Error>),
+
+    #[error("The transaction has no sender and the inner middleware has no default one")]
+    MissingSender,
+}
+
+impl<M: Middleware> MiddlewareError for MevShareMiddlewareError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        Self::Middleware(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            Self::Middleware(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{json_rpc_response, mock_relay};
+    use ethers::providers::Provider;
+    use ethers::signers::LocalWallet;
+    use ethers::types::U64;
+
+    #[tokio::test]
+    async fn test_middleware_forwards_calls() {
+        let (provider, mock) = Provider::mocked();
+        let auth_wallet = LocalWallet::new(&mut ethers::core::rand::thread_rng());
+
+        assert!(matches!(
+            MevShareMiddleware::new(provider.clone(), auth_wallet.clone(), 1337),
+            Err(crate::Error::UnsupportedNetwork(_))
+        ));
+
+        let client = MevShareMiddleware::new(provider, auth_wallet, 1).unwrap();
+        mock.push(U64::from(17_000_000)).unwrap();
+
+        assert_eq!(
+            client.get_block_number().await.unwrap(),
+            U64::from(17_000_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_private_preferences() {
+        let (url, mut requests) = mock_relay(|_| {
+            Some(json_rpc_response(&format!(
+                "\"{:?}\"",
+                TxHash::repeat_byte(1)
+            )))
+        })
+        .await;
+        let (provider, _) = Provider::mocked();
+        let auth_wallet = LocalWallet::new(&mut ethers::core::rand::thread_rng());
+
+        let mut client = MevShareMiddleware::new(provider, auth_wallet, 1)
+            .unwrap()
+            .with_privacy([Hint::Hash], [Builder::Flashbots])
+            .with_fast(false);
+        client.rpc.set_base_url(url);
+
+        let hash = client.send_private(Bytes::from(vec![1])).await.unwrap();
+        assert_eq!(hash, TxHash::repeat_byte(1));
+
+        let request = requests.recv().await.unwrap();
+        let (_, body) = request.split_once("\r\n\r\n").unwrap();
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["method"], "eth_sendprivatetransaction");
+        assert_eq!(
+            body["params"][0]["preferences"],
+            serde_json::json!({
+                "fast": false,
+                "privacy": { "hints": ["hash"], "builders": ["flashbots"] },
+            })
+        );
+    }
+}
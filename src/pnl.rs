@@ -0,0 +1,490 @@
+//! Accounting for the profit and cost of the bundles that landed, per strategy and overall.
+//!
+//! Once a bundle lands, its [`BundlePnl`] records what it earned and what it cost: the value received, the gas paid by
+//! the searcher transactions, the coinbase payment to the builder, and the gas fee refunds owed and received, reconciled
+//! later from [`crate::MevShareClient::fee_refunds_by_bundle`]. A [`PnlStore`] keeps the records and answers
+//! per-strategy and aggregate queries.
+//!
+//! [`MemoryPnlStore`] keeps them in memory; with the `dedup` feature, the [`crate::dedup::SqliteDedupStore`] that
+//! remembers submissions keeps them too, across restarts.
+//!
+//! # Example
+//!
+//! ```
+//! let store = SqliteDedupStore::open("submitted.sqlite")?;
+//!
+//! let (receipts, block) = pending_bundle.inclusion().await?;
+//! let mut pnl = BundlePnl::landed("backrun", bundle_hash, block, &receipts, searcher.address());
+//! pnl.received = arbitrage_profit;
+//! pnl.coinbase_paid = bid;
+//! store.record(&pnl)?;
+//!
+//! // later, once the relay computed the refunds
+//! store.record_refunds(&client.fee_refunds_by_bundle(bundle_hash).await?)?;
+//!
+//! let summary = store.summary(Some("backrun"))?;
+//! info!(%summary, usd = summary.realized_usd(oracle.eth_usd().await?), "backrun PnL");
+//! ```
+
+use crate::api::types::{FeeRefunds, RefundStatus};
+use crate::price::signed_wei_to_usd;
+use crate::Result;
+use ethers::types::{Address, TransactionReceipt, TxHash, I256, U256, U64};
+use ethers::utils::format_ether;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::sync::{Mutex, PoisonError};
+
+/// Profit and cost of a landed bundle, in wei.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundlePnl {
+    /// Bundle hash.
+    pub bundle_hash: TxHash,
+    /// Strategy that sent the bundle, to break profits down by.
+    pub strategy: String,
+    /// Block the bundle landed in.
+    pub block: U64,
+    /// Value the bundle earned, e.g. the tokens an arbitrage brought back, valued in wei.
+    pub received: U256,
+    /// Gas fees paid by the searcher transactions of the bundle.
+    pub gas_paid: U256,
+    /// Direct payments to the builder.
+    pub coinbase_paid: U256,
+    /// Gas fee refunds owed by the relay, not paid yet.
+    pub refund_owed: U256,
+    /// Gas fee refunds paid by the relay.
+    pub refund_received: U256,
+}
+
+impl BundlePnl {
+    /// Profit and cost of a bundle of `strategy` that landed in `block`, with the gas fees paid by the transactions `searcher`
+    /// sent, from the `receipts` of the bundle, e.g. those returned by [`crate::bundle::PendingBundle::inclusion`].
+    ///
+    /// [`Self::received`] and [`Self::coinbase_paid`] depend on the strategy, and are left for the caller to set.
+    #[must_use]
+    pub fn landed(
+        strategy: impl Into<String>,
+        bundle_hash: TxHash,
+        block: U64,
+        receipts: &[TransactionReceipt],
+        searcher: Address,
+    ) -> Self {
+        let gas_paid = receipts
+            .iter()
+            .filter(|receipt| receipt.from == searcher)
+            .fold(U256::zero(), |total, receipt| {
+                total
+                    + receipt.gas_used.unwrap_or_default()
+                        * receipt.effective_gas_price.unwrap_or_default()
+            });
+
+        Self {
+            bundle_hash,
+            strategy: strategy.into(),
+            block,
+            received: U256::zero(),
+            gas_paid,
+            coinbase_paid: U256::zero(),
+            refund_owed: U256::zero(),
+            refund_received: U256::zero(),
+        }
+    }
+
+    /// What the bundle cost: gas fees and coinbase payments.
+    #[must_use]
+    pub fn cost(&self) -> U256 {
+        self.gas_paid + self.coinbase_paid
+    }
+
+    /// Realized profit: the value received and the refunds paid, minus the cost.
+    #[must_use]
+    pub fn realized(&self) -> I256 {
+        signed(self.received + self.refund_received) - signed(self.cost())
+    }
+
+    /// Expected profit: the realized profit plus the refunds owed.
+    #[must_use]
+    pub fn expected(&self) -> I256 {
+        self.realized() + signed(self.refund_owed)
+    }
+}
+
+/// Wei amounts fit in 255 bits by a wide margin.
+fn signed(amount: U256) -> I256 {
+    I256::from_raw(amount)
+}
+
+/// Aggregate of [`BundlePnl`]s, in wei. See [`PnlStore::summary`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PnlSummary {
+    /// Number of bundles.
+    pub bundles: usize,
+    pub received: U256,
+    pub gas_paid: U256,
+    pub coinbase_paid: U256,
+    pub refund_owed: U256,
+    pub refund_received: U256,
+}
+
+impl PnlSummary {
+    /// Adds `pnl` to the summary.
+    pub fn add(&mut self, pnl: &BundlePnl) {
+        self.bundles += 1;
+        self.received += pnl.received;
+        self.gas_paid += pnl.gas_paid;
+        self.coinbase_paid += pnl.coinbase_paid;
+        self.refund_owed += pnl.refund_owed;
+        self.refund_received += pnl.refund_received;
+    }
+
+    /// What the bundles cost: gas fees and coinbase payments.
+    #[must_use]
+    pub fn cost(&self) -> U256 {
+        self.gas_paid + self.coinbase_paid
+    }
+
+    /// Realized profit, see [`BundlePnl::realized`].
+    #[must_use]
+    pub fn realized(&self) -> I256 {
+        signed(self.received + self.refund_received) - signed(self.cost())
+    }
+
+    /// Expected profit, see [`BundlePnl::expected`].
+    #[must_use]
+    pub fn expected(&self) -> I256 {
+        self.realized() + signed(self.refund_owed)
+    }
+
+    /// Realized profit in USD, at `eth_usd` dollars per ETH, see [`crate::price`].
+    #[must_use]
+    pub fn realized_usd(&self, eth_usd: f64) -> f64 {
+        signed_wei_to_usd(self.realized(), eth_usd)
+    }
+
+    /// Expected profit in USD, at `eth_usd` dollars per ETH, see [`crate::price`].
+    #[must_use]
+    pub fn expected_usd(&self, eth_usd: f64) -> f64 {
+        signed_wei_to_usd(self.expected(), eth_usd)
+    }
+}
+
+impl<'a> FromIterator<&'a BundlePnl> for PnlSummary {
+    fn from_iter<T: IntoIterator<Item = &'a BundlePnl>>(iter: T) -> Self {
+        iter.into_iter().fold(Self::default(), |mut summary, pnl| {
+            summary.add(pnl);
+            summary
+        })
+    }
+}
+
+impl Display for PnlSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} bundles: realized {} ETH ({} ETH received, {} ETH refunded, {} ETH cost), {} ETH refunds owed",
+            self.bundles,
+            format_ether(self.realized()),
+            format_ether(self.received),
+            format_ether(self.refund_received),
+            format_ether(self.cost()),
+            format_ether(self.refund_owed)
+        )
+    }
+}
+
+/// Where [`BundlePnl`]s are kept, by bundle hash.
+pub trait PnlStore: Send + Sync {
+    /// Records `pnl`, replacing any previous record of the same bundle.
+    ///
+    /// # Errors
+    ///
+    /// * If the store can't be written, e.g. [`crate::Error::Sqlite`].
+    fn record(&self, pnl: &BundlePnl) -> Result<()>;
+
+    /// Sets the refunds `owed` and `received` of a recorded bundle; unknown bundles are ignored.
+    ///
+    /// # Errors
+    ///
+    /// * If the store can't be written, e.g. [`crate::Error::Sqlite`].
+    fn record_refund(&self, bundle_hash: TxHash, owed: U256, received: U256) -> Result<()>;
+
+    /// The records of `strategy`, or of all strategies, by block.
+    ///
+    /// # Errors
+    ///
+    /// * If the store can't be queried, e.g. [`crate::Error::Sqlite`].
+    fn entries(&self, strategy: Option<&str>) -> Result<Vec<BundlePnl>>;
+
+    /// Reconciles the refunds of the recorded bundles with those reported by the relay, e.g. by
+    /// [`crate::MevShareClient::fee_refunds_by_bundle`] or [`crate::MevShareClient::fee_refunds_by_block`].
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Self::record_refund`].
+    fn record_refunds(&self, refunds: &FeeRefunds) -> Result<()> {
+        let mut by_bundle: HashMap<TxHash, (U256, U256)> = HashMap::new();
+        for refund in &refunds.refunds {
+            let (owed, received) = by_bundle.entry(refund.hash).or_default();
+            match refund.status {
+                RefundStatus::Pending => *owed += refund.amount,
+                RefundStatus::Received => *received += refund.amount,
+                RefundStatus::Unknown => {}
+            }
+        }
+
+        by_bundle
+            .into_iter()
+            .try_for_each(|(bundle_hash, (owed, received))| {
+                self.record_refund(bundle_hash, owed, received)
+            })
+    }
+
+    /// Aggregate profit and cost of `strategy`, or of all strategies.
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Self::entries`].
+    fn summary(&self, strategy: Option<&str>) -> Result<PnlSummary> {
+        Ok(self.entries(strategy)?.iter().collect())
+    }
+
+    /// Aggregate profit and cost of each strategy.
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Self::entries`].
+    fn summary_by_strategy(&self) -> Result<BTreeMap<String, PnlSummary>> {
+        let mut summaries: BTreeMap<String, PnlSummary> = BTreeMap::new();
+        for pnl in self.entries(None)? {
+            summaries.entry(pnl.strategy.clone()).or_default().add(&pnl);
+        }
+        Ok(summaries)
+    }
+}
+
+/// A [`PnlStore`] in memory, lost on restart.
+#[derive(Debug, Default)]
+pub struct MemoryPnlStore(Mutex<HashMap<TxHash, BundlePnl>>);
+
+impl PnlStore for MemoryPnlStore {
+    fn record(&self, pnl: &BundlePnl) -> Result<()> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(pnl.bundle_hash, pnl.clone());
+        Ok(())
+    }
+
+    fn record_refund(&self, bundle_hash: TxHash, owed: U256, received: U256) -> Result<()> {
+        if let Some(pnl) = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(&bundle_hash)
+        {
+            pnl.refund_owed = owed;
+            pnl.refund_received = received;
+        }
+        Ok(())
+    }
+
+    fn entries(&self, strategy: Option<&str>) -> Result<Vec<BundlePnl>> {
+        let mut entries: Vec<BundlePnl> = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .filter(|pnl| strategy.is_none() || strategy == Some(pnl.strategy.as_str()))
+            .cloned()
+            .collect();
+        entries.sort_by_key(|pnl| pnl.block);
+        Ok(entries)
+    }
+}
+
+#[cfg(feature = "dedup")]
+mod sqlite {
+    use super::{BundlePnl, PnlStore};
+    use crate::dedup::SqliteDedupStore;
+    use crate::Result;
+    use ethers::types::{TxHash, U256};
+    use rusqlite::types::Type;
+    use rusqlite::{params, Row};
+    use std::str::FromStr;
+    use std::sync::PoisonError;
+
+    /// Parses the text of column `index`; amounts are stored in decimal.
+    fn column<T>(
+        row: &Row<'_>,
+        index: usize,
+        parse: impl Fn(&str) -> Option<T>,
+    ) -> rusqlite::Result<T> {
+        let text: String = row.get(index)?;
+        parse(&text).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(index, Type::Text, text.into())
+        })
+    }
+
+    fn amount(row: &Row<'_>, index: usize) -> rusqlite::Result<U256> {
+        column(row, index, |text| U256::from_dec_str(text).ok())
+    }
+
+    impl PnlStore for SqliteDedupStore {
+        fn record(&self, pnl: &BundlePnl) -> Result<()> {
+            self.connection
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .execute(
+                    "INSERT OR REPLACE INTO pnl (bundle_hash, strategy, block, received, gas_paid, coinbase_paid, \
+                     refund_owed, refund_received) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        format!("{:?}", pnl.bundle_hash),
+                        pnl.strategy,
+                        pnl.block.as_u64(),
+                        pnl.received.to_string(),
+                        pnl.gas_paid.to_string(),
+                        pnl.coinbase_paid.to_string(),
+                        pnl.refund_owed.to_string(),
+                        pnl.refund_received.to_string(),
+                    ],
+                )?;
+            Ok(())
+        }
+
+        fn record_refund(&self, bundle_hash: TxHash, owed: U256, received: U256) -> Result<()> {
+            self.connection
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .execute(
+                    "UPDATE pnl SET refund_owed = ?2, refund_received = ?3 WHERE bundle_hash = ?1",
+                    params![
+                        format!("{bundle_hash:?}"),
+                        owed.to_string(),
+                        received.to_string()
+                    ],
+                )?;
+            Ok(())
+        }
+
+        fn entries(&self, strategy: Option<&str>) -> Result<Vec<BundlePnl>> {
+            let connection = self
+                .connection
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let mut statement = connection.prepare(
+                "SELECT bundle_hash, strategy, block, received, gas_paid, coinbase_paid, refund_owed, refund_received \
+                 FROM pnl WHERE ?1 IS NULL OR strategy = ?1 ORDER BY block",
+            )?;
+
+            let entries = statement
+                .query_map(params![strategy], |row| {
+                    Ok(BundlePnl {
+                        bundle_hash: column(row, 0, |text| TxHash::from_str(text).ok())?,
+                        strategy: row.get(1)?,
+                        block: row.get::<_, u64>(2)?.into(),
+                        received: amount(row, 3)?,
+                        gas_paid: amount(row, 4)?,
+                        coinbase_paid: amount(row, 5)?,
+                        refund_owed: amount(row, 6)?,
+                        refund_received: amount(row, 7)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<_>>()?;
+
+            Ok(entries)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::FeeRefund;
+
+    fn check_store(store: &dyn PnlStore) {
+        let gwei = U256::exp10(9);
+        let searcher = Address::random();
+        let receipts = [
+            TransactionReceipt {
+                from: Address::random(),
+                gas_used: Some(100_000.into()),
+                effective_gas_price: Some(gwei * 50),
+                ..Default::default()
+            },
+            TransactionReceipt {
+                from: searcher,
+                gas_used: Some(200_000.into()),
+                effective_gas_price: Some(gwei * 40),
+                ..Default::default()
+            },
+        ];
+
+        let mut backrun =
+            BundlePnl::landed("backrun", TxHash::random(), 100.into(), &receipts, searcher);
+        assert_eq!(backrun.gas_paid, gwei * 8_000_000);
+        backrun.received = gwei * 20_000_000;
+        backrun.coinbase_paid = gwei * 10_000_000;
+        store.record(&backrun).unwrap();
+
+        let mut liquidation =
+            BundlePnl::landed("liquidation", TxHash::random(), 101.into(), &[], searcher);
+        liquidation.coinbase_paid = gwei * 1_000_000;
+        store.record(&liquidation).unwrap();
+
+        let refund = |hash, amount, status| FeeRefund {
+            hash,
+            amount,
+            block_number: 100.into(),
+            status,
+            recipient: searcher,
+        };
+        store
+            .record_refunds(&FeeRefunds {
+                refunds: vec![
+                    refund(
+                        backrun.bundle_hash,
+                        gwei * 1_000_000,
+                        RefundStatus::Received,
+                    ),
+                    refund(backrun.bundle_hash, gwei * 500_000, RefundStatus::Pending),
+                    refund(TxHash::random(), gwei, RefundStatus::Received),
+                ],
+                cursor: None,
+            })
+            .unwrap();
+
+        let summary = store.summary(Some("backrun")).unwrap();
+        assert_eq!(summary.bundles, 1);
+        assert_eq!(summary.realized(), signed(gwei * 3_000_000));
+        assert_eq!(summary.expected(), signed(gwei * 3_500_000));
+
+        let all = store.summary(None).unwrap();
+        assert_eq!(all.bundles, 2);
+        assert_eq!(all.realized(), signed(gwei * 2_000_000));
+
+        let by_strategy = store.summary_by_strategy().unwrap();
+        assert_eq!(
+            by_strategy["liquidation"].realized(),
+            -signed(gwei * 1_000_000)
+        );
+        assert_eq!(
+            store
+                .entries(None)
+                .unwrap()
+                .first()
+                .map(|pnl| pnl.bundle_hash),
+            Some(backrun.bundle_hash)
+        );
+    }
+
+    #[test]
+    fn test_memory_pnl_store() {
+        check_store(&MemoryPnlStore::default());
+    }
+
+    #[cfg(feature = "dedup")]
+    #[test]
+    fn test_sqlite_pnl_store() {
+        check_store(&crate::dedup::SqliteDedupStore::open_in_memory().unwrap());
+    }
+}
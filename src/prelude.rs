@@ -1,9 +1,10 @@
-pub use crate::api::types::{
-    Body, Builder, GetEventHistoryParams, Hint,
-    Hint::{Calldata, ContractAddress, FunctionSelector, Hash, Logs},
-    Inclusion, Metadata, MevShareEvent, PendingBundle, Privacy, Refund, RefundConfig,
-    SendBundleParams, SendTransactionParams, SimulateBundleParams, SimulateBundleResponse,
-    UserStats, Validity,
-};
-pub use crate::client::MevShareClient;
-pub use sugars::hset as set;
+//! The most commonly used items, to glob-import with `use mev_share_rs::prelude::*`.
+//!
+//! Everything else lives in the [`crate::bundle`], [`crate::transaction`], [`crate::stream`], [`crate::history`]
+//! and [`crate::stats`] modules.
+
+pub use crate::bundle::{Body, Builder, Builders, Hint, SendBundleParams, SimulateBundleParams};
+pub use crate::history::GetEventHistoryParams;
+pub use crate::stream::MevShareEvent;
+pub use crate::transaction::SendTransactionParams;
+pub use crate::MevShareClient;
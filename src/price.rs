@@ -0,0 +1,263 @@
+//! Valuing ETH amounts in USD, for profitability estimates, budgets and profit and loss reports that read in dollars.
+//!
+//! A [`PriceOracle`] caches the ETH/USD price of a [`PriceSource`]: a [`ChainlinkFeed`] read through the provider, a
+//! [`FixedPrice`], or any user-supplied source. The price then converts amounts with [`wei_to_usd`] and
+//! [`usd_to_wei`], or with the `_usd` methods of [`crate::pnl::PnlSummary`], [`crate::budget::BudgetUsage`] and
+//! [`crate::bundle::SimulateBundleResponse`].
+//!
+//! # Example
+//!
+//! ```
+//! let oracle = PriceOracle::new(ChainlinkFeed::eth_usd(provider.clone()));
+//!
+//! // a budget of $500 an hour
+//! let client = MevShareClient::new(auth_wallet, provider)
+//!     .await?
+//!     .with_budget(Budget::builder().max_value(oracle.to_wei(500.0).await?).build());
+//!
+//! let simulation = client.simulate_bundle(bundle, None).await?;
+//! info!(profit = simulation.profit_usd(oracle.eth_usd().await?), "simulated");
+//! ```
+
+use crate::{Error, Result};
+use async_trait::async_trait;
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::utils::{format_ether, format_units, parse_ether};
+use instant::Instant;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+/// Where a [`PriceOracle`] gets the ETH/USD price from.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait PriceSource: Send + Sync {
+    /// Returns the price of one ETH, in USD.
+    async fn eth_usd(&self) -> Result<f64>;
+}
+
+/// A constant price, e.g. for tests and back-of-the-envelope estimates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FixedPrice(pub f64);
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl PriceSource for FixedPrice {
+    async fn eth_usd(&self) -> Result<f64> {
+        Ok(self.0)
+    }
+}
+
+/// The Chainlink ETH/USD feed on Ethereum mainnet.
+pub const CHAINLINK_ETH_USD: Address = H160([
+    0x5f, 0x4e, 0xc3, 0xdf, 0x9c, 0xbd, 0x43, 0x71, 0x4f, 0xe2, 0x74, 0x0f, 0x5e, 0x36, 0x16, 0x15,
+    0x5c, 0x5b, 0x84, 0x19,
+]);
+
+/// `latestRoundData()`
+const LATEST_ROUND_DATA: [u8; 4] = [0xfe, 0xaf, 0x96, 0x8c];
+/// `decimals()`
+const DECIMALS: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+
+/// A Chainlink price feed, read with `eth_call`s through the provider.
+#[derive(Clone, Debug)]
+pub struct ChainlinkFeed {
+    provider: Provider<Ws>,
+    feed: Address,
+    max_age: Duration,
+}
+
+impl ChainlinkFeed {
+    /// Reads the ETH/USD aggregator at `feed`, e.g. on another network than mainnet.
+    #[must_use]
+    pub fn new(provider: Provider<Ws>, feed: Address) -> Self {
+        Self {
+            provider,
+            feed,
+            max_age: Duration::from_secs(60 * 60),
+        }
+    }
+
+    /// Reads the mainnet ETH/USD feed, [`CHAINLINK_ETH_USD`].
+    #[must_use]
+    pub fn eth_usd(provider: Provider<Ws>) -> Self {
+        Self::new(provider, CHAINLINK_ETH_USD)
+    }
+
+    /// Fails with [`Error::Price`] if the feed wasn't updated for `max_age`. Defaults to an hour, the heartbeat of the
+    /// mainnet ETH/USD feed.
+    #[must_use]
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    async fn call(&self, selector: [u8; 4]) -> Result<Bytes> {
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(self.feed)
+            .data(selector.to_vec())
+            .into();
+        Ok(self.provider.call(&tx, None).await?)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl PriceSource for ChainlinkFeed {
+    async fn eth_usd(&self) -> Result<f64> {
+        let (round, decimals) =
+            futures::try_join!(self.call(LATEST_ROUND_DATA), self.call(DECIMALS))?;
+        if round.len() < 5 * 32 || decimals.len() < 32 {
+            return Err(Error::Price(format!(
+                "unexpected answer of feed {:?}",
+                self.feed
+            )));
+        }
+
+        // (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound)
+        let answer = I256::from_raw(U256::from_big_endian(&round[32..64]));
+        let updated_at = U256::from_big_endian(&round[96..128]);
+        let decimals = U256::from_big_endian(&decimals[..32]);
+
+        if answer <= I256::zero() || decimals > U256::from(36) {
+            return Err(Error::Price(format!(
+                "feed {:?} answered {answer}",
+                self.feed
+            )));
+        }
+        let now = crate::helpers::system_now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let age = now.saturating_sub(updated_at.low_u64());
+        if age > self.max_age.as_secs() {
+            return Err(Error::Price(format!("feed {:?} is {age}s old", self.feed)));
+        }
+
+        let units = format_units(answer.into_raw(), decimals.as_u32())
+            .map_err(|err| Error::Price(err.to_string()))?;
+        units
+            .parse()
+            .map_err(|_| Error::Price(format!("feed {:?} answered {units}", self.feed)))
+    }
+}
+
+/// Caches the price of a [`PriceSource`] for a while, see [`crate::price`].
+pub struct PriceOracle {
+    source: Box<dyn PriceSource>,
+    ttl: Duration,
+    cache: Mutex<Option<(Instant, f64)>>,
+}
+
+impl PriceOracle {
+    /// Gets the price from `source` at most once a minute.
+    #[must_use]
+    pub fn new(source: impl PriceSource + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+            ttl: Duration::from_secs(60),
+            cache: Mutex::default(),
+        }
+    }
+
+    /// Gets the price from the source at most once per `ttl`.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Returns the price of one ETH, in USD.
+    ///
+    /// # Errors
+    ///
+    /// * Any error of the source, e.g. [`Error::Price`] or [`Error::Provider`] for a [`ChainlinkFeed`].
+    pub async fn eth_usd(&self) -> Result<f64> {
+        if let Some((at, price)) = *self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+            && at.elapsed() < self.ttl
+        {
+            return Ok(price);
+        }
+
+        let price = self.source.eth_usd().await?;
+        *self.cache.lock().unwrap_or_else(PoisonError::into_inner) = Some((Instant::now(), price));
+        Ok(price)
+    }
+
+    /// Values `wei` in USD.
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Self::eth_usd`].
+    pub async fn to_usd(&self, wei: U256) -> Result<f64> {
+        Ok(wei_to_usd(wei, self.eth_usd().await?))
+    }
+
+    /// Converts `usd` into wei, e.g. to set [`crate::budget::Budget::max_value`] in dollars.
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Self::eth_usd`] and [`usd_to_wei`].
+    pub async fn to_wei(&self, usd: f64) -> Result<U256> {
+        usd_to_wei(usd, self.eth_usd().await?)
+    }
+}
+
+/// Values `wei` in USD at `eth_usd` dollars per ETH.
+#[must_use]
+pub fn wei_to_usd(wei: U256, eth_usd: f64) -> f64 {
+    format_ether(wei).parse::<f64>().unwrap_or(f64::NAN) * eth_usd
+}
+
+/// Values a signed amount of `wei`, e.g. a profit, in USD at `eth_usd` dollars per ETH.
+#[must_use]
+pub fn signed_wei_to_usd(wei: I256, eth_usd: f64) -> f64 {
+    let usd = wei_to_usd(wei.unsigned_abs(), eth_usd);
+    if wei.is_negative() {
+        -usd
+    } else {
+        usd
+    }
+}
+
+/// Converts `usd` into wei at `eth_usd` dollars per ETH.
+///
+/// # Errors
+///
+/// * [`Error::Price`] if `usd` is negative or either isn't finite.
+pub fn usd_to_wei(usd: f64, eth_usd: f64) -> Result<U256> {
+    let eth = usd / eth_usd;
+    if !eth.is_finite() || eth < 0.0 {
+        return Err(Error::Price(format!(
+            "can't convert ${usd} at ${eth_usd}/ETH"
+        )));
+    }
+
+    parse_ether(format!("{eth:.18}")).map_err(|err| Error::Price(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_price_oracle() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        let oracle = PriceOracle::new(FixedPrice(2_000.0));
+        assert!(close(oracle.eth_usd().await.unwrap(), 2_000.0));
+        assert!(close(
+            oracle.to_usd(parse_ether("1.5").unwrap()).await.unwrap(),
+            3_000.0
+        ));
+        assert_eq!(
+            oracle.to_wei(500.0).await.unwrap(),
+            parse_ether("0.25").unwrap()
+        );
+
+        let loss = -I256::from_raw(parse_ether("0.1").unwrap());
+        assert!(close(signed_wei_to_usd(loss, 2_000.0), -200.0));
+        assert!(usd_to_wei(-1.0, 2_000.0).is_err());
+        assert!(usd_to_wei(1.0, 0.0).is_err());
+    }
+}
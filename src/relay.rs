@@ -0,0 +1,253 @@
+//! Submitting the same bundle to several builders at once, on top of the Flashbots relay, to raise its chances of
+//! landing whichever builder wins the block.
+//!
+//! Builders accept bundles in the legacy `eth_sendBundle` format, authenticated with the same
+//! `X-Flashbots-Signature` as the relay: a [`MultiRelayClient`] sends each bundle to all of its [`Relay`]s
+//! concurrently, and reports how each of them answered.
+//!
+//! # Example
+//!
+//! ```
+//! let relays = MultiRelayClient::new(auth_wallet).with_relays(Relay::KNOWN);
+//!
+//! let bundle = EthSendBundleParams::builder()
+//!     .txs(vec![signed_tx_1, signed_tx_2])
+//!     .block_number(block + 1)
+//!     .build();
+//!
+//! let submissions = relays.send_bundle(&bundle).await;
+//! for (relay, err) in submissions.failed() {
+//!     warn!(relay = relay.name, %err, "bundle rejected");
+//! }
+//! ```
+//!
+//! Relays outside of [`Relay::KNOWN`] can be targeted on their own, e.g. to mirror a bundle to the Eden relay when it
+//! doesn't land through Flashbots:
+//!
+//! ```
+//! let eden = MultiRelayClient::new(auth_wallet).with_relay(Relay::EDEN);
+//!
+//! if pending_bundle.inclusion().await.is_err() {
+//!     let retry = EthSendBundleParams { block_number: block + 2, ..bundle };
+//!     eden.send_bundle(&retry).await;
+//! }
+//! ```
+
+use crate::api::rpc_client::MevShareRpcClient;
+use crate::api::types::{EthSendBundleParams, SendBundleResponse};
+use crate::client::MevShareRequest;
+use crate::signer::AuthSigner;
+use crate::{Error, Result};
+use futures::future::join_all;
+use std::fmt::Display;
+
+/// A relay or builder endpoint accepting `eth_sendBundle`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Relay {
+    /// Name of the relay, for logs.
+    pub name: &'static str,
+    /// URL of its JSON-RPC API.
+    pub url: &'static str,
+}
+
+impl Relay {
+    /// The Flashbots relay.
+    pub const FLASHBOTS: Self = Self::new("flashbots", "https://relay.flashbots.net");
+    /// Titan Builder.
+    pub const TITAN: Self = Self::new("titan", "https://rpc.titanbuilder.xyz");
+    /// rsync-builder.
+    pub const RSYNC: Self = Self::new("rsync", "https://rsync-builder.xyz");
+    /// beaverbuild.
+    pub const BEAVERBUILD: Self = Self::new("beaverbuild", "https://rpc.beaverbuild.org");
+    /// builder0x69.
+    pub const BUILDER0X69: Self = Self::new("builder0x69", "https://builder0x69.io");
+    /// The Eden Network relay. It authenticates searchers by the `X-Flashbots-Signature` too, the signing address
+    /// being the one staked or registered with Eden for priority.
+    pub const EDEN: Self = Self::new("eden", "https://api.edennetwork.io/v1/bundle");
+
+    /// The Flashbots relay and the mainnet builders winning most blocks, accepting bundles from any searcher.
+    pub const KNOWN: [Self; 5] = [
+        Self::FLASHBOTS,
+        Self::TITAN,
+        Self::RSYNC,
+        Self::BEAVERBUILD,
+        Self::BUILDER0X69,
+    ];
+
+    /// A relay named `name` serving its JSON-RPC API at `url`, e.g. a builder missing from [`Self::KNOWN`].
+    #[must_use]
+    pub const fn new(name: &'static str, url: &'static str) -> Self {
+        Self { name, url }
+    }
+}
+
+impl Display for Relay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name, self.url)
+    }
+}
+
+/// Sends bundles to several relays and builders concurrently. See the [module documentation](self).
+pub struct MultiRelayClient {
+    auth: AuthSigner,
+    relays: Vec<(Relay, MevShareRpcClient)>,
+}
+
+impl MultiRelayClient {
+    /// A client without relays yet, signing its requests with `auth_wallet`. Add relays with [`Self::with_relay`] or
+    /// [`Self::with_relays`].
+    pub fn new(auth_wallet: impl Into<AuthSigner>) -> Self {
+        Self {
+            auth: auth_wallet.into(),
+            relays: Vec::new(),
+        }
+    }
+
+    /// Sends the bundles to `relay` too.
+    #[must_use]
+    pub fn with_relay(mut self, relay: Relay) -> Self {
+        let rpc = MevShareRpcClient::new(relay.url, self.auth.clone());
+        self.relays.push((relay, rpc));
+        self
+    }
+
+    /// Sends the bundles to `relays` too, e.g. [`Relay::KNOWN`].
+    #[must_use]
+    pub fn with_relays(self, relays: impl IntoIterator<Item = Relay>) -> Self {
+        relays.into_iter().fold(self, Self::with_relay)
+    }
+
+    /// The relays bundles are sent to.
+    pub fn relays(&self) -> impl Iterator<Item = &Relay> {
+        self.relays.iter().map(|(relay, _)| relay)
+    }
+
+    /// Sends the bundle to every relay concurrently, and returns once all of them answered.
+    ///
+    /// Unlike [`crate::MevShareClient::send_legacy_bundle`], the bundle doesn't count against a
+    /// [`crate::budget::Budget`] nor is it tracked by a [`crate::guard::CancelGuard`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let submissions = relays.send_bundle(&bundle).await;
+    /// if !submissions.is_accepted() {
+    ///     return Err(submissions.into_errors().next().unwrap().1);
+    /// }
+    /// ```
+    pub async fn send_bundle(&self, params: &EthSendBundleParams) -> RelaySubmissions {
+        let submissions = self.relays.iter().map(|(relay, rpc)| async move {
+            RelaySubmission {
+                relay: *relay,
+                result: rpc
+                    .post(MevShareRequest::SendLegacyBundle, [params])
+                    .await
+                    .map_err(Error::from),
+            }
+        });
+
+        RelaySubmissions(join_all(submissions).await)
+    }
+}
+
+/// How one relay answered a [`MultiRelayClient::send_bundle`].
+#[derive(Debug)]
+pub struct RelaySubmission {
+    /// The relay the bundle was sent to.
+    pub relay: Relay,
+    /// Its answer.
+    pub result: Result<SendBundleResponse>,
+}
+
+/// How every relay answered a [`MultiRelayClient::send_bundle`], in the order they were added to the client.
+#[derive(Debug)]
+pub struct RelaySubmissions(pub Vec<RelaySubmission>);
+
+impl RelaySubmissions {
+    /// Whether at least one relay accepted the bundle.
+    #[must_use]
+    pub fn is_accepted(&self) -> bool {
+        self.0.iter().any(|submission| submission.result.is_ok())
+    }
+
+    /// The relays that accepted the bundle, with their answer.
+    pub fn accepted(&self) -> impl Iterator<Item = (&Relay, &SendBundleResponse)> {
+        self.0
+            .iter()
+            .filter_map(|submission| Some((&submission.relay, submission.result.as_ref().ok()?)))
+    }
+
+    /// The relays that rejected the bundle or couldn't be reached, with the error.
+    pub fn failed(&self) -> impl Iterator<Item = (&Relay, &Error)> {
+        self.0
+            .iter()
+            .filter_map(|submission| Some((&submission.relay, submission.result.as_ref().err()?)))
+    }
+
+    /// The errors of the relays that rejected the bundle or couldn't be reached.
+    pub fn into_errors(self) -> impl Iterator<Item = (Relay, Error)> {
+        self.0
+            .into_iter()
+            .filter_map(|submission| Some((submission.relay, submission.result.err()?)))
+    }
+}
+
+impl Display for RelaySubmissions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, submission) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match &submission.result {
+                Ok(_) => write!(f, "{}: accepted", submission.relay.name)?,
+                Err(err) => write!(f, "{}: {err}", submission.relay.name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::TxHash;
+
+    #[test]
+    fn test_relay_submissions() {
+        let submissions = RelaySubmissions(vec![
+            RelaySubmission {
+                relay: Relay::FLASHBOTS,
+                result: Ok(SendBundleResponse {
+                    bundle_hash: TxHash::repeat_byte(1),
+                }),
+            },
+            RelaySubmission {
+                relay: Relay::TITAN,
+                result: Err(Error::InvalidParams("rejected".into())),
+            },
+        ]);
+
+        assert!(submissions.is_accepted());
+        assert_eq!(
+            submissions
+                .accepted()
+                .map(|(relay, _)| *relay)
+                .collect::<Vec<_>>(),
+            vec![Relay::FLASHBOTS]
+        );
+        assert_eq!(
+            submissions
+                .failed()
+                .map(|(relay, _)| *relay)
+                .collect::<Vec<_>>(),
+            vec![Relay::TITAN]
+        );
+        assert!(submissions
+            .to_string()
+            .starts_with("flashbots: accepted, titan: "));
+
+        let errors = submissions.into_errors().collect::<Vec<_>>();
+        assert_eq!(errors.len(), 1);
+        assert!(!RelaySubmissions(vec![]).is_accepted());
+    }
+}
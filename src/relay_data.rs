@@ -0,0 +1,297 @@
+//! Querying the data APIs of the MEV-Boost relays for the payloads they delivered to proposers, to find out which
+//! builder won the blocks a bundle targeted. See [`PendingBundle::diagnose`].
+//!
+//! # Example
+//!
+//! ```
+//! let pending_bundle = client.send_bundle(bundle).await?;
+//!
+//! if let Err(Error::BundleTimeout(..)) = pending_bundle.inclusion().await {
+//!     let diagnosis = pending_bundle.diagnose().await?;
+//!     warn!(%diagnosis, "bundle didn't land");
+//! }
+//! ```
+
+use crate::api::types::{attribute_block, BuilderAttribution, PendingBundle};
+use crate::helpers::provider::Waiter;
+use crate::helpers::DecimalU256;
+use crate::{Error, Result};
+use ethers::types::{Address, Bytes, TxHash, H256, U256, U64};
+use ethers::utils::format_ether;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use std::fmt::Display;
+
+/// Mainnet MEV-Boost relays serving the data API, queried by default.
+pub const MEV_BOOST_RELAYS: [&str; 5] = [
+    "https://boost-relay.flashbots.net",
+    "https://relay.ultrasound.money",
+    "https://bloxroute.max-profit.blxrbdn.com",
+    "https://agnostic-relay.net",
+    "https://aestus.live",
+];
+
+/// A payload a relay delivered to the proposer of a slot: the block of the builder that won it.
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DeliveredPayload {
+    /// Beacon chain slot.
+    #[serde_as(as = "DisplayFromStr")]
+    pub slot: u64,
+    /// Hash of the parent block.
+    pub parent_hash: H256,
+    /// Hash of the block.
+    pub block_hash: H256,
+    /// BLS public key of the builder of the block.
+    pub builder_pubkey: Bytes,
+    /// BLS public key of the proposer.
+    pub proposer_pubkey: Bytes,
+    /// Where the proposer is paid.
+    pub proposer_fee_recipient: Address,
+    /// Gas limit of the block.
+    #[serde_as(as = "DisplayFromStr")]
+    pub gas_limit: u64,
+    /// Gas used by the block.
+    #[serde_as(as = "DisplayFromStr")]
+    pub gas_used: u64,
+    /// What the builder paid the proposer, in wei.
+    #[serde_as(as = "DecimalU256")]
+    pub value: U256,
+    /// Number of the block.
+    #[serde_as(as = "DisplayFromStr")]
+    pub block_number: u64,
+    /// Number of transactions of the block.
+    #[serde_as(as = "DisplayFromStr")]
+    pub num_tx: u64,
+}
+
+/// Client of the data APIs of a set of MEV-Boost relays.
+pub struct RelayDataClient {
+    relays: Vec<String>,
+    http: reqwest::Client,
+}
+
+impl Default for RelayDataClient {
+    /// Queries the [`MEV_BOOST_RELAYS`].
+    fn default() -> Self {
+        Self::new(MEV_BOOST_RELAYS)
+    }
+}
+
+impl RelayDataClient {
+    /// Queries the data APIs of `relays`, given by their base URL.
+    pub fn new(relays: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            relays: relays.into_iter().map(Into::into).collect(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Returns the payload delivered for block `block`, and the relay that delivered it, asking all the relays
+    /// concurrently.
+    ///
+    /// Returns `None` if no relay delivered the block, e.g. because the proposer built it locally, or because it isn't
+    /// produced yet.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::RelayData`] if no relay answered.
+    pub async fn payload_delivered(
+        &self,
+        block: U64,
+    ) -> Result<Option<(String, DeliveredPayload)>> {
+        let responses = join_all(self.relays.iter().map(|relay| async move {
+            let payloads: Vec<DeliveredPayload> = self
+                .http
+                .get(format!(
+                    "{}/relay/v1/data/bidtraces/proposer_payload_delivered",
+                    relay.trim_end_matches('/')
+                ))
+                .query(&[("block_number", block.as_u64())])
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|err| Error::RelayData(relay.clone(), err))?
+                .json()
+                .await
+                .map_err(|err| Error::RelayData(relay.clone(), err))?;
+
+            Ok((relay, payloads))
+        }))
+        .await;
+
+        let mut first_error = None;
+        let mut answered = false;
+        for response in responses {
+            match response {
+                Ok((relay, payloads)) => {
+                    answered = true;
+                    if let Some(payload) = payloads.into_iter().next() {
+                        return Ok(Some((relay.clone(), payload)));
+                    }
+                }
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+
+        match first_error {
+            Some(err) if !answered => Err(err),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Who won a block a bundle targeted, see [`InclusionDiagnosis`].
+#[derive(Clone, Debug)]
+pub struct BlockDiagnosis {
+    /// Number of the block.
+    pub block: U64,
+    /// The relay that delivered the block, and the payload it delivered, if any.
+    pub delivered: Option<(String, DeliveredPayload)>,
+    /// The builder of the block, recognized on-chain, if the block is produced.
+    pub attribution: Option<BuilderAttribution>,
+}
+
+impl Display for BlockDiagnosis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "block {}: ", self.block)?;
+
+        match (&self.attribution, &self.delivered) {
+            (None, _) => write!(f, "not produced yet"),
+            (Some(attribution), Some((relay, payload))) => write!(
+                f,
+                "won by {attribution} (builder {}), delivered by {relay} for {} ETH",
+                payload.builder_pubkey,
+                format_ether(payload.value)
+            ),
+            (Some(attribution), None) => {
+                write!(f, "built by {attribution}, not delivered by a known relay")
+            }
+        }
+    }
+}
+
+/// Who won each block of the inclusion window of a bundle, see [`PendingBundle::diagnose`].
+#[derive(Clone, Debug)]
+pub struct InclusionDiagnosis {
+    /// Hash of the bundle.
+    pub bundle_hash: TxHash,
+    /// The blocks of the inclusion window, in order.
+    pub blocks: Vec<BlockDiagnosis>,
+}
+
+impl Display for InclusionDiagnosis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bundle {:?}", self.bundle_hash)?;
+        for block in &self.blocks {
+            write!(f, "; {block}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<M: Waiter> PendingBundle<M> {
+    /// Reports which builder won each block of the inclusion window of the bundle, asking the
+    /// [`MEV_BOOST_RELAYS`], e.g. once the bundle timed out, to tell whether it was outbid or didn't reach the builders
+    /// that won.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::diagnose_with`].
+    pub async fn diagnose(&self) -> Result<InclusionDiagnosis> {
+        self.diagnose_with(&RelayDataClient::default()).await
+    }
+
+    /// Reports which builder won each block of the inclusion window of the bundle, asking the relays of `relays`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Provider`] if a block of the window can't be fetched.
+    /// * [`Error::RelayData`] if no relay answered for a block of the window.
+    pub async fn diagnose_with(&self, relays: &RelayDataClient) -> Result<InclusionDiagnosis> {
+        let inclusion = &self.request.inclusion;
+        let max_block = inclusion.max_block.unwrap_or(inclusion.block);
+
+        let mut blocks = Vec::new();
+        let mut block = inclusion.block;
+        while block <= max_block {
+            let attribution = attribute_block(&*self.provider, block).await?;
+            let delivered = match attribution {
+                Some(_) => relays.payload_delivered(block).await?,
+                None => None,
+            };
+
+            blocks.push(BlockDiagnosis {
+                block,
+                delivered,
+                attribution,
+            });
+            block += U64::one();
+        }
+
+        Ok(InclusionDiagnosis {
+            bundle_hash: self.hash,
+            blocks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::KNOWN_BUILDERS;
+
+    #[test]
+    fn test_delivered_payload() {
+        let payload: DeliveredPayload = serde_json::from_value(serde_json::json!({
+            "slot": "7000000",
+            "parent_hash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "block_hash": "0x0000000000000000000000000000000000000000000000000000000000000002",
+            "builder_pubkey": "0xaa",
+            "proposer_pubkey": "0xbb",
+            "proposer_fee_recipient": "0x388c818ca8b9251b393131c08a736a67ccb19297",
+            "gas_limit": "30000000",
+            "gas_used": "12000000",
+            "value": "50000000000000000",
+            "block_number": "18000000",
+            "num_tx": "150"
+        }))
+        .unwrap();
+        assert_eq!(payload.block_number, 18_000_000);
+        assert_eq!(payload.value, U256::exp10(16) * 5);
+
+        let titan = KNOWN_BUILDERS.iter().find(|known| known.name == "Titan");
+        let diagnosis = InclusionDiagnosis {
+            bundle_hash: TxHash::zero(),
+            blocks: vec![
+                BlockDiagnosis {
+                    block: 18_000_000.into(),
+                    delivered: Some(("https://relay.ultrasound.money".to_owned(), payload)),
+                    attribution: Some(BuilderAttribution {
+                        block: Some(18_000_000.into()),
+                        coinbase: Address::zero(),
+                        extra_data: Bytes::default(),
+                        builder: titan,
+                    }),
+                },
+                BlockDiagnosis {
+                    block: 18_000_001.into(),
+                    delivered: None,
+                    attribution: None,
+                },
+            ],
+        };
+        assert_eq!(
+            diagnosis.blocks[0].to_string(),
+            "block 18000000: won by Titan (builder 0xaa), delivered by https://relay.ultrasound.money for \
+             0.050000000000000000 ETH"
+        );
+        assert_eq!(
+            diagnosis.blocks[1].to_string(),
+            "block 18000001: not produced yet"
+        );
+    }
+}
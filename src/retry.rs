@@ -0,0 +1,94 @@
+//! Retrying the requests to the relay that fail transiently: the relay is unreachable, resets the connection, does not
+//! respond in time, or answers with a `5xx` or `429` HTTP status. See [`crate::MevShareClient::with_retry_policy`].
+//!
+//! Sending a transaction or a bundle is not retried unless [`RetryPolicy::retry_sends`] says so: the relay may have
+//! accepted it before failing, and a duplicate counts against the rate limit of the searcher.
+//!
+//! # Example
+//!
+//! ```
+//! let client = MevShareClient::new(auth_wallet, provider)
+//!     .await?
+//!     .with_retry_policy(
+//!         RetryPolicy::builder()
+//!             .max_attempts(4)
+//!             .initial_backoff(Duration::from_millis(200))
+//!             .build(),
+//!     );
+//! ```
+
+use ethers::core::rand::{thread_rng, Rng};
+use std::time::Duration;
+use typed_builder::TypedBuilder;
+
+/// When, and how many times, the requests failing transiently are retried.
+///
+/// Retries back off exponentially, with jitter so that the clients failing together don't retry together.
+#[derive(Clone, Debug, PartialEq, Eq, TypedBuilder)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts of a request, the first one included. Defaults to 3.
+    #[builder(default = 3)]
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubled at each attempt. Defaults to 100ms.
+    #[builder(default = Duration::from_millis(100))]
+    pub initial_backoff: Duration,
+    /// Maximum wait between two attempts, unless the relay asks for more with `Retry-After`. Defaults to 5s.
+    #[builder(default = Duration::from_secs(5))]
+    pub max_backoff: Duration,
+    /// Whether to retry sending transactions and bundles too, e.g. for bundles whose replacement UUID makes a
+    /// duplicate harmless. Defaults to `false`.
+    #[builder(default)]
+    pub retry_sends: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl RetryPolicy {
+    /// Wait after the failed attempt number `attempt`, counting from 1: between half and all of the exponential
+    /// backoff, and no less than the `Retry-After` of the relay, if any.
+    #[must_use]
+    pub fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let exponential = self
+            .initial_backoff
+            .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff);
+        let jittered = exponential / 2 + exponential.mul_f64(thread_rng().gen_range(0.0..0.5));
+
+        jittered.max(retry_after.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::builder()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(300))
+            .build();
+
+        for _ in 0..100 {
+            let first = policy.backoff(1, None);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+
+            let second = policy.backoff(2, None);
+            assert!(second >= Duration::from_millis(100) && second <= Duration::from_millis(200));
+
+            // capped
+            let tenth = policy.backoff(10, None);
+            assert!(tenth >= Duration::from_millis(150) && tenth <= Duration::from_millis(300));
+        }
+
+        // the relay knows best
+        assert_eq!(
+            policy.backoff(1, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+    }
+}
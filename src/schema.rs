@@ -0,0 +1,156 @@
+//! JSON Schema of the MEV-Share API types, and an [OpenRPC] document of the relay methods built from them.
+//!
+//! Enabled by the `schema` feature. Every request and response type implements [`schemars::JsonSchema`], so gateways
+//! can validate payloads against the exact shape this crate sends, and other languages can generate clients from it.
+//!
+//! # Example
+//!
+//! ```
+//! // a single type
+//! let schema = schemars::schema_for!(mev_share_rs::bundle::SendBundleParams);
+//!
+//! // every relay method
+//! std::fs::write("mev-share.openrpc.json", serde_json::to_string_pretty(&mev_share_rs::schema::openrpc())?)?;
+//! ```
+//!
+//! [OpenRPC]: https://spec.open-rpc.org
+
+use crate::api::types::{
+    CancelBundleParams, CancelPrivateTransactionParams, EthCallBundleParams, EthCallBundleResponse,
+    EthSendBundleParams, FeeRefunds, GetFeeRefundsByRecipientParams, SendBundleParams,
+    SendBundleResponse, SendTransactionParams, SimulateBundleParams, SimulateBundleResponse,
+};
+use crate::client::MevShareRequest;
+use mev_share_types::schema::HexHash;
+use schemars::r#gen::SchemaSettings;
+use schemars::schema::{Schema, SchemaObject};
+use schemars::{JsonSchema, SchemaGenerator};
+use serde_json::{json, Value};
+
+/// [OpenRPC](https://spec.open-rpc.org) version of the document returned by [`openrpc`].
+pub const OPENRPC_VERSION: &str = "1.2.6";
+
+/// Returns an [OpenRPC](https://spec.open-rpc.org) document describing the JSON-RPC methods of the relay used by this crate, with the JSON Schema
+/// of their params and results under `components.schemas`.
+#[must_use]
+pub fn openrpc() -> Value {
+    let mut generator = SchemaSettings::draft07()
+        .with(|settings| settings.definitions_path = "#/components/schemas/".into())
+        .into_generator();
+
+    let methods = vec![
+        method::<SendTransactionParams, HexHash>(
+            &mut generator,
+            MevShareRequest::SendPrivateTransaction,
+            "Sends a signed transaction to the relay, to be shared with searchers according to its privacy preferences.",
+        ),
+        method::<SendBundleParams, SendBundleResponse>(
+            &mut generator,
+            MevShareRequest::SendBundle,
+            "Sends a bundle to the relay, e.g. a backrun of a transaction hinted on the event stream.",
+        ),
+        method::<(SendBundleParams, SimulateBundleParams), SimulateBundleResponse>(
+            &mut generator,
+            MevShareRequest::SimBundle,
+            "Simulates a bundle, on top of the given block state.",
+        ),
+        method::<CancelPrivateTransactionParams, bool>(
+            &mut generator,
+            MevShareRequest::CancelPrivateTransaction,
+            "Withdraws a private transaction from the relay.",
+        ),
+        method::<CancelBundleParams, Value>(
+            &mut generator,
+            MevShareRequest::CancelBundle,
+            "Withdraws the bundles sent with a replacement UUID.",
+        ),
+        method::<EthSendBundleParams, SendBundleResponse>(
+            &mut generator,
+            MevShareRequest::SendLegacyBundle,
+            "Sends a bundle in the legacy format, predating mev_sendBundle.",
+        ),
+        method::<EthCallBundleParams, EthCallBundleResponse>(
+            &mut generator,
+            MevShareRequest::CallBundle,
+            "Simulates a bundle in the legacy format on top of the state of a block.",
+        ),
+        method::<GetFeeRefundsByRecipientParams, FeeRefunds>(
+            &mut generator,
+            MevShareRequest::GetFeeRefundsByRecipient,
+            "Lists the gas fee refunds owed, or paid, to a recipient, one page at a time.",
+        ),
+    ];
+
+    json!({
+        "openrpc": OPENRPC_VERSION,
+        "info": {
+            "title": "MEV-Share",
+            "description": "MEV-Share relay JSON-RPC API, as used by mev-share-rs.",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "methods": methods,
+        "components": {
+            "schemas": generator.take_definitions(),
+        },
+    })
+}
+
+/// Describes `request`, taking `P` as positional params: a tuple for several, any other type for a single one.
+fn method<P: JsonSchema, R: JsonSchema>(
+    generator: &mut SchemaGenerator,
+    request: MevShareRequest,
+    summary: &str,
+) -> Value {
+    let params = match generator.subschema_for::<P>() {
+        // tuples are inlined as arrays of their items
+        Schema::Object(SchemaObject {
+            array: Some(array), ..
+        }) if P::schema_name().starts_with("Tuple_of_") => match array.items {
+            Some(schemars::schema::SingleOrVec::Vec(items)) => items,
+            _ => Vec::new(),
+        },
+        schema => vec![schema],
+    };
+
+    json!({
+        "name": request.as_method_name(),
+        "summary": summary,
+        "paramStructure": "by-position",
+        "params": params
+            .into_iter()
+            .enumerate()
+            .map(|(i, schema)| json!({ "name": format!("param{i}"), "required": true, "schema": schema }))
+            .collect::<Vec<_>>(),
+        "result": { "name": "result", "schema": generator.subschema_for::<R>() },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openrpc() {
+        let document = openrpc();
+
+        let send_bundle = &document["methods"][1];
+        assert_eq!(send_bundle["name"], "mev_sendBundle");
+        assert_eq!(
+            send_bundle["params"][0]["schema"]["$ref"],
+            "#/components/schemas/SendBundleParams"
+        );
+
+        let sim_bundle = &document["methods"][2];
+        assert_eq!(sim_bundle["params"].as_array().unwrap().len(), 2);
+
+        let schemas = &document["components"]["schemas"];
+        assert_eq!(
+            schemas["Inclusion"]["properties"]["block"]["$ref"],
+            "#/components/schemas/HexQuantity"
+        );
+        assert!(schemas["Body"]["anyOf"][1]["properties"]["canRevert"].is_object());
+        assert!(schemas["Hint"]
+            .to_string()
+            .contains(r#""function_selector""#));
+    }
+}
@@ -0,0 +1,556 @@
+//! A local JSON-RPC server sharing a single authenticated [`MevShareClient`] with non-Rust services.
+//!
+//! Enabled by the `server` feature. Clients connect over websocket and speak JSON-RPC 2.0:
+//!
+//! * `eth_sendPrivateTransaction`, `mev_sendBundle` and `mev_simBundle` take the same params as the relay,
+//!   and are signed with the server's authentication key. Sends return the transaction/bundle hash right away.
+//! * `mev_getStatus(hash)` returns the [`OrderStatus`] of a transaction or bundle sent through the server, until an
+//!   hour after it's included or failed.
+//! * `mev_subscribe(filter?)` streams the hints matching an optional [`EventFilter`] as `mev_subscription`
+//!   notifications, until `mev_unsubscribe(id)`. Every subscription shares the server's connection to the SSE stream.
+//!
+//! # Example
+//!
+//! ```
+//! let client = MevShareClient::new(auth_wallet, provider).await?;
+//!
+//! Server::new(client).serve("127.0.0.1:8546").await?;
+//! ```
+//!
+//! ```sh
+//! websocat ws://127.0.0.1:8546
+//! {"jsonrpc":"2.0","id":1,"method":"mev_subscribe","params":[{"to":["0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"]}]}
+//! ```
+
+use crate::api::types::{
+    EventFilter, MevShareEvent, SendBundleParams, SendTransactionParams, SimulateBundleParams,
+};
+use crate::{MevShareClient, Result};
+use ethers::types::{TxHash, U64};
+use futures::{SinkExt, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::AbortHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::*;
+
+/// Number of hints buffered for each subscriber before it starts missing some.
+const EVENTS_BUFFER: usize = 1024;
+
+/// How long `mev_getStatus` keeps reporting the status of an order once it's included or failed.
+const SETTLED_RETENTION: Duration = Duration::from_secs(60 * 60);
+
+/// How long to wait before accepting connections again after failing to, e.g. because the process ran out of file
+/// descriptors.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Status of a transaction or bundle sent through the [`Server`], see `mev_getStatus`.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum OrderStatus {
+    /// Waiting for inclusion.
+    Pending,
+    /// Included in `block`.
+    Included { block: U64 },
+    /// Not included, reverted or discarded.
+    Failed { error: String },
+}
+
+/// Serves a [`MevShareClient`] over a local websocket JSON-RPC API. See the [module docs](self).
+pub struct Server {
+    client: MevShareClient,
+    orders: Mutex<OrderBook>,
+    events: broadcast::Sender<Arc<MevShareEvent>>,
+    next_subscription: AtomicU64,
+}
+
+impl Server {
+    /// Serves `client`, see [`Self::serve`].
+    #[must_use]
+    pub fn new(client: MevShareClient) -> Self {
+        Self {
+            client,
+            orders: Mutex::default(),
+            events: broadcast::channel(EVENTS_BUFFER).0,
+            next_subscription: AtomicU64::default(),
+        }
+    }
+
+    /// Listens on `addr` and serves connections. Failing to accept a connection, e.g. because the process ran out of
+    /// file descriptors, is logged and retried shortly after.
+    ///
+    /// Don't expose it beyond localhost: anybody that can connect sends orders with your authentication key.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Io`] if `addr` can't be bound.
+    pub async fn serve(self, addr: impl ToSocketAddrs) -> Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        info!(addr = ?listener.local_addr()?, "serving MEV-Share");
+
+        let server = Arc::new(self);
+        tokio::spawn(server.clone().forward_events());
+
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    warn!(%err, "failed to accept a connection");
+                    tokio::time::sleep(ACCEPT_BACKOFF).await;
+                    continue;
+                }
+            };
+            let server = server.clone();
+
+            tokio::spawn(async move {
+                if let Err(err) = server.handle_connection(stream).await {
+                    debug!(%peer, ?err, "connection closed");
+                }
+            });
+        }
+    }
+
+    /// Broadcasts the hints of the SSE stream to the subscribers.
+    async fn forward_events(self: Arc<Self>) {
+        let mut stream = Box::pin(self.client.subscribe_bundles());
+
+        while let Some(event) = stream.next().await {
+            match event {
+                // no subscribers is not an error
+                Ok(event) => _ = self.events.send(Arc::new(event)),
+                Err(err) => warn!(?err, "error on the event stream"),
+            }
+        }
+
+        error!("event stream ended");
+    }
+
+    async fn handle_connection(self: Arc<Self>, stream: TcpStream) -> Result<()> {
+        let (mut sink, mut source) = tokio_tungstenite::accept_async(stream).await?.split();
+        let (sender, mut outbox) = mpsc::unbounded_channel();
+        let connection = Connection {
+            sender,
+            subscriptions: Arc::default(),
+        };
+
+        let write = async {
+            while let Some(message) = outbox.recv().await {
+                sink.send(Message::Text(message)).await?;
+            }
+            Ok(())
+        };
+
+        let read = async {
+            while let Some(message) = source.next().await {
+                match message? {
+                    Message::Text(request) => {
+                        tokio::spawn(self.clone().handle_request(request, connection.clone()));
+                    }
+                    Message::Close(_) => break,
+                    _ => {}
+                }
+            }
+            Ok(())
+        };
+
+        let result = tokio::select! {
+            result = write => result,
+            result = read => result,
+        };
+
+        for (_, subscription) in connection
+            .subscriptions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain()
+        {
+            subscription.abort();
+        }
+
+        result
+    }
+
+    async fn handle_request(self: Arc<Self>, request: String, connection: Connection) {
+        let request: Request = match serde_json::from_str(&request) {
+            Ok(request) => request,
+            Err(err) => {
+                connection.respond(&Value::Null, Err(RpcFailure::parse(&err)));
+                return;
+            }
+        };
+
+        let id = &request.id;
+        let params = request.params();
+
+        match request.method {
+            "eth_sendPrivateTransaction" => {
+                self.send_private_transaction(id, params, &connection).await;
+            }
+            "mev_sendBundle" => self.send_bundle(id, params, &connection).await,
+            "mev_simBundle" => connection.respond(id, self.simulate_bundle(params).await),
+            "mev_getStatus" => connection.respond(id, self.status(params)),
+            "mev_subscribe" => connection.respond(id, self.subscribe(params, &connection)),
+            "mev_unsubscribe" => connection.respond(
+                id,
+                parse(params).map(|(subscription,)| json!(connection.unsubscribe(subscription))),
+            ),
+            method => connection.respond(
+                id,
+                Err(RpcFailure {
+                    code: -32601,
+                    message: format!("method not found: {method}"),
+                }),
+            ),
+        }
+    }
+
+    /// Responds with the transaction hash as soon as the relay accepts it, then tracks its inclusion.
+    async fn send_private_transaction(&self, id: &Value, params: &str, connection: &Connection) {
+        let (params,): (SendTransactionParams,) = match parse(params) {
+            Ok(params) => params,
+            Err(err) => return connection.respond(id, Err(err)),
+        };
+
+        match self.client.send_private_transaction(params).await {
+            Ok(pending) => {
+                self.set_status(pending.hash, OrderStatus::Pending);
+                connection.respond(id, Ok(json!(pending.hash)));
+
+                let inclusion = pending.inclusion().await.map(|(_, block)| block);
+                self.settle(pending.hash, inclusion);
+            }
+            Err(err) => connection.respond(id, Err(err.into())),
+        }
+    }
+
+    /// Responds with the bundle hash as soon as the relay accepts it, then tracks its inclusion.
+    async fn send_bundle(&self, id: &Value, params: &str, connection: &Connection) {
+        let (params,): (SendBundleParams,) = match parse(params) {
+            Ok(params) => params,
+            Err(err) => return connection.respond(id, Err(err)),
+        };
+
+        match self.client.send_bundle(params).await {
+            Ok(pending) => {
+                let hash = pending.hash;
+                self.set_status(hash, OrderStatus::Pending);
+                connection.respond(id, Ok(json!({ "bundleHash": hash })));
+
+                let inclusion = pending.inclusion().await.map(|(_, block)| block);
+                self.settle(hash, inclusion);
+            }
+            Err(err) => connection.respond(id, Err(err.into())),
+        }
+    }
+
+    async fn simulate_bundle(&self, params: &str) -> std::result::Result<Value, RpcFailure> {
+        // the simulation options are optional
+        let params: Vec<Value> = parse(params)?;
+        let bundle: SendBundleParams = param(&params, 0)?;
+        let options: SimulateBundleParams = param_or_default(&params, 1)?;
+        let simulation = self.client.simulate_bundle(bundle, options).await?;
+        Ok(json!(simulation))
+    }
+
+    fn status(&self, params: &str) -> std::result::Result<Value, RpcFailure> {
+        let (hash,): (TxHash,) = parse(params)?;
+        let orders = self.orders.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(json!(orders.statuses.get(&hash)))
+    }
+
+    /// Forwards the hints matching the filter in `params` to `connection`, until it unsubscribes or goes away.
+    fn subscribe(
+        &self,
+        params: &str,
+        connection: &Connection,
+    ) -> std::result::Result<Value, RpcFailure> {
+        let filter = subscription_filter(params)?;
+
+        let subscription = self.next_subscription.fetch_add(1, Ordering::Relaxed);
+        let mut events = self.events.subscribe();
+        let sender = connection.sender.clone();
+
+        let task = tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) if filter.matches(&event) => {
+                        let notification = json!({
+                            "jsonrpc": "2.0",
+                            "method": "mev_subscription",
+                            "params": { "subscription": subscription, "result": &*event },
+                        });
+                        if sender.send(notification.to_string()).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!(
+                            subscription,
+                            missed, "subscriber is lagging behind, hints were dropped"
+                        );
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        connection
+            .subscriptions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(subscription, task.abort_handle());
+
+        Ok(json!(subscription))
+    }
+
+    fn set_status(&self, hash: TxHash, status: OrderStatus) {
+        self.orders
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .statuses
+            .insert(hash, status);
+    }
+
+    fn settle(&self, hash: TxHash, inclusion: Result<U64>) {
+        let status = match inclusion {
+            Ok(block) => OrderStatus::Included { block },
+            Err(err) => OrderStatus::Failed {
+                error: err.to_string(),
+            },
+        };
+
+        debug!(?hash, ?status, "order settled");
+        self.orders
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .settle(hash, status, Instant::now());
+    }
+}
+
+/// The statuses of the orders sent through the [`Server`]: pending ones until they settle, then settled ones for
+/// [`SETTLED_RETENTION`].
+#[derive(Default)]
+struct OrderBook {
+    statuses: HashMap<TxHash, OrderStatus>,
+    /// When the orders settled, oldest first.
+    settled: VecDeque<(Instant, TxHash)>,
+}
+
+impl OrderBook {
+    /// Records that `hash` settled with `status` at `now`, and forgets the orders settled for longer than
+    /// [`SETTLED_RETENTION`].
+    fn settle(&mut self, hash: TxHash, status: OrderStatus, now: Instant) {
+        while let Some(&(settled_at, expired)) = self.settled.front()
+            && now.duration_since(settled_at) > SETTLED_RETENTION
+        {
+            self.settled.pop_front();
+            // unless it was sent again since
+            if self.statuses.get(&expired) != Some(&OrderStatus::Pending) {
+                self.statuses.remove(&expired);
+            }
+        }
+
+        self.statuses.insert(hash, status);
+        self.settled.push_back((now, hash));
+    }
+}
+
+/// A websocket connection to the [`Server`].
+#[derive(Clone)]
+struct Connection {
+    sender: mpsc::UnboundedSender<String>,
+    subscriptions: Arc<Mutex<HashMap<u64, AbortHandle>>>,
+}
+
+impl Connection {
+    fn respond(&self, id: &Value, result: std::result::Result<Value, RpcFailure>) {
+        let response = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+        };
+
+        // the connection is gone if this fails, nobody to tell
+        _ = self.sender.send(response.to_string());
+    }
+
+    /// Stops the subscription `id`, returning whether it existed.
+    fn unsubscribe(&self, id: u64) -> bool {
+        let subscription = self
+            .subscriptions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&id);
+
+        subscription
+            .map(|subscription| subscription.abort())
+            .is_some()
+    }
+}
+
+/// A JSON-RPC error object.
+#[derive(Serialize, Debug)]
+struct RpcFailure {
+    code: i32,
+    message: String,
+}
+
+impl RpcFailure {
+    fn parse(err: &serde_json::Error) -> Self {
+        Self {
+            code: -32700,
+            message: err.to_string(),
+        }
+    }
+
+    fn invalid_params(err: &serde_json::Error) -> Self {
+        Self {
+            code: -32602,
+            message: format!("invalid params: {err}"),
+        }
+    }
+}
+
+impl From<crate::Error> for RpcFailure {
+    fn from(err: crate::Error) -> Self {
+        Self {
+            code: -32000,
+            message: err.to_string(),
+        }
+    }
+}
+
+/// A JSON-RPC request to the [`Server`].
+#[derive(Deserialize)]
+struct Request<'a> {
+    id: Value,
+    method: &'a str,
+    #[serde(borrow)]
+    params: Option<&'a RawValue>,
+}
+
+impl Request<'_> {
+    /// The params of the request, `[]` if it has none.
+    fn params(&self) -> &str {
+        self.params.map_or("[]", RawValue::get)
+    }
+}
+
+fn parse<'a, T: Deserialize<'a>>(params: &'a str) -> std::result::Result<T, RpcFailure> {
+    serde_json::from_str(params).map_err(|err| RpcFailure::invalid_params(&err))
+}
+
+/// Parses the `index`-th of `params`, which is required.
+fn param<T: DeserializeOwned>(
+    params: &[Value],
+    index: usize,
+) -> std::result::Result<T, RpcFailure> {
+    let param = params.get(index).ok_or_else(|| RpcFailure {
+        code: -32602,
+        message: format!("invalid params: missing param {index}"),
+    })?;
+    T::deserialize(param).map_err(|err| RpcFailure::invalid_params(&err))
+}
+
+/// Parses the `index`-th of `params`, defaulted if missing or `null`.
+fn param_or_default<T: DeserializeOwned + Default>(
+    params: &[Value],
+    index: usize,
+) -> std::result::Result<T, RpcFailure> {
+    match params.get(index) {
+        None | Some(Value::Null) => Ok(T::default()),
+        Some(param) => T::deserialize(param).map_err(|err| RpcFailure::invalid_params(&err)),
+    }
+}
+
+/// Parses the params of `mev_subscribe`, an optional [`EventFilter`].
+fn subscription_filter(params: &str) -> std::result::Result<EventFilter, RpcFailure> {
+    let params: Vec<Value> = parse(params)?;
+    param_or_default(&params, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_status_json() {
+        assert_eq!(
+            json!(OrderStatus::Included {
+                block: U64::from(10)
+            }),
+            json!({ "status": "included", "block": "0xa" })
+        );
+        assert_eq!(json!(OrderStatus::Pending), json!({ "status": "pending" }));
+
+        let (filter,): (Option<EventFilter>,) = parse(r#"[{"withLogs": true}]"#).unwrap();
+        assert!(filter.is_some_and(|filter| filter.with_logs));
+        assert!(parse::<(TxHash,)>("[]").is_err_and(|err| err.code == -32602));
+    }
+
+    #[test]
+    fn test_optional_params() {
+        assert!(
+            subscription_filter(r#"[{"withLogs": true}]"#)
+                .unwrap()
+                .with_logs
+        );
+        assert!(!subscription_filter("[]").unwrap().with_logs);
+        assert!(!subscription_filter("[null]").unwrap().with_logs);
+
+        // a request without params has none
+        let request: Request =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","id":1,"method":"mev_subscribe"}"#).unwrap();
+        assert!(!subscription_filter(request.params()).unwrap().with_logs);
+
+        // trailing params are optional, leading ones aren't
+        let params: Vec<Value> = parse(r#"[{"version":"v0.1"}]"#).unwrap();
+        let options: SimulateBundleParams = param_or_default(&params, 1).unwrap();
+        assert_eq!(options.parent_block, None);
+        assert!(param::<TxHash>(&[], 0).is_err_and(|err| err.code == -32602));
+        assert!(param_or_default::<SimulateBundleParams>(&[json!(1)], 0)
+            .is_err_and(|err| err.code == -32602));
+    }
+
+    #[test]
+    fn test_order_book_pruning() {
+        let start = Instant::now();
+        let mut orders = OrderBook::default();
+        let included = OrderStatus::Included { block: 1.into() };
+
+        orders.settle(TxHash::repeat_byte(1), included.clone(), start);
+        // sent again before its status expires
+        orders.settle(TxHash::repeat_byte(2), included.clone(), start);
+        orders
+            .statuses
+            .insert(TxHash::repeat_byte(2), OrderStatus::Pending);
+
+        orders.settle(
+            TxHash::repeat_byte(3),
+            included.clone(),
+            start + SETTLED_RETENTION,
+        );
+        assert_eq!(orders.statuses.len(), 3);
+
+        orders.settle(
+            TxHash::repeat_byte(4),
+            included,
+            start + SETTLED_RETENTION + Duration::from_secs(1),
+        );
+        assert!(!orders.statuses.contains_key(&TxHash::repeat_byte(1)));
+        assert_eq!(
+            orders.statuses.get(&TxHash::repeat_byte(2)),
+            Some(&OrderStatus::Pending)
+        );
+        assert_eq!(orders.statuses.len(), 3);
+        assert_eq!(orders.settled.len(), 2);
+    }
+}
@@ -0,0 +1,299 @@
+//! Resolving the function selectors disclosed by hints into human-readable signatures, e.g.
+//! `0x38ed1739` into `swapExactTokensForTokens(uint256,uint256,address[],address,uint256)`, for dashboards and research.
+//!
+//! A [`SignatureResolver`] looks selectors up in its [`SignatureSource`]s, in order, and caches the answers:
+//!
+//! * [`LocalSignatures`]: a local database, e.g. [`LocalSignatures::common`] or a file of signatures;
+//! * [`FourByteDirectory`]: the [4byte.directory] API.
+//!
+//! # Example
+//!
+//! ```
+//! let resolver = SignatureResolver::new()
+//!     .with_source(LocalSignatures::common())
+//!     .with_source(FourByteDirectory::default());
+//!
+//! while let Some(event) = events.next().await {
+//!     let event = event?;
+//!     let signatures = resolver.resolve_event(&event).await;
+//!     for (tx, signature) in event.txs.iter().flatten().zip(signatures) {
+//!         info!(to = ?tx.to, ?signature, "hint");
+//!     }
+//! }
+//! ```
+//!
+//! [4byte.directory]: https://www.4byte.directory
+
+use crate::api::types::{EventHistory, MevShareEvent, Transaction};
+use crate::{Result, RpcError};
+use async_trait::async_trait;
+use ethers::types::Selector;
+use ethers::utils::{hex, id};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+use tracing::warn;
+
+/// Where a [`SignatureResolver`] looks selectors up.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait SignatureSource: Send + Sync {
+    /// Returns the signature of `selector`, if known.
+    async fn lookup(&self, selector: Selector) -> Result<Option<String>>;
+}
+
+/// Signatures of the functions most often seen in hints: tokens, WETH and the Uniswap routers.
+const COMMON_SIGNATURES: [&str; 16] = [
+    "transfer(address,uint256)",
+    "approve(address,uint256)",
+    "transferFrom(address,address,uint256)",
+    "deposit()",
+    "withdraw(uint256)",
+    "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+    "swapTokensForExactTokens(uint256,uint256,address[],address,uint256)",
+    "swapExactETHForTokens(uint256,address[],address,uint256)",
+    "swapETHForExactTokens(uint256,address[],address,uint256)",
+    "swapExactTokensForETH(uint256,uint256,address[],address,uint256)",
+    "swapTokensForExactETH(uint256,uint256,address[],address,uint256)",
+    "swapExactTokensForTokensSupportingFeeOnTransferTokens(uint256,uint256,address[],address,uint256)",
+    "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+    "multicall(bytes[])",
+    "multicall(uint256,bytes[])",
+    "execute(bytes,bytes[],uint256)",
+];
+
+/// A local signature database.
+#[derive(Clone, Debug, Default)]
+pub struct LocalSignatures(HashMap<Selector, String>);
+
+impl LocalSignatures {
+    /// Indexes `signatures`, e.g. `transfer(address,uint256)`, by selector.
+    pub fn new<S: Into<String>>(signatures: impl IntoIterator<Item = S>) -> Self {
+        Self(
+            signatures
+                .into_iter()
+                .map(Into::into)
+                .map(|signature| (id(&signature), signature))
+                .collect(),
+        )
+    }
+
+    /// The functions most often seen in hints: tokens, WETH and the Uniswap routers.
+    #[must_use]
+    pub fn common() -> Self {
+        Self::new(COMMON_SIGNATURES)
+    }
+
+    /// Loads the signatures in the file at `path`, one per line; blank lines and `#` comments are skipped.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Io`] if the file can't be read.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let signatures = fs::read_to_string(path)?;
+
+        Ok(Self::new(
+            signatures
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#')),
+        ))
+    }
+
+    /// Returns the signature of `selector`, if known.
+    #[must_use]
+    pub fn get(&self, selector: Selector) -> Option<&str> {
+        self.0.get(&selector).map(String::as_str)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl SignatureSource for LocalSignatures {
+    async fn lookup(&self, selector: Selector) -> Result<Option<String>> {
+        Ok(self.get(selector).map(ToOwned::to_owned))
+    }
+}
+
+/// The [4byte.directory](https://www.4byte.directory) signature database.
+///
+/// Several functions may share a selector: the one registered first is returned, it being the most likely genuine.
+#[derive(Clone, Debug)]
+pub struct FourByteDirectory {
+    client: reqwest::Client,
+    url: String,
+}
+
+/// Response of `GET /api/v1/signatures/`.
+#[derive(Deserialize)]
+struct FourByteSignatures {
+    results: Vec<FourByteSignature>,
+}
+
+#[derive(Deserialize)]
+struct FourByteSignature {
+    id: u64,
+    text_signature: String,
+}
+
+impl FourByteDirectory {
+    /// Queries the API at `url`, e.g. a mirror, instead of <https://www.4byte.directory>.
+    #[must_use]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into(),
+        }
+    }
+}
+
+impl Default for FourByteDirectory {
+    fn default() -> Self {
+        Self::new("https://www.4byte.directory")
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl SignatureSource for FourByteDirectory {
+    async fn lookup(&self, selector: Selector) -> Result<Option<String>> {
+        let signatures: FourByteSignatures = self
+            .client
+            .get(format!(
+                "{}/api/v1/signatures/",
+                self.url.trim_end_matches('/')
+            ))
+            .query(&[("hex_signature", format!("0x{}", hex::encode(selector)))])
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(RpcError::from)?
+            .json()
+            .await
+            .map_err(RpcError::from)?;
+
+        Ok(signatures
+            .results
+            .into_iter()
+            .min_by_key(|signature| signature.id)
+            .map(|signature| signature.text_signature))
+    }
+}
+
+/// Resolves selectors into signatures, see [`crate::signatures`].
+#[derive(Default)]
+pub struct SignatureResolver {
+    sources: Vec<Box<dyn SignatureSource>>,
+    cache: Mutex<HashMap<Selector, Option<String>>>,
+}
+
+impl SignatureResolver {
+    /// A resolver without sources, resolving nothing until [`Self::with_source`] is called.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Looks selectors up in `source` after the sources added before.
+    #[must_use]
+    pub fn with_source(mut self, source: impl SignatureSource + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Returns the signature of `selector`, if any source knows it.
+    ///
+    /// Answers, unknown selectors included, are cached; failed lookups are logged and retried on the next call.
+    pub async fn resolve(&self, selector: Selector) -> Option<String> {
+        if let Some(signature) = self.cache().get(&selector) {
+            return signature.clone();
+        }
+
+        let mut failed = false;
+        for source in &self.sources {
+            match source.lookup(selector).await {
+                Ok(Some(signature)) => {
+                    self.cache().insert(selector, Some(signature.clone()));
+                    return Some(signature);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    warn!(%err, selector = hex::encode(selector), "signature lookup failed");
+                    failed = true;
+                }
+            }
+        }
+
+        if !failed {
+            self.cache().insert(selector, None);
+        }
+        None
+    }
+
+    /// Resolves the function selector of each of `txs`, in order; `None` where undisclosed or unknown.
+    pub async fn resolve_txs(&self, txs: &[Transaction]) -> Vec<Option<String>> {
+        let mut signatures = Vec::with_capacity(txs.len());
+        for tx in txs {
+            signatures.push(match tx.function_selector {
+                Some(selector) => self.resolve(selector).await,
+                None => None,
+            });
+        }
+        signatures
+    }
+
+    /// Resolves the function selectors of the transactions of a stream event, see [`Self::resolve_txs`].
+    pub async fn resolve_event(&self, event: &MevShareEvent) -> Vec<Option<String>> {
+        self.resolve_txs(event.txs.as_deref().unwrap_or_default())
+            .await
+    }
+
+    /// Resolves the function selectors of the transactions of a history event, see [`Self::resolve_txs`].
+    pub async fn resolve_history(&self, event: &EventHistory) -> Vec<Option<String>> {
+        self.resolve_txs(event.hint.txs.as_deref().unwrap_or_default())
+            .await
+    }
+
+    fn cache(&self) -> std::sync::MutexGuard<'_, HashMap<Selector, Option<String>>> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_signatures() {
+        let signatures = LocalSignatures::common();
+        assert_eq!(
+            signatures.get([0x38, 0xed, 0x17, 0x39]),
+            Some("swapExactTokensForTokens(uint256,uint256,address[],address,uint256)")
+        );
+        assert_eq!(
+            signatures.get([0x7f, 0xf3, 0x6a, 0xb5]),
+            Some("swapExactETHForTokens(uint256,address[],address,uint256)")
+        );
+        assert_eq!(signatures.get([0; 4]), None);
+    }
+
+    #[tokio::test]
+    async fn test_signature_resolver() {
+        let resolver = SignatureResolver::new().with_source(LocalSignatures::common());
+        let event: MevShareEvent = serde_json::from_str(include_str!(
+            "../mev-share-types/src/fixtures/sse_event.json"
+        ))
+        .unwrap();
+
+        let signatures = resolver.resolve_event(&event).await;
+        assert_eq!(signatures.len(), event.txs.as_ref().map_or(0, Vec::len));
+        assert_eq!(
+            resolver.resolve([0xa9, 0x05, 0x9c, 0xbb]).await.as_deref(),
+            Some("transfer(address,uint256)")
+        );
+        assert_eq!(resolver.resolve([0; 4]).await, None);
+        assert!(resolver.cache().contains_key(&[0; 4]));
+    }
+}
@@ -0,0 +1,306 @@
+//! Signers for the `X-Flashbots-Signature` header that authenticates every request to the relay.
+//!
+//! The authentication key carries the searcher reputation, so backends may want to keep it out of the process that talks
+//! to the relay. A [`RemoteSigner`] delegates the signature to a dedicated signing service over JSON-RPC `eth_sign`,
+//! as exposed by e.g. [Web3Signer] or [Clef]: only the keccak hash of each request body leaves the process.
+//!
+//! # Example
+//!
+//! ```
+//! use mev_share_rs::signer::RemoteSigner;
+//!
+//! let auth_signer = RemoteSigner::new("http://signer.internal:9000", auth_address);
+//! let client = MevShareClient::new(auth_signer, provider).await?;
+//! ```
+//!
+//! Deployments authenticating differently, e.g. a gateway expecting another header name, a canonicalized payload or a
+//! MAC, implement [`AuthScheme`] and plug it in with [`crate::MevShareClient::with_auth_scheme`].
+//!
+//! Relays, matchmakers and gateways built on this crate authenticate incoming requests the same way, with
+//! [`verify_flashbots_signature`].
+//!
+//! [Web3Signer]: https://docs.web3signer.consensys.io
+//! [Clef]: https://geth.ethereum.org/docs/tools/clef/introduction
+
+use crate::api::types::{JsonRpcRequest, JsonRpcResponse};
+use crate::error::{JsonError, RpcError};
+use crate::Error;
+use async_trait::async_trait;
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::{Address, Bytes, Signature};
+use ethers::utils::{hex, keccak256};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::json;
+
+/// Name of the header authenticating the requests to the relay.
+pub const FLASHBOTS_SIGNATURE_HEADER: &str = "X-Flashbots-Signature";
+
+/// The message signed for the `X-Flashbots-Signature` header of `body`: the hex of its keccak hash.
+fn signed_message(body: &[u8]) -> String {
+    format!("0x{}", hex::encode(keccak256(body)))
+}
+
+/// Signs the requests to the relay, with a key either held in memory or by a signing service.
+///
+/// Client constructors take anything that converts into an `AuthSigner`, i.e. a [`LocalWallet`] or a [`RemoteSigner`].
+#[derive(Clone, Debug)]
+pub enum AuthSigner {
+    Local(LocalWallet),
+    Remote(RemoteSigner),
+}
+
+impl AuthSigner {
+    /// Returns the address the relay attributes the requests, and their reputation, to.
+    #[must_use]
+    pub fn address(&self) -> Address {
+        match self {
+            Self::Local(wallet) => wallet.address(),
+            Self::Remote(signer) => signer.address,
+        }
+    }
+
+    /// Computes the `X-Flashbots-Signature` header value of `body`: `<address>:<signature of keccak(body)>`.
+    ///
+    /// # Errors
+    ///
+    /// * [`RpcError::Signing`] if the local wallet fails to sign.
+    /// * [`RpcError::Network`], [`RpcError::RemoteSigning`] or [`RpcError::InvalidSignature`] if the remote signer
+    ///   can't be reached, refuses to sign, or signs with another key.
+    pub async fn flashbots_signature(&self, body: &str) -> Result<String, RpcError> {
+        let message = signed_message(body.as_bytes());
+
+        let signature = match self {
+            Self::Local(wallet) => wallet.sign_message(&message).await?,
+            Self::Remote(signer) => signer.sign_message(&message).await?,
+        };
+
+        Ok(format!("{:?}:0x{signature}", self.address()))
+    }
+}
+
+/// How requests to the relay are authenticated: the headers sent along each request body.
+///
+/// [`AuthSigner`] implements the Flashbots scheme, a `X-Flashbots-Signature` header with the signature of the keccak hash
+/// of the body. Errors specific to other schemes can be surfaced with [`RpcError::Auth`].
+///
+/// # Example
+///
+/// ```
+/// struct Hmac { key_id: String, secret: Vec<u8> }
+///
+/// #[async_trait]
+/// impl AuthScheme for Hmac {
+///     async fn authenticate(&self, body: &str) -> Result<HeaderMap, RpcError> {
+///         let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret).map_err(|err| RpcError::Auth(err.into()))?;
+///         mac.update(body.as_bytes());
+///
+///         let mut headers = HeaderMap::new();
+///         headers.insert("X-Key-Id", HeaderValue::from_str(&self.key_id)?);
+///         headers.insert("X-Signature", HeaderValue::from_str(&hex::encode(mac.finalize().into_bytes()))?);
+///         Ok(headers)
+///     }
+/// }
+///
+/// let client = MevShareClient::new(auth_wallet, provider).await?.with_auth_scheme(Hmac::new(key_id, secret));
+/// ```
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait AuthScheme: Send + Sync {
+    /// Returns the headers authenticating a request with `body`, the exact bytes sent to the relay.
+    async fn authenticate(&self, body: &str) -> Result<HeaderMap, RpcError>;
+}
+
+/// The Flashbots scheme, see [`AuthSigner::flashbots_signature`].
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AuthScheme for AuthSigner {
+    async fn authenticate(&self, body: &str) -> Result<HeaderMap, RpcError> {
+        let signature = self.flashbots_signature(body).await?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            FLASHBOTS_SIGNATURE_HEADER,
+            HeaderValue::from_str(&signature)?,
+        );
+        Ok(headers)
+    }
+}
+
+impl From<LocalWallet> for AuthSigner {
+    fn from(wallet: LocalWallet) -> Self {
+        Self::Local(wallet)
+    }
+}
+
+impl From<RemoteSigner> for AuthSigner {
+    fn from(signer: RemoteSigner) -> Self {
+        Self::Remote(signer)
+    }
+}
+
+/// Delegates signatures to a signing service exposing JSON-RPC `eth_sign` (EIP-191) at `url`, for the key of `address`.
+#[derive(Clone, Debug)]
+pub struct RemoteSigner {
+    url: String,
+    address: Address,
+    http: reqwest::Client,
+}
+
+impl RemoteSigner {
+    /// Signs with the key of `address`, held by the signing service at `url`.
+    pub fn new(url: impl Into<String>, address: Address) -> Self {
+        Self::with_client(url, address, reqwest::Client::new())
+    }
+
+    /// Like [`Self::new`], but sends the requests with `http`, e.g. to set up mutual TLS or an authorization header.
+    pub fn with_client(url: impl Into<String>, address: Address, http: reqwest::Client) -> Self {
+        Self {
+            url: url.into(),
+            address,
+            http,
+        }
+    }
+
+    /// Asks the signing service to sign `message`, and checks that it signed with the key of `self.address`.
+    async fn sign_message(&self, message: &str) -> Result<Signature, RpcError> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method: "eth_sign",
+            params: json!([self.address, Bytes::from(message.as_bytes().to_vec())]),
+        };
+
+        let response = self
+            .http
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await?
+            .text()
+            .await?;
+
+        let signature = match serde_json::from_str::<JsonRpcResponse<Bytes>>(&response) {
+            Ok(JsonRpcResponse::Success(data)) => data.result,
+            Ok(JsonRpcResponse::Error(err)) => return Err(RpcError::RemoteSigning(err)),
+            Err(source) => {
+                return Err(JsonError::Deserialization {
+                    source,
+                    text: response,
+                }
+                .into())
+            }
+        };
+
+        let signature = Signature::try_from(signature.as_ref())?;
+        signature.verify(message, self.address)?;
+
+        Ok(signature)
+    }
+}
+
+/// Verifies the `X-Flashbots-Signature` `header` of a request with `body`, and returns the address that signed it,
+/// to attribute the request to, e.g. for rate limiting or reputation.
+///
+/// # Example
+///
+/// ```
+/// let header = request.headers().get(FLASHBOTS_SIGNATURE_HEADER).ok_or(Unauthorized)?.to_str()?;
+/// let searcher = verify_flashbots_signature(header, &body)?;
+/// ```
+///
+/// # Errors
+///
+/// * [`crate::Error::InvalidSignatureHeader`] if the header is not `<address>:<signature>`, or the signature is not
+///   the one of `<address>` over `body`.
+pub fn verify_flashbots_signature(header: &str, body: &[u8]) -> crate::Result<Address> {
+    let invalid = Error::InvalidSignatureHeader;
+
+    let (address, signature) = header
+        .split_once(':')
+        .ok_or_else(|| invalid("expected <address>:<signature>".to_owned()))?;
+    let address: Address = address
+        .parse()
+        .map_err(|err| invalid(format!("invalid address {address:?}: {err}")))?;
+    let signature: Signature = signature
+        .parse()
+        .map_err(|err| invalid(format!("invalid signature {signature:?}: {err}")))?;
+
+    let signer = signature
+        .recover(signed_message(body))
+        .map_err(|err| invalid(err.to_string()))?;
+
+    if signer != address {
+        return Err(invalid(format!(
+            "signed by {signer:?}, not by {address:?}, or for another body"
+        )));
+    }
+
+    Ok(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_flashbots_signature() {
+        let wallet = LocalWallet::new(&mut ethers::core::rand::thread_rng());
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"mev_sendBundle","params":[]}"#;
+
+        let header = AuthSigner::from(wallet.clone())
+            .flashbots_signature(body)
+            .await
+            .unwrap();
+
+        let (address, signature) = header.split_once(':').unwrap();
+        assert_eq!(address, format!("{:?}", wallet.address()));
+
+        let signature: Signature = signature.parse().unwrap();
+        let message = format!("0x{}", hex::encode(keccak256(body)));
+        assert!(signature.verify(message, wallet.address()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_auth_scheme() {
+        let wallet = LocalWallet::new(&mut ethers::core::rand::thread_rng());
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"mev_sendBundle","params":[]}"#;
+
+        let scheme: Box<dyn AuthScheme> = Box::new(AuthSigner::from(wallet.clone()));
+        let headers = scheme.authenticate(body).await.unwrap();
+
+        assert_eq!(headers.len(), 1);
+        let header = headers[FLASHBOTS_SIGNATURE_HEADER].to_str().unwrap();
+        assert_eq!(
+            verify_flashbots_signature(header, body.as_bytes()).unwrap(),
+            wallet.address()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_flashbots_signature() {
+        let wallet = LocalWallet::new(&mut ethers::core::rand::thread_rng());
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"mev_sendBundle","params":[]}"#;
+
+        let header = AuthSigner::from(wallet.clone())
+            .flashbots_signature(body)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            verify_flashbots_signature(&header, body.as_bytes()).unwrap(),
+            wallet.address()
+        );
+
+        let tampered = body.replace("[]", "[{}]");
+        assert!(matches!(
+            verify_flashbots_signature(&header, tampered.as_bytes()),
+            Err(Error::InvalidSignatureHeader(_))
+        ));
+
+        let (_, signature) = header.split_once(':').unwrap();
+        let impersonated = format!("{:?}:{signature}", Address::random());
+        assert!(verify_flashbots_signature(&impersonated, body.as_bytes()).is_err());
+
+        assert!(verify_flashbots_signature("0x1234", body.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use super::{key, EventSink, Serialization};
+use crate::api::types::MevShareEvent;
+use crate::Result;
+use async_trait::async_trait;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::util::Timeout;
+
+/// Produces the events to a Kafka topic, keyed by event hash.
+pub struct KafkaSink {
+    producer: FutureProducer,
+    topic: String,
+    serialization: Serialization,
+}
+
+impl KafkaSink {
+    /// Produces to `topic` with `producer`, configured by the caller (brokers, acks, compression, ...).
+    pub fn new(producer: FutureProducer, topic: impl Into<String>) -> Self {
+        Self {
+            producer,
+            topic: topic.into(),
+            serialization: Serialization::default(),
+        }
+    }
+
+    /// Encodes the events with `serialization` instead of JSON.
+    #[must_use]
+    pub fn with_serialization(mut self, serialization: Serialization) -> Self {
+        self.serialization = serialization;
+        self
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaSink {
+    async fn publish(&self, event: &MevShareEvent) -> Result<()> {
+        let payload = self.serialization.encode(event)?;
+        let key = key(event);
+
+        self.producer
+            .send(
+                FutureRecord::to(&self.topic).key(&key).payload(&payload),
+                Timeout::Never,
+            )
+            .await
+            .map_err(|(err, _)| err)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+//! Forwarding the MEV-Share event stream to an event bus, so that one connection to the relay feeds a fleet of
+//! strategy workers.
+//!
+//! Every sink is behind its own feature:
+//!
+//! * `kafka`: [`KafkaSink`] produces to a topic, keyed by the event hash.
+//! * `nats`: [`NatsSink`] publishes to a subject, with the event hash as message id.
+//! * `redis`: [`RedisSink`] appends to a Redis stream with `XADD`.
+//!
+//! Events are serialized as JSON by default, the way the relay sends them; see [`Serialization`] for other formats.
+//!
+//! # Example
+//!
+//! ```
+//! let producer: FutureProducer = ClientConfig::new().set("bootstrap.servers", "kafka:9092").create()?;
+//! let sink = KafkaSink::new(producer, "mev-share-hints");
+//!
+//! // runs until the stream or the sink fails
+//! sink::forward(&client, &sink).await?;
+//! ```
+
+#[cfg(feature = "kafka")]
+mod kafka;
+#[cfg(feature = "nats")]
+mod nats;
+#[cfg(feature = "redis")]
+mod redis;
+
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaSink;
+#[cfg(feature = "nats")]
+pub use nats::NatsSink;
+#[cfg(feature = "redis")]
+pub use redis::RedisSink;
+
+use crate::api::types::MevShareEvent;
+use crate::{MevShareClient, Result, Waiter};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::fmt::Debug;
+use std::pin::pin;
+use std::sync::Arc;
+use tracing::*;
+
+/// Where [`forward`] publishes the events to.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait EventSink: Send + Sync {
+    /// Publishes `event`, returning once the bus, or its client, accepted it.
+    async fn publish(&self, event: &MevShareEvent) -> Result<()>;
+}
+
+/// Encodes an event into a message payload.
+pub type Encoder = Arc<dyn Fn(&MevShareEvent) -> Vec<u8> + Send + Sync>;
+
+/// How events are encoded into message payloads.
+#[derive(Clone, Default)]
+pub enum Serialization {
+    /// The JSON of the relay event stream.
+    #[default]
+    Json,
+    /// Any other encoding, e.g. protobuf or a projection of the fields the workers need.
+    Custom(Encoder),
+}
+
+impl Serialization {
+    /// Encodes `event`.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Json`] if `event` can't be serialized as JSON.
+    pub fn encode(&self, event: &MevShareEvent) -> Result<Vec<u8>> {
+        match self {
+            Self::Json => Ok(serde_json::to_vec(event)?),
+            Self::Custom(encode) => Ok(encode(event)),
+        }
+    }
+}
+
+impl Debug for Serialization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json => f.write_str("Json"),
+            Self::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+/// Publishes every event of the live stream of `client` to `sink`, until either fails.
+///
+/// # Errors
+///
+/// * [`crate::Error::EventSource`] if the stream fails.
+/// * The errors of the `sink`, e.g. [`crate::Error::Kafka`].
+pub async fn forward<M: Waiter>(client: &MevShareClient<M>, sink: &impl EventSink) -> Result<()> {
+    let mut events = pin!(client.subscribe_bundles());
+
+    while let Some(event) = events.next().await {
+        let event = event?;
+        trace!(?event.hash, "forwarding event");
+        sink.publish(&event).await?;
+    }
+
+    Ok(())
+}
+
+/// Message key of `event`: its hash, so that partitioned buses keep the events of a hash in order.
+#[cfg(any(feature = "kafka", feature = "nats", feature = "redis"))]
+fn key(event: &MevShareEvent) -> String {
+    format!("{:?}", event.hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        let event: MevShareEvent = serde_json::from_str(include_str!(
+            "../../mev-share-types/src/fixtures/sse_event.json"
+        ))
+        .unwrap();
+
+        let json = Serialization::Json.encode(&event).unwrap();
+        let decoded: MevShareEvent = serde_json::from_slice(&json).unwrap();
+        assert_eq!(decoded.hash, event.hash);
+
+        let hash_only = Serialization::Custom(Arc::new(|event| event.hash.as_bytes().to_vec()));
+        assert_eq!(hash_only.encode(&event).unwrap(), event.hash.as_bytes());
+    }
+}
@@ -0,0 +1,48 @@
+use super::{key, EventSink, Serialization};
+use crate::api::types::MevShareEvent;
+use crate::Result;
+use async_nats::header::NATS_MESSAGE_ID;
+use async_nats::{Client, HeaderMap};
+use async_trait::async_trait;
+
+/// Publishes the events to a NATS subject, with the event hash as `Nats-Msg-Id` so that `JetStream` streams drop
+/// duplicates, e.g. from two forwarders running for redundancy.
+pub struct NatsSink {
+    client: Client,
+    subject: String,
+    serialization: Serialization,
+}
+
+impl NatsSink {
+    /// Publishes to `subject` with `client`.
+    pub fn new(client: Client, subject: impl Into<String>) -> Self {
+        Self {
+            client,
+            subject: subject.into(),
+            serialization: Serialization::default(),
+        }
+    }
+
+    /// Encodes the events with `serialization` instead of JSON.
+    #[must_use]
+    pub fn with_serialization(mut self, serialization: Serialization) -> Self {
+        self.serialization = serialization;
+        self
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    async fn publish(&self, event: &MevShareEvent) -> Result<()> {
+        let payload = self.serialization.encode(event)?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(NATS_MESSAGE_ID, key(event).as_str());
+
+        self.client
+            .publish_with_headers(self.subject.clone(), headers, payload.into())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use super::{key, EventSink, Serialization};
+use crate::api::types::MevShareEvent;
+use crate::Result;
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+
+/// Appends the events to a Redis stream, as entries with a `hash` and an `event` field.
+///
+/// Workers read them with `XREAD`, or `XREADGROUP` to share the load within a consumer group.
+pub struct RedisSink {
+    connection: ConnectionManager,
+    stream: String,
+    max_len: Option<usize>,
+    serialization: Serialization,
+}
+
+impl RedisSink {
+    /// Appends to the stream at key `stream` through `connection`.
+    pub fn new(connection: ConnectionManager, stream: impl Into<String>) -> Self {
+        Self {
+            connection,
+            stream: stream.into(),
+            max_len: None,
+            serialization: Serialization::default(),
+        }
+    }
+
+    /// Trims the stream to about `max_len` entries. Defaults to unbounded.
+    #[must_use]
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Encodes the events with `serialization` instead of JSON.
+    #[must_use]
+    pub fn with_serialization(mut self, serialization: Serialization) -> Self {
+        self.serialization = serialization;
+        self
+    }
+}
+
+#[async_trait]
+impl EventSink for RedisSink {
+    async fn publish(&self, event: &MevShareEvent) -> Result<()> {
+        let fields = [
+            ("hash", key(event).into_bytes()),
+            ("event", self.serialization.encode(event)?),
+        ];
+
+        // cheap: clones share the underlying multiplexed connection
+        let mut connection = self.connection.clone();
+
+        let _: String = match self.max_len {
+            Some(max_len) => {
+                connection
+                    .xadd_maxlen(&self.stream, StreamMaxlen::Approx(max_len), "*", &fields)
+                    .await?
+            }
+            None => connection.xadd(&self.stream, "*", &fields).await?,
+        };
+
+        Ok(())
+    }
+}
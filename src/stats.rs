@@ -0,0 +1,6 @@
+//! Searcher and bundle statistics kept by the Flashbots relay, and the gas fee refunds it owes.
+
+pub use crate::api::types::{
+    bundle_contribution, wei_to_eth, BuilderTimestamp, BundleStats, FeeRefund, FeeRefundTotals,
+    FeeRefunds, GetFeeRefundsByRecipientParams, RefundRule, RefundStatus, UserStats,
+};
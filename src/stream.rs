@@ -0,0 +1,3 @@
+//! Types of the events received from [`crate::MevShareClient::subscribe_bundles`].
+
+pub use crate::api::types::{EventFilter, HintedTransaction, MevShareEvent, Transaction};
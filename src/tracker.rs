@@ -0,0 +1,289 @@
+//! Rolling statistics of what a client submits and how it fares, so that the health of a strategy is observable
+//! without processing logs.
+//!
+//! A client [with a tracker](crate::MevShareClient::with_submission_tracker) records every [`Outcome`] of its bundles
+//! and transactions: sent, accepted by the relay, simulated successfully, included, reverted or timed out. The
+//! tracker keeps the outcomes of the last window for [`SubmissionTracker::stats`], and all-time totals, exported in the
+//! Prometheus text format by [`SubmissionTracker::encode_prometheus`].
+//!
+//! # Example
+//!
+//! ```
+//! let tracker = Arc::new(SubmissionTracker::new(Duration::from_secs(60 * 60)));
+//! let client = MevShareClient::new(auth_wallet, provider)
+//!     .await?
+//!     .with_submission_tracker(tracker.clone());
+//!
+//! // e.g. from the handler of a `/metrics` endpoint
+//! let body = tracker.encode_prometheus();
+//!
+//! let stats = tracker.stats();
+//! info!(%stats, inclusion_rate = ?stats.inclusion_rate(), "last hour");
+//! ```
+
+use instant::Instant;
+use std::collections::VecDeque;
+use std::fmt::{Display, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// What happened to a submission, see [`SubmissionTracker::record`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// Sent to the relay.
+    Sent,
+    /// Accepted by the relay.
+    Accepted,
+    /// Simulated successfully.
+    Simulated,
+    /// Included on-chain.
+    Included,
+    /// Included, but reverted.
+    Reverted,
+    /// Not included before its maximum block.
+    TimedOut,
+}
+
+impl Outcome {
+    /// Every outcome, in the order of the life of a submission.
+    pub const ALL: [Outcome; 6] = [
+        Outcome::Sent,
+        Outcome::Accepted,
+        Outcome::Simulated,
+        Outcome::Included,
+        Outcome::Reverted,
+        Outcome::TimedOut,
+    ];
+
+    /// Label of the outcome in the exported metrics.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sent => "sent",
+            Self::Accepted => "accepted",
+            Self::Simulated => "simulated",
+            Self::Included => "included",
+            Self::Reverted => "reverted",
+            Self::TimedOut => "timed_out",
+        }
+    }
+}
+
+/// Number of submissions with each [`Outcome`], see [`SubmissionTracker::stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SubmissionStats {
+    pub sent: u64,
+    pub accepted: u64,
+    pub simulated: u64,
+    pub included: u64,
+    pub reverted: u64,
+    pub timed_out: u64,
+}
+
+impl SubmissionStats {
+    /// Number of submissions with `outcome`.
+    #[must_use]
+    pub fn get(&self, outcome: Outcome) -> u64 {
+        match outcome {
+            Outcome::Sent => self.sent,
+            Outcome::Accepted => self.accepted,
+            Outcome::Simulated => self.simulated,
+            Outcome::Included => self.included,
+            Outcome::Reverted => self.reverted,
+            Outcome::TimedOut => self.timed_out,
+        }
+    }
+
+    fn add(&mut self, outcome: Outcome) {
+        let count = match outcome {
+            Outcome::Sent => &mut self.sent,
+            Outcome::Accepted => &mut self.accepted,
+            Outcome::Simulated => &mut self.simulated,
+            Outcome::Included => &mut self.included,
+            Outcome::Reverted => &mut self.reverted,
+            Outcome::TimedOut => &mut self.timed_out,
+        };
+        *count += 1;
+    }
+
+    /// Share of the sent submissions that the relay accepted, if any were sent.
+    #[must_use]
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        rate(self.accepted, self.sent)
+    }
+
+    /// Share of the submissions that landed, reverted or not, that were included without reverting.
+    #[must_use]
+    pub fn inclusion_rate(&self) -> Option<f64> {
+        rate(
+            self.included,
+            self.included + self.reverted + self.timed_out,
+        )
+    }
+
+    /// Share of the submissions that landed that reverted.
+    #[must_use]
+    pub fn revert_rate(&self) -> Option<f64> {
+        rate(self.reverted, self.included + self.reverted)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn rate(count: u64, total: u64) -> Option<f64> {
+    (total > 0).then(|| count as f64 / total as f64)
+}
+
+impl Display for SubmissionStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} sent, {} accepted, {} simulated, {} included, {} reverted, {} timed out",
+            self.sent, self.accepted, self.simulated, self.included, self.reverted, self.timed_out
+        )
+    }
+}
+
+/// Records the [`Outcome`]s of the submissions of a client. See [`crate::tracker`].
+#[derive(Debug)]
+pub struct SubmissionTracker {
+    window: Duration,
+    recent: Mutex<VecDeque<(Instant, Outcome)>>,
+    totals: [AtomicU64; Outcome::ALL.len()],
+}
+
+impl SubmissionTracker {
+    /// Keeps the outcomes of the last `window` for [`Self::stats`].
+    #[must_use]
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            recent: Mutex::default(),
+            totals: Default::default(),
+        }
+    }
+
+    /// Records that a submission had `outcome`.
+    pub fn record(&self, outcome: Outcome) {
+        self.totals[outcome as usize].fetch_add(1, Ordering::Relaxed);
+
+        let now = Instant::now();
+        let mut recent = self.recent.lock().unwrap_or_else(PoisonError::into_inner);
+        Self::prune(&mut recent, now, self.window);
+        recent.push_back((now, outcome));
+    }
+
+    /// Outcomes within the window.
+    pub fn stats(&self) -> SubmissionStats {
+        let mut recent = self.recent.lock().unwrap_or_else(PoisonError::into_inner);
+        Self::prune(&mut recent, Instant::now(), self.window);
+
+        recent
+            .iter()
+            .fold(SubmissionStats::default(), |mut stats, (_, outcome)| {
+                stats.add(*outcome);
+                stats
+            })
+    }
+
+    /// Outcomes since the tracker was created.
+    pub fn totals(&self) -> SubmissionStats {
+        let total = |outcome: Outcome| self.totals[outcome as usize].load(Ordering::Relaxed);
+
+        SubmissionStats {
+            sent: total(Outcome::Sent),
+            accepted: total(Outcome::Accepted),
+            simulated: total(Outcome::Simulated),
+            included: total(Outcome::Included),
+            reverted: total(Outcome::Reverted),
+            timed_out: total(Outcome::TimedOut),
+        }
+    }
+
+    /// Encodes the totals, as the `mev_share_submissions_total` counter, and the window, as the
+    /// `mev_share_submissions_window` gauge, labelled by outcome, in the Prometheus text exposition format.
+    pub fn encode_prometheus(&self) -> String {
+        let (totals, window) = (self.totals(), self.stats());
+        let mut text = String::new();
+
+        for (name, kind, help, stats) in [
+            (
+                "mev_share_submissions_total",
+                "counter",
+                "Submissions to MEV-Share, by outcome.",
+                totals,
+            ),
+            (
+                "mev_share_submissions_window",
+                "gauge",
+                "Submissions to MEV-Share within the tracker window, by outcome.",
+                window,
+            ),
+        ] {
+            // writing to a `String` can't fail
+            let _ = writeln!(text, "# HELP {name} {help}");
+            let _ = writeln!(text, "# TYPE {name} {kind}");
+            for outcome in Outcome::ALL {
+                let _ = writeln!(
+                    text,
+                    "{name}{{outcome=\"{}\"}} {}",
+                    outcome.as_str(),
+                    stats.get(outcome)
+                );
+            }
+        }
+
+        text
+    }
+
+    fn prune(recent: &mut VecDeque<(Instant, Outcome)>, now: Instant, window: Duration) {
+        while let Some((at, _)) = recent.front()
+            && now.duration_since(*at) > window
+        {
+            recent.pop_front();
+        }
+    }
+}
+
+impl Default for SubmissionTracker {
+    /// Keeps the outcomes of the last hour.
+    fn default() -> Self {
+        Self::new(Duration::from_secs(60 * 60))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submission_tracker() {
+        let tracker = SubmissionTracker::new(Duration::from_millis(50));
+        for outcome in [
+            Outcome::Sent,
+            Outcome::Accepted,
+            Outcome::Sent,
+            Outcome::Accepted,
+            Outcome::Included,
+            Outcome::TimedOut,
+        ] {
+            tracker.record(outcome);
+        }
+
+        let stats = tracker.stats();
+        assert_eq!((stats.sent, stats.accepted, stats.included), (2, 2, 1));
+        assert_eq!(stats.acceptance_rate(), Some(1.0));
+        assert_eq!(stats.inclusion_rate(), Some(0.5));
+        assert_eq!(stats.revert_rate(), Some(0.0));
+
+        std::thread::sleep(Duration::from_millis(60));
+        tracker.record(Outcome::Sent);
+        assert_eq!(tracker.stats().sent, 1);
+        assert_eq!(tracker.totals().sent, 3);
+
+        let text = tracker.encode_prometheus();
+        assert!(text.contains("# TYPE mev_share_submissions_total counter\n"));
+        assert!(text.contains("mev_share_submissions_total{outcome=\"sent\"} 3\n"));
+        assert!(text.contains("mev_share_submissions_window{outcome=\"included\"} 0\n"));
+    }
+}
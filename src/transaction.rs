@@ -0,0 +1,8 @@
+//! Types to send private transactions with [`crate::MevShareClient::send_private_transaction`].
+
+pub use crate::api::types::{
+    Builder, Builders, CancelPrivateTransactionParams, CancelPrivateTransactionResponse, Hint,
+    OnReorg, PendingTransaction, Preferences, Preset, RefundConfig, ReplacementOutcome,
+    SendTransactionParams, TransactionProgress, TransactionStatus, TransactionValidity,
+    PROTECT_HINTS, TX_WAIT_MAX_BLOCKS,
+};
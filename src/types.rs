@@ -0,0 +1,12 @@
+//! Third-party types that appear in the public API.
+//!
+//! Use these rather than naming `ethers` or `reqwest` types directly, so that a different `ethers` version in your own
+//! dependency tree doesn't lead to "expected `U64`, found `U64`" mismatches.
+
+pub use ethers::providers::{Provider, ProviderError, Ws};
+pub use ethers::signers::{LocalWallet, Signer};
+pub use ethers::types::transaction::eip2718::TypedTransaction;
+pub use ethers::types::{
+    Address, Bytes, Log, Selector, Transaction, TransactionReceipt, TxHash, H256, U256, U64,
+};
+pub use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
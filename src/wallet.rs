@@ -0,0 +1,235 @@
+//! Loading the authentication and sender wallets from somewhere safer than raw private keys in the environment.
+//!
+//! Wallets come from encrypted keystores, with [`from_keystore`], or are derived from a BIP-39 mnemonic, with
+//! [`from_mnemonic`] or an [`HdWallet`] when several keys are derived from the same phrase.
+
+use crate::Result;
+use ethers::signers::coins_bip39::English;
+use ethers::signers::{LocalWallet, MnemonicBuilder};
+use std::fmt::Debug;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Derivation path of the Ethereum accounts of a mnemonic, followed by the account index: `m/44'/60'/0'/0/<index>`.
+pub const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
+
+/// Decrypts the JSON keystore at `path`, e.g. one created by `geth account new` or `cast wallet new`.
+///
+/// # Example
+///
+/// ```
+/// let auth_wallet = wallet::from_keystore("keys/auth.json", &env::var("AUTH_KEYSTORE_PASSWORD")?)?;
+/// let client = MevShareClient::new(auth_wallet, provider).await?;
+/// ```
+///
+/// # Errors
+///
+/// * [`crate::Error::Wallet`] if the keystore can't be read or `passphrase` is wrong.
+pub fn from_keystore(path: impl AsRef<Path>, passphrase: impl AsRef<[u8]>) -> Result<LocalWallet> {
+    Ok(LocalWallet::decrypt_keystore(path, passphrase)?)
+}
+
+/// Derives the wallet at `derivation_path`, e.g. `m/44'/60'/0'/0/0`, from the BIP-39 English `phrase`.
+///
+/// # Example
+///
+/// ```
+/// let auth_wallet = wallet::from_mnemonic(&env::var("AUTH_MNEMONIC")?, "m/44'/60'/0'/0/0")?;
+/// ```
+///
+/// # Errors
+///
+/// * [`crate::Error::Wallet`] if `phrase` is not a valid mnemonic, or `derivation_path` is not a valid path.
+pub fn from_mnemonic(phrase: &str, derivation_path: &str) -> Result<LocalWallet> {
+    Ok(MnemonicBuilder::<English>::default()
+        .phrase(phrase)
+        .derivation_path(derivation_path)?
+        .build()?)
+}
+
+/// A BIP-39 mnemonic the wallets of several accounts are derived from, by index, e.g. a dedicated auth key and a pool
+/// of sender keys rotated between bundles.
+///
+/// # Example
+///
+/// ```
+/// let hd_wallet = HdWallet::new(env::var("SEARCHER_MNEMONIC")?);
+///
+/// let auth_wallet = hd_wallet.derive(0)?;
+/// let senders = hd_wallet.rotation(1..11)?;
+///
+/// let tx = TransactionRequest::pay(target, amount).from(senders.next().address());
+/// ```
+#[derive(Clone)]
+pub struct HdWallet {
+    phrase: String,
+    password: Option<String>,
+    path_prefix: String,
+}
+
+impl HdWallet {
+    /// Derives wallets from the BIP-39 English `phrase`, at [`DEFAULT_DERIVATION_PATH_PREFIX`].
+    pub fn new(phrase: impl Into<String>) -> Self {
+        Self {
+            phrase: phrase.into(),
+            password: None,
+            path_prefix: DEFAULT_DERIVATION_PATH_PREFIX.to_owned(),
+        }
+    }
+
+    /// Derives the seed with the BIP-39 `password`, also known as the 25th word.
+    #[must_use]
+    pub fn with_password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Derives the wallets at `path_prefix` followed by their index, e.g. `m/44'/60'/1'/0/` for the accounts of the
+    /// second Ledger Live account. The prefix must end with `/`.
+    #[must_use]
+    pub fn with_path_prefix(mut self, path_prefix: impl Into<String>) -> Self {
+        self.path_prefix = path_prefix.into();
+        self
+    }
+
+    /// Derives the wallet at `index`.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::Wallet`] if the phrase is not a valid mnemonic, or the path prefix is not a valid path.
+    pub fn derive(&self, index: u32) -> Result<LocalWallet> {
+        let mut builder = MnemonicBuilder::<English>::default()
+            .phrase(self.phrase.as_str())
+            .derivation_path(&format!("{}{index}", self.path_prefix))?;
+        if let Some(password) = &self.password {
+            builder = builder.password(password);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Derives the wallets at `indices`, in order.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::derive`].
+    pub fn derive_range(&self, indices: Range<u32>) -> Result<Vec<LocalWallet>> {
+        indices.map(|index| self.derive(index)).collect()
+    }
+
+    /// Derives the wallets at `indices`, to be used in turn, see [`WalletRotation`].
+    ///
+    /// # Errors
+    ///
+    /// * Same as [`Self::derive`].
+    /// * [`crate::Error::InvalidParams`] if `indices` is empty.
+    pub fn rotation(&self, indices: Range<u32>) -> Result<WalletRotation> {
+        WalletRotation::new(self.derive_range(indices)?)
+    }
+}
+
+/// Never prints the mnemonic.
+impl Debug for HdWallet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HdWallet")
+            .field("path_prefix", &self.path_prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Wallets used in turn, round robin, e.g. to spread bundles over several senders so that their nonces don't conflict.
+///
+/// Can be shared between tasks: every call to [`Self::next`] returns the next wallet.
+#[derive(Debug)]
+pub struct WalletRotation {
+    wallets: Vec<LocalWallet>,
+    next: AtomicUsize,
+}
+
+impl WalletRotation {
+    /// Uses `wallets` in turn, starting with the first one.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::InvalidParams`] if `wallets` is empty.
+    pub fn new(wallets: Vec<LocalWallet>) -> Result<Self> {
+        if wallets.is_empty() {
+            return Err(crate::Error::InvalidParams(
+                "no wallets to rotate".to_owned(),
+            ));
+        }
+        Ok(Self {
+            wallets,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Returns the next wallet to use, and moves the rotation forward.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&self) -> &LocalWallet {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.wallets.len();
+        &self.wallets[index]
+    }
+
+    /// Returns every wallet of the rotation, e.g. to check their balances.
+    #[must_use]
+    pub fn wallets(&self) -> &[LocalWallet] {
+        &self.wallets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::Signer;
+
+    #[test]
+    fn test_from_keystore() {
+        let dir = std::env::temp_dir();
+        let mut rng = ethers::core::rand::thread_rng();
+        let (wallet, name) = LocalWallet::new_keystore(&dir, &mut rng, "passphrase", None).unwrap();
+        let path = dir.join(name);
+
+        assert_eq!(
+            from_keystore(&path, "passphrase").unwrap().address(),
+            wallet.address()
+        );
+        assert!(matches!(
+            from_keystore(&path, "wrong"),
+            Err(crate::Error::Wallet(_))
+        ));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    /// The mnemonic of the Anvil and Hardhat development accounts.
+    const TEST_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn test_from_mnemonic() {
+        let address = |wallet: &LocalWallet| format!("{:?}", wallet.address());
+
+        assert_eq!(
+            address(&from_mnemonic(TEST_MNEMONIC, "m/44'/60'/0'/0/0").unwrap()),
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+        );
+        assert!(matches!(
+            from_mnemonic(TEST_MNEMONIC, "not a path"),
+            Err(crate::Error::Wallet(_))
+        ));
+
+        let hd_wallet = HdWallet::new(TEST_MNEMONIC);
+        assert_eq!(
+            address(&hd_wallet.derive(1).unwrap()),
+            "0x70997970c51812dc3a010c7d01b50e0d17dc79c8"
+        );
+        assert!(!format!("{hd_wallet:?}").contains("junk"));
+
+        let rotation = hd_wallet.rotation(0..2).unwrap();
+        let turns: Vec<_> = (0..3).map(|_| rotation.next().address()).collect();
+        assert_eq!(turns[0], turns[2]);
+        assert_ne!(turns[0], turns[1]);
+
+        assert!(hd_wallet.rotation(2..2).is_err());
+    }
+}